@@ -1,6 +1,10 @@
 use std::path::{Path, PathBuf};
 
-use luthier_core::{CreateOrchestratorRequest, CreateOrchestratorResult};
+use luthier_core::{
+    CreateOrchestratorRequest, CreateOrchestratorResult, DesktopEntryInstallRequest,
+    DesktopEntryInstallResult, DesktopEntryUninstallRequest, DesktopEntryUninstallResult,
+    ImportedGameConfig, SteamShortcutExportRequest, SteamShortcutExportResult,
+};
 use luthier_orchestrator_core::{
     doctor::DoctorReport, prefix::PrefixSetupPlan, GameConfig, RegistryKey,
 };
@@ -32,6 +36,8 @@ pub trait BackendLoggerPort: Send + Sync {
 
 pub trait RuntimeEnvironmentPort: Send + Sync {
     fn path_entries(&self) -> Vec<PathBuf>;
+    /// `$XDG_DATA_HOME`, falling back to `~/.local/share`.
+    fn xdg_data_home(&self) -> Option<PathBuf>;
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -204,6 +210,34 @@ pub trait LuthierCorePort: Send + Sync {
     ) -> BackendResult<CreateOrchestratorResult>;
     fn sha256_file(&self, path: &Path) -> BackendResult<String>;
     fn validate_game_config(&self, config: &GameConfig) -> BackendResult<()>;
+    fn import_lutris_config(
+        &self,
+        source: &str,
+        game_root: &Path,
+    ) -> BackendResult<ImportedGameConfig>;
+    fn import_heroic_config(
+        &self,
+        source: &str,
+        game_root: &Path,
+        exe: Option<&str>,
+    ) -> BackendResult<ImportedGameConfig>;
+    fn import_bottles_config(
+        &self,
+        source: &str,
+        game_root: &Path,
+    ) -> BackendResult<ImportedGameConfig>;
+    fn export_steam_shortcut(
+        &self,
+        request: &SteamShortcutExportRequest,
+    ) -> BackendResult<SteamShortcutExportResult>;
+    fn install_desktop_entry(
+        &self,
+        request: &DesktopEntryInstallRequest,
+    ) -> BackendResult<DesktopEntryInstallResult>;
+    fn uninstall_desktop_entry(
+        &self,
+        request: &DesktopEntryUninstallRequest,
+    ) -> BackendResult<DesktopEntryUninstallResult>;
 }
 
 pub trait BaseBinaryResolverPort: Send + Sync {
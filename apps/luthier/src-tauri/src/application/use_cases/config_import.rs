@@ -0,0 +1,191 @@
+use std::path::Path;
+
+use luthier_core::ImportedGameConfig;
+
+use crate::application::ports::{
+    BackendLogEvent, BackendLogLevel, BackendLoggerPort, FileSystemPort, LuthierCorePort,
+};
+use crate::error::{BackendError, BackendResult, BackendResultExt, CommandStringResult};
+use crate::models::dto::{ImportLauncherConfigInput, ImportLauncherConfigOutput};
+
+/// Turns game definitions from other launchers into payloads the editor can
+/// open, alongside [`super::payload_import::PayloadImportUseCase`].
+pub struct ConfigImportUseCase<'a> {
+    file_system: &'a dyn FileSystemPort,
+    luthier_core: &'a dyn LuthierCorePort,
+    logger: &'a dyn BackendLoggerPort,
+}
+
+impl<'a> ConfigImportUseCase<'a> {
+    pub fn new(
+        file_system: &'a dyn FileSystemPort,
+        luthier_core: &'a dyn LuthierCorePort,
+        logger: &'a dyn BackendLoggerPort,
+    ) -> Self {
+        Self {
+            file_system,
+            luthier_core,
+            logger,
+        }
+    }
+
+    pub fn import_lutris_config(
+        &self,
+        input: ImportLauncherConfigInput,
+    ) -> BackendResult<ImportLauncherConfigOutput> {
+        self.log_info(
+            "GO-CR-606",
+            "import_lutris_config_requested",
+            serde_json::json!({ "path": &input.path, "game_root": &input.game_root }),
+        );
+
+        let source = self.read_utf8_file(&input.path)?;
+        let imported = self
+            .luthier_core
+            .import_lutris_config(&source, Path::new(&input.game_root))
+            .map_err(|err| err.with_context("failed to import lutris config"))?;
+
+        self.finish("lutris", &input, imported)
+    }
+
+    pub fn import_heroic_config(
+        &self,
+        input: ImportLauncherConfigInput,
+    ) -> BackendResult<ImportLauncherConfigOutput> {
+        self.log_info(
+            "GO-CR-608",
+            "import_heroic_config_requested",
+            serde_json::json!({ "path": &input.path, "game_root": &input.game_root }),
+        );
+
+        let source = self.read_utf8_file(&input.path)?;
+        let imported = self
+            .luthier_core
+            .import_heroic_config(
+                &source,
+                Path::new(&input.game_root),
+                input.exe_path.as_deref(),
+            )
+            .map_err(|err| err.with_context("failed to import heroic config"))?;
+
+        self.finish("heroic", &input, imported)
+    }
+
+    pub fn import_bottles_config(
+        &self,
+        input: ImportLauncherConfigInput,
+    ) -> BackendResult<ImportLauncherConfigOutput> {
+        self.log_info(
+            "GO-CR-609",
+            "import_bottles_config_requested",
+            serde_json::json!({ "path": &input.path, "game_root": &input.game_root }),
+        );
+
+        let source = self.read_utf8_file(&input.path)?;
+        let imported = self
+            .luthier_core
+            .import_bottles_config(&source, Path::new(&input.game_root))
+            .map_err(|err| err.with_context("failed to import bottles config"))?;
+
+        self.finish("bottles", &input, imported)
+    }
+
+    pub fn import_lutris_config_command_string(
+        &self,
+        input: ImportLauncherConfigInput,
+    ) -> CommandStringResult<ImportLauncherConfigOutput> {
+        self.import_lutris_config(input)
+            .into_command_string_result()
+    }
+
+    pub fn import_heroic_config_command_string(
+        &self,
+        input: ImportLauncherConfigInput,
+    ) -> CommandStringResult<ImportLauncherConfigOutput> {
+        self.import_heroic_config(input)
+            .into_command_string_result()
+    }
+
+    pub fn import_bottles_config_command_string(
+        &self,
+        input: ImportLauncherConfigInput,
+    ) -> CommandStringResult<ImportLauncherConfigOutput> {
+        self.import_bottles_config(input)
+            .into_command_string_result()
+    }
+
+    fn finish(
+        &self,
+        source: &str,
+        input: &ImportLauncherConfigInput,
+        imported: ImportedGameConfig,
+    ) -> BackendResult<ImportLauncherConfigOutput> {
+        let payload_json = serde_json::to_string_pretty(&imported.config)?;
+
+        self.log_info(
+            "GO-CR-607",
+            "import_launcher_config_completed",
+            serde_json::json!({
+                "source": source,
+                "path": &input.path,
+                "payload_len": payload_json.len(),
+                "warnings": imported.warnings.len(),
+            }),
+        );
+
+        Ok(ImportLauncherConfigOutput {
+            payload_json,
+            warnings: imported.warnings,
+        })
+    }
+
+    fn read_utf8_file(&self, path: &str) -> BackendResult<String> {
+        let bytes = self
+            .file_system
+            .read_bytes(Path::new(path))
+            .map_err(|err| err.with_context("failed to read launcher config file"))?;
+
+        String::from_utf8(bytes)
+            .map_err(BackendError::from)
+            .map_err(|err| err.with_context("launcher config file is not valid UTF-8"))
+    }
+
+    fn log_info(&self, event_code: &str, message: &str, context: serde_json::Value) {
+        let _ = self.logger.log(&BackendLogEvent {
+            level: BackendLogLevel::Info,
+            event_code: event_code.to_string(),
+            message: message.to_string(),
+            context,
+        });
+    }
+}
+
+pub fn import_lutris_config_command(
+    input: ImportLauncherConfigInput,
+    file_system: &dyn FileSystemPort,
+    luthier_core: &dyn LuthierCorePort,
+    logger: &dyn BackendLoggerPort,
+) -> CommandStringResult<ImportLauncherConfigOutput> {
+    ConfigImportUseCase::new(file_system, luthier_core, logger)
+        .import_lutris_config_command_string(input)
+}
+
+pub fn import_heroic_config_command(
+    input: ImportLauncherConfigInput,
+    file_system: &dyn FileSystemPort,
+    luthier_core: &dyn LuthierCorePort,
+    logger: &dyn BackendLoggerPort,
+) -> CommandStringResult<ImportLauncherConfigOutput> {
+    ConfigImportUseCase::new(file_system, luthier_core, logger)
+        .import_heroic_config_command_string(input)
+}
+
+pub fn import_bottles_config_command(
+    input: ImportLauncherConfigInput,
+    file_system: &dyn FileSystemPort,
+    luthier_core: &dyn LuthierCorePort,
+    logger: &dyn BackendLoggerPort,
+) -> CommandStringResult<ImportLauncherConfigOutput> {
+    ConfigImportUseCase::new(file_system, luthier_core, logger)
+        .import_bottles_config_command_string(input)
+}
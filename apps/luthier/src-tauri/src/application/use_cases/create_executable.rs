@@ -52,6 +52,7 @@ impl<'a> CreateExecutableUseCase<'a> {
                 "make_executable": input.make_executable,
                 "hero_image_bytes_len": input.hero_image_bytes.as_ref().map(|bytes| bytes.len()),
                 "icon_png_bytes_len": input.icon_png_bytes.as_ref().map(|bytes| bytes.len()),
+                "signed": input.signing_key_path.is_some(),
                 "hints_count": base_binary_hints.len(),
             }),
         );
@@ -86,6 +87,12 @@ impl<'a> CreateExecutableUseCase<'a> {
             icon_png_bytes,
             backup_existing: input.backup_existing,
             make_executable: input.make_executable,
+            signing_key_path: input
+                .signing_key_path
+                .as_deref()
+                .map(str::trim)
+                .filter(|path| !path.is_empty())
+                .map(PathBuf::from),
         };
 
         self.luthier_core
@@ -0,0 +1,222 @@
+use std::path::PathBuf;
+
+use luthier_core::{
+    DesktopEntryInstallRequest, DesktopEntryUninstallRequest, SteamShortcutExportRequest,
+};
+
+use crate::application::ports::{
+    BackendLogEvent, BackendLogLevel, BackendLoggerPort, LuthierCorePort, RuntimeEnvironmentPort,
+};
+use crate::error::{BackendError, BackendResult, BackendResultExt, CommandStringResult};
+use crate::models::dto::{
+    ExportSteamShortcutInput, ExportSteamShortcutOutput, InstallDesktopEntryInput,
+    InstallDesktopEntryOutput, UninstallDesktopEntryInput, UninstallDesktopEntryOutput,
+};
+
+/// Registers generated launchers with Steam and the desktop menu.
+pub struct LauncherExportUseCase<'a> {
+    luthier_core: &'a dyn LuthierCorePort,
+    runtime_environment: &'a dyn RuntimeEnvironmentPort,
+    logger: &'a dyn BackendLoggerPort,
+}
+
+impl<'a> LauncherExportUseCase<'a> {
+    pub fn new(
+        luthier_core: &'a dyn LuthierCorePort,
+        runtime_environment: &'a dyn RuntimeEnvironmentPort,
+        logger: &'a dyn BackendLoggerPort,
+    ) -> Self {
+        Self {
+            luthier_core,
+            runtime_environment,
+            logger,
+        }
+    }
+
+    pub fn export_steam_shortcut(
+        &self,
+        input: ExportSteamShortcutInput,
+    ) -> BackendResult<ExportSteamShortcutOutput> {
+        self.log_info(
+            "GO-CR-701",
+            "export_steam_shortcut_requested",
+            serde_json::json!({
+                "launcher_path": &input.launcher_path,
+                "steam_user_dir": &input.steam_user_dir,
+            }),
+        );
+
+        let result = self
+            .luthier_core
+            .export_steam_shortcut(&SteamShortcutExportRequest {
+                launcher_path: PathBuf::from(&input.launcher_path),
+                steam_user_dir: PathBuf::from(&input.steam_user_dir),
+                app_name: input.app_name,
+            })
+            .map_err(|err| err.with_context("failed to export steam shortcut"))?;
+
+        self.log_info(
+            "GO-CR-702",
+            "export_steam_shortcut_completed",
+            serde_json::json!({
+                "app_id": result.app_id,
+                "shortcuts_path": &result.shortcuts_path,
+                "created": result.created,
+                "artwork_files": result.artwork_paths.len(),
+            }),
+        );
+
+        Ok(ExportSteamShortcutOutput {
+            app_id: result.app_id,
+            app_name: result.app_name,
+            shortcuts_path: result.shortcuts_path,
+            created: result.created,
+            artwork_paths: result.artwork_paths,
+        })
+    }
+
+    pub fn install_desktop_entry(
+        &self,
+        input: InstallDesktopEntryInput,
+    ) -> BackendResult<InstallDesktopEntryOutput> {
+        self.log_info(
+            "GO-CR-703",
+            "install_desktop_entry_requested",
+            serde_json::json!({ "launcher_path": &input.launcher_path }),
+        );
+
+        let data_home = self.resolve_data_home(input.data_home.as_deref())?;
+        let result = self
+            .luthier_core
+            .install_desktop_entry(&DesktopEntryInstallRequest {
+                launcher_path: PathBuf::from(&input.launcher_path),
+                data_home,
+                name: input.name,
+            })
+            .map_err(|err| err.with_context("failed to install desktop entry"))?;
+
+        self.log_info(
+            "GO-CR-704",
+            "install_desktop_entry_completed",
+            serde_json::json!({
+                "desktop_file": &result.desktop_file,
+                "created": result.created,
+                "icon_files": result.icon_files.len(),
+            }),
+        );
+
+        Ok(InstallDesktopEntryOutput {
+            desktop_id: result.desktop_id,
+            desktop_file: result.desktop_file,
+            icon_files: result.icon_files,
+            created: result.created,
+        })
+    }
+
+    pub fn uninstall_desktop_entry(
+        &self,
+        input: UninstallDesktopEntryInput,
+    ) -> BackendResult<UninstallDesktopEntryOutput> {
+        self.log_info(
+            "GO-CR-705",
+            "uninstall_desktop_entry_requested",
+            serde_json::json!({ "launcher_path": &input.launcher_path }),
+        );
+
+        let data_home = self.resolve_data_home(input.data_home.as_deref())?;
+        let result = self
+            .luthier_core
+            .uninstall_desktop_entry(&DesktopEntryUninstallRequest {
+                launcher_path: PathBuf::from(&input.launcher_path),
+                data_home,
+            })
+            .map_err(|err| err.with_context("failed to uninstall desktop entry"))?;
+
+        self.log_info(
+            "GO-CR-706",
+            "uninstall_desktop_entry_completed",
+            serde_json::json!({
+                "desktop_id": &result.desktop_id,
+                "removed_files": result.removed_files.len(),
+            }),
+        );
+
+        Ok(UninstallDesktopEntryOutput {
+            desktop_id: result.desktop_id,
+            removed_files: result.removed_files,
+        })
+    }
+
+    pub fn export_steam_shortcut_command_string(
+        &self,
+        input: ExportSteamShortcutInput,
+    ) -> CommandStringResult<ExportSteamShortcutOutput> {
+        self.export_steam_shortcut(input)
+            .into_command_string_result()
+    }
+
+    pub fn install_desktop_entry_command_string(
+        &self,
+        input: InstallDesktopEntryInput,
+    ) -> CommandStringResult<InstallDesktopEntryOutput> {
+        self.install_desktop_entry(input)
+            .into_command_string_result()
+    }
+
+    pub fn uninstall_desktop_entry_command_string(
+        &self,
+        input: UninstallDesktopEntryInput,
+    ) -> CommandStringResult<UninstallDesktopEntryOutput> {
+        self.uninstall_desktop_entry(input)
+            .into_command_string_result()
+    }
+
+    fn resolve_data_home(&self, requested: Option<&str>) -> BackendResult<PathBuf> {
+        match requested.map(str::trim).filter(|path| !path.is_empty()) {
+            Some(path) => Ok(PathBuf::from(path)),
+            None => self
+                .runtime_environment
+                .xdg_data_home()
+                .ok_or_else(|| BackendError::validation("neither XDG_DATA_HOME nor HOME is set")),
+        }
+    }
+
+    fn log_info(&self, event_code: &str, message: &str, context: serde_json::Value) {
+        let _ = self.logger.log(&BackendLogEvent {
+            level: BackendLogLevel::Info,
+            event_code: event_code.to_string(),
+            message: message.to_string(),
+            context,
+        });
+    }
+}
+
+pub fn export_steam_shortcut_command(
+    input: ExportSteamShortcutInput,
+    luthier_core: &dyn LuthierCorePort,
+    runtime_environment: &dyn RuntimeEnvironmentPort,
+    logger: &dyn BackendLoggerPort,
+) -> CommandStringResult<ExportSteamShortcutOutput> {
+    LauncherExportUseCase::new(luthier_core, runtime_environment, logger)
+        .export_steam_shortcut_command_string(input)
+}
+
+pub fn install_desktop_entry_command(
+    input: InstallDesktopEntryInput,
+    luthier_core: &dyn LuthierCorePort,
+    runtime_environment: &dyn RuntimeEnvironmentPort,
+    logger: &dyn BackendLoggerPort,
+) -> CommandStringResult<InstallDesktopEntryOutput> {
+    LauncherExportUseCase::new(luthier_core, runtime_environment, logger)
+        .install_desktop_entry_command_string(input)
+}
+
+pub fn uninstall_desktop_entry_command(
+    input: UninstallDesktopEntryInput,
+    luthier_core: &dyn LuthierCorePort,
+    runtime_environment: &dyn RuntimeEnvironmentPort,
+    logger: &dyn BackendLoggerPort,
+) -> CommandStringResult<UninstallDesktopEntryOutput> {
+    LauncherExportUseCase::new(luthier_core, runtime_environment, logger)
+        .uninstall_desktop_entry_command_string(input)
+}
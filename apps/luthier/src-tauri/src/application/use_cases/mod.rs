@@ -1,7 +1,9 @@
+pub mod config_import;
 pub mod create_executable;
 pub mod extract_icon;
 pub mod hash_executable;
 pub mod import_registry;
+pub mod launcher_export;
 pub mod list_fs;
 pub mod payload_import;
 pub mod prepare_hero;
@@ -5,6 +5,7 @@ use luthier_orchestrator_core::asset_container::parse_asset_container;
 use luthier_orchestrator_core::asset_container::{
     append_asset_container, AssetContainerWriteInput,
 };
+use luthier_orchestrator_core::config_migration::migrate_config_value;
 
 use crate::application::ports::{
     BackendLogEvent, BackendLogLevel, BackendLoggerPort, FileSystemPort,
@@ -41,7 +42,7 @@ impl<'a> PayloadImportUseCase<'a> {
             return Err(BackendError::validation("payload json file is empty"));
         }
 
-        let out = ReadPayloadFileOutput { payload_json };
+        let out = self.migrate_payload_json(&input.path, &payload_json)?;
 
         self.log_info(
             "GO-CR-602",
@@ -73,9 +74,12 @@ impl<'a> PayloadImportUseCase<'a> {
         let parsed_container = parse_asset_container(&executable_bytes)
             .map_err(BackendError::from)
             .map_err(|err| err.with_context("failed to extract payload from orchestrator"))?;
-        let payload_bytes = parsed_container.config_json();
+        let payload_bytes = parsed_container
+            .config_json()
+            .map_err(BackendError::from)
+            .map_err(|err| err.with_context("failed to decode payload from orchestrator"))?;
 
-        let payload_json = std::str::from_utf8(payload_bytes)
+        let payload_json = std::str::from_utf8(&payload_bytes)
             .map_err(BackendError::from)
             .map_err(|err| err.with_context("payload bytes are not valid UTF-8"))?
             .to_owned();
@@ -86,7 +90,7 @@ impl<'a> PayloadImportUseCase<'a> {
             ));
         }
 
-        let out = ReadPayloadFileOutput { payload_json };
+        let out = self.migrate_payload_json(&input.path, &payload_json)?;
 
         self.log_info(
             "GO-CR-604",
@@ -116,6 +120,43 @@ impl<'a> PayloadImportUseCase<'a> {
             .into_command_string_result()
     }
 
+    /// Upgrades older payloads to the current `config_version` before the
+    /// frontend parses them, so imports never reinterpret legacy shapes.
+    fn migrate_payload_json(
+        &self,
+        path: &str,
+        payload_json: &str,
+    ) -> BackendResult<ReadPayloadFileOutput> {
+        let mut value: serde_json::Value = serde_json::from_str(payload_json)
+            .map_err(BackendError::from)
+            .map_err(|err| err.with_context("payload is not valid JSON"))?;
+        let report = migrate_config_value(&mut value)
+            .map_err(BackendError::from)
+            .map_err(|err| err.with_context("failed to migrate payload config"))?;
+
+        if report.is_noop() {
+            return Ok(ReadPayloadFileOutput {
+                payload_json: payload_json.to_string(),
+                migration: None,
+            });
+        }
+
+        self.log_info(
+            "GO-CR-605",
+            "payload_config_migrated",
+            serde_json::json!({
+                "path": path,
+                "from_version": report.from_version,
+                "to_version": report.to_version,
+                "changes": report.changes.len(),
+            }),
+        );
+        Ok(ReadPayloadFileOutput {
+            payload_json: serde_json::to_string_pretty(&value)?,
+            migration: Some(report),
+        })
+    }
+
     fn read_utf8_file(&self, path: &str) -> BackendResult<String> {
         let bytes = self
             .file_system
@@ -212,7 +253,10 @@ mod tests {
 
     #[test]
     fn reads_payload_json_file_as_utf8_text() {
-        let fs = FakeFileSystem::default().with_file("/tmp/payload.json", br#"{"a":1}"#.to_vec());
+        let fs = FakeFileSystem::default().with_file(
+            "/tmp/payload.json",
+            br#"{"config_version":4,"a":1}"#.to_vec(),
+        );
         let logger = NoopLogger;
         let use_case = PayloadImportUseCase::new(&fs, &logger);
 
@@ -222,18 +266,21 @@ mod tests {
             })
             .expect("should read payload json");
 
-        assert_eq!(output.payload_json, r#"{"a":1}"#);
+        assert_eq!(output.payload_json, r#"{"config_version":4,"a":1}"#);
+        assert!(output.migration.is_none());
     }
 
     #[test]
     fn extracts_payload_json_from_orchestrator_binary() {
-        let payload = br#"{"game_name":"Age3"}"#;
+        let payload = br#"{"config_version":4,"game_name":"Age3"}"#;
         let injected = append_asset_container(
             b"ELF-MOCK",
             AssetContainerWriteInput {
                 config_json: payload,
                 hero_image: None,
                 icon_png: None,
+                named_assets: &[],
+                signing_key: None,
             },
         )
         .expect("container build");
@@ -248,6 +295,33 @@ mod tests {
             })
             .expect("should extract embedded payload");
 
-        assert_eq!(output.payload_json, r#"{"game_name":"Age3"}"#);
+        assert_eq!(
+            output.payload_json,
+            r#"{"config_version":4,"game_name":"Age3"}"#
+        );
+    }
+
+    #[test]
+    fn migrates_legacy_payload_before_returning_it() {
+        let fs = FakeFileSystem::default().with_file(
+            "/tmp/legacy.json",
+            br#"{"config_version":1,"environment":{"prime_offload":true}}"#.to_vec(),
+        );
+        let logger = NoopLogger;
+        let use_case = PayloadImportUseCase::new(&fs, &logger);
+
+        let output = use_case
+            .read_payload_json_file(ReadPayloadFileInput {
+                path: "/tmp/legacy.json".to_string(),
+            })
+            .expect("should migrate payload json");
+
+        let value: serde_json::Value =
+            serde_json::from_str(&output.payload_json).expect("migrated json");
+        assert_eq!(value["config_version"], 4);
+        assert_eq!(value["environment"]["prime_offload"], "OptionalOn");
+        let report = output.migration.expect("migration report");
+        assert_eq!(report.from_version, 1);
+        assert_eq!(report.changes[0].field, "environment.prime_offload");
     }
 }
@@ -2,7 +2,11 @@ use std::env;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use luthier_core::{CreateOrchestratorRequest, CreateOrchestratorResult};
+use luthier_core::{
+    CreateOrchestratorRequest, CreateOrchestratorResult, DesktopEntryInstallRequest,
+    DesktopEntryInstallResult, DesktopEntryUninstallRequest, DesktopEntryUninstallResult,
+    ImportedGameConfig, SteamShortcutExportRequest, SteamShortcutExportResult,
+};
 use luthier_orchestrator_core::{doctor::DoctorReport, prefix::PrefixSetupPlan, GameConfig};
 
 use crate::application::{
@@ -27,13 +31,15 @@ use crate::infrastructure::{
 use crate::models::hero::HeroSearchResult;
 
 pub use crate::models::dto::{
-    CreateExecutableInput, CreateExecutableOutput, ExtractExecutableIconInput,
-    ExtractExecutableIconOutput, HashExeInput, HashExeOutput, ImportRegistryFileInput,
-    ImportRegistryFileOutput, ListChildDirectoriesInput, ListChildDirectoriesOutput,
+    CreateExecutableInput, CreateExecutableOutput, ExportSteamShortcutInput,
+    ExportSteamShortcutOutput, ExtractExecutableIconInput, ExtractExecutableIconOutput,
+    HashExeInput, HashExeOutput, ImportLauncherConfigInput, ImportLauncherConfigOutput,
+    ImportRegistryFileInput, ImportRegistryFileOutput, InstallDesktopEntryInput,
+    InstallDesktopEntryOutput, ListChildDirectoriesInput, ListChildDirectoriesOutput,
     ListDirectoryEntriesInput, ListDirectoryEntriesOutput, PrepareHeroImageInput,
     PrepareHeroImageOutput, ReadPayloadFileInput, ReadPayloadFileOutput, SearchHeroImageInput,
     SearchHeroImageOutput, TestConfigurationInput, TestConfigurationOutput,
-    WinetricksAvailableOutput,
+    UninstallDesktopEntryInput, UninstallDesktopEntryOutput, WinetricksAvailableOutput,
 };
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -54,6 +60,52 @@ impl LuthierCorePort for NativeLuthierCoreAdapter {
     fn validate_game_config(&self, config: &GameConfig) -> BackendResult<()> {
         luthier_core::validate_game_config(config).map_err(Into::into)
     }
+
+    fn import_lutris_config(
+        &self,
+        source: &str,
+        game_root: &Path,
+    ) -> BackendResult<ImportedGameConfig> {
+        luthier_core::import_lutris_config(source, game_root).map_err(Into::into)
+    }
+
+    fn import_heroic_config(
+        &self,
+        source: &str,
+        game_root: &Path,
+        exe: Option<&str>,
+    ) -> BackendResult<ImportedGameConfig> {
+        luthier_core::import_heroic_config(source, game_root, exe).map_err(Into::into)
+    }
+
+    fn import_bottles_config(
+        &self,
+        source: &str,
+        game_root: &Path,
+    ) -> BackendResult<ImportedGameConfig> {
+        luthier_core::import_bottles_config(source, game_root).map_err(Into::into)
+    }
+
+    fn export_steam_shortcut(
+        &self,
+        request: &SteamShortcutExportRequest,
+    ) -> BackendResult<SteamShortcutExportResult> {
+        luthier_core::export_steam_shortcut(request).map_err(Into::into)
+    }
+
+    fn install_desktop_entry(
+        &self,
+        request: &DesktopEntryInstallRequest,
+    ) -> BackendResult<DesktopEntryInstallResult> {
+        luthier_core::install_desktop_entry(request).map_err(Into::into)
+    }
+
+    fn uninstall_desktop_entry(
+        &self,
+        request: &DesktopEntryUninstallRequest,
+    ) -> BackendResult<DesktopEntryUninstallResult> {
+        luthier_core::uninstall_desktop_entry(request).map_err(Into::into)
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -157,6 +209,10 @@ impl RuntimeEnvironmentPort for NativeRuntimeEnvironmentAdapter {
             .map(|value| env::split_paths(&value).collect())
             .unwrap_or_default()
     }
+
+    fn xdg_data_home(&self) -> Option<PathBuf> {
+        luthier_core::xdg_data_home()
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default)]
@@ -339,3 +395,87 @@ pub fn extract_payload_json_from_orchestrator(
         &logger,
     )
 }
+
+pub fn import_lutris_config(
+    input: ImportLauncherConfigInput,
+) -> Result<ImportLauncherConfigOutput, String> {
+    let file_system = LocalFileSystemRepository::new();
+    let luthier_core = NativeLuthierCoreAdapter;
+    let logger = StderrJsonBackendLogger::new();
+    use_cases::config_import::import_lutris_config_command(
+        input,
+        &file_system,
+        &luthier_core,
+        &logger,
+    )
+}
+
+pub fn import_heroic_config(
+    input: ImportLauncherConfigInput,
+) -> Result<ImportLauncherConfigOutput, String> {
+    let file_system = LocalFileSystemRepository::new();
+    let luthier_core = NativeLuthierCoreAdapter;
+    let logger = StderrJsonBackendLogger::new();
+    use_cases::config_import::import_heroic_config_command(
+        input,
+        &file_system,
+        &luthier_core,
+        &logger,
+    )
+}
+
+pub fn import_bottles_config(
+    input: ImportLauncherConfigInput,
+) -> Result<ImportLauncherConfigOutput, String> {
+    let file_system = LocalFileSystemRepository::new();
+    let luthier_core = NativeLuthierCoreAdapter;
+    let logger = StderrJsonBackendLogger::new();
+    use_cases::config_import::import_bottles_config_command(
+        input,
+        &file_system,
+        &luthier_core,
+        &logger,
+    )
+}
+
+pub fn export_steam_shortcut(
+    input: ExportSteamShortcutInput,
+) -> Result<ExportSteamShortcutOutput, String> {
+    let luthier_core = NativeLuthierCoreAdapter;
+    let runtime_environment = NativeRuntimeEnvironmentAdapter;
+    let logger = StderrJsonBackendLogger::new();
+    use_cases::launcher_export::export_steam_shortcut_command(
+        input,
+        &luthier_core,
+        &runtime_environment,
+        &logger,
+    )
+}
+
+pub fn install_desktop_entry(
+    input: InstallDesktopEntryInput,
+) -> Result<InstallDesktopEntryOutput, String> {
+    let luthier_core = NativeLuthierCoreAdapter;
+    let runtime_environment = NativeRuntimeEnvironmentAdapter;
+    let logger = StderrJsonBackendLogger::new();
+    use_cases::launcher_export::install_desktop_entry_command(
+        input,
+        &luthier_core,
+        &runtime_environment,
+        &logger,
+    )
+}
+
+pub fn uninstall_desktop_entry(
+    input: UninstallDesktopEntryInput,
+) -> Result<UninstallDesktopEntryOutput, String> {
+    let luthier_core = NativeLuthierCoreAdapter;
+    let runtime_environment = NativeRuntimeEnvironmentAdapter;
+    let logger = StderrJsonBackendLogger::new();
+    use_cases::launcher_export::uninstall_desktop_entry_command(
+        input,
+        &luthier_core,
+        &runtime_environment,
+        &logger,
+    )
+}
@@ -6,14 +6,18 @@ mod infrastructure;
 mod models;
 
 pub use crate::facade::{
-    create_executable, create_executable_with_base_hints, extract_executable_icon,
-    extract_payload_json_from_orchestrator, hash_executable, import_registry_file,
-    list_child_directories, list_directory_entries, prepare_hero_image, read_payload_json_file,
-    search_hero_image, test_configuration, winetricks_available, CreateExecutableInput,
-    CreateExecutableOutput, ExtractExecutableIconInput, ExtractExecutableIconOutput, HashExeInput,
-    HashExeOutput, ImportRegistryFileInput, ImportRegistryFileOutput, ListChildDirectoriesInput,
-    ListChildDirectoriesOutput, ListDirectoryEntriesInput, ListDirectoryEntriesOutput,
-    PrepareHeroImageInput, PrepareHeroImageOutput, ReadPayloadFileInput, ReadPayloadFileOutput,
-    SearchHeroImageInput, SearchHeroImageOutput, TestConfigurationInput, TestConfigurationOutput,
-    WinetricksAvailableOutput,
+    create_executable, create_executable_with_base_hints, export_steam_shortcut,
+    extract_executable_icon, extract_payload_json_from_orchestrator, hash_executable,
+    import_bottles_config, import_heroic_config, import_lutris_config, import_registry_file,
+    install_desktop_entry, list_child_directories, list_directory_entries, prepare_hero_image,
+    read_payload_json_file, search_hero_image, test_configuration, uninstall_desktop_entry,
+    winetricks_available, CreateExecutableInput, CreateExecutableOutput, ExportSteamShortcutInput,
+    ExportSteamShortcutOutput, ExtractExecutableIconInput, ExtractExecutableIconOutput,
+    HashExeInput, HashExeOutput, ImportLauncherConfigInput, ImportLauncherConfigOutput,
+    ImportRegistryFileInput, ImportRegistryFileOutput, InstallDesktopEntryInput,
+    InstallDesktopEntryOutput, ListChildDirectoriesInput, ListChildDirectoriesOutput,
+    ListDirectoryEntriesInput, ListDirectoryEntriesOutput, PrepareHeroImageInput,
+    PrepareHeroImageOutput, ReadPayloadFileInput, ReadPayloadFileOutput, SearchHeroImageInput,
+    SearchHeroImageOutput, TestConfigurationInput, TestConfigurationOutput,
+    UninstallDesktopEntryInput, UninstallDesktopEntryOutput, WinetricksAvailableOutput,
 };
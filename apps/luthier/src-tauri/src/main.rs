@@ -3,16 +3,20 @@ use std::path::PathBuf;
 
 #[cfg(feature = "tauri-commands")]
 use luthier_backend::{
-    create_executable_with_base_hints, extract_executable_icon,
-    extract_payload_json_from_orchestrator, hash_executable, import_registry_file,
+    create_executable_with_base_hints, export_steam_shortcut, extract_executable_icon,
+    extract_payload_json_from_orchestrator, hash_executable, import_bottles_config,
+    import_heroic_config, import_lutris_config, import_registry_file, install_desktop_entry,
     list_child_directories, list_directory_entries, prepare_hero_image, read_payload_json_file,
-    search_hero_image, test_configuration, winetricks_available, CreateExecutableInput,
-    CreateExecutableOutput, ExtractExecutableIconInput, ExtractExecutableIconOutput, HashExeInput,
-    HashExeOutput, ImportRegistryFileInput, ImportRegistryFileOutput, ListChildDirectoriesInput,
-    ListChildDirectoriesOutput, ListDirectoryEntriesInput, ListDirectoryEntriesOutput,
-    PrepareHeroImageInput, PrepareHeroImageOutput, ReadPayloadFileInput, ReadPayloadFileOutput,
-    SearchHeroImageInput, SearchHeroImageOutput, TestConfigurationInput, TestConfigurationOutput,
-    WinetricksAvailableOutput,
+    search_hero_image, test_configuration, uninstall_desktop_entry, winetricks_available,
+    CreateExecutableInput, CreateExecutableOutput, ExportSteamShortcutInput,
+    ExportSteamShortcutOutput, ExtractExecutableIconInput, ExtractExecutableIconOutput,
+    HashExeInput, HashExeOutput, ImportLauncherConfigInput, ImportLauncherConfigOutput,
+    ImportRegistryFileInput, ImportRegistryFileOutput, InstallDesktopEntryInput,
+    InstallDesktopEntryOutput, ListChildDirectoriesInput, ListChildDirectoriesOutput,
+    ListDirectoryEntriesInput, ListDirectoryEntriesOutput, PrepareHeroImageInput,
+    PrepareHeroImageOutput, ReadPayloadFileInput, ReadPayloadFileOutput, SearchHeroImageInput,
+    SearchHeroImageOutput, TestConfigurationInput, TestConfigurationOutput,
+    UninstallDesktopEntryInput, UninstallDesktopEntryOutput, WinetricksAvailableOutput,
 };
 #[cfg(feature = "tauri-commands")]
 use tauri::async_runtime::spawn_blocking;
@@ -143,6 +147,66 @@ async fn cmd_extract_payload_json_from_orchestrator(
     .await
 }
 
+#[cfg(feature = "tauri-commands")]
+#[tauri::command]
+async fn cmd_import_lutris_config(
+    input: ImportLauncherConfigInput,
+) -> CommandResult<ImportLauncherConfigOutput> {
+    run_blocking_command("import lutris config", move || import_lutris_config(input)).await
+}
+
+#[cfg(feature = "tauri-commands")]
+#[tauri::command]
+async fn cmd_import_heroic_config(
+    input: ImportLauncherConfigInput,
+) -> CommandResult<ImportLauncherConfigOutput> {
+    run_blocking_command("import heroic config", move || import_heroic_config(input)).await
+}
+
+#[cfg(feature = "tauri-commands")]
+#[tauri::command]
+async fn cmd_import_bottles_config(
+    input: ImportLauncherConfigInput,
+) -> CommandResult<ImportLauncherConfigOutput> {
+    run_blocking_command("import bottles config", move || {
+        import_bottles_config(input)
+    })
+    .await
+}
+
+#[cfg(feature = "tauri-commands")]
+#[tauri::command]
+async fn cmd_export_steam_shortcut(
+    input: ExportSteamShortcutInput,
+) -> CommandResult<ExportSteamShortcutOutput> {
+    run_blocking_command("export steam shortcut", move || {
+        export_steam_shortcut(input)
+    })
+    .await
+}
+
+#[cfg(feature = "tauri-commands")]
+#[tauri::command]
+async fn cmd_install_desktop_entry(
+    input: InstallDesktopEntryInput,
+) -> CommandResult<InstallDesktopEntryOutput> {
+    run_blocking_command("install desktop entry", move || {
+        install_desktop_entry(input)
+    })
+    .await
+}
+
+#[cfg(feature = "tauri-commands")]
+#[tauri::command]
+async fn cmd_uninstall_desktop_entry(
+    input: UninstallDesktopEntryInput,
+) -> CommandResult<UninstallDesktopEntryOutput> {
+    run_blocking_command("uninstall desktop entry", move || {
+        uninstall_desktop_entry(input)
+    })
+    .await
+}
+
 #[cfg(feature = "tauri-commands")]
 #[tauri::command]
 async fn cmd_search_hero_image(
@@ -173,6 +237,12 @@ fn main() {
             cmd_list_directory_entries,
             cmd_read_payload_json_file,
             cmd_extract_payload_json_from_orchestrator,
+            cmd_import_lutris_config,
+            cmd_import_heroic_config,
+            cmd_import_bottles_config,
+            cmd_export_steam_shortcut,
+            cmd_install_desktop_entry,
+            cmd_uninstall_desktop_entry,
             cmd_search_hero_image,
             cmd_prepare_hero_image
         ])
@@ -1,3 +1,5 @@
+use luthier_core::ConfigValidationIssue;
+use luthier_orchestrator_core::config_migration::ConfigMigrationReport;
 use serde::{Deserialize, Serialize};
 
 pub use super::registry::{ImportRegistryFileInput, ImportRegistryFileOutput};
@@ -13,6 +15,8 @@ pub struct CreateExecutableInput {
     pub hero_image_bytes: Option<Vec<u8>>,
     #[serde(default)]
     pub icon_png_bytes: Option<Vec<u8>>,
+    #[serde(default)]
+    pub signing_key_path: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -126,4 +130,79 @@ pub struct ReadPayloadFileInput {
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ReadPayloadFileOutput {
     pub payload_json: String,
+    /// Config migrations applied to the payload before it was returned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub migration: Option<ConfigMigrationReport>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportLauncherConfigInput {
+    /// Game definition exported by the other launcher.
+    pub path: String,
+    pub game_root: String,
+    /// Heroic only: the game executable, for settings files without `targetExe`.
+    #[serde(default)]
+    pub exe_path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ImportLauncherConfigOutput {
+    pub payload_json: String,
+    /// Settings that were not carried over, plus validation issues of the
+    /// generated config.
+    pub warnings: Vec<ConfigValidationIssue>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportSteamShortcutInput {
+    pub launcher_path: String,
+    /// `userdata/<account id>` inside the Steam installation.
+    pub steam_user_dir: String,
+    /// Shortcut title; the launcher's embedded game name when omitted.
+    #[serde(default)]
+    pub app_name: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ExportSteamShortcutOutput {
+    pub app_id: u32,
+    pub app_name: String,
+    pub shortcuts_path: String,
+    /// False when the launcher's existing shortcut was updated.
+    pub created: bool,
+    pub artwork_paths: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InstallDesktopEntryInput {
+    pub launcher_path: String,
+    /// Menu title; the launcher's embedded game name when omitted.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Overrides `$XDG_DATA_HOME`.
+    #[serde(default)]
+    pub data_home: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InstallDesktopEntryOutput {
+    pub desktop_id: String,
+    pub desktop_file: String,
+    pub icon_files: Vec<String>,
+    /// False when the launcher's existing entry was rewritten.
+    pub created: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UninstallDesktopEntryInput {
+    pub launcher_path: String,
+    #[serde(default)]
+    pub data_home: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UninstallDesktopEntryOutput {
+    /// None when no entry was installed for the launcher.
+    pub desktop_id: Option<String>,
+    pub removed_files: Vec<String>,
 }
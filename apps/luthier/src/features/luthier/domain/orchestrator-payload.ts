@@ -186,6 +186,10 @@ export function parseImportedGameConfigJson(raw: string): GameConfig {
   const environment = parsed.environment as Record<string, unknown>
   requireObject(environment, 'gamescope')
   requireObject(environment, 'custom_vars')
+  if (environment.env_edits === undefined) {
+    environment.env_edits = []
+  }
+  requireArray(environment, 'env_edits')
 
   const compatibility = parsed.compatibility as Record<string, unknown>
   requireArray(compatibility, 'wrapper_commands')
@@ -204,8 +208,28 @@ export function parseImportedGameConfigJson(raw: string): GameConfig {
   requireString(splash, 'hero_image_url')
 
   const scripts = parsed.scripts as Record<string, unknown>
-  requireString(scripts, 'pre_launch')
-  requireString(scripts, 'post_launch')
+  for (const phase of ['pre_launch', 'post_launch']) {
+    if (scripts[phase] === undefined) {
+      scripts[phase] = []
+    }
+    requireArray(scripts, phase)
+  }
+
+  if (parsed.profiles === undefined) {
+    parsed.profiles = []
+  }
+  requireArray(parsed, 'profiles')
+  if (parsed.rules === undefined) {
+    parsed.rules = []
+  }
+  requireArray(parsed, 'rules')
+  if (parsed.launch_entries === undefined) {
+    parsed.launch_entries = []
+  }
+  requireArray(parsed, 'launch_entries')
+  if (parsed.default_entry === undefined) {
+    parsed.default_entry = null
+  }
 
   return parsed as GameConfig
 }
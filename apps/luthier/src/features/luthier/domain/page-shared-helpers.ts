@@ -6,7 +6,7 @@
  * keeping business logic decoupled from the view layer.
  */
 
-import type { FeatureState } from '../../../models/config'
+import type { FeatureState, ScriptStep } from '../../../models/config'
 
 export function isLikelyAbsolutePath(path: string) {
   const trimmed = path.trim()
@@ -81,3 +81,40 @@ export function buildFeatureState(enabled: boolean, mandatory: boolean): Feature
   if (enabled) return mandatory ? 'MandatoryOn' : 'OptionalOn'
   return mandatory ? 'MandatoryOff' : 'OptionalOff'
 }
+
+/**
+ * The scripts textareas edit one inline bash step per phase, named like the
+ * steps created when legacy string scripts are migrated. Other steps are kept.
+ */
+export function inlineScriptBody(steps: ScriptStep[], name: string): string {
+  return steps.find((step) => step.name === name && step.interpreter === 'bash')?.command ?? ''
+}
+
+export function withInlineScript(
+  steps: ScriptStep[],
+  name: string,
+  body: string,
+  mandatory: boolean
+): ScriptStep[] {
+  const index = steps.findIndex((step) => step.name === name && step.interpreter === 'bash')
+  if (!body.trim()) {
+    return index < 0 ? steps : steps.filter((_, i) => i !== index)
+  }
+  if (index >= 0) {
+    return steps.map((step, i) => (i === index ? { ...step, command: body } : step))
+  }
+  return [
+    ...steps,
+    {
+      name,
+      interpreter: 'bash',
+      command: body,
+      args: [],
+      cwd: null,
+      timeout_secs: 600,
+      mandatory,
+      run_once: false,
+      background: false,
+    },
+  ]
+}
@@ -2,6 +2,7 @@ import { describe, expect, it } from 'vitest'
 
 import { defaultGameConfig, type GameConfig } from '../../../models/config'
 import { luthierTranslate } from '../copy'
+import { withInlineScript } from './page-shared-helpers'
 import {
   buildConfigurationSummary,
   type SummaryBuilderContext,
@@ -139,8 +140,8 @@ describe('summary builder', () => {
           config.winecfg.desktop_integration = { state: 'OptionalOn', use_wine_default: false }
           config.winecfg.mime_associations = { state: 'OptionalOn', use_wine_default: false }
 
-          config.scripts.pre_launch = 'echo pre'
-          config.scripts.post_launch = 'echo post'
+          config.scripts.pre_launch = withInlineScript([], 'pre-launch', 'echo pre', true)
+          config.scripts.post_launch = withInlineScript([], 'post-launch', 'echo post', false)
           config.compatibility.wrapper_commands = [
             {
               state: 'OptionalOn',
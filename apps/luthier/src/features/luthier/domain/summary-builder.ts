@@ -103,8 +103,8 @@ export function buildConfigurationSummary(ctx: SummaryBuilderContext): SummaryRo
   ]
 
   const scriptEnvItems = [
-    cfg.scripts.pre_launch.trim() ? 'pre-launch' : '',
-    cfg.scripts.post_launch.trim() ? 'post-launch' : '',
+    cfg.scripts.pre_launch.length > 0 ? 'pre-launch' : '',
+    cfg.scripts.post_launch.length > 0 ? 'post-launch' : '',
     cfg.compatibility.wrapper_commands.length > 0
       ? `${ct('luthier_wrappers')}: ${cfg.compatibility.wrapper_commands.length}`
       : '',
@@ -38,4 +38,6 @@ export {
   featureStateEnabled,
   featureStateMandatory,
   buildFeatureState,
+  inlineScriptBody,
+  withInlineScript,
 } from './domain/page-shared-helpers'
@@ -42,9 +42,11 @@ import {
   buildFeatureState,
   featureStateEnabled,
   featureStateMandatory,
+  inlineScriptBody,
   posixDirname,
   relativeInsideBase,
   SwitchChoiceCard,
+  withInlineScript,
   type LuthierPageSectionProps,
 } from '../page-shared'
 import { LaunchWrapperCommandsPanel } from './launch-wrapper-commands-panel'
@@ -84,12 +86,20 @@ export function LaunchEnvironmentTabSection(props: LuthierPageSectionProps) {
             footer={
               <Textarea
                 rows={8}
-                value={config().scripts.pre_launch}
+                value={inlineScriptBody(config().scripts.pre_launch, 'pre-launch')}
                 placeholder="#!/usr/bin/env bash\necho Preparando..."
                 onInput={(e) =>
                   patchConfig((prev) => ({
                     ...prev,
-                    scripts: { ...prev.scripts, pre_launch: e.currentTarget.value },
+                    scripts: {
+                      ...prev.scripts,
+                      pre_launch: withInlineScript(
+                        prev.scripts.pre_launch,
+                        'pre-launch',
+                        e.currentTarget.value,
+                        true
+                      ),
+                    },
                   }))
                 }
               />
@@ -105,12 +115,20 @@ export function LaunchEnvironmentTabSection(props: LuthierPageSectionProps) {
             footer={
               <Textarea
                 rows={8}
-                value={config().scripts.post_launch}
+                value={inlineScriptBody(config().scripts.post_launch, 'post-launch')}
                 placeholder="#!/usr/bin/env bash\necho Finalizado..."
                 onInput={(e) =>
                   patchConfig((prev) => ({
                     ...prev,
-                    scripts: { ...prev.scripts, post_launch: e.currentTarget.value },
+                    scripts: {
+                      ...prev.scripts,
+                      post_launch: withInlineScript(
+                        prev.scripts.post_launch,
+                        'post-launch',
+                        e.currentTarget.value,
+                        false
+                      ),
+                    },
                   }))
                 }
               />
@@ -1,6 +1,20 @@
 export type FeatureState = 'MandatoryOn' | 'MandatoryOff' | 'OptionalOn' | 'OptionalOff'
 export type WinecfgFeaturePolicy = { state: FeatureState; use_wine_default: boolean }
 
+export type ScriptInterpreter = 'bash' | 'sh' | 'prefix'
+
+export type ScriptStep = {
+  name: string
+  interpreter: ScriptInterpreter
+  command: string
+  args: string[]
+  cwd: string | null
+  timeout_secs: number
+  mandatory: boolean
+  run_once: boolean
+  background: boolean
+}
+
 export type RuntimePrimary = 'ProtonUmu' | 'ProtonNative' | 'Wine'
 
 export type RuntimePreference = 'Auto' | 'Proton' | 'Wine'
@@ -50,6 +64,12 @@ export type GameConfig = {
     mangohud: FeatureState
     prime_offload: FeatureState
     custom_vars: Record<string, string>
+    env_edits: Array<{
+      name: string
+      value: string
+      mode: 'append' | 'prepend'
+      separator: string
+    }>
   }
   compatibility: {
     wine_wayland: FeatureState
@@ -114,14 +134,40 @@ export type GameConfig = {
     hero_image_url: string
   }
   scripts: {
-    pre_launch: string
-    post_launch: string
+    pre_launch: ScriptStep[]
+    post_launch: ScriptStep[]
   }
+  profiles: Array<{ name: string; description: string; overlay: Record<string, unknown> }>
+  rules: Array<{
+    name: string
+    when: {
+      session_type?: Array<'wayland' | 'x11' | 'tty'>
+      gpu_vendor?: Array<'nvidia' | 'amd' | 'intel'>
+      hybrid_gpu?: boolean | null
+      os_id?: string[]
+      env_present?: string[]
+      screen?: {
+        min_width?: number | null
+        max_width?: number | null
+        min_height?: number | null
+        max_height?: number | null
+      } | null
+    }
+    patch: Record<string, unknown>
+  }>
+  launch_entries: Array<{
+    name: string
+    relative_exe_path: string
+    launch_args: string[]
+    working_dir: string | null
+    env: Record<string, string>
+  }>
+  default_entry: string | null
 }
 
 export function defaultGameConfig(): GameConfig {
   return {
-    config_version: 1,
+    config_version: 4,
     created_by: 'luthier-ui',
     game_name: '',
     exe_hash: '',
@@ -155,6 +201,7 @@ export function defaultGameConfig(): GameConfig {
       mangohud: 'OptionalOff',
       prime_offload: 'OptionalOff',
       custom_vars: {},
+      env_edits: [],
     },
     compatibility: {
       wine_wayland: 'OptionalOff',
@@ -214,8 +261,12 @@ export function defaultGameConfig(): GameConfig {
       hero_image_url: '',
     },
     scripts: {
-      pre_launch: '',
-      post_launch: '',
+      pre_launch: [],
+      post_launch: [],
     },
+    profiles: [],
+    rules: [],
+    launch_entries: [],
+    default_entry: null,
   }
 }
@@ -0,0 +1,130 @@
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context};
+use clap::ValueEnum;
+use serde_json::Value;
+
+/// Authoring formats accepted for a `GameConfig`. All of them map one-to-one onto
+/// the JSON document; launchers always embed canonical JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConfigFormat {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl ConfigFormat {
+    pub fn from_path(path: &Path) -> anyhow::Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("json") => Ok(Self::Json),
+            Some("toml") => Ok(Self::Toml),
+            Some("yaml" | "yml") => Ok(Self::Yaml),
+            _ => bail!(
+                "cannot tell the config format of {} (expected .json, .toml, .yaml or .yml)",
+                path.display()
+            ),
+        }
+    }
+
+    pub fn parse(self, raw: &[u8]) -> anyhow::Result<Value> {
+        match self {
+            Self::Json => serde_json::from_slice(raw).context("invalid JSON"),
+            Self::Toml => {
+                let text = std::str::from_utf8(raw).context("TOML must be UTF-8")?;
+                toml::from_str(text).context("invalid TOML")
+            }
+            Self::Yaml => serde_yaml::from_slice(raw).context("invalid YAML"),
+        }
+    }
+
+    pub fn render(self, value: &Value) -> anyhow::Result<String> {
+        match self {
+            Self::Json => {
+                let mut out = serde_json::to_string_pretty(value)?;
+                out.push('\n');
+                Ok(out)
+            }
+            // TOML has no null; absent keys read back as `None`.
+            Self::Toml => toml::to_string_pretty(&without_nulls(value))
+                .map_err(|err| anyhow!("failed to render TOML: {err}")),
+            Self::Yaml => serde_yaml::to_string(value).context("failed to render YAML"),
+        }
+    }
+}
+
+fn without_nulls(value: &Value) -> Value {
+    match value {
+        Value::Object(object) => Value::Object(
+            object
+                .iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key.clone(), without_nulls(value)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(without_nulls).collect()),
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Value {
+        serde_json::json!({
+            "game_name": "Sample",
+            "launch_args": ["-windowed"],
+            "winecfg": {
+                "windows_version": null,
+                "dll_overrides": [{ "dll": "d3d9", "mode": "native,builtin" }]
+            },
+            "environment": { "custom_vars": { "DXVK_HUD": "fps" } }
+        })
+    }
+
+    #[test]
+    fn detects_format_from_extension() {
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("game.TOML")).expect("toml"),
+            ConfigFormat::Toml
+        );
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("game.yml")).expect("yml"),
+            ConfigFormat::Yaml
+        );
+        assert!(ConfigFormat::from_path(Path::new("game.txt")).is_err());
+    }
+
+    #[test]
+    fn every_format_roundtrips_the_same_document() {
+        let mut expected = sample();
+        expected["winecfg"]
+            .as_object_mut()
+            .expect("winecfg")
+            .remove("windows_version");
+
+        for format in [ConfigFormat::Json, ConfigFormat::Toml, ConfigFormat::Yaml] {
+            let rendered = format.render(&sample()).expect("render");
+            let parsed = format.parse(rendered.as_bytes()).expect("parse");
+            assert_eq!(without_nulls(&parsed), expected, "{format:?}");
+        }
+    }
+
+    #[test]
+    fn reads_commented_toml_recipes() {
+        let raw = br#"
+            game_name = "Sample" # shown in the splash
+
+            # Native d3d9 for the bundled ENB.
+            [[winecfg.dll_overrides]]
+            dll = "d3d9"
+            mode = "native,builtin"
+        "#;
+        let value = ConfigFormat::Toml.parse(raw).expect("parse");
+        assert_eq!(value["winecfg"]["dll_overrides"][0]["dll"], "d3d9");
+    }
+}
@@ -1,12 +1,29 @@
+mod config_format;
+
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use anyhow::Context;
 use clap::{Parser, Subcommand};
+use config_format::ConfigFormat;
 use luthier_core::{
-    create_orchestrator_binary, sha256_file, validate_game_config, CreateOrchestratorRequest,
+    create_orchestrator_binary, export_steam_shortcut, import_bottles_config, import_heroic_config,
+    import_lutris_config, install_desktop_entry, resolve_config_templates, sha256_file,
+    uninstall_desktop_entry, validate_game_config, xdg_data_home, ConfigDocumentLoaderPort,
+    CreateOrchestratorRequest, DesktopEntryInstallRequest, DesktopEntryUninstallRequest,
+    ImportedGameConfig, LuthierError, SteamShortcutExportRequest,
+};
+use luthier_orchestrator_core::asset_container::parse_asset_container;
+use luthier_orchestrator_core::config_migration::migrate_config_value;
+use luthier_orchestrator_core::config_schema::{game_config_schema, validate_game_config_value};
+use luthier_orchestrator_core::injector::{
+    check_repack_signature, launcher_base_sha256_hex, read_manifest_from_file, rebase_launcher,
+    replace_assets, AssetChange, AssetReplacement, InjectOptions, RewrittenSignature,
+};
+use luthier_orchestrator_core::signing::{PayloadSigningKey, TrustedKey};
+use luthier_orchestrator_core::{
+    doctor::run_doctor, prefix::build_prefix_setup_plan, GameConfig, OrchestratorError,
 };
-use luthier_orchestrator_core::{doctor::run_doctor, prefix::build_prefix_setup_plan, GameConfig};
 
 #[derive(Debug, Parser)]
 #[command(name = "luthier-cli")]
@@ -28,6 +45,38 @@ enum Command {
         #[arg(long)]
         game_root: PathBuf,
     },
+    /// Print the JSON Schema of the current GameConfig version.
+    Schema,
+    /// Print a config with every template it extends merged in.
+    Resolve {
+        #[arg(long)]
+        config: PathBuf,
+        #[arg(long, value_enum, default_value = "json")]
+        to: ConfigFormat,
+    },
+    /// Convert a config or template between JSON, TOML and YAML.
+    Convert {
+        #[arg(long)]
+        input: PathBuf,
+        #[arg(long, help = "Output file; prints to stdout when omitted")]
+        output: Option<PathBuf>,
+        #[arg(
+            long,
+            value_enum,
+            help = "Target format; defaults to the output file extension"
+        )]
+        to: Option<ConfigFormat>,
+    },
+    /// Build a config from another launcher's game definition.
+    Import {
+        #[command(subcommand)]
+        source: ImportSource,
+    },
+    /// Register a generated launcher with another application.
+    Export {
+        #[command(subcommand)]
+        target: ExportTarget,
+    },
     Create {
         #[arg(long)]
         base: PathBuf,
@@ -35,20 +84,174 @@ enum Command {
         config: PathBuf,
         #[arg(long)]
         output: PathBuf,
+        #[arg(long, help = "Ed25519 key file used to sign the embedded manifest")]
+        signing_key: Option<PathBuf>,
+    },
+    /// Replace the payload of an existing launcher without its base binary.
+    Repack {
+        #[arg(long)]
+        launcher: PathBuf,
+        #[arg(long)]
+        config: Option<PathBuf>,
+        #[arg(long)]
+        hero_image: Option<PathBuf>,
+        #[arg(long)]
+        icon_png: Option<PathBuf>,
+        #[arg(
+            long,
+            help = "Write to another path instead of updating the launcher in place"
+        )]
+        output: Option<PathBuf>,
+        #[arg(long, help = "Ed25519 key file used to sign the embedded manifest")]
+        signing_key: Option<PathBuf>,
+        #[arg(
+            long,
+            conflicts_with = "signing_key",
+            help = "Write a signed launcher back unsigned instead of refusing"
+        )]
+        drop_signature: bool,
+        #[arg(long)]
+        no_backup: bool,
+    },
+    /// Move launchers onto a newer orchestrator base binary, keeping their payloads.
+    Rebase {
+        #[arg(long, help = "New orchestrator base binary (without a payload)")]
+        base: PathBuf,
+        #[arg(
+            required = true,
+            value_name = "LAUNCHER_OR_DIR",
+            help = "Launcher files, or directories scanned (non-recursively) for launchers"
+        )]
+        targets: Vec<PathBuf>,
+        #[arg(long, help = "Only report what would change")]
+        dry_run: bool,
+        #[arg(long, help = "Ed25519 key file used to re-sign the rebased manifests")]
+        signing_key: Option<PathBuf>,
+        #[arg(long)]
+        no_backup: bool,
+    },
+    /// Generate an Ed25519 key file for signing launchers.
+    Keygen {
+        #[arg(long)]
+        signer: String,
+        #[arg(long)]
+        out: PathBuf,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ImportSource {
+    /// Lutris game config (`~/.config/lutris/games/*.yml`) or installer script.
+    Lutris {
+        file: PathBuf,
+        #[command(flatten)]
+        target: ImportTarget,
+    },
+    /// Heroic per-game settings (`GamesConfig/<app>.json`).
+    Heroic {
+        file: PathBuf,
+        #[arg(
+            long,
+            help = "Game executable, absolute or relative to --game-root; required when the file has no targetExe"
+        )]
+        exe: Option<PathBuf>,
+        #[command(flatten)]
+        target: ImportTarget,
+    },
+    /// Bottles `bottle.yml`; extra programs become launch entries.
+    Bottles {
+        file: PathBuf,
+        #[command(flatten)]
+        target: ImportTarget,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ExportTarget {
+    /// Add or update a non-Steam shortcut in `shortcuts.vdf`. Close Steam first;
+    /// it overwrites the file on exit.
+    Steam {
+        #[arg(long)]
+        launcher: PathBuf,
+        #[arg(long, help = "Steam userdata directory of the account (userdata/<id>)")]
+        steam_user_dir: PathBuf,
+        #[arg(long, help = "Shortcut title; defaults to the embedded game name")]
+        name: Option<String>,
+    },
+    /// Install a menu entry and icon under $XDG_DATA_HOME, or remove it.
+    Desktop {
+        #[arg(long)]
+        launcher: PathBuf,
+        #[arg(long, help = "Menu title; defaults to the embedded game name")]
+        name: Option<String>,
+        #[arg(
+            long,
+            help = "Data directory to install into instead of $XDG_DATA_HOME"
+        )]
+        data_home: Option<PathBuf>,
+        #[arg(long, conflicts_with = "name")]
+        uninstall: bool,
     },
 }
 
+#[derive(Debug, clap::Args)]
+struct ImportTarget {
+    #[arg(long, help = "Directory the game is installed in")]
+    game_root: PathBuf,
+    #[arg(long, help = "Output file; prints to stdout when omitted")]
+    output: Option<PathBuf>,
+    #[arg(
+        long,
+        value_enum,
+        help = "Target format; defaults to the output file extension, or JSON on stdout"
+    )]
+    to: Option<ConfigFormat>,
+}
+
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
         Command::Hash { exe } => run_hash(exe),
         Command::Test { config, game_root } => run_test(config, game_root),
+        Command::Schema => run_schema(),
+        Command::Resolve { config, to } => run_resolve(&config, to),
+        Command::Convert { input, output, to } => run_convert(&input, output.as_deref(), to),
+        Command::Import { source } => run_import(source),
+        Command::Export { target } => run_export(target),
         Command::Create {
             base,
             config,
             output,
-        } => run_create(base, config, output),
+            signing_key,
+        } => run_create(base, config, output, signing_key),
+        Command::Repack {
+            launcher,
+            config,
+            hero_image,
+            icon_png,
+            output,
+            signing_key,
+            drop_signature,
+            no_backup,
+        } => run_repack(RepackArgs {
+            launcher,
+            config,
+            hero_image,
+            icon_png,
+            output,
+            signing_key,
+            drop_signature,
+            no_backup,
+        }),
+        Command::Rebase {
+            base,
+            targets,
+            dry_run,
+            signing_key,
+            no_backup,
+        } => run_rebase(&base, &targets, dry_run, signing_key.as_deref(), no_backup),
+        Command::Keygen { signer, out } => run_keygen(&signer, &out),
     }
 }
 
@@ -59,7 +262,23 @@ fn run_hash(exe: PathBuf) -> anyhow::Result<()> {
 }
 
 fn run_test(config_path: PathBuf, game_root: PathBuf) -> anyhow::Result<()> {
-    let config: GameConfig = load_config(&config_path)?;
+    let value = load_config_value(&config_path)?;
+    let schema_issues = validate_game_config_value(&value);
+    if !schema_issues.is_empty() {
+        let output = serde_json::json!({
+            "status": "BLOCKER",
+            "schema_issues": schema_issues,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        anyhow::bail!(
+            "{} does not match the GameConfig schema ({} issue(s))",
+            config_path.display(),
+            schema_issues.len()
+        );
+    }
+
+    let config: GameConfig = serde_json::from_value(value)
+        .with_context(|| format!("invalid config at {}", config_path.display()))?;
     validate_game_config(&config)?;
 
     let missing_files = collect_missing_files(&config, &game_root)?;
@@ -78,6 +297,7 @@ fn run_test(config_path: PathBuf, game_root: PathBuf) -> anyhow::Result<()> {
 
     let output = serde_json::json!({
         "status": status,
+        "schema_issues": schema_issues,
         "missing_files": missing_files,
         "doctor": doctor,
         "prefix_setup_plan": prefix_plan,
@@ -87,7 +307,143 @@ fn run_test(config_path: PathBuf, game_root: PathBuf) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn run_create(base: PathBuf, config_path: PathBuf, output: PathBuf) -> anyhow::Result<()> {
+fn run_schema() -> anyhow::Result<()> {
+    println!("{}", serde_json::to_string_pretty(&game_config_schema())?);
+    Ok(())
+}
+
+fn run_convert(
+    input: &Path,
+    output: Option<&Path>,
+    to: Option<ConfigFormat>,
+) -> anyhow::Result<()> {
+    let format = match (to, output) {
+        (Some(format), _) => format,
+        (None, Some(output)) => ConfigFormat::from_path(output)?,
+        (None, None) => anyhow::bail!("--to is required when writing to stdout"),
+    };
+
+    // Documents are converted as written, so templates and `extends` survive.
+    let document = FormatAwareDocumentLoader.load_document(input)?;
+    let rendered = format.render(&document)?;
+
+    match output {
+        Some(output) => fs::write(output, rendered)
+            .with_context(|| format!("failed to write {}", output.display()))?,
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+fn run_import(source: ImportSource) -> anyhow::Result<()> {
+    let (file, target) = match &source {
+        ImportSource::Lutris { file, target }
+        | ImportSource::Heroic { file, target, .. }
+        | ImportSource::Bottles { file, target } => (file, target),
+    };
+
+    let game_root = fs::canonicalize(&target.game_root)
+        .with_context(|| format!("failed to resolve {}", target.game_root.display()))?;
+    let source_text =
+        fs::read_to_string(file).with_context(|| format!("failed to read {}", file.display()))?;
+    let imported = match &source {
+        ImportSource::Lutris { .. } => import_lutris_config(&source_text, &game_root),
+        ImportSource::Heroic { exe, .. } => {
+            let exe = exe.as_deref().map(Path::to_string_lossy);
+            import_heroic_config(&source_text, &game_root, exe.as_deref())
+        }
+        ImportSource::Bottles { .. } => import_bottles_config(&source_text, &game_root),
+    };
+    let ImportedGameConfig { config, warnings } =
+        imported.with_context(|| format!("failed to import {}", file.display()))?;
+
+    for warning in &warnings {
+        eprintln!(
+            "warning: {} ({}): {}",
+            warning.field, warning.code, warning.message
+        );
+    }
+
+    let format = match (target.to, target.output.as_deref()) {
+        (Some(format), _) => format,
+        (None, Some(output)) => ConfigFormat::from_path(output)?,
+        (None, None) => ConfigFormat::Json,
+    };
+    let rendered = format.render(&serde_json::to_value(&config)?)?;
+
+    match &target.output {
+        Some(output) => fs::write(output, rendered)
+            .with_context(|| format!("failed to write {}", output.display()))?,
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
+
+fn run_export(target: ExportTarget) -> anyhow::Result<()> {
+    match target {
+        ExportTarget::Steam {
+            launcher,
+            steam_user_dir,
+            name,
+        } => {
+            let launcher_path = fs::canonicalize(&launcher)
+                .with_context(|| format!("failed to resolve {}", launcher.display()))?;
+            let result = export_steam_shortcut(&SteamShortcutExportRequest {
+                launcher_path,
+                steam_user_dir,
+                app_name: name,
+            })
+            .with_context(|| format!("failed to export {}", launcher.display()))?;
+
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+        ExportTarget::Desktop {
+            launcher,
+            name,
+            data_home,
+            uninstall,
+        } => {
+            let data_home = match data_home.or_else(xdg_data_home) {
+                Some(data_home) => data_home,
+                None => anyhow::bail!("neither XDG_DATA_HOME nor HOME is set; pass --data-home"),
+            };
+            // The launcher may already be gone when its entry is removed.
+            let launcher_path = std::path::absolute(&launcher)
+                .with_context(|| format!("failed to resolve {}", launcher.display()))?;
+
+            if uninstall {
+                let result = uninstall_desktop_entry(&DesktopEntryUninstallRequest {
+                    launcher_path,
+                    data_home,
+                })
+                .with_context(|| format!("failed to uninstall {}", launcher.display()))?;
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            } else {
+                let result = install_desktop_entry(&DesktopEntryInstallRequest {
+                    launcher_path,
+                    data_home,
+                    name,
+                })
+                .with_context(|| format!("failed to install {}", launcher.display()))?;
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn run_resolve(config_path: &Path, to: ConfigFormat) -> anyhow::Result<()> {
+    let config = load_config(config_path)?;
+    print!("{}", to.render(&serde_json::to_value(&config)?)?);
+    Ok(())
+}
+
+fn run_create(
+    base: PathBuf,
+    config_path: PathBuf,
+    output: PathBuf,
+    signing_key: Option<PathBuf>,
+) -> anyhow::Result<()> {
     let config: GameConfig = load_config(&config_path)?;
 
     let result = create_orchestrator_binary(&CreateOrchestratorRequest {
@@ -98,18 +454,307 @@ fn run_create(base: PathBuf, config_path: PathBuf, output: PathBuf) -> anyhow::R
         icon_png_bytes: None,
         backup_existing: true,
         make_executable: true,
+        signing_key_path: signing_key,
     })?;
 
     println!("{}", serde_json::to_string_pretty(&result)?);
     Ok(())
 }
 
-fn load_config(path: &PathBuf) -> anyhow::Result<GameConfig> {
-    let raw = fs::read_to_string(path)
+struct RepackArgs {
+    launcher: PathBuf,
+    config: Option<PathBuf>,
+    hero_image: Option<PathBuf>,
+    icon_png: Option<PathBuf>,
+    output: Option<PathBuf>,
+    signing_key: Option<PathBuf>,
+    drop_signature: bool,
+    no_backup: bool,
+}
+
+fn run_repack(args: RepackArgs) -> anyhow::Result<()> {
+    let config_json = match &args.config {
+        Some(path) => {
+            let config = load_config(path)?;
+            validate_game_config(&config)?;
+            Some(serde_json::to_vec_pretty(&config)?)
+        }
+        None => None,
+    };
+    let read_asset = |path: &PathBuf| -> anyhow::Result<AssetChange> {
+        let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+        Ok(AssetChange::Replace(bytes))
+    };
+    let replacement = AssetReplacement {
+        config_json,
+        hero_image: args
+            .hero_image
+            .as_ref()
+            .map(read_asset)
+            .transpose()?
+            .unwrap_or_default(),
+        icon_png: args
+            .icon_png
+            .as_ref()
+            .map(read_asset)
+            .transpose()?
+            .unwrap_or_default(),
+        ..AssetReplacement::default()
+    };
+    let signing_key = load_signing_key(args.signing_key.as_deref())?;
+    let options = InjectOptions {
+        backup_existing: !args.no_backup,
+        make_executable: true,
+        signing_key,
+    };
+    let signature = check_repack_signature(&args.launcher, &options, args.drop_signature)
+        .with_context(|| format!("refusing to repack {}", args.launcher.display()))?;
+
+    let output = args.output.as_ref().unwrap_or(&args.launcher);
+    let result = replace_assets(&args.launcher, &replacement, output, options)
+        .with_context(|| format!("failed to repack {}", args.launcher.display()))?;
+
+    let summary = serde_json::json!({
+        "output_path": result.output_path.to_string_lossy(),
+        "config_size_bytes": result.config_len,
+        "config_sha256_hex": result.config_sha256_hex,
+        "signature": signature.as_str(),
+    });
+    println!("{}", serde_json::to_string_pretty(&summary)?);
+    Ok(())
+}
+
+fn run_rebase(
+    base: &Path,
+    targets: &[PathBuf],
+    dry_run: bool,
+    signing_key: Option<&Path>,
+    no_backup: bool,
+) -> anyhow::Result<()> {
+    let new_base = fs::read(base).with_context(|| format!("failed to read {}", base.display()))?;
+    if parse_asset_container(&new_base).is_ok() {
+        anyhow::bail!(
+            "{} already carries a payload; pass a clean orchestrator build as --base",
+            base.display()
+        );
+    }
+    let new_base_sha256 = sha256_file(base)?;
+    let signing_key = load_signing_key(signing_key)?;
+
+    let launchers = collect_launchers(targets)?;
+    if launchers.is_empty() {
+        anyhow::bail!("no launchers found in the given targets");
+    }
+
+    let mut failures = 0;
+    let mut reports = Vec::with_capacity(launchers.len());
+    for launcher in &launchers {
+        let outcome = rebase_one(
+            launcher,
+            &new_base,
+            &new_base_sha256,
+            dry_run,
+            InjectOptions {
+                backup_existing: !no_backup,
+                make_executable: true,
+                signing_key: signing_key.clone(),
+            },
+        );
+        let report = match outcome {
+            Ok(report) => report,
+            Err(err) => {
+                failures += 1;
+                serde_json::json!({
+                    "launcher": launcher,
+                    "status": "failed",
+                    "error": format!("{err:#}"),
+                })
+            }
+        };
+        reports.push(report);
+    }
+
+    let output = serde_json::json!({
+        "base": base,
+        "new_base_sha256": new_base_sha256,
+        "dry_run": dry_run,
+        "launchers": reports,
+    });
+    println!("{}", serde_json::to_string_pretty(&output)?);
+
+    if failures > 0 {
+        anyhow::bail!(
+            "{failures} of {} launcher(s) failed to rebase",
+            launchers.len()
+        );
+    }
+    Ok(())
+}
+
+fn rebase_one(
+    launcher: &Path,
+    new_base: &[u8],
+    new_base_sha256: &str,
+    dry_run: bool,
+    options: InjectOptions,
+) -> anyhow::Result<serde_json::Value> {
+    let old_base_sha256 = launcher_base_sha256_hex(launcher)
+        .with_context(|| format!("failed to read payload of {}", launcher.display()))?;
+    let was_signed = read_manifest_from_file(launcher)?.signature.is_some();
+    let will_sign = options.signing_key.is_some();
+
+    let status = if old_base_sha256 == new_base_sha256 {
+        "up_to_date"
+    } else if dry_run {
+        "would_rebase"
+    } else {
+        rebase_launcher(launcher, new_base, launcher, options)
+            .with_context(|| format!("failed to rebase {}", launcher.display()))?;
+        let written = launcher_base_sha256_hex(launcher)?;
+        if written != new_base_sha256 {
+            anyhow::bail!(
+                "verification failed for {}: base hash is {written}",
+                launcher.display()
+            );
+        }
+        "rebased"
+    };
+    let signature = match status {
+        "up_to_date" if was_signed => "signed",
+        "up_to_date" => "unsigned",
+        _ => RewrittenSignature::after_rewrite(was_signed, will_sign).as_str(),
+    };
+
+    Ok(serde_json::json!({
+        "launcher": launcher,
+        "status": status,
+        "old_base_sha256": old_base_sha256,
+        "new_base_sha256": new_base_sha256,
+        "signature": signature,
+    }))
+}
+
+/// Expands directories into the launchers they contain. Files without a
+/// container are skipped when found by scanning, but explicit paths are kept so
+/// they show up as failures in the report.
+fn collect_launchers(targets: &[PathBuf]) -> anyhow::Result<Vec<PathBuf>> {
+    let mut launchers = Vec::new();
+    for target in targets {
+        if !target.is_dir() {
+            launchers.push(target.clone());
+            continue;
+        }
+
+        let mut entries = fs::read_dir(target)
+            .with_context(|| format!("failed to list {}", target.display()))?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        entries.sort();
+        for path in entries {
+            let is_backup = path.extension().is_some_and(|ext| ext == "bak");
+            if !path.is_file() || is_backup {
+                continue;
+            }
+            match read_manifest_from_file(&path) {
+                Err(
+                    OrchestratorError::ContainerNotFound | OrchestratorError::ContainerTruncated,
+                ) => {}
+                _ => launchers.push(path),
+            }
+        }
+    }
+    Ok(launchers)
+}
+
+fn load_signing_key(path: Option<&Path>) -> anyhow::Result<Option<PayloadSigningKey>> {
+    path.map(|path| {
+        PayloadSigningKey::from_key_file(path)
+            .with_context(|| format!("failed to load signing key {}", path.display()))
+    })
+    .transpose()
+}
+
+fn run_keygen(signer: &str, out: &Path) -> anyhow::Result<()> {
+    if out.exists() {
+        anyhow::bail!("refusing to overwrite existing key file {}", out.display());
+    }
+
+    let key = PayloadSigningKey::generate(signer)?;
+    fs::write(out, key.to_key_file_json()?)
+        .with_context(|| format!("failed to write key file {}", out.display()))?;
+    restrict_key_file_permissions(out)?;
+
+    // Printed in the trust store entry shape so it can be pasted into `keys`.
+    let trusted = TrustedKey {
+        signer: key.signer().to_string(),
+        public_key_hex: key.public_key_hex(),
+    };
+    println!("{}", serde_json::to_string_pretty(&trusted)?);
+    Ok(())
+}
+
+#[cfg(unix)]
+fn restrict_key_file_permissions(path: &Path) -> anyhow::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600))
+        .with_context(|| format!("failed to restrict permissions on {}", path.display()))
+}
+
+#[cfg(not(unix))]
+fn restrict_key_file_permissions(_path: &Path) -> anyhow::Result<()> {
+    Ok(())
+}
+
+fn load_config(path: &Path) -> anyhow::Result<GameConfig> {
+    let value = load_config_value(path)?;
+    serde_json::from_value(value).with_context(|| format!("invalid config at {}", path.display()))
+}
+
+/// Reads one JSON, TOML or YAML document as written.
+struct FormatAwareDocumentLoader;
+
+impl ConfigDocumentLoaderPort for FormatAwareDocumentLoader {
+    fn load_document(&self, path: &Path) -> Result<serde_json::Value, LuthierError> {
+        let invalid = |err: anyhow::Error| {
+            LuthierError::InvalidConfigTemplate(format!("{}: {err:#}", path.display()))
+        };
+        let format = ConfigFormat::from_path(path).map_err(invalid)?;
+        let raw = fs::read(path)?;
+        format.parse(&raw).map_err(invalid)
+    }
+}
+
+/// Reads a JSON, TOML or YAML config, flattens the templates it extends and
+/// migrates the result to the current `config_version`.
+fn load_config_value(path: &Path) -> anyhow::Result<serde_json::Value> {
+    let resolved = resolve_config_templates(path, &FormatAwareDocumentLoader)
         .with_context(|| format!("failed to read config {}", path.display()))?;
-    let cfg: GameConfig = serde_json::from_str(&raw)
-        .with_context(|| format!("invalid config json at {}", path.display()))?;
-    Ok(cfg)
+    let mut value = resolved.document;
+    let report = migrate_config_value(&mut value)
+        .with_context(|| format!("invalid config at {}", path.display()))?;
+
+    // stdout carries the command result; template and upgrade notes go to stderr.
+    if resolved.layers.len() > 1 {
+        let layers = resolved
+            .layers
+            .iter()
+            .map(|layer| layer.display().to_string())
+            .collect::<Vec<_>>();
+        eprintln!("{} resolved from {}", path.display(), layers.join(" <- "));
+    }
+    if !report.is_noop() {
+        eprintln!(
+            "{} migrated from config_version {} to {}:",
+            path.display(),
+            report.from_version,
+            report.to_version
+        );
+        for change in &report.changes {
+            eprintln!("  {}: {}", change.field, change.message);
+        }
+    }
+    Ok(value)
 }
 
 fn collect_missing_files(config: &GameConfig, game_root: &Path) -> anyhow::Result<Vec<String>> {
@@ -120,7 +765,12 @@ fn collect_missing_files(config: &GameConfig, game_root: &Path) -> anyhow::Resul
         missing.push(config.relative_exe_path.clone());
     }
 
-    for file in &config.integrity_files {
+    // Every launch entry must be present, not only the one a player picks.
+    let entry_exes = config
+        .launch_entries
+        .iter()
+        .map(|entry| &entry.relative_exe_path);
+    for file in config.integrity_files.iter().chain(entry_exes) {
         let path = resolve_relative_path(game_root, file)?;
         if !path.exists() {
             missing.push(file.clone());
@@ -170,3 +820,138 @@ fn has_windows_drive_prefix(path: &str) -> bool {
     let bytes = path.as_bytes();
     bytes.len() >= 2 && bytes[1] == b':' && bytes[0].is_ascii_alphabetic()
 }
+
+#[cfg(test)]
+mod tests {
+    use luthier_orchestrator_core::asset_container::{
+        append_asset_container, AssetContainerWriteInput,
+    };
+    use luthier_orchestrator_core::config_migration::{
+        load_game_config_json, CURRENT_CONFIG_VERSION,
+    };
+    use luthier_orchestrator_core::injector::extract_config_from_file;
+
+    use super::*;
+
+    /// Payload as written before config versions 2 and 3: legacy feature
+    /// states and plain-string scripts.
+    fn legacy_v1_config() -> serde_json::Value {
+        serde_json::json!({
+            "config_version": 1,
+            "created_by": "luthier",
+            "game_name": "Legacy Game",
+            "exe_hash": "a1b2c3",
+            "relative_exe_path": "./game.exe",
+            "launch_args": [],
+            "runner": {
+                "proton_version": "GE-Proton9-10",
+                "auto_update": false,
+                "esync": true,
+                "fsync": true,
+                "runtime_preference": "Auto"
+            },
+            "environment": {
+                "gamemode": "Enabled",
+                "gamescope": {
+                    "state": "Disabled",
+                    "resolution": null,
+                    "fsr": false,
+                    "game_width": "",
+                    "game_height": "",
+                    "output_width": "",
+                    "output_height": "",
+                    "upscale_method": "fsr",
+                    "window_type": "fullscreen",
+                    "enable_limiter": false,
+                    "fps_limiter": "",
+                    "fps_limiter_no_focus": "",
+                    "force_grab_cursor": false,
+                    "additional_options": ""
+                },
+                "mangohud": "Disabled",
+                "prime_offload": false,
+                "custom_vars": {}
+            },
+            "compatibility": {
+                "wine_wayland": "Disabled",
+                "hdr": "Disabled",
+                "auto_dxvk_nvapi": "Disabled",
+                "easy_anti_cheat_runtime": "Disabled",
+                "battleye_runtime": "Disabled",
+                "staging": "Disabled",
+                "wrapper_commands": []
+            },
+            "winecfg": {
+                "windows_version": null,
+                "dll_overrides": [],
+                "auto_capture_mouse": "Enabled",
+                "window_decorations": "Enabled",
+                "window_manager_control": "Enabled",
+                "virtual_desktop": { "state": "Disabled", "resolution": null },
+                "screen_dpi": null,
+                "desktop_integration": "Enabled",
+                "mime_associations": "Disabled",
+                "desktop_folders": [],
+                "drives": [],
+                "audio_driver": null
+            },
+            "dependencies": [],
+            "extra_system_dependencies": [],
+            "requirements": {
+                "runtime": { "strict": false, "primary": "ProtonNative", "fallback_order": ["Wine"] },
+                "umu": "Enabled",
+                "winetricks": "Disabled",
+                "gamescope": "Disabled",
+                "gamemode": "Enabled",
+                "mangohud": "Disabled",
+                "steam_runtime": "Disabled"
+            },
+            "registry_keys": [],
+            "integrity_files": [],
+            "folder_mounts": [],
+            "scripts": { "pre_launch": "echo ready", "post_launch": "" }
+        })
+    }
+
+    #[test]
+    fn rebases_launchers_built_with_a_legacy_config() {
+        let root =
+            std::env::temp_dir().join(format!("luthier-cli-rebase-legacy-{}", std::process::id()));
+        fs::create_dir_all(&root).expect("create temp dir");
+        let launcher = root.join("game");
+        let config_bytes = serde_json::to_vec(&legacy_v1_config()).expect("serialize config");
+        let binary = append_asset_container(
+            b"OLD-BASE",
+            AssetContainerWriteInput {
+                config_json: &config_bytes,
+                hero_image: None,
+                icon_png: None,
+                named_assets: &[],
+                signing_key: None,
+            },
+        )
+        .expect("write legacy launcher");
+        fs::write(&launcher, binary).expect("write launcher");
+        let base = root.join("base");
+        fs::write(&base, b"NEW-BASE").expect("write base");
+        let new_base_sha256 = sha256_file(&base).expect("hash base");
+
+        let report = rebase_one(
+            &launcher,
+            b"NEW-BASE",
+            &new_base_sha256,
+            false,
+            InjectOptions::default(),
+        )
+        .expect("rebase legacy launcher");
+
+        assert_eq!(report["status"], "rebased");
+        let migrated =
+            load_game_config_json(&extract_config_from_file(&launcher).expect("extract config"))
+                .expect("embedded config loads");
+        assert_eq!(migrated.report.from_version, CURRENT_CONFIG_VERSION);
+        assert_eq!(migrated.config.scripts.pre_launch[0].command, "echo ready");
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+}
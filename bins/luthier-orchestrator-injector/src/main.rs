@@ -1,22 +1,38 @@
+#![cfg_attr(test, allow(clippy::expect_used, clippy::unwrap_used))]
+
+use std::collections::HashMap;
+use std::fs;
 use std::io;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use anyhow::Context;
+use anyhow::{anyhow, Context};
 use clap::Parser;
-use luthier_orchestrator_core::injector::{inject_from_files, InjectOptions};
+use luthier_orchestrator_core::asset_container::DEFAULT_NAMED_ASSET_MIME_TYPE;
+use luthier_orchestrator_core::injector::{
+    check_repack_signature, inject_from_files, replace_assets, AssetChange, AssetReplacement,
+    ExtractedNamedAsset, InjectOptions, NamedAssetFile, RewrittenSignature,
+};
 use luthier_orchestrator_core::observability::{
     emit_ndjson, new_trace_id, LogEvent, LogIdentity, LogLevel,
 };
+use luthier_orchestrator_core::signing::PayloadSigningKey;
 
 #[derive(Debug, Parser)]
 #[command(name = "luthier-orchestrator-injector")]
 #[command(about = "Embeds typed assets into a base Luthier Orchestrator binary")]
 struct Cli {
-    #[arg(long)]
-    base: PathBuf,
+    #[arg(long, required_unless_present = "repack", conflicts_with = "repack")]
+    base: Option<PathBuf>,
 
-    #[arg(long)]
-    config: PathBuf,
+    #[arg(
+        long,
+        value_name = "LAUNCHER",
+        help = "Rewrite the payload of an existing launcher on its own base binary"
+    )]
+    repack: Option<PathBuf>,
+
+    #[arg(long, required_unless_present = "repack")]
+    config: Option<PathBuf>,
 
     #[arg(long)]
     hero_image: Option<PathBuf>,
@@ -24,8 +40,62 @@ struct Cli {
     #[arg(long)]
     icon_png: Option<PathBuf>,
 
-    #[arg(long)]
-    output: PathBuf,
+    #[arg(
+        long = "asset",
+        value_name = "NAME=PATH",
+        help = "Embed an extra named asset (repeatable)"
+    )]
+    assets: Vec<String>,
+
+    #[arg(
+        long = "asset-mime",
+        value_name = "NAME=MIME",
+        help = "MIME type for a named asset (guessed from the file extension by default)"
+    )]
+    asset_mimes: Vec<String>,
+
+    #[arg(
+        long = "asset-flags",
+        value_name = "NAME=BITS",
+        help = "Opaque flag bits stored with a named asset"
+    )]
+    asset_flags: Vec<String>,
+
+    #[arg(
+        long = "remove-asset",
+        value_name = "NAME",
+        requires = "repack",
+        help = "Drop a named asset from the repacked launcher (repeatable)"
+    )]
+    remove_assets: Vec<String>,
+
+    #[arg(long, requires = "repack", conflicts_with = "hero_image")]
+    remove_hero_image: bool,
+
+    #[arg(long, requires = "repack", conflicts_with = "icon_png")]
+    remove_icon_png: bool,
+
+    #[arg(
+        long,
+        required_unless_present = "repack",
+        help = "Output path (defaults to the launcher itself with --repack)"
+    )]
+    output: Option<PathBuf>,
+
+    #[arg(
+        long,
+        value_name = "KEY_FILE",
+        help = "Sign the embedded manifest with an Ed25519 key file"
+    )]
+    signing_key: Option<PathBuf>,
+
+    #[arg(
+        long,
+        requires = "repack",
+        conflicts_with = "signing_key",
+        help = "Write a signed launcher back unsigned instead of refusing"
+    )]
+    drop_signature: bool,
 
     #[arg(long)]
     no_backup: bool,
@@ -37,6 +107,15 @@ struct Cli {
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
     let trace_id = new_trace_id();
+    let named_assets = parse_named_assets(&cli.assets, &cli.asset_mimes, &cli.asset_flags)?;
+    let signing_key = cli
+        .signing_key
+        .as_deref()
+        .map(|path| {
+            PayloadSigningKey::from_key_file(path)
+                .with_context(|| format!("failed to load signing key {}", path.display()))
+        })
+        .transpose()?;
 
     log_event(
         &trace_id,
@@ -45,27 +124,51 @@ fn main() -> anyhow::Result<()> {
         "injector_started",
         serde_json::json!({
             "base": cli.base,
+            "repack": cli.repack,
             "config": cli.config,
             "hero_image": cli.hero_image,
             "icon_png": cli.icon_png,
+            "named_assets": named_assets.iter().map(|asset| &asset.name).collect::<Vec<_>>(),
             "output": cli.output,
+            "signer": signing_key.as_ref().map(PayloadSigningKey::signer),
         }),
     );
 
     let options = InjectOptions {
         backup_existing: !cli.no_backup,
         make_executable: !cli.no_exec_bit,
+        signing_key,
     };
 
-    let result = inject_from_files(
-        &cli.base,
-        &cli.config,
-        cli.hero_image.as_deref(),
-        cli.icon_png.as_deref(),
-        &cli.output,
-        options,
-    )
-    .context("failed to inject typed assets payload into binary")?;
+    let signer_identity = options
+        .signing_key
+        .as_ref()
+        .map(PayloadSigningKey::identity);
+    let mut signature = None;
+    let result = if let Some(launcher) = cli.repack.as_deref() {
+        let replacement = build_replacement(&cli, &named_assets)?;
+        signature = Some(
+            check_repack_signature(launcher, &options, cli.drop_signature)
+                .context("refusing to repack launcher")?,
+        );
+        let output = cli.output.as_deref().unwrap_or(launcher);
+        replace_assets(launcher, &replacement, output, options)
+            .context("failed to repack launcher payload")?
+    } else {
+        let (Some(base), Some(config), Some(output)) = (&cli.base, &cli.config, &cli.output) else {
+            return Err(anyhow!("--base, --config and --output are required"));
+        };
+        inject_from_files(
+            base,
+            config,
+            cli.hero_image.as_deref(),
+            cli.icon_png.as_deref(),
+            &named_assets,
+            output,
+            options,
+        )
+        .context("failed to inject typed assets payload into binary")?
+    };
 
     log_event(
         &trace_id,
@@ -76,16 +179,136 @@ fn main() -> anyhow::Result<()> {
             "output": result.output_path,
             "config_len": result.config_len,
             "config_sha256": result.config_sha256_hex,
+            "signature": signature.map(RewrittenSignature::as_str),
         }),
     );
 
     println!("Output: {}", result.output_path.display());
     println!("Config bytes: {}", result.config_len);
     println!("Config SHA-256: {}", result.config_sha256_hex);
+    if let Some(signer) = &signer_identity {
+        println!("Signed by: {} ({})", signer.signer, signer.fingerprint);
+    }
+    if signature == Some(RewrittenSignature::Dropped) {
+        println!("Signature: dropped");
+    }
 
     Ok(())
 }
 
+fn build_replacement(
+    cli: &Cli,
+    named_assets: &[NamedAssetFile],
+) -> anyhow::Result<AssetReplacement> {
+    let read =
+        |path: &Path| fs::read(path).with_context(|| format!("failed to read {}", path.display()));
+    let change = |path: Option<&Path>, remove: bool| -> anyhow::Result<AssetChange> {
+        Ok(match path {
+            Some(path) => AssetChange::Replace(read(path)?),
+            None if remove => AssetChange::Remove,
+            None => AssetChange::Keep,
+        })
+    };
+
+    Ok(AssetReplacement {
+        config_json: cli.config.as_deref().map(read).transpose()?,
+        hero_image: change(cli.hero_image.as_deref(), cli.remove_hero_image)?,
+        icon_png: change(cli.icon_png.as_deref(), cli.remove_icon_png)?,
+        named_assets: named_assets
+            .iter()
+            .map(|file| {
+                Ok(ExtractedNamedAsset {
+                    name: file.name.clone(),
+                    mime_type: file.mime_type.clone(),
+                    flags: file.flags,
+                    bytes: read(&file.path)?,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?,
+        remove_named_assets: cli.remove_assets.clone(),
+    })
+}
+
+fn parse_named_assets(
+    assets: &[String],
+    mimes: &[String],
+    flags: &[String],
+) -> anyhow::Result<Vec<NamedAssetFile>> {
+    let mut mime_by_name = HashMap::new();
+    for raw in mimes {
+        let (name, mime) = split_named_arg(raw, "--asset-mime")?;
+        mime_by_name.insert(name, mime.to_string());
+    }
+
+    let mut flags_by_name = HashMap::new();
+    for raw in flags {
+        let (name, bits) = split_named_arg(raw, "--asset-flags")?;
+        let bits = parse_flag_bits(bits)
+            .with_context(|| format!("invalid --asset-flags value for '{name}'"))?;
+        flags_by_name.insert(name, bits);
+    }
+
+    let mut out = Vec::with_capacity(assets.len());
+    for raw in assets {
+        let (name, path) = split_named_arg(raw, "--asset")?;
+        let path = PathBuf::from(path);
+        let mime_type = mime_by_name
+            .remove(name)
+            .unwrap_or_else(|| guess_mime_type(&path).to_string());
+        out.push(NamedAssetFile {
+            name: name.to_string(),
+            mime_type,
+            flags: flags_by_name.remove(name).unwrap_or(0),
+            path,
+        });
+    }
+
+    if let Some(name) = mime_by_name.keys().chain(flags_by_name.keys()).next() {
+        return Err(anyhow!("no --asset was given for named asset '{name}'"));
+    }
+
+    Ok(out)
+}
+
+fn split_named_arg<'a>(raw: &'a str, flag: &str) -> anyhow::Result<(&'a str, &'a str)> {
+    raw.split_once('=')
+        .map(|(name, value)| (name.trim(), value.trim()))
+        .filter(|(name, value)| !name.is_empty() && !value.is_empty())
+        .ok_or_else(|| anyhow!("{flag} expects NAME=VALUE, got '{raw}'"))
+}
+
+fn parse_flag_bits(raw: &str) -> anyhow::Result<u32> {
+    let parsed = if let Some(hex) = raw.strip_prefix("0x") {
+        u32::from_str_radix(hex, 16)
+    } else if let Some(bin) = raw.strip_prefix("0b") {
+        u32::from_str_radix(bin, 2)
+    } else {
+        raw.parse::<u32>()
+    };
+    parsed.map_err(|err| anyhow!(err))
+}
+
+fn guess_mime_type(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .and_then(|value| value.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "ico" => "image/vnd.microsoft.icon",
+        "json" => "application/json",
+        "txt" | "md" => "text/plain",
+        "pdf" => "application/pdf",
+        "ogg" => "audio/ogg",
+        "ttf" => "font/ttf",
+        _ => DEFAULT_NAMED_ASSET_MIME_TYPE,
+    }
+}
+
 fn log_event(
     trace_id: &str,
     level: LogLevel,
@@ -104,3 +327,69 @@ fn log_event(
     let mut stderr = io::stderr();
     let _ = emit_ndjson(&mut stderr, &event);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_named_assets_with_mime_and_flags() {
+        let assets = parse_named_assets(
+            &[
+                "manual=docs/manual.pdf".to_string(),
+                "cover=cover.png".to_string(),
+            ],
+            &["cover=image/x-custom".to_string()],
+            &["manual=0x3".to_string()],
+        )
+        .expect("parse named assets");
+
+        assert_eq!(assets.len(), 2);
+        assert_eq!(assets[0].name, "manual");
+        assert_eq!(assets[0].mime_type, "application/pdf");
+        assert_eq!(assets[0].flags, 3);
+        assert_eq!(assets[1].mime_type, "image/x-custom");
+        assert_eq!(assets[1].flags, 0);
+    }
+
+    #[test]
+    fn rejects_metadata_for_unknown_named_asset() {
+        let err = parse_named_assets(&[], &["ghost=text/plain".to_string()], &[])
+            .expect_err("mime without asset must fail");
+        assert!(err.to_string().contains("ghost"));
+    }
+
+    #[test]
+    fn repack_mode_does_not_require_base_or_output() {
+        let cli = Cli::try_parse_from([
+            "luthier-orchestrator-injector",
+            "--repack",
+            "game",
+            "--remove-asset",
+            "manual.pdf",
+            "--remove-icon-png",
+        ])
+        .expect("parse repack args");
+        assert_eq!(cli.repack.as_deref(), Some(Path::new("game")));
+        assert!(cli.output.is_none());
+
+        let replacement = build_replacement(&cli, &[]).expect("replacement");
+        assert!(replacement.config_json.is_none());
+        assert_eq!(replacement.hero_image, AssetChange::Keep);
+        assert_eq!(replacement.icon_png, AssetChange::Remove);
+        assert_eq!(replacement.remove_named_assets, vec!["manual.pdf"]);
+
+        assert!(
+            Cli::try_parse_from(["luthier-orchestrator-injector", "--config", "c.json"]).is_err()
+        );
+        assert!(Cli::try_parse_from([
+            "luthier-orchestrator-injector",
+            "--repack",
+            "game",
+            "--drop-signature",
+            "--signing-key",
+            "key.json",
+        ])
+        .is_err());
+    }
+}
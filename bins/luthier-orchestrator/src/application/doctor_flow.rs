@@ -1,5 +1,6 @@
 use luthier_orchestrator_core::{
-    doctor::{run_doctor, DoctorReport},
+    doctor::{run_doctor_with_host, DoctorReport},
+    host_env::HostEnvironment,
     GameConfig,
 };
 
@@ -10,8 +11,9 @@ pub struct DoctorFlowExecution {
 
 pub fn execute_doctor_flow(
     embedded_config: Option<&GameConfig>,
+    host: &dyn HostEnvironment,
 ) -> anyhow::Result<DoctorFlowExecution> {
-    let report = run_doctor(embedded_config);
+    let report = run_doctor_with_host(embedded_config, host);
 
     Ok(DoctorFlowExecution { report })
 }
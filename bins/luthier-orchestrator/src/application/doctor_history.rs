@@ -0,0 +1,112 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{anyhow, Context};
+use luthier_orchestrator_core::doctor::{DoctorHistory, DoctorSnapshot, DriftEvent};
+use luthier_orchestrator_core::observability::LogLevel;
+use luthier_orchestrator_core::prefix::compact_exe_hash_key;
+
+use crate::logging::log_event;
+
+pub fn doctor_history_path(exe_hash: &str) -> anyhow::Result<PathBuf> {
+    let home = std::env::var_os("HOME").ok_or_else(|| anyhow!("HOME is not set"))?;
+    Ok(PathBuf::from(home)
+        .join(".local/share/Luthier/doctor-history")
+        .join(format!("{}.json", compact_exe_hash_key(exe_hash))))
+}
+
+pub fn load_doctor_history(exe_hash: &str) -> anyhow::Result<DoctorHistory> {
+    let path = doctor_history_path(exe_hash)?;
+    if !path.exists() {
+        return Ok(DoctorHistory::default());
+    }
+
+    let raw = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read doctor history at {}", path.display()))?;
+    let parsed = serde_json::from_str::<DoctorHistory>(&raw)
+        .with_context(|| format!("invalid doctor history at {}", path.display()))?;
+    Ok(parsed)
+}
+
+pub fn save_doctor_history(exe_hash: &str, history: &DoctorHistory) -> anyhow::Result<PathBuf> {
+    let path = doctor_history_path(exe_hash)?;
+    let parent = path
+        .parent()
+        .ok_or_else(|| anyhow!("doctor history path has no parent"))?;
+    fs::create_dir_all(parent).with_context(|| {
+        format!(
+            "failed to create doctor history directory {}",
+            parent.display()
+        )
+    })?;
+
+    let payload =
+        serde_json::to_vec_pretty(history).context("failed to serialize doctor history")?;
+    fs::write(&path, payload)
+        .with_context(|| format!("failed to write doctor history to {}", path.display()))?;
+    Ok(path)
+}
+
+/// Appends `snapshot` to the game's history and returns the updated history.
+/// History is diagnostic only, so a failure is logged instead of stopping the
+/// caller; `None` then means the stored history could not be read.
+pub fn record_doctor_run(
+    trace_id: &str,
+    exe_hash: &str,
+    snapshot: DoctorSnapshot,
+) -> Option<DoctorHistory> {
+    let mut history = match load_doctor_history(exe_hash) {
+        Ok(history) => history,
+        Err(err) => {
+            log_history_unavailable(trace_id, &err);
+            return None;
+        }
+    };
+    history.record_run(snapshot);
+    if let Err(err) = save_doctor_history(exe_hash, &history) {
+        log_history_unavailable(trace_id, &err);
+    }
+    Some(history)
+}
+
+/// Keeps `snapshot` as the baseline later runs are compared against.
+pub fn record_successful_launch(trace_id: &str, exe_hash: &str, snapshot: DoctorSnapshot) {
+    let result = load_doctor_history(exe_hash).and_then(|mut history| {
+        history.last_successful_launch = Some(snapshot);
+        save_doctor_history(exe_hash, &history)
+    });
+    if let Err(err) = result {
+        log_history_unavailable(trace_id, &err);
+    }
+}
+
+pub fn log_doctor_drift(trace_id: &str, baseline: &DoctorSnapshot, events: &[DriftEvent]) {
+    for event in events {
+        log_event(
+            trace_id,
+            LogLevel::Warn,
+            "doctor",
+            "GO-DR-020",
+            "doctor_drift_detected",
+            serde_json::json!({
+                "kind": event.kind,
+                "subject": &event.subject,
+                "before": &event.before,
+                "after": &event.after,
+                "baseline_generated_at": &baseline.generated_at,
+            }),
+        );
+    }
+}
+
+fn log_history_unavailable(trace_id: &str, err: &anyhow::Error) {
+    log_event(
+        trace_id,
+        LogLevel::Warn,
+        "doctor",
+        "GO-DR-021",
+        "doctor_history_unavailable",
+        serde_json::json!({
+            "error": format!("{err:#}"),
+        }),
+    );
+}
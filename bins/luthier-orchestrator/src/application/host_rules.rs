@@ -0,0 +1,48 @@
+use anyhow::Context;
+use luthier_orchestrator_core::host_env::{HostEnvironment, SystemHost};
+use luthier_orchestrator_core::host_rules::{apply_host_rules, HostFacts};
+use luthier_orchestrator_core::observability::LogLevel;
+use luthier_orchestrator_core::GameConfig;
+
+use crate::logging::log_event;
+
+/// Applies the config's host rules for this machine. Runs after runtime
+/// overrides, so a matching rule wins over the player's optional toggles.
+pub fn apply_host_rules_for_current_host(
+    trace_id: &str,
+    config: GameConfig,
+) -> anyhow::Result<GameConfig> {
+    apply_host_rules_for_host(trace_id, config, &SystemHost)
+}
+
+pub fn apply_host_rules_for_host(
+    trace_id: &str,
+    config: GameConfig,
+    host: &dyn HostEnvironment,
+) -> anyhow::Result<GameConfig> {
+    if config.rules.is_empty() {
+        return Ok(config);
+    }
+
+    let applied = apply_host_rules(&config, &HostFacts::detect_with(host))
+        .context("failed to apply host rules")?;
+    let matched = applied
+        .evaluations
+        .iter()
+        .filter(|evaluation| evaluation.matched)
+        .map(|evaluation| evaluation.name.as_str())
+        .collect::<Vec<_>>();
+    log_event(
+        trace_id,
+        LogLevel::Info,
+        "config",
+        "GO-CFG-025",
+        "host_rules_applied",
+        serde_json::json!({
+            "evaluated": applied.evaluations.len(),
+            "matched": matched,
+        }),
+    );
+
+    Ok(applied.config)
+}
@@ -0,0 +1,115 @@
+use anyhow::anyhow;
+use luthier_orchestrator_core::launch_entries::{
+    resolve_launch_entry, ResolvedLaunchEntry, MAIN_LAUNCH_ENTRY,
+};
+use luthier_orchestrator_core::observability::LogLevel;
+use luthier_orchestrator_core::{GameConfig, OrchestratorError};
+
+use crate::logging::log_event;
+
+/// Picks the executable for this run: `--entry`, then the payload's
+/// `default_entry`, then `relative_exe_path`.
+pub fn resolve_launch_entry_for_run(
+    config: &GameConfig,
+    requested: Option<&str>,
+) -> anyhow::Result<ResolvedLaunchEntry> {
+    resolve_launch_entry(config, requested).map_err(|err| match err {
+        OrchestratorError::UnknownLaunchEntry(name) => anyhow!(
+            "launch entry '{name}' not found (available: {})",
+            available_launch_entries(config)
+        ),
+        other => other.into(),
+    })
+}
+
+pub fn log_launch_entry(trace_id: &str, entry: &ResolvedLaunchEntry) {
+    log_event(
+        trace_id,
+        LogLevel::Info,
+        "config",
+        "GO-CFG-026",
+        "launch_entry_selected",
+        serde_json::json!({
+            "entry": entry.name,
+            "relative_exe_path": entry.relative_exe_path,
+            "working_dir": entry.working_dir,
+        }),
+    );
+}
+
+pub fn available_launch_entries(config: &GameConfig) -> String {
+    std::iter::once(MAIN_LAUNCH_ENTRY)
+        .chain(
+            config
+                .launch_entries
+                .iter()
+                .map(|entry| entry.name.as_str()),
+        )
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Entry that follows `current` in the splash picker; the main executable sits
+/// before the first entry.
+pub fn next_launch_entry(config: &GameConfig, current: Option<&str>) -> Option<String> {
+    let next_index = match current {
+        None => 0,
+        Some(name) => config
+            .launch_entries
+            .iter()
+            .position(|entry| entry.name == name)
+            .map_or(0, |index| index + 1),
+    };
+    config
+        .launch_entries
+        .get(next_index)
+        .map(|entry| entry.name.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use luthier_orchestrator_core::LaunchEntry;
+
+    use super::*;
+    use crate::application::runtime_overrides::tests::sample_config;
+
+    fn config_with_entries() -> GameConfig {
+        let mut config = sample_config();
+        config.launch_entries = ["Launcher", "Server"]
+            .into_iter()
+            .map(|name| LaunchEntry {
+                name: name.to_string(),
+                relative_exe_path: format!("./{name}.exe"),
+                launch_args: Vec::new(),
+                working_dir: None,
+                env: HashMap::new(),
+            })
+            .collect();
+        config
+    }
+
+    #[test]
+    fn unknown_entry_lists_available_entries() {
+        let err = resolve_launch_entry_for_run(&config_with_entries(), Some("Editor"))
+            .expect_err("unknown entry");
+        assert!(err
+            .to_string()
+            .contains("available: main, Launcher, Server"));
+    }
+
+    #[test]
+    fn picker_cycles_through_entries_and_back_to_main_executable() {
+        let config = config_with_entries();
+        assert_eq!(
+            next_launch_entry(&config, None).as_deref(),
+            Some("Launcher")
+        );
+        assert_eq!(
+            next_launch_entry(&config, Some("Launcher")).as_deref(),
+            Some("Server")
+        );
+        assert_eq!(next_launch_entry(&config, Some("Server")), None);
+    }
+}
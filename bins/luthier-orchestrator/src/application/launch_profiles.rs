@@ -0,0 +1,223 @@
+use anyhow::anyhow;
+use luthier_orchestrator_core::config_overlay::apply_launch_profile;
+use luthier_orchestrator_core::observability::LogLevel;
+use luthier_orchestrator_core::GameConfig;
+use serde::Serialize;
+
+use crate::application::runtime_overrides::RuntimeOverrides;
+use crate::logging::log_event;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LaunchProfileSource {
+    Base,
+    Cli,
+    SavedDefault,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResolvedLaunchProfile {
+    pub config: GameConfig,
+    pub profile: Option<String>,
+    pub source: LaunchProfileSource,
+    /// Saved default that no longer exists in the payload and was ignored.
+    pub stale_default: Option<String>,
+}
+
+/// Picks the launch profile for this run and merges it over the base config.
+/// `--profile` wins over the default saved in the overrides file. An unknown
+/// `--profile` is an error; a stale saved default falls back to the base config.
+pub fn resolve_launch_profile(
+    config: GameConfig,
+    requested: Option<&str>,
+    overrides: &RuntimeOverrides,
+) -> anyhow::Result<ResolvedLaunchProfile> {
+    if let Some(name) = requested {
+        if !has_launch_profile(&config, name) {
+            return Err(anyhow!(
+                "launch profile '{name}' not found (available: {})",
+                available_profiles(&config)
+            ));
+        }
+        return Ok(ResolvedLaunchProfile {
+            config: apply_launch_profile(&config, name)?,
+            profile: Some(name.to_string()),
+            source: LaunchProfileSource::Cli,
+            stale_default: None,
+        });
+    }
+
+    match overrides.default_profile.as_deref() {
+        Some(name) if has_launch_profile(&config, name) => Ok(ResolvedLaunchProfile {
+            config: apply_launch_profile(&config, name)?,
+            profile: Some(name.to_string()),
+            source: LaunchProfileSource::SavedDefault,
+            stale_default: None,
+        }),
+        stale => Ok(ResolvedLaunchProfile {
+            config,
+            profile: None,
+            source: LaunchProfileSource::Base,
+            stale_default: stale.map(str::to_string),
+        }),
+    }
+}
+
+/// Applies `profile` when set, otherwise returns the base config unchanged.
+pub fn apply_launch_profile_or_base(
+    config: &GameConfig,
+    profile: Option<&str>,
+) -> anyhow::Result<GameConfig> {
+    match profile {
+        Some(name) => Ok(apply_launch_profile(config, name)?),
+        None => Ok(config.clone()),
+    }
+}
+
+pub fn log_resolved_launch_profile(trace_id: &str, resolved: &ResolvedLaunchProfile) {
+    if let Some(stale) = &resolved.stale_default {
+        log_event(
+            trace_id,
+            LogLevel::Warn,
+            "config",
+            "GO-CFG-024",
+            "saved_launch_profile_missing",
+            serde_json::json!({
+                "default_profile": stale,
+                "available": available_profiles(&resolved.config),
+            }),
+        );
+    }
+
+    log_event(
+        trace_id,
+        LogLevel::Info,
+        "config",
+        "GO-CFG-023",
+        "launch_profile_applied",
+        serde_json::json!({
+            "profile": resolved.profile,
+            "source": resolved.source,
+        }),
+    );
+}
+
+pub fn has_launch_profile(config: &GameConfig, name: &str) -> bool {
+    config.profiles.iter().any(|profile| profile.name == name)
+}
+
+pub fn available_profiles(config: &GameConfig) -> String {
+    if config.profiles.is_empty() {
+        return "none".to_string();
+    }
+    config
+        .profiles
+        .iter()
+        .map(|profile| profile.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Profile that follows `current` in the splash picker; the base config sits
+/// before the first profile.
+pub fn next_launch_profile(config: &GameConfig, current: Option<&str>) -> Option<String> {
+    let next_index = match current {
+        None => 0,
+        Some(name) => match config
+            .profiles
+            .iter()
+            .position(|profile| profile.name == name)
+        {
+            Some(index) => index + 1,
+            None => 0,
+        },
+    };
+    config
+        .profiles
+        .get(next_index)
+        .map(|profile| profile.name.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use luthier_orchestrator_core::{FeatureState, LaunchProfile};
+
+    use super::*;
+    use crate::application::runtime_overrides::tests::sample_config;
+
+    fn config_with_profiles() -> GameConfig {
+        let mut config = sample_config();
+        config.profiles = vec![
+            LaunchProfile {
+                name: "Deck".to_string(),
+                description: "800p with FSR".to_string(),
+                overlay: serde_json::json!({
+                    "environment": { "gamescope": { "state": "MandatoryOn", "fsr": true } }
+                }),
+            },
+            LaunchProfile {
+                name: "Desktop".to_string(),
+                description: String::new(),
+                overlay: serde_json::json!({ "environment": { "mangohud": "MandatoryOn" } }),
+            },
+        ];
+        config
+    }
+
+    #[test]
+    fn cli_profile_wins_over_saved_default() {
+        let overrides = RuntimeOverrides {
+            default_profile: Some("Desktop".to_string()),
+            ..RuntimeOverrides::default()
+        };
+
+        let resolved = resolve_launch_profile(config_with_profiles(), Some("Deck"), &overrides)
+            .expect("resolve");
+        assert_eq!(resolved.profile.as_deref(), Some("Deck"));
+        assert_eq!(resolved.source, LaunchProfileSource::Cli);
+        assert_eq!(
+            resolved.config.environment.gamescope.state,
+            FeatureState::MandatoryOn
+        );
+
+        let resolved =
+            resolve_launch_profile(config_with_profiles(), None, &overrides).expect("resolve");
+        assert_eq!(resolved.source, LaunchProfileSource::SavedDefault);
+        assert_eq!(
+            resolved.config.environment.mangohud,
+            FeatureState::MandatoryOn
+        );
+    }
+
+    #[test]
+    fn unknown_cli_profile_fails_and_stale_default_falls_back_to_base() {
+        let err = resolve_launch_profile(
+            config_with_profiles(),
+            Some("Handheld"),
+            &RuntimeOverrides::default(),
+        )
+        .expect_err("unknown profile");
+        assert!(err.to_string().contains("available: Deck, Desktop"));
+
+        let overrides = RuntimeOverrides {
+            default_profile: Some("Removed".to_string()),
+            ..RuntimeOverrides::default()
+        };
+        let resolved =
+            resolve_launch_profile(config_with_profiles(), None, &overrides).expect("resolve");
+        assert_eq!(resolved.source, LaunchProfileSource::Base);
+        assert_eq!(resolved.profile, None);
+        assert_eq!(resolved.stale_default.as_deref(), Some("Removed"));
+    }
+
+    #[test]
+    fn picker_cycles_through_profiles_and_back_to_base() {
+        let config = config_with_profiles();
+        assert_eq!(next_launch_profile(&config, None).as_deref(), Some("Deck"));
+        assert_eq!(
+            next_launch_profile(&config, Some("Deck")).as_deref(),
+            Some("Desktop")
+        );
+        assert_eq!(next_launch_profile(&config, Some("Desktop")), None);
+    }
+}
@@ -1,4 +1,8 @@
 pub mod doctor_flow;
+pub mod doctor_history;
+pub mod host_rules;
+pub mod launch_entries;
+pub mod launch_profiles;
 pub mod play_flow;
 pub mod ports;
 pub mod runtime_overrides;
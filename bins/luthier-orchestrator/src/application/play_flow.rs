@@ -1,6 +1,7 @@
 use anyhow::{anyhow, Context};
 use luthier_orchestrator_core::{
-    doctor::{run_doctor, CheckStatus},
+    doctor::{diff_snapshots, run_doctor, CheckStatus, DoctorSnapshot},
+    host_env::SystemHost,
     observability::LogLevel,
     prefix::build_prefix_setup_plan,
     process::{ExternalCommand, StepStatus},
@@ -9,16 +10,22 @@ use serde_json::Value;
 
 use crate::{
     application::{
+        doctor_history::{log_doctor_drift, record_doctor_run, record_successful_launch},
+        host_rules::apply_host_rules_for_current_host,
+        launch_entries::{log_launch_entry, resolve_launch_entry_for_run},
+        launch_profiles::{log_resolved_launch_profile, resolve_launch_profile},
         ports::{FlowMountStatus, OrchestratorRuntimeFlowPort},
         runtime_overrides::{apply_runtime_overrides, load_runtime_overrides},
     },
     instance_lock::acquire_instance_lock,
     logging::log_event,
     services::{
-        integrity_service::validate_integrity, launch_plan_builder::build_launch_command,
+        integrity_service::validate_integrity,
+        launch_plan_builder::build_launch_command,
         prefix_setup_service::build_prefix_setup_execution_context,
-        registry_apply_service::apply_registry_keys_if_present, runtime_flags::dry_run_enabled,
-        script_runner::execute_script_if_present,
+        registry_apply_service::apply_registry_keys_if_present,
+        runtime_flags::dry_run_enabled,
+        script_runner::{run_script_phase, stop_background_steps, ScriptContext, ScriptPhase},
         winecfg_apply_service::apply_winecfg_overrides_if_present,
     },
 };
@@ -55,9 +62,15 @@ impl PlayFlowExecution {
 pub fn execute_play_flow(
     trace_id: &str,
     runtime_flow: &dyn OrchestratorRuntimeFlowPort,
+    profile: Option<&str>,
+    entry: Option<&str>,
 ) -> anyhow::Result<PlayFlowExecution> {
-    let mut config = runtime_flow.load_embedded_config_required()?;
+    let config = runtime_flow.load_embedded_config_required()?;
     let overrides = load_runtime_overrides(&config.exe_hash)?;
+    let resolved = resolve_launch_profile(config, profile, &overrides)?;
+    log_resolved_launch_profile(trace_id, &resolved);
+    let active_profile = resolved.profile;
+    let mut config = resolved.config;
     apply_runtime_overrides(&mut config, &overrides);
 
     log_event(
@@ -72,6 +85,9 @@ pub fn execute_play_flow(
             "gamemode": overrides.gamemode,
         }),
     );
+    let config = apply_host_rules_for_current_host(trace_id, config)?;
+    let entry = resolve_launch_entry_for_run(&config, entry)?;
+    log_launch_entry(trace_id, &entry);
 
     let instance_lock = match acquire_instance_lock(&config.exe_hash) {
         Ok(lock) => lock,
@@ -113,14 +129,14 @@ pub fn execute_play_flow(
     let dry_run = dry_run_enabled();
 
     let game_exe_path = runtime_flow
-        .resolve_relative_path(&game_root, &config.relative_exe_path)
-        .with_context(|| format!("invalid relative_exe_path '{}'", config.relative_exe_path))?;
+        .resolve_relative_path(&game_root, &entry.relative_exe_path)
+        .with_context(|| format!("invalid relative_exe_path '{}'", entry.relative_exe_path))?;
     if !game_exe_path.exists() {
         let output = serde_json::json!({
             "integrity": {
                 "status": "BLOCKER",
-                "missing_files": [config.relative_exe_path.clone()],
-                "missing_executable": config.relative_exe_path,
+                "missing_files": [entry.relative_exe_path.clone()],
+                "missing_executable": entry.relative_exe_path,
             },
             "launch": {
                 "status": "aborted",
@@ -135,8 +151,8 @@ pub fn execute_play_flow(
         ));
     }
 
-    let missing_files =
-        validate_integrity(&config, &game_root).context("invalid integrity path in payload")?;
+    let missing_files = validate_integrity(&config, &entry, &game_root)
+        .context("invalid integrity path in payload")?;
     if !missing_files.is_empty() {
         let output = serde_json::json!({
             "integrity": {
@@ -164,7 +180,8 @@ pub fn execute_play_flow(
         "integrity_check_passed",
         serde_json::json!({
             "integrity_files_count": config.integrity_files.len(),
-            "relative_exe_path": config.relative_exe_path,
+            "entry": entry.name,
+            "relative_exe_path": entry.relative_exe_path,
         }),
     );
 
@@ -180,6 +197,13 @@ pub fn execute_play_flow(
         }),
     );
 
+    let doctor_snapshot = DoctorSnapshot::capture(&report, &SystemHost);
+    let history = record_doctor_run(trace_id, &config.exe_hash, doctor_snapshot.clone());
+    if let Some(baseline) = history.and_then(|history| history.last_successful_launch) {
+        let drift = diff_snapshots(&baseline, &doctor_snapshot);
+        log_doctor_drift(trace_id, &baseline, &drift);
+    }
+
     if matches!(report.summary, CheckStatus::BLOCKER) {
         let output = serde_json::json!({
             "doctor": report,
@@ -233,9 +257,14 @@ pub fn execute_play_flow(
         ));
     }
 
-    let registry_apply_result =
-        apply_registry_keys_if_present(&config, &report, &prefix_setup.prefix_root_path, dry_run)
-            .context("failed to apply registry keys")?;
+    let registry_apply_result = apply_registry_keys_if_present(
+        &config,
+        &report,
+        &prefix_setup.prefix_root_path,
+        &game_root,
+        dry_run,
+    )
+    .context("failed to apply registry keys")?;
 
     if let Some(result) = &registry_apply_result {
         log_event(
@@ -379,56 +408,52 @@ pub fn execute_play_flow(
         }),
     );
 
-    let launch_plan =
-        build_launch_command(&config, &report, &game_root, &prefix_setup.prefix_root_path)
-            .context("failed to build launch command")?;
-
-    let pre_script_result = execute_script_if_present(
-        "pre-launch-script",
-        &config.scripts.pre_launch,
-        &launch_plan.cwd,
+    let launch_plan = build_launch_command(
+        &config,
+        &entry,
+        &report,
+        &game_root,
+        &prefix_setup.prefix_root_path,
+    )
+    .context("failed to build launch command")?;
+    // Script steps resolve `cwd` against the game root whichever entry is launched.
+    let script_context = ScriptContext::for_launch(
+        &report,
+        &game_root,
+        &prefix_setup.prefix_root_path,
         &launch_plan.env,
         dry_run,
-        true,
+    )
+    .context("failed to prepare script steps")?;
+    let pre_launch = run_script_phase(
+        trace_id,
+        ScriptPhase::PreLaunch,
+        &config.scripts.pre_launch,
+        &script_context,
     );
 
-    if let Some(result) = &pre_script_result {
-        log_event(
-            trace_id,
-            LogLevel::Info,
-            "scripts",
-            "GO-SC-020",
-            "pre_launch_script_executed",
-            serde_json::json!({
-                "status": result.status,
-                "exit_code": result.exit_code,
-                "duration_ms": result.duration_ms,
-            }),
-        );
-    }
-
-    if let Some(result) = &pre_script_result {
-        if matches!(result.status, StepStatus::Failed | StepStatus::TimedOut) {
-            let output = serde_json::json!({
-                "doctor": report,
-                "prefix_setup_plan": prefix_plan,
-                "prefix_setup_execution": setup_results,
-                "registry_apply": registry_apply_result,
-                "winecfg_apply": winecfg_apply_result,
-                "folder_mounts": mount_results,
-                "pre_launch": result,
-                "launch": {
-                    "status": "aborted",
-                    "reason": "pre-launch script failed"
-                }
-            });
+    if pre_launch.has_mandatory_failure() {
+        let background_scripts = stop_background_steps(trace_id, pre_launch.background);
+        let output = serde_json::json!({
+            "doctor": report,
+            "prefix_setup_plan": prefix_plan,
+            "prefix_setup_execution": setup_results,
+            "registry_apply": registry_apply_result,
+            "winecfg_apply": winecfg_apply_result,
+            "folder_mounts": mount_results,
+            "pre_launch": pre_launch.results,
+            "background_scripts": background_scripts,
+            "launch": {
+                "status": "aborted",
+                "reason": "pre-launch script failed"
+            }
+        });
 
-            return Ok(PlayFlowExecution::failed(
-                output,
-                anyhow!("pre-launch script failed"),
-                "failed to serialize pre-launch failure",
-            ));
-        }
+        return Ok(PlayFlowExecution::failed(
+            output,
+            anyhow!("pre-launch script failed"),
+            "failed to serialize pre-launch failure",
+        ));
     }
 
     log_event(
@@ -471,30 +496,14 @@ pub fn execute_play_flow(
         }),
     );
 
-    let post_script_result = execute_script_if_present(
-        "post-launch-script",
+    let background_scripts = stop_background_steps(trace_id, pre_launch.background);
+    let post_launch = run_script_phase(
+        trace_id,
+        ScriptPhase::PostLaunch,
         &config.scripts.post_launch,
-        &launch_plan.cwd,
-        &launch_plan.env,
-        dry_run,
-        false,
+        &script_context,
     );
 
-    if let Some(result) = &post_script_result {
-        log_event(
-            trace_id,
-            LogLevel::Info,
-            "scripts",
-            "GO-SC-021",
-            "post_launch_script_executed",
-            serde_json::json!({
-                "status": result.status,
-                "exit_code": result.exit_code,
-                "duration_ms": result.duration_ms,
-            }),
-        );
-    }
-
     let launch_status = match game_result.status {
         StepStatus::Success => "completed",
         StepStatus::Skipped => "skipped",
@@ -502,6 +511,8 @@ pub fn execute_play_flow(
     };
 
     let output = serde_json::json!({
+        "profile": active_profile,
+        "entry": entry.name,
         "doctor": report,
         "prefix_setup_plan": prefix_plan,
         "prefix_setup_execution": setup_results,
@@ -509,15 +520,20 @@ pub fn execute_play_flow(
         "winecfg_apply": winecfg_apply_result,
         "folder_mounts": mount_results,
         "launch_plan": launch_plan,
-        "pre_launch": pre_script_result,
+        "pre_launch": pre_launch.results,
+        "background_scripts": background_scripts,
         "game_launch": game_result,
-        "post_launch": post_script_result,
+        "post_launch": post_launch.results,
         "launch": {
             "status": launch_status,
             "dry_run": dry_run
         }
     });
 
+    if matches!(game_result.status, StepStatus::Success) && !dry_run {
+        record_successful_launch(trace_id, &config.exe_hash, doctor_snapshot);
+    }
+
     if matches!(
         game_result.status,
         StepStatus::Failed | StepStatus::TimedOut
@@ -21,6 +21,9 @@ pub struct RuntimeOverrides {
     pub auto_dxvk_nvapi: Option<bool>,
     pub easy_anti_cheat_runtime: Option<bool>,
     pub battleye_runtime: Option<bool>,
+    /// Launch profile used when no `--profile` is given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_profile: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -179,7 +182,7 @@ fn apply_optional_override(state: &mut FeatureState, override_value: Option<bool
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use std::collections::HashMap;
 
     use luthier_orchestrator_core::{
@@ -271,6 +274,7 @@ mod tests {
             auto_dxvk_nvapi: Some(true),
             easy_anti_cheat_runtime: Some(true),
             battleye_runtime: Some(false),
+            default_profile: None,
         };
 
         apply_runtime_overrides(&mut config, &overrides);
@@ -382,7 +386,7 @@ mod tests {
         );
     }
 
-    fn sample_config() -> GameConfig {
+    pub(crate) fn sample_config() -> GameConfig {
         GameConfig {
             config_version: 1,
             created_by: "test".to_string(),
@@ -418,6 +422,7 @@ mod tests {
                 mangohud: FeatureState::OptionalOff,
                 prime_offload: FeatureState::OptionalOff,
                 custom_vars: HashMap::new(),
+                env_edits: Vec::new(),
             },
             compatibility: CompatibilityConfig {
                 wine_wayland: FeatureState::OptionalOff,
@@ -483,9 +488,13 @@ mod tests {
             folder_mounts: vec![],
             splash: SplashConfig::default(),
             scripts: ScriptsConfig {
-                pre_launch: String::new(),
-                post_launch: String::new(),
+                pre_launch: Vec::new(),
+                post_launch: Vec::new(),
             },
+            profiles: Vec::new(),
+            rules: Vec::new(),
+            launch_entries: Vec::new(),
+            default_entry: None,
         }
     }
 }
@@ -116,9 +116,14 @@ pub fn execute_winecfg_flow(
         ));
     }
 
-    let registry_apply_result =
-        apply_registry_keys_if_present(&config, &report, &prefix_setup.prefix_root_path, dry_run)
-            .context("failed to apply registry keys")?;
+    let registry_apply_result = apply_registry_keys_if_present(
+        &config,
+        &report,
+        &prefix_setup.prefix_root_path,
+        &game_root,
+        dry_run,
+    )
+    .context("failed to apply registry keys")?;
 
     let winecfg_apply_result = apply_winecfg_overrides_if_present(
         &config,
@@ -151,8 +156,9 @@ pub fn execute_winecfg_flow(
         }
     }
 
-    let command_plan = build_winecfg_command(&config, &report, &prefix_setup.prefix_root_path)
-        .context("failed to build winecfg command")?;
+    let command_plan =
+        build_winecfg_command(&config, &report, &game_root, &prefix_setup.prefix_root_path)
+            .context("failed to build winecfg command")?;
 
     log_event(
         trace_id,
@@ -1,12 +1,13 @@
 use std::path::PathBuf;
 
 use clap::{Parser, ValueEnum};
+use luthier_orchestrator_core::signing::SignaturePolicy;
 
 #[derive(Debug, Parser)]
 #[command(name = "luthier-orchestrator")]
 #[command(about = "Luthier Orchestrator CLI")]
 #[command(
-    after_help = "Examples:\n  game --doctor\n  game --doctor --play\n  game --play\n  game --play-splash\n  game --set-mangohud on --set-gamescope off\n  game --set-mangohud off --play\n  game --show-manifest\n  game --extract-config --out ./payload.json\n  game --extract-hero-image\n  game --extract-icon"
+    after_help = "Examples:\n  game --doctor\n  game --doctor --format junit --fail-on blocker\n  game --doctor --simulate-host ./host.json\n  game --doctor --diff\n  game --doctor --play\n  game --play\n  game --play-splash\n  game --set-mangohud on --set-gamescope off\n  game --set-mangohud off --play\n  game --profile \"Steam Deck\" --play\n  game --set-default-profile Desktop\n  game --entry \"Dedicated Server\" --play\n  game --show-manifest\n  game --extract-config --out ./payload.json\n  game --extract-hero-image\n  game --extract-icon\n  game --extract-asset manual.pdf --out ./manual.pdf"
 )]
 pub struct Cli {
     #[arg(long, help = "Run game launch pipeline without splash")]
@@ -22,6 +23,39 @@ pub struct Cli {
     #[arg(long, help = "Run doctor checks and print categorized result")]
     pub doctor: bool,
 
+    #[arg(
+        long = "format",
+        value_enum,
+        requires = "doctor",
+        help = "Doctor output: json (the full report), text (a table grouped by status) or junit"
+    )]
+    pub doctor_format: Option<DoctorFormat>,
+
+    #[arg(
+        long,
+        value_enum,
+        requires = "doctor",
+        default_value = "warn",
+        help = "Lowest doctor summary that makes --doctor exit non-zero (1 for WARN, 2 for BLOCKER)"
+    )]
+    pub fail_on: DoctorFailOn,
+
+    #[arg(
+        long = "simulate-host",
+        value_name = "HOST_JSON",
+        requires = "doctor",
+        conflicts_with_all = ["play", "play_splash", "winecfg", "doctor_diff"],
+        help = "Run doctor checks against a host described in a JSON file instead of this machine"
+    )]
+    pub simulate_host: Option<PathBuf>,
+
+    #[arg(
+        long = "diff",
+        requires = "doctor",
+        help = "Compare this doctor run with the one recorded before the last successful launch"
+    )]
+    pub doctor_diff: bool,
+
     #[arg(long, help = "Run Wine configuration flow")]
     pub winecfg: bool,
 
@@ -46,18 +80,60 @@ pub struct Cli {
     #[arg(long = "extract-icon", help = "Extract embedded icon_png asset")]
     pub extract_icon: bool,
 
+    #[arg(
+        long = "extract-asset",
+        value_name = "NAME",
+        help = "Extract an embedded named asset"
+    )]
+    pub extract_asset: Option<String>,
+
     #[arg(
         long,
-        help = "Output path for --extract-config, --extract-hero-image or --extract-icon"
+        help = "Output path for --extract-config, --extract-hero-image, --extract-icon or --extract-asset"
     )]
     pub out: Option<PathBuf>,
 
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Launch profile to apply for --play, --play-splash or --doctor (defaults to the saved default profile)"
+    )]
+    pub profile: Option<String>,
+
+    #[arg(
+        long = "set-default-profile",
+        value_name = "NAME",
+        conflicts_with = "clear_default_profile",
+        help = "Save the launch profile used when --profile is not given"
+    )]
+    pub set_default_profile: Option<String>,
+
+    #[arg(
+        long = "clear-default-profile",
+        help = "Forget the saved default launch profile"
+    )]
+    pub clear_default_profile: bool,
+
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Launch entry to run for --play or --play-splash (defaults to the payload's default entry)"
+    )]
+    pub entry: Option<String>,
+
     #[arg(
         long,
         help = "Locale override for splash/UI text (example: pt-BR, en-US)"
     )]
     pub lang: Option<String>,
 
+    #[arg(
+        long,
+        value_enum,
+        help = "Payload signature policy for play; can only be stricter than the trust store policy"
+    )]
+    pub signature_policy: Option<SignaturePolicyArg>,
+
     #[arg(long, value_enum, help = "Override MangoHud optional state")]
     pub set_mangohud: Option<OptionalToggle>,
 
@@ -105,3 +181,33 @@ pub enum OptionalToggle {
     Off,
     Default,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DoctorFormat {
+    Json,
+    Text,
+    Junit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DoctorFailOn {
+    Warn,
+    Blocker,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SignaturePolicyArg {
+    Refuse,
+    Warn,
+    Ignore,
+}
+
+impl From<SignaturePolicyArg> for SignaturePolicy {
+    fn from(value: SignaturePolicyArg) -> Self {
+        match value {
+            SignaturePolicyArg::Refuse => Self::Refuse,
+            SignaturePolicyArg::Warn => Self::Warn,
+            SignaturePolicyArg::Ignore => Self::Ignore,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::{anyhow, Context};
 
 use crate::{
+    application::launch_profiles::{available_profiles, has_launch_profile},
     application::runtime_overrides::{
         apply_toggle_request, build_feature_view, feature_overridable, load_runtime_overrides,
         runtime_overrides_path, save_runtime_overrides, set_optional_override,
@@ -92,6 +93,19 @@ pub fn run_config_command(_trace_id: &str, cli: &Cli, print_output: bool) -> any
         changed |= set_optional_override(&mut overrides.gamescope, requested);
     }
 
+    if let Some(name) = cli.set_default_profile.as_deref() {
+        if !has_launch_profile(&config, name) {
+            return Err(anyhow!(
+                "launch profile '{name}' not found (available: {})",
+                available_profiles(&config)
+            ));
+        }
+        changed |= overrides.default_profile.as_deref() != Some(name);
+        overrides.default_profile = Some(name.to_string());
+    } else if cli.clear_default_profile {
+        changed |= overrides.default_profile.take().is_some();
+    }
+
     let override_path = if changed {
         save_runtime_overrides(&config.exe_hash, &overrides)?
     } else {
@@ -151,8 +165,18 @@ pub fn run_config_command(_trace_id: &str, cli: &Cli, print_output: bool) -> any
             "override_file": override_path,
             "changed": changed,
             "features": features,
+            "default_profile": overrides.default_profile,
+            "profiles": config
+                .profiles
+                .iter()
+                .map(|profile| serde_json::json!({
+                    "name": profile.name,
+                    "description": profile.description,
+                }))
+                .collect::<Vec<_>>(),
             "usage": {
                 "set": "--set-mangohud on|off|default --set-gamescope on|off|default --set-gamemode on|off|default --set-umu on|off|default --set-winetricks on|off|default --set-steam-runtime on|off|default --set-prime-offload on|off|default --set-wine-wayland on|off|default --set-hdr on|off|default --set-auto-dxvk-nvapi on|off|default --set-easy-anti-cheat-runtime on|off|default --set-battleye-runtime on|off|default",
+                "profile": "--set-default-profile <name> --clear-default-profile --profile <name>",
                 "play": "--play"
             }
         });
@@ -3,41 +3,146 @@ use std::io::IsTerminal;
 
 use anyhow::Context;
 use luthier_orchestrator_core::{
-    doctor::{CheckStatus, DependencyStatus, DoctorReport},
+    doctor::{diff_snapshots, CheckStatus, DependencyStatus, DoctorReport, DoctorSnapshot},
+    host_env::{FixtureHost, HostEnvironment, SystemHost},
     FeatureState, GameConfig, RuntimeCandidate,
 };
 
+use super::doctor_format::{render_drift_text, render_junit_report, render_text_table};
 use crate::application::doctor_flow::execute_doctor_flow;
+use crate::application::doctor_history::{load_doctor_history, record_doctor_run};
+use crate::application::host_rules::apply_host_rules_for_host;
+use crate::application::launch_profiles::{log_resolved_launch_profile, resolve_launch_profile};
+use crate::application::runtime_overrides::{apply_runtime_overrides, load_runtime_overrides};
+use crate::cli::{Cli, DoctorFormat};
 use crate::infrastructure::payload_loader::try_load_embedded_config;
 
 const CORE_ESSENTIAL_DEPENDENCIES: &[&str] = &["winetricks", "umu-run", "proton", "wine"];
+const DEFAULT_REPORT_NAME: &str = "luthier-doctor";
+
+/// Runs the checks, prints them in the requested format and returns the
+/// report summary; the caller turns it into the exit code.
+pub fn run_doctor_command(trace_id: &str, cli: &Cli) -> anyhow::Result<CheckStatus> {
+    let profile = cli.profile.as_deref();
+    let simulated_host = cli
+        .simulate_host
+        .as_deref()
+        .map(|path| {
+            FixtureHost::load(path)
+                .with_context(|| format!("failed to load simulated host {}", path.display()))
+        })
+        .transpose()?;
+    let host: &dyn HostEnvironment = match &simulated_host {
+        Some(fixture) => fixture,
+        None => &SystemHost,
+    };
+
+    let config = try_load_embedded_config()
+        .context("failed to inspect embedded payload")?
+        .map(|config| {
+            let overrides = load_runtime_overrides(&config.exe_hash)?;
+            let resolved = resolve_launch_profile(config, profile, &overrides)?;
+            log_resolved_launch_profile(trace_id, &resolved);
+            let mut config = resolved.config;
+            apply_runtime_overrides(&mut config, &overrides);
+            apply_host_rules_for_host(trace_id, config, host)
+        })
+        .transpose()?;
+    if config.is_none() && profile.is_some() {
+        anyhow::bail!("--profile requires an embedded payload");
+    }
+    let execution = execute_doctor_flow(config.as_ref(), host)?;
+    let snapshot = DoctorSnapshot::capture(&execution.report, host);
+    // Only runs against this machine go into its history.
+    let history = match (&config, &simulated_host) {
+        (Some(cfg), None) => record_doctor_run(trace_id, &cfg.exe_hash, snapshot.clone()),
+        _ => None,
+    };
+    if cli.doctor_diff {
+        let Some(cfg) = config.as_ref() else {
+            anyhow::bail!("--diff requires an embedded payload");
+        };
+        let baseline = match history {
+            Some(history) => history.last_successful_launch,
+            None => load_doctor_history(&cfg.exe_hash)?.last_successful_launch,
+        };
+        print_doctor_diff(cli.doctor_format, baseline.as_ref(), &snapshot)?;
+        return Ok(execution.report.summary);
+    }
 
-pub fn run_doctor_command(_trace_id: &str) -> anyhow::Result<()> {
-    let config = try_load_embedded_config().context("failed to inspect embedded payload")?;
-    let execution = execute_doctor_flow(config.as_ref())?;
     let mut categories = build_categorized_doctor_output(&execution.report, config.as_ref());
     sort_categories(&mut categories);
-    print_categorized_doctor_output(&categories, true);
-    if matches!(execution.report.summary, CheckStatus::BLOCKER) {
-        anyhow::bail!("doctor found blocking issues");
+    match cli.doctor_format {
+        None => print_categorized_doctor_output(&categories, true),
+        Some(DoctorFormat::Json) => println!(
+            "{}",
+            serde_json::to_string_pretty(&execution.report)
+                .context("failed to serialize doctor report")?
+        ),
+        Some(DoctorFormat::Text) => {
+            print!(
+                "{}",
+                render_text_table(&categories, execution.report.summary)
+            )
+        }
+        Some(DoctorFormat::Junit) => {
+            let report_name = config
+                .as_ref()
+                .map(|cfg| cfg.game_name.trim())
+                .filter(|name| !name.is_empty())
+                .unwrap_or(DEFAULT_REPORT_NAME);
+            print!(
+                "{}",
+                render_junit_report(&categories, report_name, cli.fail_on)
+            )
+        }
     }
 
+    Ok(execution.report.summary)
+}
+
+fn print_doctor_diff(
+    format: Option<DoctorFormat>,
+    baseline: Option<&DoctorSnapshot>,
+    current: &DoctorSnapshot,
+) -> anyhow::Result<()> {
+    match format {
+        None | Some(DoctorFormat::Text) => print!("{}", render_drift_text(baseline, current)),
+        Some(DoctorFormat::Json) => {
+            let drift = baseline
+                .map(|baseline| diff_snapshots(baseline, current))
+                .unwrap_or_default();
+            let output = serde_json::json!({
+                "baseline": baseline,
+                "current": current,
+                "drift": drift,
+            });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&output).context("failed to serialize doctor diff")?
+            );
+        }
+        Some(DoctorFormat::Junit) => anyhow::bail!("--diff supports the text and json formats"),
+    }
     Ok(())
 }
 
 #[derive(Debug, Default)]
-struct DoctorCategories {
-    essential: Vec<DoctorEntry>,
-    additional: Vec<DoctorEntry>,
-    optional: Vec<DoctorEntry>,
+pub(super) struct DoctorCategories {
+    pub(super) essential: Vec<DoctorEntry>,
+    pub(super) additional: Vec<DoctorEntry>,
+    pub(super) optional: Vec<DoctorEntry>,
+    pub(super) host_rules: Vec<DoctorEntry>,
 }
 
 #[derive(Debug, Clone)]
-struct DoctorEntry {
-    name: String,
-    status: CheckStatus,
-    note: String,
-    resolved_path: Option<String>,
+pub(super) struct DoctorEntry {
+    pub(super) name: String,
+    pub(super) status: CheckStatus,
+    pub(super) note: String,
+    pub(super) resolved_path: Option<String>,
+    /// Install commands, native package manager first.
+    pub(super) remediation: Vec<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -87,10 +192,36 @@ fn build_categorized_doctor_output(
                 status: dep.status,
                 note: dep.note.clone(),
                 resolved_path: dep.resolved_path.clone(),
+                remediation: dep
+                    .remediation
+                    .iter()
+                    .map(|remediation| remediation.command.clone())
+                    .collect(),
             },
         );
     }
 
+    // Kept in evaluation order: later matches override earlier ones.
+    out.host_rules = report
+        .host_rules
+        .iter()
+        .map(|rule| DoctorEntry {
+            name: rule.name.clone(),
+            status: if rule.matched {
+                CheckStatus::OK
+            } else {
+                CheckStatus::INFO
+            },
+            note: if rule.matched {
+                "aplicada".to_string()
+            } else {
+                format!("não aplicada: {}", rule.unmet.join(", "))
+            },
+            resolved_path: None,
+            remediation: Vec::new(),
+        })
+        .collect();
+
     out
 }
 
@@ -113,6 +244,7 @@ fn add_runtime_entry_without_policy(
             "not found".to_string()
         },
         resolved_path,
+        remediation: Vec::new(),
     });
 }
 
@@ -148,6 +280,7 @@ fn make_runtime_policy_entry(
         status,
         note: note_from_policy_state(state, found),
         resolved_path,
+        remediation: Vec::new(),
     }
 }
 
@@ -286,6 +419,7 @@ fn print_categorized_doctor_output(categories: &DoctorCategories, verbose: bool)
     print_section("Essenciais", &categories.essential, verbose, use_color);
     print_section("Adicionais", &categories.additional, verbose, use_color);
     print_section("Opcionais", &categories.optional, verbose, use_color);
+    print_section("Regras do host", &categories.host_rules, verbose, use_color);
 }
 
 fn print_section(title: &str, entries: &[DoctorEntry], verbose: bool, use_color: bool) {
@@ -316,6 +450,9 @@ fn print_section(title: &str, entries: &[DoctorEntry], verbose: bool, use_color:
             if !details.is_empty() {
                 println!("  {}", style_dimmed(&details.join(" | "), use_color));
             }
+            for command in &entry.remediation {
+                println!("  {}", style_dimmed(&format!("$ {command}"), use_color));
+            }
         }
     }
     println!();
@@ -0,0 +1,374 @@
+use luthier_orchestrator_core::doctor::{diff_snapshots, CheckStatus, DoctorSnapshot};
+
+use super::doctor::{DoctorCategories, DoctorEntry};
+use crate::cli::DoctorFailOn;
+
+const STATUS_ORDER: [CheckStatus; 4] = [
+    CheckStatus::BLOCKER,
+    CheckStatus::WARN,
+    CheckStatus::OK,
+    CheckStatus::INFO,
+];
+
+/// Exit code when the doctor could not produce a report at all (unreadable
+/// payload, bad `--simulate-host` file, failed probe), kept apart from the
+/// summary codes so CI can tell a crash from a host with warnings.
+pub const DOCTOR_FAILED_EXIT_CODE: u8 = 3;
+
+/// Maps the doctor summary to the process exit code: 2 for BLOCKER, 1 for
+/// WARN when the threshold includes warnings, 0 otherwise. A doctor run that
+/// fails exits with [`DOCTOR_FAILED_EXIT_CODE`] instead.
+pub fn doctor_exit_code(summary: CheckStatus, fail_on: DoctorFailOn) -> u8 {
+    match (summary, fail_on) {
+        (CheckStatus::BLOCKER, _) => 2,
+        (CheckStatus::WARN, DoctorFailOn::Warn) => 1,
+        _ => 0,
+    }
+}
+
+fn fails_threshold(status: CheckStatus, fail_on: DoctorFailOn) -> bool {
+    doctor_exit_code(status, fail_on) != 0
+}
+
+fn labelled_sections(categories: &DoctorCategories) -> [(&'static str, &[DoctorEntry]); 4] {
+    [
+        ("essential", &categories.essential),
+        ("additional", &categories.additional),
+        ("optional", &categories.optional),
+        ("host-rules", &categories.host_rules),
+    ]
+}
+
+fn status_label(status: CheckStatus) -> &'static str {
+    match status {
+        CheckStatus::OK => "OK",
+        CheckStatus::WARN => "WARN",
+        CheckStatus::BLOCKER => "BLOCKER",
+        CheckStatus::INFO => "INFO",
+    }
+}
+
+fn entry_path(entry: &DoctorEntry) -> Option<&str> {
+    entry
+        .resolved_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+}
+
+/// Plain table, one row per check, rows grouped from the most to the least
+/// severe status. No colors, so it can be piped or pasted into bug reports.
+pub(super) fn render_text_table(categories: &DoctorCategories, summary: CheckStatus) -> String {
+    let header = ["STATUS", "CATEGORY", "CHECK", "NOTE", "PATH"];
+    let mut rows = Vec::new();
+    for status in STATUS_ORDER {
+        for (category, entries) in labelled_sections(categories) {
+            for entry in entries.iter().filter(|entry| entry.status == status) {
+                rows.push([
+                    status_label(status).to_string(),
+                    category.to_string(),
+                    entry.name.clone(),
+                    entry.note.trim().to_string(),
+                    entry_path(entry).unwrap_or("-").to_string(),
+                ]);
+            }
+        }
+    }
+
+    let mut widths = header.map(|title| title.chars().count());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut out = String::new();
+    let mut push_row = |cells: [&str; 5]| {
+        let mut line = String::new();
+        for (index, cell) in cells.iter().enumerate() {
+            if index + 1 == cells.len() {
+                line.push_str(cell);
+            } else {
+                let padding = widths[index] - cell.chars().count();
+                line.push_str(cell);
+                line.push_str(&" ".repeat(padding + 2));
+            }
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    };
+    push_row(header);
+    for row in &rows {
+        push_row(row.each_ref().map(String::as_str));
+    }
+    out.push_str(&format!("\nsummary: {}\n", status_label(summary)));
+    out
+}
+
+/// JUnit XML with one testsuite per category and one testcase per check, so
+/// CI systems can aggregate doctor runs across many launchers. Checks at or
+/// above `fail_on` are failures, INFO checks are skipped.
+pub(super) fn render_junit_report(
+    categories: &DoctorCategories,
+    suite_name: &str,
+    fail_on: DoctorFailOn,
+) -> String {
+    let mut suites = String::new();
+    let (mut total_tests, mut total_failures, mut total_skipped) = (0, 0, 0);
+
+    for (category, entries) in labelled_sections(categories) {
+        if entries.is_empty() {
+            continue;
+        }
+        let failures = entries
+            .iter()
+            .filter(|entry| fails_threshold(entry.status, fail_on))
+            .count();
+        let skipped = entries
+            .iter()
+            .filter(|entry| entry.status == CheckStatus::INFO)
+            .count();
+        total_tests += entries.len();
+        total_failures += failures;
+        total_skipped += skipped;
+
+        suites.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{failures}\" skipped=\"{skipped}\">\n",
+            xml_escape(category),
+            entries.len(),
+        ));
+        for entry in entries {
+            suites.push_str(&render_testcase(entry, suite_name, category, fail_on));
+        }
+        suites.push_str("  </testsuite>\n");
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"{}\" tests=\"{total_tests}\" failures=\"{total_failures}\" skipped=\"{total_skipped}\">\n{suites}</testsuites>\n",
+        xml_escape(suite_name),
+    )
+}
+
+fn render_testcase(
+    entry: &DoctorEntry,
+    suite_name: &str,
+    category: &str,
+    fail_on: DoctorFailOn,
+) -> String {
+    let open = format!(
+        "    <testcase classname=\"{}.{}\" name=\"{}\"",
+        xml_escape(suite_name),
+        category,
+        xml_escape(&entry.name),
+    );
+    let note = xml_escape(entry.note.trim());
+    let path = entry_path(entry).map(xml_escape);
+
+    let body = if fails_threshold(entry.status, fail_on) {
+        let status = status_label(entry.status);
+        match path {
+            Some(path) => {
+                format!("      <failure type=\"{status}\" message=\"{note}\">{path}</failure>\n")
+            }
+            None => format!("      <failure type=\"{status}\" message=\"{note}\"/>\n"),
+        }
+    } else if entry.status == CheckStatus::INFO {
+        format!("      <skipped message=\"{note}\"/>\n")
+    } else {
+        let details = [Some(note), path]
+            .into_iter()
+            .flatten()
+            .filter(|value| !value.is_empty())
+            .collect::<Vec<_>>();
+        if details.is_empty() {
+            return format!("{open}/>\n");
+        }
+        format!(
+            "      <system-out>{}: {}</system-out>\n",
+            status_label(entry.status),
+            details.join(" | "),
+        )
+    };
+
+    format!("{open}>\n{body}    </testcase>\n")
+}
+
+/// `--doctor --diff` text: one line per change since the run recorded before
+/// the last successful launch.
+pub(super) fn render_drift_text(
+    baseline: Option<&DoctorSnapshot>,
+    current: &DoctorSnapshot,
+) -> String {
+    let Some(baseline) = baseline else {
+        return "No successful launch recorded yet; nothing to compare against.\n".to_string();
+    };
+    let drift = diff_snapshots(baseline, current);
+    if drift.is_empty() {
+        return format!(
+            "No changes since the last successful launch ({}).\n",
+            baseline.generated_at
+        );
+    }
+
+    let mut out = format!(
+        "Changes since the last successful launch ({}):\n",
+        baseline.generated_at
+    );
+    for event in &drift {
+        out.push_str(&format!("  - {}\n", event.describe()));
+    }
+    out
+}
+
+fn xml_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            // Control characters other than tab and newlines are not valid XML 1.0.
+            ch if ch.is_control() && !matches!(ch, '\t' | '\n' | '\r') => {}
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, status: CheckStatus, note: &str, path: Option<&str>) -> DoctorEntry {
+        DoctorEntry {
+            name: name.to_string(),
+            status,
+            note: note.to_string(),
+            resolved_path: path.map(str::to_string),
+            remediation: Vec::new(),
+        }
+    }
+
+    fn sample_categories() -> DoctorCategories {
+        DoctorCategories {
+            essential: vec![
+                entry(
+                    "proton",
+                    CheckStatus::OK,
+                    "required and available",
+                    Some("/opt/proton"),
+                ),
+                entry(
+                    "umu-run",
+                    CheckStatus::BLOCKER,
+                    "required but missing",
+                    None,
+                ),
+            ],
+            additional: Vec::new(),
+            optional: vec![entry(
+                "mangohud",
+                CheckStatus::WARN,
+                "optional & <missing>",
+                None,
+            )],
+            host_rules: vec![entry("deck", CheckStatus::INFO, "não aplicada", None)],
+        }
+    }
+
+    #[test]
+    fn maps_summary_to_exit_code_by_threshold() {
+        assert_eq!(doctor_exit_code(CheckStatus::OK, DoctorFailOn::Warn), 0);
+        assert_eq!(doctor_exit_code(CheckStatus::INFO, DoctorFailOn::Warn), 0);
+        assert_eq!(doctor_exit_code(CheckStatus::WARN, DoctorFailOn::Warn), 1);
+        assert_eq!(
+            doctor_exit_code(CheckStatus::WARN, DoctorFailOn::Blocker),
+            0
+        );
+        assert_eq!(
+            doctor_exit_code(CheckStatus::BLOCKER, DoctorFailOn::Warn),
+            2
+        );
+        assert_eq!(
+            doctor_exit_code(CheckStatus::BLOCKER, DoctorFailOn::Blocker),
+            2
+        );
+        for status in STATUS_ORDER {
+            for fail_on in [DoctorFailOn::Warn, DoctorFailOn::Blocker] {
+                assert_ne!(doctor_exit_code(status, fail_on), DOCTOR_FAILED_EXIT_CODE);
+            }
+        }
+    }
+
+    #[test]
+    fn text_table_groups_rows_by_severity() {
+        let table = render_text_table(&sample_categories(), CheckStatus::BLOCKER);
+        let statuses = table
+            .lines()
+            .skip(1)
+            .take_while(|line| !line.is_empty())
+            .filter_map(|line| line.split_whitespace().next())
+            .collect::<Vec<_>>();
+
+        assert_eq!(statuses, vec!["BLOCKER", "WARN", "OK", "INFO"]);
+        assert!(
+            table.contains("OK       essential   proton    required and available  /opt/proton\n")
+        );
+        assert!(table.ends_with("\nsummary: BLOCKER\n"));
+    }
+
+    #[test]
+    fn junit_report_counts_failures_against_threshold() {
+        let categories = sample_categories();
+
+        let strict = render_junit_report(&categories, "Sample \"Game\"", DoctorFailOn::Warn);
+        assert!(strict.contains(
+            "<testsuites name=\"Sample &quot;Game&quot;\" tests=\"4\" failures=\"2\" skipped=\"1\">"
+        ));
+        assert!(
+            strict.contains("<failure type=\"WARN\" message=\"optional &amp; &lt;missing&gt;\"/>")
+        );
+        assert!(!strict.contains("additional"));
+
+        let lenient = render_junit_report(&categories, "Sample", DoctorFailOn::Blocker);
+        assert!(lenient.contains("tests=\"4\" failures=\"1\" skipped=\"1\""));
+        assert!(lenient.contains("<system-out>WARN: optional &amp; &lt;missing&gt;</system-out>"));
+        assert!(lenient.contains("<skipped message=\"não aplicada\"/>"));
+    }
+
+    #[test]
+    fn drift_text_lists_changes_since_the_last_successful_launch() {
+        let baseline = DoctorSnapshot {
+            generated_at: "2026-10-16T20:00:00.000Z".to_string(),
+            summary: CheckStatus::OK,
+            selected_runtime: Some(luthier_orchestrator_core::RuntimeCandidate::ProtonNative),
+            proton: Some("/opt/GE-Proton10-3/proton".to_string()),
+            proton_version: Some("GE-Proton10-3".to_string()),
+            wine: None,
+            umu_run: None,
+            session_type: Some(luthier_orchestrator_core::SessionType::Wayland),
+            dependencies: Default::default(),
+        };
+
+        assert_eq!(
+            render_drift_text(None, &baseline),
+            "No successful launch recorded yet; nothing to compare against.\n"
+        );
+        assert_eq!(
+            render_drift_text(Some(&baseline), &baseline),
+            "No changes since the last successful launch (2026-10-16T20:00:00.000Z).\n"
+        );
+
+        let current = DoctorSnapshot {
+            session_type: Some(luthier_orchestrator_core::SessionType::X11),
+            ..baseline.clone()
+        };
+        assert_eq!(
+            render_drift_text(Some(&baseline), &current),
+            "Changes since the last successful launch (2026-10-16T20:00:00.000Z):\n  \
+             - display session changed: wayland -> x11\n"
+        );
+    }
+}
@@ -1,14 +1,16 @@
 mod config;
 mod doctor;
+mod doctor_format;
 mod payload;
 mod play;
 mod winecfg;
 
 pub use config::run_config_command;
 pub use doctor::run_doctor_command;
+pub use doctor_format::{doctor_exit_code, DOCTOR_FAILED_EXIT_CODE};
 pub use payload::{
-    run_extract_config_command, run_extract_hero_image_command, run_extract_icon_command,
-    run_show_manifest_command,
+    run_extract_asset_command, run_extract_config_command, run_extract_hero_image_command,
+    run_extract_icon_command, run_show_manifest_command,
 };
 pub use play::run_play;
 pub use winecfg::run_winecfg_command;
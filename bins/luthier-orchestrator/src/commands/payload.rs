@@ -2,6 +2,9 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Context};
+use luthier_orchestrator_core::signing::{
+    evaluate_manifest_trust, load_trust_store, trust_store_path, SignatureVerdict, TrustStore,
+};
 
 use crate::infrastructure::payload_loader::load_embedded_payload_required;
 
@@ -10,9 +13,31 @@ pub fn run_show_manifest_command(_trace_id: &str) -> anyhow::Result<()> {
     let pretty =
         serde_json::to_string_pretty(&payload.manifest).context("failed to format manifest")?;
     println!("{pretty}");
+
+    // Keep stdout as plain manifest JSON; the signer summary goes to stderr.
+    let store = trust_store_path()
+        .and_then(|path| load_trust_store(&path))
+        .unwrap_or_else(|_| TrustStore::default());
+    let verdict = evaluate_manifest_trust(&payload.manifest, &store);
+    eprintln!("{}", describe_signature(&verdict));
     Ok(())
 }
 
+fn describe_signature(verdict: &SignatureVerdict) -> String {
+    match verdict {
+        SignatureVerdict::Unsigned => "Signed by: nobody (unsigned payload)".to_string(),
+        SignatureVerdict::Invalid { reason } => format!("Signed by: INVALID signature ({reason})"),
+        SignatureVerdict::Untrusted { signer } => format!(
+            "Signed by: {} [{}] (not in trust store)",
+            signer.signer, signer.fingerprint
+        ),
+        SignatureVerdict::Trusted { signer } => format!(
+            "Signed by: {} [{}] (trusted)",
+            signer.signer, signer.fingerprint
+        ),
+    }
+}
+
 pub fn run_extract_config_command(_trace_id: &str, out: Option<&Path>) -> anyhow::Result<()> {
     let payload = load_embedded_payload_required()?;
 
@@ -30,9 +55,9 @@ pub fn run_extract_config_command(_trace_id: &str, out: Option<&Path>) -> anyhow
 }
 
 pub fn run_extract_hero_image_command(_trace_id: &str, out: Option<&Path>) -> anyhow::Result<()> {
-    let payload = load_embedded_payload_required()?;
+    let mut payload = load_embedded_payload_required()?;
     let hero = payload
-        .hero_image
+        .hero_image()?
         .ok_or_else(|| anyhow!("embedded hero_image asset not found"))?;
 
     let output_path = if let Some(path) = out {
@@ -57,9 +82,9 @@ pub fn run_extract_hero_image_command(_trace_id: &str, out: Option<&Path>) -> an
 }
 
 pub fn run_extract_icon_command(_trace_id: &str, out: Option<&Path>) -> anyhow::Result<()> {
-    let payload = load_embedded_payload_required()?;
+    let mut payload = load_embedded_payload_required()?;
     let icon = payload
-        .icon_png
+        .icon_png()?
         .ok_or_else(|| anyhow!("embedded icon_png asset not found"))?;
 
     let output_path = if let Some(path) = out {
@@ -78,6 +103,37 @@ pub fn run_extract_icon_command(_trace_id: &str, out: Option<&Path>) -> anyhow::
     Ok(())
 }
 
+pub fn run_extract_asset_command(
+    _trace_id: &str,
+    name: &str,
+    out: Option<&Path>,
+) -> anyhow::Result<()> {
+    let mut payload = load_embedded_payload_required()?;
+    let bytes = payload
+        .named_asset(name)?
+        .ok_or_else(|| anyhow!("embedded named asset '{name}' not found"))?;
+
+    let output_path = if let Some(path) = out {
+        path.to_path_buf()
+    } else {
+        // Asset names are validated by the container, so they are safe file names.
+        current_game_root()?.join(format!(
+            "{}-{}",
+            executable_name_for_default_output()?,
+            name
+        ))
+    };
+
+    fs::write(&output_path, bytes).with_context(|| {
+        format!(
+            "failed to write named asset '{name}' to {}",
+            output_path.display()
+        )
+    })?;
+    println!("{}", output_path.display());
+    Ok(())
+}
+
 fn current_game_root() -> anyhow::Result<PathBuf> {
     let exe_path = std::env::current_exe().context("failed to resolve current executable path")?;
     let parent = exe_path.parent().ok_or_else(|| {
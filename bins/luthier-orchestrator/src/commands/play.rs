@@ -1,10 +1,33 @@
 use crate::application::play_flow;
 use crate::infrastructure::flow_runtime_adapter::NativeOrchestratorRuntimeFlowAdapter;
+use crate::infrastructure::payload_loader::load_embedded_payload_required;
+use crate::logging::log_event;
+use crate::services::signature_service::enforce_payload_signature;
 use anyhow::Context;
+use luthier_orchestrator_core::observability::LogLevel;
+use luthier_orchestrator_core::signing::SignaturePolicy;
+
+pub fn run_play(
+    trace_id: &str,
+    signature_policy: Option<SignaturePolicy>,
+    profile: Option<&str>,
+    entry: Option<&str>,
+) -> anyhow::Result<()> {
+    let payload = load_embedded_payload_required()?;
+    enforce_payload_signature(trace_id, &payload.manifest, signature_policy)?;
+    if !payload.migration.is_noop() {
+        log_event(
+            trace_id,
+            LogLevel::Info,
+            "config",
+            "GO-CFG-022",
+            "embedded_config_migrated",
+            serde_json::json!(&payload.migration),
+        );
+    }
 
-pub fn run_play(trace_id: &str) -> anyhow::Result<()> {
     let runtime_flow = NativeOrchestratorRuntimeFlowAdapter;
-    let execution = play_flow::execute_play_flow(trace_id, &runtime_flow)?;
+    let execution = play_flow::execute_play_flow(trace_id, &runtime_flow, profile, entry)?;
 
     println!(
         "{}",
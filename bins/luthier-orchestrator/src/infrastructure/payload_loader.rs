@@ -1,16 +1,43 @@
-use std::fs;
+use std::fs::File;
 
 use anyhow::{anyhow, Context};
-use luthier_orchestrator_core::asset_container::{parse_asset_container, AssetManifest};
+use luthier_orchestrator_core::asset_container::AssetManifest;
+use luthier_orchestrator_core::config_migration::{
+    load_game_config_json, ConfigMigrationReport, MigratedGameConfig,
+};
+use luthier_orchestrator_core::container_reader::AssetContainerReader;
 use luthier_orchestrator_core::{GameConfig, OrchestratorError};
 
-#[derive(Debug, Clone)]
+/// Embedded payload of the running executable. Only the manifest and the config
+/// are read up front; art and named assets are read from disk when asked for.
+#[derive(Debug)]
 pub struct EmbeddedPayloadAssets {
     pub manifest: AssetManifest,
     pub config_json: Vec<u8>,
     pub config: GameConfig,
-    pub hero_image: Option<Vec<u8>>,
-    pub icon_png: Option<Vec<u8>>,
+    /// What the config migrations changed while loading an older payload.
+    pub migration: ConfigMigrationReport,
+    container: AssetContainerReader<File>,
+}
+
+impl EmbeddedPayloadAssets {
+    pub fn hero_image(&mut self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.container
+            .hero_image()
+            .context("failed to read embedded hero image")
+    }
+
+    pub fn icon_png(&mut self) -> anyhow::Result<Option<Vec<u8>>> {
+        self.container
+            .icon_png()
+            .context("failed to read embedded icon")
+    }
+
+    pub fn named_asset(&mut self, name: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        self.container
+            .named_asset(name)
+            .with_context(|| format!("failed to read embedded asset '{name}'"))
+    }
 }
 
 pub fn load_embedded_payload_required() -> anyhow::Result<EmbeddedPayloadAssets> {
@@ -20,27 +47,34 @@ pub fn load_embedded_payload_required() -> anyhow::Result<EmbeddedPayloadAssets>
 
 pub fn try_load_embedded_payload() -> anyhow::Result<Option<EmbeddedPayloadAssets>> {
     let current_exe = std::env::current_exe().context("failed to resolve current executable")?;
-    let binary = fs::read(&current_exe)
-        .with_context(|| format!("failed to read executable at {}", current_exe.display()))?;
 
-    let parsed = match parse_asset_container(&binary) {
-        Ok(parsed) => parsed,
+    let mut container = match AssetContainerReader::open(&current_exe) {
+        Ok(container) => container,
         Err(OrchestratorError::ContainerNotFound | OrchestratorError::ContainerTruncated) => {
             return Ok(None);
         }
+        Err(OrchestratorError::Io(err)) => {
+            return Err(anyhow!(err)).with_context(|| {
+                format!("failed to read executable at {}", current_exe.display())
+            });
+        }
         Err(err) => return Err(anyhow!(err)),
     };
 
-    let config_json = parsed.config_json().to_vec();
-    let config: GameConfig =
-        serde_json::from_slice(&config_json).context("invalid embedded GameConfig")?;
+    let config_json = container
+        .config_json()
+        .context("failed to decode embedded GameConfig")?;
+    let MigratedGameConfig {
+        config,
+        report: migration,
+    } = load_game_config_json(&config_json).context("invalid embedded GameConfig")?;
 
     Ok(Some(EmbeddedPayloadAssets {
-        manifest: parsed.manifest.clone(),
+        manifest: container.manifest().clone(),
         config_json,
         config,
-        hero_image: parsed.hero_image().map(ToOwned::to_owned),
-        icon_png: parsed.icon_png().map(ToOwned::to_owned),
+        migration,
+        container,
     }))
 }
 
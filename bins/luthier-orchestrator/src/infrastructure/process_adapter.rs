@@ -1,6 +1,8 @@
 use luthier_orchestrator_core::prefix::PrefixSetupPlan;
 
-pub use luthier_orchestrator_core::process::{CommandExecutionResult, ExternalCommand, StepStatus};
+pub use luthier_orchestrator_core::process::{
+    BackgroundProcess, CommandExecutionResult, ExternalCommand, StepStatus,
+};
 
 pub fn execute_prefix_setup_plan(
     plan: &PrefixSetupPlan,
@@ -18,6 +20,14 @@ pub fn execute_external_command(
     luthier_orchestrator_core::process::execute_external_command(command, env_pairs, dry_run)
 }
 
+pub fn spawn_background_command(
+    command: &ExternalCommand,
+    env_pairs: &[(String, String)],
+    dry_run: bool,
+) -> (CommandExecutionResult, Option<BackgroundProcess>) {
+    luthier_orchestrator_core::process::spawn_background_command(command, env_pairs, dry_run)
+}
+
 pub fn has_mandatory_failures(results: &[CommandExecutionResult]) -> bool {
     luthier_orchestrator_core::process::has_mandatory_failures(results)
 }
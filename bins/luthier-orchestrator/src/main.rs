@@ -10,22 +10,25 @@ mod logging;
 mod services;
 mod splash;
 
+use std::process::ExitCode;
+
 use anyhow::Context;
 use clap::Parser;
+use luthier_orchestrator_core::doctor::CheckStatus;
 use luthier_orchestrator_core::observability::{new_trace_id, LogLevel};
 
 use crate::cli::Cli;
 use crate::commands::{
-    run_config_command, run_doctor_command, run_extract_config_command,
-    run_extract_hero_image_command, run_extract_icon_command, run_play, run_show_manifest_command,
-    run_winecfg_command,
+    doctor_exit_code, run_config_command, run_doctor_command, run_extract_asset_command,
+    run_extract_config_command, run_extract_hero_image_command, run_extract_icon_command, run_play,
+    run_show_manifest_command, run_winecfg_command, DOCTOR_FAILED_EXIT_CODE,
 };
 use crate::infrastructure::payload_loader::try_load_embedded_config;
 use crate::logging::log_event;
 use crate::splash::{run_splash_flow, SplashLaunchMode};
 
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
+async fn main() -> anyhow::Result<ExitCode> {
     let cli = Cli::parse();
     let trace_id = new_trace_id();
 
@@ -33,16 +36,16 @@ async fn main() -> anyhow::Result<()> {
         log_startup_event(&trace_id, &cli);
     }
 
-    if route_explicit_commands(&trace_id, &cli)? {
-        return Ok(());
+    if let Some(code) = route_explicit_commands(&trace_id, &cli)? {
+        return Ok(ExitCode::from(code));
     }
 
     if route_implicit_splash(&cli)? {
-        return Ok(());
+        return Ok(ExitCode::SUCCESS);
     }
 
     print_noop_hint();
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
 
 fn has_config_override_flags(cli: &Cli) -> bool {
@@ -58,6 +61,8 @@ fn has_config_override_flags(cli: &Cli) -> bool {
         || cli.set_auto_dxvk_nvapi.is_some()
         || cli.set_easy_anti_cheat_runtime.is_some()
         || cli.set_battleye_runtime.is_some()
+        || cli.set_default_profile.is_some()
+        || cli.clear_default_profile
 }
 
 fn log_startup_event(trace_id: &str, cli: &Cli) {
@@ -76,8 +81,12 @@ fn log_startup_event(trace_id: &str, cli: &Cli) {
             "extract_config": cli.extract_config,
             "extract_hero_image": cli.extract_hero_image,
             "extract_icon": cli.extract_icon,
+            "extract_asset": cli.extract_asset,
             "out": cli.out.as_ref().map(|v| v.display().to_string()),
+            "profile": cli.profile,
+            "entry": cli.entry,
             "lang": cli.lang,
+            "signature_policy": cli.signature_policy.as_ref().map(|v| format!("{v:?}")),
             "set_mangohud": cli.set_mangohud.as_ref().map(|v| format!("{v:?}")),
             "set_gamescope": cli.set_gamescope.as_ref().map(|v| format!("{v:?}")),
             "set_gamemode": cli.set_gamemode.as_ref().map(|v| format!("{v:?}")),
@@ -90,6 +99,8 @@ fn log_startup_event(trace_id: &str, cli: &Cli) {
             "set_auto_dxvk_nvapi": cli.set_auto_dxvk_nvapi.as_ref().map(|v| format!("{v:?}")),
             "set_easy_anti_cheat_runtime": cli.set_easy_anti_cheat_runtime.as_ref().map(|v| format!("{v:?}")),
             "set_battleye_runtime": cli.set_battleye_runtime.as_ref().map(|v| format!("{v:?}")),
+            "set_default_profile": cli.set_default_profile,
+            "clear_default_profile": cli.clear_default_profile,
         }),
     );
 }
@@ -100,6 +111,7 @@ fn should_log_startup_event(cli: &Cli) -> bool {
         || cli.extract_config
         || cli.extract_hero_image
         || cli.extract_icon
+        || cli.extract_asset.is_some()
     {
         return false;
     }
@@ -107,16 +119,37 @@ fn should_log_startup_event(cli: &Cli) -> bool {
     has_execution_stage_requested(cli)
 }
 
-fn route_explicit_commands(trace_id: &str, cli: &Cli) -> anyhow::Result<bool> {
+/// Returns the exit code once an explicit command ran. A doctor run sets it
+/// from its summary, unless play or winecfg follows: then only a BLOCKER
+/// stops the run and the code is left to that stage.
+fn route_explicit_commands(trace_id: &str, cli: &Cli) -> anyhow::Result<Option<u8>> {
     if !has_cli_actions(cli) {
-        return Ok(false);
+        return Ok(None);
     }
 
+    let mut exit_code = 0;
     if cli.doctor {
-        run_doctor_command(trace_id).context("doctor command failed")?;
+        let summary = match run_doctor_command(trace_id, cli) {
+            Ok(summary) => summary,
+            Err(err) => {
+                eprintln!("Error: {:?}", err.context("doctor command failed"));
+                return Ok(Some(DOCTOR_FAILED_EXIT_CODE));
+            }
+        };
+        exit_code = doctor_exit_code(summary, cli.fail_on);
+        if has_execution_stage_requested(cli) {
+            if matches!(summary, CheckStatus::BLOCKER) {
+                eprintln!("doctor found blocking issues");
+                return Ok(Some(exit_code));
+            }
+            exit_code = 0;
+        }
     }
 
-    let has_extract_command = cli.extract_config || cli.extract_hero_image || cli.extract_icon;
+    let has_extract_command = cli.extract_config
+        || cli.extract_hero_image
+        || cli.extract_icon
+        || cli.extract_asset.is_some();
     if cli.out.is_some() && !has_extract_command {
         return Err(anyhow::anyhow!("--out requires an extract command"));
     }
@@ -141,13 +174,19 @@ fn route_explicit_commands(trace_id: &str, cli: &Cli) -> anyhow::Result<bool> {
             .context("failed to extract embedded icon asset")?;
     }
 
+    if let Some(name) = cli.extract_asset.as_deref() {
+        run_extract_asset_command(trace_id, name, cli.out.as_deref())
+            .context("failed to extract embedded named asset")?;
+    }
+
     if has_config_override_flags(cli) {
         let should_print_config_output = !has_execution_stage_requested(cli)
             && !cli.doctor
             && !cli.show_manifest
             && !cli.extract_config
             && !cli.extract_hero_image
-            && !cli.extract_icon;
+            && !cli.extract_icon
+            && cli.extract_asset.is_none();
         run_config_command(trace_id, cli, should_print_config_output)
             .context("failed to apply runtime override flags")?;
     }
@@ -158,20 +197,30 @@ fn route_explicit_commands(trace_id: &str, cli: &Cli) -> anyhow::Result<bool> {
         run_winecfg_command(trace_id).context("winecfg command failed")?;
     }
 
-    Ok(true)
+    Ok(Some(exit_code))
 }
 
 fn route_play_command(trace_id: &str, cli: &Cli) -> anyhow::Result<()> {
+    let signature_policy = cli.signature_policy.map(Into::into);
     if cli.play_splash {
         run_splash_flow(
             SplashLaunchMode::ExplicitPlayWithSplash,
             cli.lang.as_deref(),
+            signature_policy,
+            cli.profile.as_deref(),
+            cli.entry.as_deref(),
         )
         .context("splash play flow failed")?;
         return Ok(());
     }
 
-    run_play(trace_id).context("play flow failed")?;
+    run_play(
+        trace_id,
+        signature_policy,
+        cli.profile.as_deref(),
+        cli.entry.as_deref(),
+    )
+    .context("play flow failed")?;
     Ok(())
 }
 
@@ -181,8 +230,14 @@ fn route_implicit_splash(cli: &Cli) -> anyhow::Result<bool> {
     }
 
     if try_load_embedded_config()?.is_some() {
-        run_splash_flow(SplashLaunchMode::ImplicitDoubleClick, cli.lang.as_deref())
-            .context("implicit splash flow failed")?;
+        run_splash_flow(
+            SplashLaunchMode::ImplicitDoubleClick,
+            cli.lang.as_deref(),
+            cli.signature_policy.map(Into::into),
+            cli.profile.as_deref(),
+            cli.entry.as_deref(),
+        )
+        .context("implicit splash flow failed")?;
         return Ok(true);
     }
 
@@ -202,6 +257,7 @@ fn has_cli_actions(cli: &Cli) -> bool {
         || cli.extract_config
         || cli.extract_hero_image
         || cli.extract_icon
+        || cli.extract_asset.is_some()
         || cli.out.is_some()
         || has_config_override_flags(cli)
 }
@@ -212,6 +268,6 @@ fn has_execution_stage_requested(cli: &Cli) -> bool {
 
 fn print_noop_hint() {
     println!(
-        "Nada para executar. Use --show-manifest, --extract-config, --extract-hero-image, --extract-icon, --doctor, --winecfg, --set-<feature> on|off|default, --play ou --play-splash."
+        "Nada para executar. Use --show-manifest, --extract-config, --extract-hero-image, --extract-icon, --extract-asset <nome>, --doctor, --winecfg, --set-<feature> on|off|default, --set-default-profile <nome>, --play ou --play-splash."
     );
 }
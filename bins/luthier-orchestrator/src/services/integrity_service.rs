@@ -1,16 +1,16 @@
 use std::path::Path;
 
 use anyhow::Context;
-use luthier_orchestrator_core::GameConfig;
+use luthier_orchestrator_core::{launch_entries::ResolvedLaunchEntry, GameConfig};
 
 use crate::infrastructure::paths::resolve_relative_path;
 
-pub fn validate_integrity(config: &GameConfig, game_root: &Path) -> anyhow::Result<Vec<String>> {
-    validate_required_paths(
-        game_root,
-        &config.relative_exe_path,
-        &config.integrity_files,
-    )
+pub fn validate_integrity(
+    config: &GameConfig,
+    entry: &ResolvedLaunchEntry,
+    game_root: &Path,
+) -> anyhow::Result<Vec<String>> {
+    validate_required_paths(game_root, &entry.relative_exe_path, &config.integrity_files)
 }
 
 pub fn validate_required_paths(
@@ -6,16 +6,19 @@ use std::{
 
 use anyhow::{anyhow, Context};
 use luthier_orchestrator_core::{
-    doctor::DoctorReport, prefix::base_env_for_prefix, FeatureState, GameConfig, RuntimeCandidate,
+    doctor::DoctorReport, launch_entries::ResolvedLaunchEntry, prefix::base_env_for_prefix,
+    variables::VariableContext, EnvEditMode, FeatureState, GameConfig, RuntimeCandidate,
 };
 
 use crate::{
     application::runtime_overrides::feature_enabled, domain::models::LaunchCommandPlan,
     infrastructure::paths::resolve_relative_path,
+    services::prefix_setup_service::variable_context_for_runtime,
 };
 
 pub fn build_launch_command(
     config: &GameConfig,
+    entry: &ResolvedLaunchEntry,
     report: &DoctorReport,
     game_root: &Path,
     prefix_path: &Path,
@@ -25,35 +28,15 @@ pub fn build_launch_command(
         .selected_runtime
         .ok_or_else(|| anyhow!("doctor did not select a runtime"))?;
 
-    let runtime_program =
-        match selected_runtime {
-            RuntimeCandidate::ProtonUmu => {
-                report.runtime.umu_run.clone().ok_or_else(|| {
-                    anyhow!("selected runtime ProtonUmu but umu-run path is missing")
-                })?
-            }
-            RuntimeCandidate::ProtonNative => report.runtime.proton.clone().ok_or_else(|| {
-                anyhow!("selected runtime ProtonNative but proton path is missing")
-            })?,
-            RuntimeCandidate::Wine => report
-                .runtime
-                .wine
-                .clone()
-                .ok_or_else(|| anyhow!("selected runtime Wine but wine path is missing"))?,
-        };
-
-    let game_exe = resolve_relative_path(game_root, &config.relative_exe_path)
+    let game_exe = resolve_relative_path(game_root, &entry.relative_exe_path)
         .context("invalid relative_exe_path in payload")?;
     let game_exe_str = game_exe.to_string_lossy().into_owned();
+    let variables = variable_context_for_runtime(game_root, prefix_path, selected_runtime);
 
-    let mut runtime_args = match selected_runtime {
-        RuntimeCandidate::ProtonNative => vec!["run".to_string(), game_exe_str],
-        _ => vec![game_exe_str],
-    };
-    runtime_args.extend(config.launch_args.clone());
-
-    let mut command_tokens = vec![runtime_program.clone()];
-    command_tokens.extend(runtime_args);
+    let mut command_tokens = runtime_invocation(report)?;
+    command_tokens.push(game_exe_str);
+    command_tokens
+        .extend(expand_all(&entry.launch_args, &variables).context("invalid launch_args")?);
 
     let gamescope_active = feature_enabled(config.environment.gamescope.state);
     let mangohud_active = feature_enabled(config.requirements.mangohud);
@@ -104,7 +87,8 @@ pub fn build_launch_command(
                 continue;
             };
 
-            let args = split_wrapper_args(&wrapper.args);
+            let args = expand_all(&split_wrapper_args(&wrapper.args), &variables)
+                .with_context(|| format!("invalid args for wrapper '{}'", wrapper.executable))?;
             command_tokens = wrap_command(wrapper_program, args, command_tokens);
         }
     }
@@ -255,26 +239,67 @@ pub fn build_launch_command(
         upsert_env(&mut env_pairs, "DRI_PRIME", "1");
     }
 
-    for (key, value) in &config.environment.custom_vars {
+    apply_config_env(&mut env_pairs, config, &variables)?;
+    for (key, value) in &entry.env {
         if is_protected_env_key(key) {
             continue;
         }
+        let value = variables
+            .expand(value)
+            .with_context(|| format!("invalid launch entry env var '{key}'"))?;
         upsert_env(&mut env_pairs, key, value);
     }
 
+    let cwd = match &entry.working_dir {
+        Some(working_dir) => resolve_relative_path(game_root, working_dir)
+            .context("invalid launch entry working_dir in payload")?,
+        None => game_root.to_path_buf(),
+    };
+
     Ok(LaunchCommandPlan {
         program,
         args,
-        cwd: game_root.to_string_lossy().into_owned(),
+        cwd: cwd.to_string_lossy().into_owned(),
         runtime: format!("{:?}", selected_runtime),
         env: env_pairs,
         notes: plan_notes,
     })
 }
 
+/// Tokens that run a Windows program with the selected runtime: `umu-run`,
+/// `proton run` or `wine`, followed by the program and its arguments.
+pub fn runtime_invocation(report: &DoctorReport) -> anyhow::Result<Vec<String>> {
+    let selected_runtime = report
+        .runtime
+        .selected_runtime
+        .ok_or_else(|| anyhow!("doctor did not select a runtime"))?;
+
+    let tokens =
+        match selected_runtime {
+            RuntimeCandidate::ProtonUmu => {
+                vec![report.runtime.umu_run.clone().ok_or_else(|| {
+                    anyhow!("selected runtime ProtonUmu but umu-run path is missing")
+                })?]
+            }
+            RuntimeCandidate::ProtonNative => vec![
+                report.runtime.proton.clone().ok_or_else(|| {
+                    anyhow!("selected runtime ProtonNative but proton path is missing")
+                })?,
+                "run".to_string(),
+            ],
+            RuntimeCandidate::Wine => vec![report
+                .runtime
+                .wine
+                .clone()
+                .ok_or_else(|| anyhow!("selected runtime Wine but wine path is missing"))?],
+        };
+    Ok(tokens)
+}
+
 pub fn build_winecfg_command(
     config: &GameConfig,
     report: &DoctorReport,
+    game_root: &Path,
     prefix_path: &Path,
 ) -> anyhow::Result<LaunchCommandPlan> {
     let selected_runtime = report
@@ -367,12 +392,8 @@ pub fn build_winecfg_command(
         upsert_env(&mut env_pairs, "DRI_PRIME", "1");
     }
 
-    for (key, value) in &config.environment.custom_vars {
-        if is_protected_env_key(key) {
-            continue;
-        }
-        upsert_env(&mut env_pairs, key, value);
-    }
+    let variables = variable_context_for_runtime(game_root, prefix_path, selected_runtime);
+    apply_config_env(&mut env_pairs, config, &variables)?;
 
     Ok(LaunchCommandPlan {
         program,
@@ -384,6 +405,68 @@ pub fn build_winecfg_command(
     })
 }
 
+/// Sets `environment.custom_vars`, then applies `environment.env_edits` in
+/// order, expanding `${...}` in every value.
+fn apply_config_env(
+    env_pairs: &mut Vec<(String, String)>,
+    config: &GameConfig,
+    variables: &VariableContext,
+) -> anyhow::Result<()> {
+    for (key, value) in &config.environment.custom_vars {
+        if is_protected_env_key(key) {
+            continue;
+        }
+        let value = variables
+            .expand(value)
+            .with_context(|| format!("invalid custom env var '{key}'"))?;
+        upsert_env(env_pairs, key, value);
+    }
+
+    for edit in &config.environment.env_edits {
+        if is_protected_env_key(&edit.name) {
+            continue;
+        }
+        let value = variables
+            .expand(&edit.value)
+            .with_context(|| format!("invalid env edit for '{}'", edit.name))?;
+        let existing = env_pairs
+            .iter()
+            .find(|(key, _)| key == &edit.name)
+            .map(|(_, value)| value.as_str())
+            .or_else(|| variables.host_var(&edit.name))
+            .filter(|existing| !existing.is_empty());
+        let combined = match (existing, edit.mode) {
+            (None, _) => value,
+            (Some(existing), EnvEditMode::Append) => {
+                format!("{existing}{}{value}", edit.separator)
+            }
+            (Some(existing), EnvEditMode::Prepend) => {
+                format!("{value}{}{existing}", edit.separator)
+            }
+        };
+        upsert_env(env_pairs, edit.name.clone(), combined);
+    }
+
+    Ok(())
+}
+
+fn expand_all(values: &[String], variables: &VariableContext) -> anyhow::Result<Vec<String>> {
+    values
+        .iter()
+        .map(|value| Ok(variables.expand(value)?))
+        .collect()
+}
+
+/// The doctor's verdict on the `esync`/`fsync` check; reports without the
+/// check leave the payload toggle in charge.
+pub(crate) fn host_supports_sync(report: &DoctorReport, name: &str) -> bool {
+    report
+        .dependencies
+        .iter()
+        .find(|dep| dep.name == name)
+        .is_none_or(|dep| dep.found)
+}
+
 fn dependency_path(report: &DoctorReport, name: &str) -> Option<String> {
     report
         .dependencies
@@ -646,10 +729,10 @@ fn apply_heroic_like_runtime_env_defaults(
             );
         }
 
-        apply_proton_feature_envs(env_pairs, config);
+        apply_proton_feature_envs(env_pairs, config, report);
         apply_proton_aux_runtime_envs(env_pairs, config, report);
     } else {
-        apply_wine_feature_envs(env_pairs, config);
+        apply_wine_feature_envs(env_pairs, config, report);
     }
 
     if matches!(runtime, RuntimeCandidate::ProtonUmu) {
@@ -673,12 +756,16 @@ fn apply_heroic_like_runtime_env_defaults(
     }
 }
 
-fn apply_proton_feature_envs(env_pairs: &mut Vec<(String, String)>, config: &GameConfig) {
-    if !config.runner.esync {
+fn apply_proton_feature_envs(
+    env_pairs: &mut Vec<(String, String)>,
+    config: &GameConfig,
+    report: &DoctorReport,
+) {
+    if !config.runner.esync || !host_supports_sync(report, "esync") {
         upsert_env(env_pairs, "PROTON_NO_ESYNC", "1");
     }
 
-    if !config.runner.fsync {
+    if !config.runner.fsync || !host_supports_sync(report, "fsync") {
         upsert_env(env_pairs, "PROTON_NO_FSYNC", "1");
     }
 
@@ -698,12 +785,16 @@ fn apply_proton_feature_envs(env_pairs: &mut Vec<(String, String)>, config: &Gam
     }
 }
 
-fn apply_wine_feature_envs(env_pairs: &mut Vec<(String, String)>, config: &GameConfig) {
-    if config.runner.esync {
+fn apply_wine_feature_envs(
+    env_pairs: &mut Vec<(String, String)>,
+    config: &GameConfig,
+    report: &DoctorReport,
+) {
+    if config.runner.esync && host_supports_sync(report, "esync") {
         upsert_env(env_pairs, "WINEESYNC", "1");
     }
 
-    if config.runner.fsync {
+    if config.runner.fsync && host_supports_sync(report, "fsync") {
         upsert_env(env_pairs, "WINEFSYNC", "1");
     }
 
@@ -781,7 +872,55 @@ fn proton_root_from_script(proton_binary_path: &str) -> Option<String> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
+
+    use luthier_orchestrator_core::doctor::{CheckStatus, DependencyStatus, RuntimeDiscovery};
+    use luthier_orchestrator_core::EnvEdit;
+
     use super::*;
+    use crate::application::runtime_overrides::tests::sample_config;
+
+    #[test]
+    fn config_env_expands_variables_and_applies_edits_in_order() {
+        let mut config = sample_config();
+        config.environment.custom_vars = HashMap::from([(
+            "GAME_SAVES".to_string(),
+            "${WIN_USER_DOCUMENTS}/Saves".to_string(),
+        )]);
+        let edit = |name: &str, value: &str, mode| EnvEdit {
+            name: name.to_string(),
+            value: value.to_string(),
+            mode,
+            separator: ":".to_string(),
+        };
+        config.environment.env_edits = vec![
+            edit("LD_LIBRARY_PATH", "${GAME_ROOT}/lib", EnvEditMode::Prepend),
+            edit("PATH", "${GAME_ROOT}/bin", EnvEditMode::Append),
+            edit("VK_ADD_LAYER_PATH", "/opt/layers", EnvEditMode::Append),
+        ];
+        let variables = VariableContext {
+            game_root: PathBuf::from("/games/sample"),
+            prefix: PathBuf::from("/prefix/pfx"),
+            windows_user: "steamuser".to_string(),
+            host_env: HashMap::from([("LD_LIBRARY_PATH".to_string(), "/usr/lib".to_string())]),
+        };
+        let mut env = vec![("PATH".to_string(), "/proton/bin".to_string())];
+
+        apply_config_env(&mut env, &config, &variables).expect("apply env");
+
+        let value = |key: &str| {
+            env.iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value.as_str())
+        };
+        assert_eq!(
+            value("GAME_SAVES"),
+            Some("/prefix/pfx/drive_c/users/steamuser/Documents/Saves")
+        );
+        assert_eq!(value("LD_LIBRARY_PATH"), Some("/games/sample/lib:/usr/lib"));
+        assert_eq!(value("PATH"), Some("/proton/bin:/games/sample/bin"));
+        assert_eq!(value("VK_ADD_LAYER_PATH"), Some("/opt/layers"));
+    }
 
     #[test]
     fn parse_resolution_accepts_valid_formats() {
@@ -856,6 +995,64 @@ mod tests {
         assert!(args.is_empty());
     }
 
+    #[test]
+    fn sync_envs_follow_what_the_host_supports() {
+        let mut config = sample_config();
+        config.runner.esync = true;
+        config.runner.fsync = true;
+        let sync_check = |name: &str, found: bool| DependencyStatus {
+            name: name.to_string(),
+            state: None,
+            status: if found {
+                CheckStatus::OK
+            } else {
+                CheckStatus::WARN
+            },
+            found,
+            resolved_path: None,
+            note: String::new(),
+            remediation: Vec::new(),
+        };
+        let report = DoctorReport {
+            generated_at: String::new(),
+            has_embedded_config: true,
+            runtime: RuntimeDiscovery {
+                proton: None,
+                wine: None,
+                umu_run: None,
+                selected_runtime: Some(RuntimeCandidate::ProtonNative),
+                runtime_status: CheckStatus::OK,
+                runtime_note: String::new(),
+                remediation: Vec::new(),
+            },
+            dependencies: vec![sync_check("fsync", false), sync_check("esync", true)],
+            graphics: Default::default(),
+            host_rules: Vec::new(),
+            summary: CheckStatus::WARN,
+        };
+        let keys = |env: &[(String, String)]| {
+            env.iter()
+                .map(|(key, _)| key.as_str())
+                .filter(|key| key.contains("SYNC"))
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        };
+
+        let mut proton_env = Vec::new();
+        apply_proton_feature_envs(&mut proton_env, &config, &report);
+        assert_eq!(keys(&proton_env), vec!["PROTON_NO_FSYNC"]);
+
+        let mut wine_env = Vec::new();
+        apply_wine_feature_envs(&mut wine_env, &config, &report);
+        assert_eq!(keys(&wine_env), vec!["WINEESYNC"]);
+
+        let mut without_checks = report.clone();
+        without_checks.dependencies.clear();
+        let mut wine_env = Vec::new();
+        apply_wine_feature_envs(&mut wine_env, &config, &without_checks);
+        assert_eq!(keys(&wine_env), vec!["WINEESYNC", "WINEFSYNC"]);
+    }
+
     #[test]
     fn protected_env_key_helper_identifies_reserved_keys() {
         assert!(is_protected_env_key("WINEPREFIX"));
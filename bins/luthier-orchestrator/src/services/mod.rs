@@ -4,4 +4,5 @@ pub mod prefix_setup_service;
 pub mod registry_apply_service;
 pub mod runtime_flags;
 pub mod script_runner;
+pub mod signature_service;
 pub mod winecfg_apply_service;
@@ -7,11 +7,13 @@ use anyhow::anyhow;
 use luthier_orchestrator_core::{
     doctor::DoctorReport,
     prefix::{base_env_for_prefix, PrefixSetupPlan},
+    variables::VariableContext,
     GameConfig, RuntimeCandidate,
 };
 
 use crate::{
     application::runtime_overrides::feature_enabled, domain::models::PrefixSetupExecutionContext,
+    services::launch_plan_builder::host_supports_sync,
 };
 
 pub fn build_prefix_setup_execution_context(
@@ -100,6 +102,26 @@ pub fn effective_prefix_path_for_runtime(prefix_root: &Path, runtime: RuntimeCan
     }
 }
 
+/// Context for `${...}` expansion. Proton always creates the `steamuser`
+/// profile; plain Wine names it after the Unix user.
+pub fn variable_context_for_runtime(
+    game_root: &Path,
+    prefix_root: &Path,
+    runtime: RuntimeCandidate,
+) -> VariableContext {
+    let windows_user = match runtime {
+        RuntimeCandidate::ProtonNative | RuntimeCandidate::ProtonUmu => "steamuser".to_string(),
+        RuntimeCandidate::Wine => std::env::var("USER")
+            .or_else(|_| std::env::var("LOGNAME"))
+            .unwrap_or_else(|_| "steamuser".to_string()),
+    };
+    VariableContext::from_host_env(
+        game_root,
+        &effective_prefix_path_for_runtime(prefix_root, runtime),
+        &windows_user,
+    )
+}
+
 fn upsert_env(
     env_pairs: &mut Vec<(String, String)>,
     key: impl Into<String>,
@@ -267,10 +289,10 @@ fn apply_heroic_like_runtime_env_defaults(
             );
         }
 
-        apply_proton_feature_envs(env_pairs, config);
+        apply_proton_feature_envs(env_pairs, config, report);
         apply_proton_aux_runtime_envs(env_pairs, config, report);
     } else {
-        apply_wine_feature_envs(env_pairs, config);
+        apply_wine_feature_envs(env_pairs, config, report);
     }
 
     if matches!(runtime, RuntimeCandidate::ProtonUmu) {
@@ -294,12 +316,16 @@ fn apply_heroic_like_runtime_env_defaults(
     }
 }
 
-fn apply_proton_feature_envs(env_pairs: &mut Vec<(String, String)>, config: &GameConfig) {
-    if !config.runner.esync {
+fn apply_proton_feature_envs(
+    env_pairs: &mut Vec<(String, String)>,
+    config: &GameConfig,
+    report: &DoctorReport,
+) {
+    if !config.runner.esync || !host_supports_sync(report, "esync") {
         upsert_env(env_pairs, "PROTON_NO_ESYNC", "1");
     }
 
-    if !config.runner.fsync {
+    if !config.runner.fsync || !host_supports_sync(report, "fsync") {
         upsert_env(env_pairs, "PROTON_NO_FSYNC", "1");
     }
 
@@ -319,12 +345,16 @@ fn apply_proton_feature_envs(env_pairs: &mut Vec<(String, String)>, config: &Gam
     }
 }
 
-fn apply_wine_feature_envs(env_pairs: &mut Vec<(String, String)>, config: &GameConfig) {
-    if config.runner.esync {
+fn apply_wine_feature_envs(
+    env_pairs: &mut Vec<(String, String)>,
+    config: &GameConfig,
+    report: &DoctorReport,
+) {
+    if config.runner.esync && host_supports_sync(report, "esync") {
         upsert_env(env_pairs, "WINEESYNC", "1");
     }
 
-    if config.runner.fsync {
+    if config.runner.fsync && host_supports_sync(report, "fsync") {
         upsert_env(env_pairs, "WINEFSYNC", "1");
     }
 
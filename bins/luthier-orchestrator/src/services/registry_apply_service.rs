@@ -19,6 +19,7 @@ pub fn apply_registry_keys_if_present(
     config: &GameConfig,
     report: &DoctorReport,
     prefix_root_path: &Path,
+    game_root: &Path,
     dry_run: bool,
 ) -> anyhow::Result<Option<CommandExecutionResult>> {
     if config.registry_keys.is_empty() {
@@ -39,9 +40,14 @@ pub fn apply_registry_keys_if_present(
 
     let reg_windows_path =
         write_registry_import_file(&config.registry_keys, &effective_prefix_path)?;
-    let command_plan =
-        build_regedit_import_command(config, report, prefix_root_path, &reg_windows_path)
-            .context("failed to build registry import command")?;
+    let command_plan = build_regedit_import_command(
+        config,
+        report,
+        game_root,
+        prefix_root_path,
+        &reg_windows_path,
+    )
+    .context("failed to build registry import command")?;
 
     let command = ExternalCommand {
         name: "registry-import".to_string(),
@@ -72,6 +78,7 @@ struct RegeditImportCommandPlan {
 fn build_regedit_import_command(
     config: &GameConfig,
     report: &DoctorReport,
+    game_root: &Path,
     prefix_root_path: &Path,
     reg_windows_path: &str,
 ) -> anyhow::Result<RegeditImportCommandPlan> {
@@ -128,7 +135,7 @@ fn build_regedit_import_command(
     let (program, args) = split_program_and_args(std::mem::take(&mut command_tokens))
         .ok_or_else(|| anyhow!("failed to build registry import command"))?;
 
-    let mut env_pairs = build_winecfg_command(config, report, prefix_root_path)
+    let mut env_pairs = build_winecfg_command(config, report, game_root, prefix_root_path)
         .context("failed to derive registry import env from runtime")?
         .env;
 
@@ -1,27 +1,475 @@
-use crate::infrastructure::process_adapter::{
-    execute_external_command, CommandExecutionResult, ExternalCommand,
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
 };
 
-pub fn execute_script_if_present(
-    name: &str,
-    script: &str,
-    cwd: &str,
-    env_pairs: &[(String, String)],
-    dry_run: bool,
-    mandatory: bool,
-) -> Option<CommandExecutionResult> {
-    if script.trim().is_empty() {
-        return None;
-    }
-
-    let command = ExternalCommand {
-        name: name.to_string(),
-        program: "bash".to_string(),
-        args: vec!["-lc".to_string(), script.to_string()],
-        timeout_secs: Some(600),
-        cwd: Some(cwd.to_string()),
-        mandatory,
+use anyhow::{anyhow, Context};
+use luthier_orchestrator_core::{
+    doctor::DoctorReport, observability::LogLevel, process::BackgroundExit,
+    variables::VariableContext, ScriptInterpreter, ScriptStep,
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    infrastructure::{
+        paths::resolve_relative_path,
+        process_adapter::{
+            execute_external_command, has_mandatory_failures, spawn_background_command,
+            BackgroundProcess, CommandExecutionResult, ExternalCommand, StepStatus,
+        },
+    },
+    logging::log_event,
+    services::{
+        launch_plan_builder::runtime_invocation, prefix_setup_service::variable_context_for_runtime,
+    },
+};
+
+/// Time a background step gets to exit after SIGTERM before it is killed.
+const BACKGROUND_STOP_GRACE: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptPhase {
+    PreLaunch,
+    PostLaunch,
+}
+
+impl ScriptPhase {
+    fn event(self) -> (&'static str, &'static str) {
+        match self {
+            Self::PreLaunch => ("GO-SC-020", "pre_launch_script_executed"),
+            Self::PostLaunch => ("GO-SC-021", "post_launch_script_executed"),
+        }
+    }
+}
+
+/// Everything script steps share for one launch.
+#[derive(Debug, Clone)]
+pub struct ScriptContext {
+    pub game_root: PathBuf,
+    /// Holds the run-once markers.
+    pub prefix_root: PathBuf,
+    /// `umu-run`, `proton run` or `wine`, prepended to `prefix` steps.
+    pub runtime_invocation: Vec<String>,
+    pub variables: VariableContext,
+    pub env: Vec<(String, String)>,
+    pub dry_run: bool,
+}
+
+impl ScriptContext {
+    pub fn for_launch(
+        report: &DoctorReport,
+        game_root: &Path,
+        prefix_root: &Path,
+        env: &[(String, String)],
+        dry_run: bool,
+    ) -> anyhow::Result<Self> {
+        let runtime = report
+            .runtime
+            .selected_runtime
+            .ok_or_else(|| anyhow!("doctor did not select a runtime"))?;
+
+        Ok(Self {
+            game_root: game_root.to_path_buf(),
+            prefix_root: prefix_root.to_path_buf(),
+            runtime_invocation: runtime_invocation(report)?,
+            variables: variable_context_for_runtime(game_root, prefix_root, runtime),
+            env: env.to_vec(),
+            dry_run,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ScriptPhaseRun {
+    pub results: Vec<CommandExecutionResult>,
+    /// Background steps still running; see [`stop_background_steps`].
+    pub background: Vec<BackgroundProcess>,
+}
+
+impl ScriptPhaseRun {
+    pub fn has_mandatory_failure(&self) -> bool {
+        has_mandatory_failures(&self.results)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BackgroundStepStop {
+    pub name: String,
+    #[serde(flatten)]
+    pub exit: BackgroundExit,
+}
+
+/// Runs `steps` in order, logging one event per step. A failed mandatory step
+/// skips the rest of the phase.
+pub fn run_script_phase(
+    trace_id: &str,
+    phase: ScriptPhase,
+    steps: &[ScriptStep],
+    context: &ScriptContext,
+) -> ScriptPhaseRun {
+    let mut run = ScriptPhaseRun::default();
+    let mut blocked = false;
+
+    for step in steps {
+        let result = if blocked {
+            skipped_step(step, "skipped due to prior mandatory failure")
+        } else {
+            run_step(step, phase, context, &mut run.background)
+        };
+
+        let (code, message) = phase.event();
+        log_event(
+            trace_id,
+            LogLevel::Info,
+            "scripts",
+            code,
+            message,
+            serde_json::json!({
+                "step": step.name,
+                "interpreter": step.interpreter,
+                "background": is_background(step, phase),
+                "status": result.status,
+                "exit_code": result.exit_code,
+                "duration_ms": result.duration_ms,
+                "error": result.error,
+            }),
+        );
+
+        if result.mandatory && matches!(result.status, StepStatus::Failed | StepStatus::TimedOut) {
+            blocked = true;
+        }
+        run.results.push(result);
+    }
+
+    run
+}
+
+/// Stops background steps once the game has exited.
+pub fn stop_background_steps(
+    trace_id: &str,
+    background: Vec<BackgroundProcess>,
+) -> Vec<BackgroundStepStop> {
+    background
+        .into_iter()
+        .map(|process| {
+            let name = process.name.clone();
+            let exit = process.stop(BACKGROUND_STOP_GRACE);
+            log_event(
+                trace_id,
+                LogLevel::Info,
+                "scripts",
+                "GO-SC-022",
+                "background_script_stopped",
+                serde_json::json!({
+                    "step": name,
+                    "exited_before_stop": exit.exited_before_stop,
+                    "exit_code": exit.exit_code,
+                }),
+            );
+            BackgroundStepStop { name, exit }
+        })
+        .collect()
+}
+
+fn run_step(
+    step: &ScriptStep,
+    phase: ScriptPhase,
+    context: &ScriptContext,
+    background: &mut Vec<BackgroundProcess>,
+) -> CommandExecutionResult {
+    let marker = step
+        .run_once
+        .then(|| run_once_marker(&context.prefix_root, step));
+    if let Some((path, hash)) = &marker {
+        if run_once_marker_is_fresh(path, hash) {
+            return skipped_step(step, "already ran for this prefix");
+        }
+    }
+
+    let command = match build_step_command(step, context) {
+        Ok(command) => command,
+        Err(err) => {
+            let mut result = skipped_step(step, &format!("{err:#}"));
+            result.status = StepStatus::Failed;
+            return result;
+        }
+    };
+
+    let mut result = if is_background(step, phase) {
+        let (result, process) = spawn_background_command(&command, &context.env, context.dry_run);
+        background.extend(process);
+        result
+    } else {
+        execute_external_command(&command, &context.env, context.dry_run)
+    };
+
+    if let (Some((path, hash)), StepStatus::Success) = (&marker, result.status) {
+        if let Err(err) = write_run_once_marker(path, hash) {
+            result.error = Some(format!("{err:#}"));
+        }
+    }
+
+    result
+}
+
+/// Background mode only applies before launch; post-launch steps always wait.
+fn is_background(step: &ScriptStep, phase: ScriptPhase) -> bool {
+    step.background && phase == ScriptPhase::PreLaunch
+}
+
+fn build_step_command(
+    step: &ScriptStep,
+    context: &ScriptContext,
+) -> anyhow::Result<ExternalCommand> {
+    let args = step
+        .args
+        .iter()
+        .map(|arg| context.variables.expand(arg))
+        .collect::<Result<Vec<_>, _>>()
+        .with_context(|| format!("invalid args in script step '{}'", step.name))?;
+    let cwd = match &step.cwd {
+        Some(cwd) => resolve_relative_path(&context.game_root, cwd)
+            .with_context(|| format!("invalid cwd in script step '{}'", step.name))?,
+        None => context.game_root.clone(),
     };
 
-    Some(execute_external_command(&command, env_pairs, dry_run))
+    let (program, args) = match step.interpreter {
+        ScriptInterpreter::Bash | ScriptInterpreter::Sh => {
+            let (shell, flag) = match step.interpreter {
+                ScriptInterpreter::Bash => ("bash", "-lc"),
+                _ => ("sh", "-c"),
+            };
+            // `$0` is the step name, so step args arrive as `$1...`.
+            let mut shell_args = vec![flag.to_string(), step.command.clone(), step.name.clone()];
+            shell_args.extend(args);
+            (shell.to_string(), shell_args)
+        }
+        ScriptInterpreter::Prefix => {
+            let target = context
+                .variables
+                .expand(&step.command)
+                .with_context(|| format!("invalid command in script step '{}'", step.name))?;
+            let target = if target.starts_with("./") {
+                resolve_relative_path(&context.game_root, &target)
+                    .with_context(|| format!("invalid command in script step '{}'", step.name))?
+                    .to_string_lossy()
+                    .into_owned()
+            } else {
+                target
+            };
+
+            let mut tokens = context.runtime_invocation.clone();
+            tokens.push(target);
+            tokens.extend(args);
+            let mut tokens = tokens.into_iter();
+            let program = tokens
+                .next()
+                .ok_or_else(|| anyhow!("runtime invocation is empty"))?;
+            (program, tokens.collect())
+        }
+    };
+
+    Ok(ExternalCommand {
+        name: step.name.clone(),
+        program,
+        args,
+        timeout_secs: Some(step.timeout_secs),
+        cwd: Some(cwd.to_string_lossy().into_owned()),
+        mandatory: step.mandatory,
+    })
+}
+
+fn skipped_step(step: &ScriptStep, reason: &str) -> CommandExecutionResult {
+    CommandExecutionResult {
+        name: step.name.clone(),
+        program: interpreter_label(step.interpreter).to_string(),
+        args: Vec::new(),
+        mandatory: step.mandatory,
+        status: StepStatus::Skipped,
+        exit_code: None,
+        duration_ms: 0,
+        error: Some(reason.to_string()),
+    }
+}
+
+fn interpreter_label(interpreter: ScriptInterpreter) -> &'static str {
+    match interpreter {
+        ScriptInterpreter::Bash => "bash",
+        ScriptInterpreter::Sh => "sh",
+        ScriptInterpreter::Prefix => "prefix",
+    }
+}
+
+/// Marker path and the hash of what the step runs, so editing a run-once step
+/// makes it run again. The file name ends in a hash of the exact step name, as
+/// the readable part folds names like `a b` and `a/b` together.
+fn run_once_marker(prefix_root: &Path, step: &ScriptStep) -> (PathBuf, String) {
+    let readable = step
+        .name
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    let name_hash = hex_digest(Sha256::digest(step.name.as_bytes()).as_slice());
+    let path = prefix_root.join(format!(
+        ".luthier_script_{readable}_{}.sha256",
+        &name_hash[..8]
+    ));
+
+    let mut hasher = Sha256::new();
+    hasher.update(interpreter_label(step.interpreter).as_bytes());
+    hasher.update([0]);
+    hasher.update(step.command.as_bytes());
+    for arg in &step.args {
+        hasher.update([0]);
+        hasher.update(arg.as_bytes());
+    }
+    let hash = hex_digest(hasher.finalize().as_slice());
+
+    (path, hash)
+}
+
+fn hex_digest(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn run_once_marker_is_fresh(path: &Path, expected_hash: &str) -> bool {
+    let Ok(saved) = fs::read_to_string(path) else {
+        return false;
+    };
+    saved.trim() == expected_hash
+}
+
+fn write_run_once_marker(path: &Path, hash: &str) -> anyhow::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create prefix directory '{}'", parent.display()))?;
+    }
+    fs::write(path, format!("{hash}\n"))
+        .with_context(|| format!("failed to write run-once marker '{}'", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn step(name: &str, interpreter: ScriptInterpreter, command: &str) -> ScriptStep {
+        ScriptStep {
+            name: name.to_string(),
+            interpreter,
+            command: command.to_string(),
+            args: vec!["${GAME_ROOT}/saves".to_string()],
+            cwd: Some("bin".to_string()),
+            timeout_secs: 30,
+            mandatory: true,
+            run_once: false,
+            background: false,
+        }
+    }
+
+    fn context(prefix_root: &Path, dry_run: bool) -> ScriptContext {
+        ScriptContext {
+            game_root: PathBuf::from("/games/witcher"),
+            prefix_root: prefix_root.to_path_buf(),
+            runtime_invocation: vec!["/opt/proton/proton".to_string(), "run".to_string()],
+            variables: VariableContext {
+                game_root: PathBuf::from("/games/witcher"),
+                prefix: prefix_root.join("pfx"),
+                windows_user: "steamuser".to_string(),
+                host_env: HashMap::new(),
+            },
+            env: Vec::new(),
+            dry_run,
+        }
+    }
+
+    #[test]
+    fn builds_shell_and_prefix_commands() {
+        let ctx = context(Path::new("/prefixes/abc"), true);
+
+        let shell = build_step_command(
+            &step("saves", ScriptInterpreter::Sh, "mkdir -p \"$1\""),
+            &ctx,
+        )
+        .expect("shell command");
+        assert_eq!(shell.program, "sh");
+        assert_eq!(
+            shell.args,
+            vec!["-c", "mkdir -p \"$1\"", "saves", "/games/witcher/saves"]
+        );
+        assert_eq!(shell.cwd.as_deref(), Some("/games/witcher/bin"));
+        assert_eq!(shell.timeout_secs, Some(30));
+
+        let prefix = build_step_command(
+            &step("redist", ScriptInterpreter::Prefix, "./redist/setup.exe"),
+            &ctx,
+        )
+        .expect("prefix command");
+        assert_eq!(prefix.program, "/opt/proton/proton");
+        assert_eq!(
+            prefix.args,
+            vec![
+                "run",
+                "/games/witcher/redist/setup.exe",
+                "/games/witcher/saves"
+            ]
+        );
+    }
+
+    #[test]
+    fn mandatory_failure_skips_the_rest_of_the_phase() {
+        let ctx = context(Path::new("/prefixes/abc"), false);
+        let mut broken = step("broken", ScriptInterpreter::Bash, "true");
+        broken.cwd = Some("../outside".to_string());
+        let steps = vec![broken, step("next", ScriptInterpreter::Bash, "true")];
+
+        let run = run_script_phase("trace", ScriptPhase::PreLaunch, &steps, &ctx);
+        assert!(run.has_mandatory_failure());
+        assert_eq!(run.results[0].status, StepStatus::Failed);
+        assert_eq!(run.results[1].status, StepStatus::Skipped);
+    }
+
+    #[test]
+    fn run_once_step_is_skipped_after_it_succeeded() {
+        let prefix_root =
+            std::env::temp_dir().join(format!("luthier-script-run-once-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&prefix_root);
+        let mut ctx = context(&prefix_root, false);
+        ctx.game_root = std::env::temp_dir();
+        let mut once = step("first-run", ScriptInterpreter::Sh, "true");
+        once.cwd = None;
+        once.run_once = true;
+
+        let first = run_script_phase("trace", ScriptPhase::PreLaunch, &[once.clone()], &ctx);
+        assert_eq!(first.results[0].status, StepStatus::Success);
+        let second = run_script_phase("trace", ScriptPhase::PreLaunch, &[once.clone()], &ctx);
+        assert_eq!(second.results[0].status, StepStatus::Skipped);
+
+        once.command = "true # edited".to_string();
+        let edited = run_script_phase("trace", ScriptPhase::PreLaunch, &[once], &ctx);
+        assert_eq!(edited.results[0].status, StepStatus::Success);
+
+        let _ = fs::remove_dir_all(&prefix_root);
+    }
+
+    #[test]
+    fn run_once_markers_keep_names_that_sanitise_alike_apart() {
+        let prefix_root = Path::new("/prefix");
+        let paths = ["a b", "a/b", "a_b"]
+            .map(|name| run_once_marker(prefix_root, &step(name, ScriptInterpreter::Sh, "true")).0);
+        assert_ne!(paths[0], paths[1]);
+        assert_ne!(paths[0], paths[2]);
+        assert_ne!(paths[1], paths[2]);
+        assert!(paths[0].starts_with(prefix_root));
+    }
 }
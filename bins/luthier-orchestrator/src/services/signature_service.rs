@@ -0,0 +1,173 @@
+use anyhow::{anyhow, Context};
+use luthier_orchestrator_core::{
+    asset_container::AssetManifest,
+    observability::LogLevel,
+    signing::{
+        evaluate_manifest_trust, load_trust_store, trust_store_path, SignaturePolicy,
+        SignatureVerdict,
+    },
+};
+
+use crate::logging::log_event;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignatureGate {
+    Allow,
+    AllowWithWarning,
+    Refuse,
+}
+
+pub fn signature_gate(policy: SignaturePolicy, verdict: &SignatureVerdict) -> SignatureGate {
+    match (policy, verdict.is_trusted()) {
+        (SignaturePolicy::Ignore, _) | (_, true) => SignatureGate::Allow,
+        (SignaturePolicy::Warn, false) => SignatureGate::AllowWithWarning,
+        (SignaturePolicy::Refuse, false) => SignatureGate::Refuse,
+    }
+}
+
+/// The stricter of the trust store policy and the `--signature-policy` flag.
+/// The flag can sit in a shortcut's launch options, so it may only tighten
+/// what the user stored.
+pub fn effective_signature_policy(
+    stored: SignaturePolicy,
+    requested: Option<SignaturePolicy>,
+) -> SignaturePolicy {
+    let rank = |policy| match policy {
+        SignaturePolicy::Refuse => 2_u8,
+        SignaturePolicy::Warn => 1_u8,
+        SignaturePolicy::Ignore => 0_u8,
+    };
+    match requested {
+        Some(requested) if rank(requested) > rank(stored) => requested,
+        _ => stored,
+    }
+}
+
+/// Checks the embedded manifest signature against the local trust store and
+/// applies the configured policy, tightened by `policy_override`.
+pub fn enforce_payload_signature(
+    trace_id: &str,
+    manifest: &AssetManifest,
+    policy_override: Option<SignaturePolicy>,
+) -> anyhow::Result<SignatureVerdict> {
+    let store_path = trust_store_path().context("failed to resolve trust store path")?;
+    let store = load_trust_store(&store_path)
+        .with_context(|| format!("failed to read trust store {}", store_path.display()))?;
+    let policy = effective_signature_policy(store.policy, policy_override);
+    let verdict = evaluate_manifest_trust(manifest, &store);
+    let context = serde_json::json!({
+        "policy": policy.as_str(),
+        "trust_store": store_path.to_string_lossy(),
+        "verdict": &verdict,
+    });
+
+    match signature_gate(policy, &verdict) {
+        SignatureGate::Allow => {
+            log_event(
+                trace_id,
+                LogLevel::Info,
+                "signature",
+                "GO-SG-010",
+                "payload_signature_checked",
+                context,
+            );
+            Ok(verdict)
+        }
+        SignatureGate::AllowWithWarning => {
+            log_event(
+                trace_id,
+                LogLevel::Warn,
+                "signature",
+                "GO-SG-011",
+                "payload_signature_not_trusted",
+                context,
+            );
+            Ok(verdict)
+        }
+        SignatureGate::Refuse => {
+            log_event(
+                trace_id,
+                LogLevel::Error,
+                "signature",
+                "GO-SG-012",
+                "payload_signature_refused",
+                context,
+            );
+            Err(anyhow!(
+                "embedded payload is {} and the signature policy is 'refuse'; add the signer to {} or change the policy",
+                verdict.as_str(),
+                store_path.display()
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use luthier_orchestrator_core::signing::SignerIdentity;
+
+    use super::*;
+
+    fn trusted() -> SignatureVerdict {
+        SignatureVerdict::Trusted {
+            signer: SignerIdentity {
+                signer: "team".to_string(),
+                public_key_hex: "00".repeat(32),
+                fingerprint: "0000000000000000".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn refuse_policy_only_allows_trusted_payloads() {
+        assert_eq!(
+            signature_gate(SignaturePolicy::Refuse, &trusted()),
+            SignatureGate::Allow
+        );
+        assert_eq!(
+            signature_gate(SignaturePolicy::Refuse, &SignatureVerdict::Unsigned),
+            SignatureGate::Refuse
+        );
+        assert_eq!(
+            signature_gate(
+                SignaturePolicy::Refuse,
+                &SignatureVerdict::Invalid {
+                    reason: "mismatch".to_string()
+                }
+            ),
+            SignatureGate::Refuse
+        );
+    }
+
+    #[test]
+    fn warn_and_ignore_policies_never_refuse() {
+        assert_eq!(
+            signature_gate(SignaturePolicy::Warn, &SignatureVerdict::Unsigned),
+            SignatureGate::AllowWithWarning
+        );
+        assert_eq!(
+            signature_gate(SignaturePolicy::Ignore, &SignatureVerdict::Unsigned),
+            SignatureGate::Allow
+        );
+    }
+
+    #[test]
+    fn policy_flag_only_tightens_the_stored_policy() {
+        assert_eq!(
+            effective_signature_policy(SignaturePolicy::Refuse, Some(SignaturePolicy::Ignore)),
+            SignaturePolicy::Refuse
+        );
+        assert_eq!(
+            effective_signature_policy(SignaturePolicy::Warn, Some(SignaturePolicy::Ignore)),
+            SignaturePolicy::Warn
+        );
+        assert_eq!(
+            effective_signature_policy(SignaturePolicy::Warn, Some(SignaturePolicy::Refuse)),
+            SignaturePolicy::Refuse
+        );
+        assert_eq!(
+            effective_signature_policy(SignaturePolicy::Ignore, None),
+            SignaturePolicy::Ignore
+        );
+    }
+}
@@ -66,9 +66,14 @@ pub fn apply_winecfg_overrides_if_present(
             &raw_registry,
             &effective_prefix_path,
         )?;
-        let command_plan =
-            build_regedit_import_command(config, report, prefix_root_path, &reg_windows_path)
-                .context("failed to build winecfg registry import command")?;
+        let command_plan = build_regedit_import_command(
+            config,
+            report,
+            game_root,
+            prefix_root_path,
+            &reg_windows_path,
+        )
+        .context("failed to build winecfg registry import command")?;
 
         let command = ExternalCommand {
             name: "winecfg-registry-apply".to_string(),
@@ -124,6 +129,7 @@ struct RegeditImportCommandPlan {
 fn build_regedit_import_command(
     config: &GameConfig,
     report: &DoctorReport,
+    game_root: &Path,
     prefix_root_path: &Path,
     reg_windows_path: &str,
 ) -> anyhow::Result<RegeditImportCommandPlan> {
@@ -180,7 +186,7 @@ fn build_regedit_import_command(
     let (program, args) = split_program_and_args(std::mem::take(&mut command_tokens))
         .ok_or_else(|| anyhow!("failed to build registry import command"))?;
 
-    let mut env_pairs = build_winecfg_command(config, report, prefix_root_path)
+    let mut env_pairs = build_winecfg_command(config, report, game_root, prefix_root_path)
         .context("failed to derive registry import env from runtime")?
         .env;
 
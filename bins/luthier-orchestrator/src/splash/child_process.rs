@@ -4,6 +4,8 @@ use std::sync::mpsc;
 use std::thread;
 
 use anyhow::Context;
+use luthier_orchestrator_core::launch_entries::MAIN_LAUNCH_ENTRY;
+use luthier_orchestrator_core::signing::SignaturePolicy;
 
 #[derive(Debug)]
 pub(crate) enum ChildProcessStream {
@@ -17,10 +19,30 @@ pub(crate) enum ChildProcessEvent {
     Exited(Option<i32>),
 }
 
-pub(crate) fn spawn_play_child(tx: mpsc::Sender<ChildProcessEvent>) -> anyhow::Result<()> {
+/// Selections the splash forwards to the `--play` child.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct PlayChildArgs<'a> {
+    pub signature_policy: Option<SignaturePolicy>,
+    pub profile: Option<&'a str>,
+    /// `None` runs the main executable, even when the payload sets `default_entry`.
+    pub entry: Option<&'a str>,
+}
+
+pub(crate) fn spawn_play_child(
+    tx: mpsc::Sender<ChildProcessEvent>,
+    args: PlayChildArgs<'_>,
+) -> anyhow::Result<()> {
     let current_exe = std::env::current_exe().context("failed to locate current executable")?;
-    let mut child = Command::new(&current_exe)
-        .arg("--play")
+    let mut command = Command::new(&current_exe);
+    command.arg("--play");
+    if let Some(policy) = args.signature_policy {
+        command.args(["--signature-policy", policy.as_str()]);
+    }
+    if let Some(profile) = args.profile {
+        command.args(["--profile", profile]);
+    }
+    command.args(["--entry", args.entry.unwrap_or(MAIN_LAUNCH_ENTRY)]);
+    let mut child = command
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .spawn()
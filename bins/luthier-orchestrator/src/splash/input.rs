@@ -278,7 +278,25 @@ fn decode_png_icon_to_x11_buffer(png_bytes: &[u8]) -> anyhow::Result<Vec<u64>> {
 }
 
 /// Returns the hitbox `Rect` for each button in the prelaunch screen.
-pub fn prelaunch_button_rects(win_w: i32, win_h: i32, gear_visible: bool) -> PrelaunchRects {
+pub fn prelaunch_button_rects(
+    win_w: i32,
+    win_h: i32,
+    gear_visible: bool,
+    profile_visible: bool,
+    entry_visible: bool,
+) -> PrelaunchRects {
+    let profile_button = Rect {
+        x: 22,
+        y: 20,
+        w: 220,
+        h: 28,
+    };
+    let entry_button = Rect {
+        x: 22,
+        y: if profile_visible { 54 } else { 20 },
+        w: 220,
+        h: 28,
+    };
     let gear_button = Rect {
         x: win_w - 154,
         y: 20,
@@ -299,9 +317,13 @@ pub fn prelaunch_button_rects(win_w: i32, win_h: i32, gear_visible: bool) -> Pre
     };
     PrelaunchRects {
         gear_button,
+        profile_button,
+        entry_button,
         start_button,
         exit_button,
         gear_visible,
+        profile_visible,
+        entry_visible,
     }
 }
 
@@ -353,9 +375,13 @@ pub fn feedback_button_rects(win_w: i32, win_h: i32) -> (Rect, Rect) {
 /// Aggregated hitboxes for the prelaunch screen.
 pub struct PrelaunchRects {
     pub gear_button: Rect,
+    pub profile_button: Rect,
+    pub entry_button: Rect,
     pub start_button: Rect,
     pub exit_button: Rect,
     pub gear_visible: bool,
+    pub profile_visible: bool,
+    pub entry_visible: bool,
 }
 
 /// Aggregated hitboxes for the config overlay screen.
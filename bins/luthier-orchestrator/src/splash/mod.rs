@@ -6,14 +6,23 @@ use std::time::{Duration, Instant};
 use anyhow::Context;
 use image::imageops::FilterType;
 use luthier_orchestrator_core::doctor::{run_doctor, CheckStatus, DoctorReport};
+use luthier_orchestrator_core::observability::new_trace_id;
+use luthier_orchestrator_core::signing::SignaturePolicy;
 use luthier_orchestrator_core::GameConfig;
 use minifb::{Key, Scale, Window};
 
+use crate::application::host_rules::apply_host_rules_for_current_host;
+use crate::application::launch_entries::{next_launch_entry, resolve_launch_entry_for_run};
+use crate::application::launch_profiles::{
+    apply_launch_profile_or_base, log_resolved_launch_profile, next_launch_profile,
+    resolve_launch_profile,
+};
 use crate::application::runtime_overrides::{
     apply_runtime_overrides, build_feature_view, load_runtime_overrides, save_runtime_overrides,
     RuntimeOverrides,
 };
 use crate::infrastructure::payload_loader::load_embedded_payload_required;
+use crate::services::signature_service::enforce_payload_signature;
 
 pub mod assets;
 pub mod child_process;
@@ -24,7 +33,7 @@ pub mod state;
 pub mod text;
 pub mod theme;
 
-use child_process::{spawn_play_child, ChildProcessEvent};
+use child_process::{spawn_play_child, ChildProcessEvent, PlayChildArgs};
 use input::*;
 use progress_events::handle_child_event;
 use renderer::*;
@@ -41,19 +50,34 @@ pub(crate) struct SplashWindowScale {
 
 // ── Public entry point ──────────────────────────────────────────────────────
 
-pub fn run_splash_flow(mode: SplashLaunchMode, lang_override: Option<&str>) -> anyhow::Result<()> {
+pub fn run_splash_flow(
+    mode: SplashLaunchMode,
+    lang_override: Option<&str>,
+    signature_policy: Option<SignaturePolicy>,
+    profile: Option<&str>,
+    entry: Option<&str>,
+) -> anyhow::Result<()> {
     initialize_splash_locale(lang_override);
-    let payload =
+    let mut payload =
         load_embedded_payload_required().context("embedded payload is required for splash mode")?;
-    let mut config = payload.config;
-    let hero_background = decode_hero_background_from_asset(payload.hero_image.as_deref())
+    // Refuse before any window opens; the `--play` child repeats the check itself.
+    enforce_payload_signature(&new_trace_id(), &payload.manifest, signature_policy)?;
+    let hero_image = payload.hero_image()?;
+    let hero_background = decode_hero_background_from_asset(hero_image.as_deref())
         .ok()
         .flatten()
         .map(Arc::new);
-    let embedded_icon_png = payload.icon_png.map(Arc::new);
-    let overrides =
-        load_runtime_overrides(&config.exe_hash).unwrap_or_else(|_| RuntimeOverrides::default());
+    let embedded_icon_png = payload.icon_png()?.map(Arc::new);
+    let base_config = payload.config;
+    let overrides = load_runtime_overrides(&base_config.exe_hash)
+        .unwrap_or_else(|_| RuntimeOverrides::default());
+    let resolved = resolve_launch_profile(base_config.clone(), profile, &overrides)?;
+    log_resolved_launch_profile(&new_trace_id(), &resolved);
+    let active_profile = resolved.profile;
+    let mut config = resolved.config;
     apply_runtime_overrides(&mut config, &overrides);
+    let config = apply_host_rules_for_current_host(&new_trace_id(), config)?;
+    let active_entry = resolve_launch_entry_for_run(&config, entry)?.name;
 
     let doctor = run_doctor(Some(&config));
     if matches!(doctor.summary, CheckStatus::BLOCKER) {
@@ -68,6 +92,9 @@ pub fn run_splash_flow(mode: SplashLaunchMode, lang_override: Option<&str>) -> a
 
     let mut prelaunch = PrelaunchState {
         configurable_rows: build_toggle_rows(&config, &overrides),
+        base_config,
+        profile: active_profile,
+        entry: active_entry,
         config,
         overrides,
         doctor,
@@ -92,6 +119,11 @@ pub fn run_splash_flow(mode: SplashLaunchMode, lang_override: Option<&str>) -> a
                 &prelaunch.config.game_name,
                 prelaunch.hero_background.clone(),
                 embedded_icon_png.clone(),
+                PlayChildArgs {
+                    signature_policy,
+                    profile: prelaunch.profile.as_deref(),
+                    entry: prelaunch.entry.as_deref(),
+                },
             )?;
             let _ = show_post_game_feedback_window(outcome);
             let _ = overrides;
@@ -100,6 +132,24 @@ pub fn run_splash_flow(mode: SplashLaunchMode, lang_override: Option<&str>) -> a
     }
 }
 
+/// Rebuilds the effective config, doctor report and toggle rows after the
+/// player changes the launch profile or saves overrides.
+fn refresh_prelaunch_config(state: &mut PrelaunchState) -> anyhow::Result<()> {
+    let mut fresh = apply_launch_profile_or_base(&state.base_config, state.profile.as_deref())?;
+    apply_runtime_overrides(&mut fresh, &state.overrides);
+    let fresh = apply_host_rules_for_current_host(&new_trace_id(), fresh)?;
+    state.doctor = run_doctor(Some(&fresh));
+    // A profile may drop the picked entry; fall back to the main executable.
+    if let Some(entry) = &state.entry {
+        if !fresh.launch_entries.iter().any(|item| &item.name == entry) {
+            state.entry = None;
+        }
+    }
+    state.config = fresh;
+    state.configurable_rows = build_toggle_rows(&state.config, &state.overrides);
+    Ok(())
+}
+
 // ── Toggle-row helpers ───────────────────────────────────────────────────────
 
 fn build_toggle_rows(config: &GameConfig, overrides: &RuntimeOverrides) -> Vec<ToggleRow> {
@@ -236,7 +286,15 @@ fn show_prelaunch_window(
         };
 
         let gear_visible = !state.configurable_rows.is_empty();
-        let rects = prelaunch_button_rects(WIN_W as i32, WIN_H as i32, gear_visible);
+        let profile_visible = !state.base_config.profiles.is_empty();
+        let entry_visible = !state.config.launch_entries.is_empty();
+        let rects = prelaunch_button_rects(
+            WIN_W as i32,
+            WIN_H as i32,
+            gear_visible,
+            profile_visible,
+            entry_visible,
+        );
 
         if config_open {
             let cfg = config_button_rects(WIN_W as i32, WIN_H as i32);
@@ -251,11 +309,7 @@ fn show_prelaunch_window(
                 save_runtime_overrides(&state.config.exe_hash, &config_working)
                     .context("failed to save runtime overrides from splash")?;
                 state.overrides = config_working.clone();
-                let mut fresh = load_embedded_payload_required()?.config;
-                apply_runtime_overrides(&mut fresh, &state.overrides);
-                state.doctor = run_doctor(Some(&fresh));
-                state.config = fresh;
-                state.configurable_rows = build_toggle_rows(&state.config, &state.overrides);
+                refresh_prelaunch_config(state)?;
                 config_rows = state.configurable_rows.clone();
                 config_open = false;
                 state.countdown_started_at = Instant::now();
@@ -302,6 +356,39 @@ fn show_prelaunch_window(
             continue;
         }
 
+        if rects.profile_visible
+            && mouse.left_pressed
+            && rects.profile_button.contains(mouse.x, mouse.y)
+        {
+            // The choice is saved as the default so the `--play` child and the
+            // next launch pick the same profile.
+            state.profile = next_launch_profile(&state.base_config, state.profile.as_deref());
+            state.overrides.default_profile = state.profile.clone();
+            save_runtime_overrides(&state.config.exe_hash, &state.overrides)
+                .context("failed to save default launch profile from splash")?;
+            refresh_prelaunch_config(state)?;
+            state.countdown_started_at = Instant::now();
+            if matches!(state.doctor.summary, CheckStatus::BLOCKER) {
+                show_doctor_block_window(
+                    &state.config,
+                    &state.doctor,
+                    state.hero_background.as_ref(),
+                    embedded_icon_png,
+                )?;
+                return Ok(PrelaunchDecision::Exit);
+            }
+            continue;
+        }
+
+        if rects.entry_visible
+            && mouse.left_pressed
+            && rects.entry_button.contains(mouse.x, mouse.y)
+        {
+            state.entry = next_launch_entry(&state.config, state.entry.as_deref());
+            state.countdown_started_at = Instant::now();
+            continue;
+        }
+
         if mouse.left_pressed && rects.exit_button.contains(mouse.x, mouse.y) {
             return Ok(PrelaunchDecision::Exit);
         }
@@ -324,6 +411,10 @@ fn show_prelaunch_window(
                 countdown_left: countdown_left.max(0),
                 gear_visible,
                 gear_button: rects.gear_button,
+                profile_visible,
+                profile_button: rects.profile_button,
+                entry_visible,
+                entry_button: rects.entry_button,
                 start_button: rects.start_button,
                 exit_button: rects.exit_button,
                 mode,
@@ -378,7 +469,7 @@ fn show_doctor_block_window(
     }
 }
 
-fn build_doctor_block_items(config: &GameConfig, report: &DoctorReport) -> Vec<(String, bool)> {
+fn build_doctor_block_items(config: &GameConfig, report: &DoctorReport) -> Vec<DoctorBlockItem> {
     let mut out = Vec::new();
 
     let runtime_ok = !matches!(report.runtime.runtime_status, CheckStatus::BLOCKER);
@@ -387,11 +478,25 @@ fn build_doctor_block_items(config: &GameConfig, report: &DoctorReport) -> Vec<(
     } else {
         config.runner.proton_version.trim().to_lowercase()
     };
-    out.push((proton_label, runtime_ok));
+    out.push(DoctorBlockItem {
+        name: proton_label,
+        ok: runtime_ok,
+        remediation: report
+            .runtime
+            .remediation
+            .first()
+            .map(|r| r.command.clone()),
+    });
 
     for dep in &report.dependencies {
-        let ok = matches!(dep.status, CheckStatus::OK | CheckStatus::INFO);
-        out.push((dep.name.clone(), ok));
+        out.push(DoctorBlockItem {
+            name: dep.name.clone(),
+            ok: matches!(dep.status, CheckStatus::OK | CheckStatus::INFO),
+            remediation: dep
+                .remediation
+                .first()
+                .map(|remediation| remediation.command.clone()),
+        });
     }
 
     out
@@ -403,9 +508,10 @@ fn show_runtime_progress_window(
     game_name: &str,
     hero_background: Option<Arc<HeroBackground>>,
     icon_png: Option<Arc<Vec<u8>>>,
+    child_args: PlayChildArgs<'_>,
 ) -> anyhow::Result<ChildRunOutcome> {
     let (tx, rx) = mpsc::channel::<ChildProcessEvent>();
-    spawn_play_child(tx)?;
+    spawn_play_child(tx, child_args)?;
     let mut last_left_down = false;
     let mut progress = ProgressViewState::new(game_name.to_string(), hero_background.clone());
 
@@ -3,8 +3,8 @@ use fontdue::Font;
 use minifb::Window;
 
 use crate::splash::state::{
-    ChildRunOutcome, HeroBackground, MouseSnapshot, PrelaunchState, ProgressViewState, Rect,
-    SplashLaunchMode, TextMetrics, ToggleRow,
+    ChildRunOutcome, DoctorBlockItem, HeroBackground, MouseSnapshot, PrelaunchState,
+    ProgressViewState, Rect, SplashLaunchMode, TextMetrics, ToggleRow,
 };
 use crate::splash::theme::{
     system_font, BAD, BG, BORDER, BTN, BTN_HOVER, MUTED, SEPARATOR, TEXT, WIN_H, WIN_W,
@@ -67,6 +67,10 @@ pub struct PrelaunchRenderContext<'a> {
     pub countdown_left: i32,
     pub gear_visible: bool,
     pub gear_button: Rect,
+    pub profile_visible: bool,
+    pub profile_button: Rect,
+    pub entry_visible: bool,
+    pub entry_button: Rect,
     pub start_button: Rect,
     pub exit_button: Rect,
     pub mode: SplashLaunchMode,
@@ -123,6 +127,46 @@ pub fn draw_prelaunch(
         );
     }
 
+    if context.profile_visible {
+        draw_button_secondary_clean(
+            buffer,
+            context.profile_button,
+            context
+                .profile_button
+                .contains(context.mouse.x, context.mouse.y),
+        );
+        let profile_name = state
+            .profile
+            .as_deref()
+            .unwrap_or(t(SplashTextKey::ProfileBase));
+        let label = truncate_with_ellipsis(
+            &format!("{}: {profile_name}", t(SplashTextKey::ProfileLabel)),
+            context.profile_button.w - 16,
+            1,
+        );
+        draw_button_label_centered(buffer, context.profile_button, &label, TEXT, 1);
+    }
+
+    if context.entry_visible {
+        draw_button_secondary_clean(
+            buffer,
+            context.entry_button,
+            context
+                .entry_button
+                .contains(context.mouse.x, context.mouse.y),
+        );
+        let entry_name = state
+            .entry
+            .as_deref()
+            .unwrap_or(t(SplashTextKey::EntryMain));
+        let label = truncate_with_ellipsis(
+            &format!("{}: {entry_name}", t(SplashTextKey::EntryLabel)),
+            context.entry_button.w - 16,
+            1,
+        );
+        draw_button_label_centered(buffer, context.entry_button, &label, TEXT, 1);
+    }
+
     draw_button_secondary_clean(
         buffer,
         context.exit_button,
@@ -244,7 +288,7 @@ pub fn draw_config(
 pub fn draw_doctor_block(
     buffer: &mut [u32],
     _window: &Window,
-    items: &[(String, bool)],
+    items: &[DoctorBlockItem],
     exit_button: Rect,
     mouse: &MouseSnapshot,
     hero_background: Option<&HeroBackground>,
@@ -285,19 +329,25 @@ pub fn draw_doctor_block(
     );
 
     let mut y = 108;
-    for (name, ok) in items.iter().take(10) {
-        let status = if *ok {
+    let status_x = WIN_W as i32 - 160;
+    let remediation_x = 220;
+    for item in items.iter().take(10) {
+        let status = if item.ok {
             t(SplashTextKey::DepOk)
         } else {
             t(SplashTextKey::DepNotOk)
         };
-        draw_text(buffer, 28, y, name, TEXT, 1);
+        draw_text(buffer, 28, y, &item.name, TEXT, 1);
+        if let Some(command) = item.remediation.as_deref().filter(|_| !item.ok) {
+            let command = truncate_with_ellipsis(command, status_x - remediation_x - 16, 1);
+            draw_text(buffer, remediation_x, y, &command, MUTED, 1);
+        }
         draw_text(
             buffer,
-            WIN_W as i32 - 160,
+            status_x,
             y,
             status,
-            if *ok { TEXT } else { MUTED },
+            if item.ok { TEXT } else { MUTED },
             1,
         );
         fill_rect(
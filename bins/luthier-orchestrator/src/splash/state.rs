@@ -21,6 +21,15 @@ pub struct ToggleRow {
     pub value: Option<bool>,
 }
 
+#[derive(Debug, Clone)]
+pub struct DoctorBlockItem {
+    pub name: String,
+    pub ok: bool,
+    /// Install command for the host's package manager, shown next to checks
+    /// that failed.
+    pub remediation: Option<String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct HeroBackground {
     pub pixels: Vec<u32>,
@@ -28,6 +37,11 @@ pub struct HeroBackground {
 
 #[derive(Debug, Clone)]
 pub struct PrelaunchState {
+    /// Embedded config before any launch profile or override is applied.
+    pub base_config: GameConfig,
+    pub profile: Option<String>,
+    /// Launch entry handed to the `--play` child; `None` runs the main executable.
+    pub entry: Option<String>,
     pub config: GameConfig,
     pub overrides: RuntimeOverrides,
     pub doctor: DoctorReport,
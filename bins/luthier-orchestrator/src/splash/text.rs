@@ -37,6 +37,10 @@ pub(crate) enum SplashTextKey {
     ToggleDefault,
     ToggleEnabled,
     ToggleDisabled,
+    ProfileLabel,
+    ProfileBase,
+    EntryLabel,
+    EntryMain,
     MissingDepsTitle,
     MissingDepsHint,
     DepOk,
@@ -111,6 +115,10 @@ fn t_for(locale: SplashLocale, key: SplashTextKey) -> &'static str {
             SplashTextKey::ToggleDefault => "Padrao",
             SplashTextKey::ToggleEnabled => "Ativado",
             SplashTextKey::ToggleDisabled => "Desativado",
+            SplashTextKey::ProfileLabel => "Perfil",
+            SplashTextKey::ProfileBase => "Base",
+            SplashTextKey::EntryLabel => "Executar",
+            SplashTextKey::EntryMain => "Jogo",
             SplashTextKey::MissingDepsTitle => "Dependencias faltando",
             SplashTextKey::MissingDepsHint => {
                 "Instale as dependencias faltantes antes de continuar"
@@ -158,6 +166,10 @@ fn t_for(locale: SplashLocale, key: SplashTextKey) -> &'static str {
             SplashTextKey::ToggleDefault => "Default",
             SplashTextKey::ToggleEnabled => "Enabled",
             SplashTextKey::ToggleDisabled => "Disabled",
+            SplashTextKey::ProfileLabel => "Profile",
+            SplashTextKey::ProfileBase => "Base",
+            SplashTextKey::EntryLabel => "Run",
+            SplashTextKey::EntryMain => "Game",
             SplashTextKey::MissingDepsTitle => "Missing dependencies",
             SplashTextKey::MissingDepsHint => "Install missing dependencies before continuing",
             SplashTextKey::DepOk => "ok",
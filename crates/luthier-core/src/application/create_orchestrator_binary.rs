@@ -13,6 +13,11 @@ pub(crate) fn create_orchestrator_binary(
 
     let base_bytes = binary_reader.read_bytes(&request.base_binary_path)?;
     let config_bytes = serde_json::to_vec_pretty(&request.config)?;
+    let signing_key_bytes = request
+        .signing_key_path
+        .as_deref()
+        .map(|path| binary_reader.read_bytes(path))
+        .transpose()?;
 
     let inject_result =
         payload_injector.inject_orchestrator_payload(OrchestratorPayloadInjectionRequest {
@@ -20,6 +25,7 @@ pub(crate) fn create_orchestrator_binary(
             config_json_bytes: &config_bytes,
             hero_image_bytes: request.hero_image_bytes.as_deref(),
             icon_png_bytes: request.icon_png_bytes.as_deref(),
+            signing_key_bytes: signing_key_bytes.as_deref(),
             output_path: &request.output_path,
             options: OrchestratorPayloadInjectionOptions {
                 backup_existing: request.backup_existing,
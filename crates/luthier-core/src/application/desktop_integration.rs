@@ -0,0 +1,314 @@
+use std::path::{Path, PathBuf};
+
+use luthier_orchestrator_core::asset_container::parse_asset_container;
+
+use crate::application::export_steam_shortcut::launcher_display_name;
+use crate::application::ports::{ExportFileSystemPort, IconScalerPort};
+use crate::domain::desktop_entry::{
+    desktop_entry_value, desktop_id, render_desktop_entry, DesktopEntry, DESKTOP_ID_PREFIX,
+    HICOLOR_SIZES, LAUNCHER_KEY, REQUIRED_HICOLOR_SIZE,
+};
+use crate::models::{
+    DesktopEntryInstallRequest, DesktopEntryInstallResult, DesktopEntryUninstallRequest,
+    DesktopEntryUninstallResult,
+};
+use crate::LuthierError;
+
+/// Desktop ids tried per game name before giving up, for several launchers of
+/// the same game.
+const MAX_DESKTOP_ID_ATTEMPTS: usize = 100;
+
+/// Installs `<data_home>/applications/<id>.desktop` for a launcher, with its
+/// embedded icon in the hicolor theme. Reinstalling rewrites the entry that
+/// already points at the launcher.
+pub(crate) fn install_desktop_entry(
+    request: &DesktopEntryInstallRequest,
+    file_system: &dyn ExportFileSystemPort,
+    icon_scaler: &dyn IconScalerPort,
+) -> Result<DesktopEntryInstallResult, LuthierError> {
+    let launcher = launcher_path_string(&request.launcher_path)?;
+    let working_dir = request.launcher_path.parent().ok_or_else(|| {
+        LuthierError::DesktopIntegration(format!("launcher has no parent directory: {launcher}"))
+    })?;
+
+    let launcher_bytes = file_system.read_bytes(&request.launcher_path)?;
+    let container = parse_asset_container(&launcher_bytes)?;
+    let name = launcher_display_name(
+        request.name.as_deref(),
+        &container.config_json()?,
+        &request.launcher_path,
+    );
+
+    let applications_dir = applications_dir(&request.data_home);
+    let existing_id = find_installed_entry(file_system, &applications_dir, &launcher)?;
+    let id = match &existing_id {
+        Some(id) => id.clone(),
+        None => free_desktop_id(file_system, &applications_dir, &name)?,
+    };
+
+    let icon_files = match container.icon_png()? {
+        Some(icon) => install_icons(file_system, icon_scaler, &request.data_home, &id, &icon)?,
+        None => {
+            remove_icons(file_system, &request.data_home, &id, &[])?;
+            Vec::new()
+        }
+    };
+
+    let desktop_file = applications_dir.join(format!("{id}.desktop"));
+    let contents = render_desktop_entry(&DesktopEntry {
+        name,
+        launcher_path: launcher,
+        working_dir: working_dir.to_string_lossy().into_owned(),
+        icon_name: (!icon_files.is_empty()).then(|| id.clone()),
+    });
+    file_system.write_bytes(&desktop_file, contents.as_bytes())?;
+
+    Ok(DesktopEntryInstallResult {
+        desktop_id: id,
+        desktop_file: desktop_file.to_string_lossy().into_owned(),
+        icon_files: icon_files
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect(),
+        created: existing_id.is_none(),
+    })
+}
+
+/// Removes the entry installed for a launcher and its icons. The launcher
+/// itself does not need to exist anymore.
+pub(crate) fn uninstall_desktop_entry(
+    request: &DesktopEntryUninstallRequest,
+    file_system: &dyn ExportFileSystemPort,
+) -> Result<DesktopEntryUninstallResult, LuthierError> {
+    let launcher = launcher_path_string(&request.launcher_path)?;
+    let applications_dir = applications_dir(&request.data_home);
+    let Some(id) = find_installed_entry(file_system, &applications_dir, &launcher)? else {
+        return Ok(DesktopEntryUninstallResult {
+            desktop_id: None,
+            removed_files: Vec::new(),
+        });
+    };
+
+    let desktop_file = applications_dir.join(format!("{id}.desktop"));
+    file_system.remove_file(&desktop_file)?;
+    let mut removed_files = vec![desktop_file];
+    removed_files.extend(remove_icons(file_system, &request.data_home, &id, &[])?);
+
+    Ok(DesktopEntryUninstallResult {
+        desktop_id: Some(id),
+        removed_files: removed_files
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect(),
+    })
+}
+
+fn launcher_path_string(path: &Path) -> Result<String, LuthierError> {
+    if !path.is_absolute() {
+        return Err(LuthierError::DesktopIntegration(format!(
+            "launcher path must be absolute: {}",
+            path.display()
+        )));
+    }
+    Ok(path.to_string_lossy().into_owned())
+}
+
+fn applications_dir(data_home: &Path) -> PathBuf {
+    data_home.join("applications")
+}
+
+fn icon_path(data_home: &Path, size: u32, id: &str) -> PathBuf {
+    data_home
+        .join("icons/hicolor")
+        .join(format!("{size}x{size}"))
+        .join("apps")
+        .join(format!("{id}.png"))
+}
+
+/// Id of the Luthier entry whose launcher key matches `launcher`.
+fn find_installed_entry(
+    file_system: &dyn ExportFileSystemPort,
+    applications_dir: &Path,
+    launcher: &str,
+) -> Result<Option<String>, LuthierError> {
+    for path in file_system.list_files(applications_dir)? {
+        let Some(id) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(".desktop"))
+            .filter(|id| id.starts_with(DESKTOP_ID_PREFIX))
+        else {
+            continue;
+        };
+
+        let contents = file_system.read_bytes(&path)?;
+        let installed_for = desktop_entry_value(&String::from_utf8_lossy(&contents), LAUNCHER_KEY);
+        if installed_for.as_deref() == Some(launcher) {
+            return Ok(Some(id.to_string()));
+        }
+    }
+    Ok(None)
+}
+
+fn free_desktop_id(
+    file_system: &dyn ExportFileSystemPort,
+    applications_dir: &Path,
+    name: &str,
+) -> Result<String, LuthierError> {
+    (0..MAX_DESKTOP_ID_ATTEMPTS)
+        .map(|attempt| desktop_id(name, attempt))
+        .find(|id| !file_system.exists(&applications_dir.join(format!("{id}.desktop"))))
+        .ok_or_else(|| LuthierError::DesktopIntegration(format!("no free desktop id for '{name}'")))
+}
+
+/// Writes every standard size up to the icon's own, plus 48px which themes
+/// require, and drops sizes a previous install left behind.
+fn install_icons(
+    file_system: &dyn ExportFileSystemPort,
+    icon_scaler: &dyn IconScalerPort,
+    data_home: &Path,
+    id: &str,
+    icon: &[u8],
+) -> Result<Vec<PathBuf>, LuthierError> {
+    let source_size = icon_scaler.png_size(icon)?;
+    let sizes = HICOLOR_SIZES
+        .into_iter()
+        .filter(|size| *size <= source_size || *size == REQUIRED_HICOLOR_SIZE)
+        .collect::<Vec<_>>();
+
+    let mut written = Vec::with_capacity(sizes.len());
+    for size in &sizes {
+        let path = icon_path(data_home, *size, id);
+        file_system.write_bytes(&path, &icon_scaler.scale_png(icon, *size)?)?;
+        written.push(path);
+    }
+    remove_icons(file_system, data_home, id, &sizes)?;
+    Ok(written)
+}
+
+fn remove_icons(
+    file_system: &dyn ExportFileSystemPort,
+    data_home: &Path,
+    id: &str,
+    keep_sizes: &[u32],
+) -> Result<Vec<PathBuf>, LuthierError> {
+    let mut removed = Vec::new();
+    for size in HICOLOR_SIZES {
+        let path = icon_path(data_home, size, id);
+        if !keep_sizes.contains(&size) && file_system.exists(&path) {
+            file_system.remove_file(&path)?;
+            removed.push(path);
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use luthier_orchestrator_core::asset_container::{
+        append_asset_container, AssetContainerWriteInput,
+    };
+
+    use super::*;
+    use crate::application::export_steam_shortcut::tests::MemoryFileSystem;
+
+    /// Pretends every icon is 64px and tags scaled copies with their size.
+    struct FakeIconScaler;
+
+    impl IconScalerPort for FakeIconScaler {
+        fn png_size(&self, _png: &[u8]) -> Result<u32, LuthierError> {
+            Ok(64)
+        }
+
+        fn scale_png(&self, _png: &[u8], size: u32) -> Result<Vec<u8>, LuthierError> {
+            Ok(size.to_string().into_bytes())
+        }
+    }
+
+    fn store_launcher(fs: &MemoryFileSystem, path: &str, game_name: &str) {
+        let config_json = format!(r#"{{"game_name":"{game_name}"}}"#);
+        let bytes = append_asset_container(
+            b"ELF",
+            AssetContainerWriteInput {
+                config_json: config_json.as_bytes(),
+                hero_image: None,
+                icon_png: Some(b"\x89PNG icon"),
+                named_assets: &[],
+                signing_key: None,
+            },
+        )
+        .expect("container is written");
+        fs.write_bytes(Path::new(path), &bytes)
+            .expect("launcher is stored");
+    }
+
+    fn install_request(launcher: &str) -> DesktopEntryInstallRequest {
+        DesktopEntryInstallRequest {
+            launcher_path: PathBuf::from(launcher),
+            data_home: PathBuf::from("/home/user/.local/share"),
+            name: None,
+        }
+    }
+
+    #[test]
+    fn installs_reinstalls_and_uninstalls_entry_with_icons() {
+        let fs = MemoryFileSystem::default();
+        store_launcher(&fs, "/games/sample/game", "Sample Game");
+
+        let first =
+            install_desktop_entry(&install_request("/games/sample/game"), &fs, &FakeIconScaler)
+                .expect("entry is installed");
+        assert!(first.created);
+        assert_eq!(first.desktop_id, "luthier-sample-game");
+        assert_eq!(
+            first.desktop_file,
+            "/home/user/.local/share/applications/luthier-sample-game.desktop"
+        );
+        assert_eq!(first.icon_files.len(), 5);
+        assert!(first.icon_files.contains(
+            &"/home/user/.local/share/icons/hicolor/48x48/apps/luthier-sample-game.png".to_string()
+        ));
+
+        let contents = fs
+            .read_bytes(Path::new(&first.desktop_file))
+            .expect("desktop file is written");
+        let contents = String::from_utf8(contents).expect("utf-8");
+        assert!(contents.contains("Exec=\"/games/sample/game\" --play-splash\n"));
+        assert!(contents.contains("Path=/games/sample\n"));
+        assert!(contents.contains("Icon=luthier-sample-game\n"));
+
+        store_launcher(&fs, "/games/sample/game", "Renamed");
+        let second =
+            install_desktop_entry(&install_request("/games/sample/game"), &fs, &FakeIconScaler)
+                .expect("entry is reinstalled");
+        assert!(!second.created);
+        assert_eq!(second.desktop_id, first.desktop_id);
+
+        let removed = uninstall_desktop_entry(
+            &DesktopEntryUninstallRequest {
+                launcher_path: PathBuf::from("/games/sample/game"),
+                data_home: PathBuf::from("/home/user/.local/share"),
+            },
+            &fs,
+        )
+        .expect("entry is removed");
+        assert_eq!(removed.desktop_id.as_deref(), Some("luthier-sample-game"));
+        assert_eq!(removed.removed_files.len(), 6);
+        assert_eq!(fs.files.borrow().len(), 1);
+    }
+
+    #[test]
+    fn gives_launchers_of_the_same_game_distinct_ids() {
+        let fs = MemoryFileSystem::default();
+        store_launcher(&fs, "/games/a/game", "Sample");
+        store_launcher(&fs, "/games/b/game", "Sample");
+
+        let a = install_desktop_entry(&install_request("/games/a/game"), &fs, &FakeIconScaler)
+            .expect("first entry");
+        let b = install_desktop_entry(&install_request("/games/b/game"), &fs, &FakeIconScaler)
+            .expect("second entry");
+
+        assert_eq!(a.desktop_id, "luthier-sample");
+        assert_eq!(b.desktop_id, "luthier-sample-2");
+    }
+}
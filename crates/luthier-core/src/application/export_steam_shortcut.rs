@@ -0,0 +1,259 @@
+use std::path::{Path, PathBuf};
+
+use luthier_orchestrator_core::asset_container::parse_asset_container;
+use serde_json::Value;
+
+use crate::application::ports::ExportFileSystemPort;
+use crate::domain::steam_shortcuts::{
+    parse_binary_vdf, render_binary_vdf, upsert_shortcut, SteamShortcut,
+};
+use crate::models::{SteamShortcutExportRequest, SteamShortcutExportResult};
+use crate::LuthierError;
+
+const LAUNCH_OPTIONS: &str = "--play-splash";
+
+/// Registers a generated launcher as a non-Steam game for one Steam user and
+/// copies its embedded artwork into the user's custom grid folder. Running it
+/// again updates the existing shortcut.
+pub(crate) fn export_steam_shortcut(
+    request: &SteamShortcutExportRequest,
+    file_system: &dyn ExportFileSystemPort,
+) -> Result<SteamShortcutExportResult, LuthierError> {
+    let launcher_path = &request.launcher_path;
+    if !launcher_path.is_absolute() {
+        return Err(LuthierError::SteamShortcutExport(format!(
+            "launcher path must be absolute: {}",
+            launcher_path.display()
+        )));
+    }
+    let start_dir = launcher_path.parent().ok_or_else(|| {
+        LuthierError::SteamShortcutExport(format!(
+            "launcher has no parent directory: {}",
+            launcher_path.display()
+        ))
+    })?;
+
+    let launcher_bytes = file_system.read_bytes(launcher_path)?;
+    let container = parse_asset_container(&launcher_bytes)?;
+    let app_name = launcher_display_name(
+        request.app_name.as_deref(),
+        &container.config_json()?,
+        launcher_path,
+    );
+
+    let config_dir = request.steam_user_dir.join("config");
+    let grid_dir = config_dir.join("grid");
+    let shortcuts_path = config_dir.join("shortcuts.vdf");
+
+    let existing = if file_system.exists(&shortcuts_path) {
+        Some(file_system.read_bytes(&shortcuts_path)?)
+    } else {
+        None
+    };
+    let mut document = parse_binary_vdf(existing.as_deref().unwrap_or_default())?;
+
+    let icon_png = container.icon_png()?;
+    let mut shortcut = SteamShortcut {
+        app_name: app_name.clone(),
+        exe: launcher_path.to_string_lossy().into_owned(),
+        start_dir: start_dir.to_string_lossy().into_owned(),
+        icon: String::new(),
+        launch_options: LAUNCH_OPTIONS.to_string(),
+    };
+    // The icon path depends on the app id, which is only known once the
+    // entry is matched or created; the second pass fills it in.
+    let upsert = upsert_shortcut(&mut document, &shortcut)?;
+    let icon_path = grid_dir.join(format!("{}_icon.png", upsert.app_id));
+    if icon_png.is_some() {
+        shortcut.icon = icon_path.to_string_lossy().into_owned();
+        upsert_shortcut(&mut document, &shortcut)?;
+    }
+
+    let rendered = render_binary_vdf(&document);
+    if let Some(previous) = existing.as_deref().filter(|bytes| *bytes != rendered) {
+        file_system.write_bytes(&backup_path(&shortcuts_path), previous)?;
+    }
+    file_system.write_bytes(&shortcuts_path, &rendered)?;
+
+    let mut artwork_paths = Vec::new();
+    if let Some(icon) = icon_png {
+        file_system.write_bytes(&icon_path, &icon)?;
+        artwork_paths.push(icon_path);
+    }
+    if let Some(hero) = container.hero_image()? {
+        let hero_path = grid_dir.join(format!("{}_hero.{}", upsert.app_id, image_extension(&hero)));
+        file_system.write_bytes(&hero_path, &hero)?;
+        artwork_paths.push(hero_path);
+    }
+
+    Ok(SteamShortcutExportResult {
+        app_id: upsert.app_id,
+        app_name,
+        shortcuts_path: shortcuts_path.to_string_lossy().into_owned(),
+        created: upsert.created,
+        artwork_paths: artwork_paths
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect(),
+    })
+}
+
+/// Title shown for an exported launcher: the caller's choice, else the
+/// embedded game name, else the launcher's file name.
+pub(crate) fn launcher_display_name(
+    requested: Option<&str>,
+    config_json: &[u8],
+    launcher_path: &Path,
+) -> String {
+    match requested.map(str::trim) {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => embedded_game_name(config_json).unwrap_or_else(|| file_name_of(launcher_path)),
+    }
+}
+
+fn embedded_game_name(config_json: &[u8]) -> Option<String> {
+    serde_json::from_slice::<Value>(config_json)
+        .ok()?
+        .get("game_name")?
+        .as_str()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
+
+fn file_name_of(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    path.with_file_name(format!("{}.bak", file_name_of(path)))
+}
+
+/// Steam picks the image decoder from the file extension, so the hero keeps
+/// the format it was embedded in.
+fn image_extension(bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(b"\x89PNG") {
+        "png"
+    } else if bytes.starts_with(&[0xff, 0xd8, 0xff]) {
+        "jpg"
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        "webp"
+    } else {
+        "png"
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::cell::RefCell;
+    use std::collections::BTreeMap;
+
+    use luthier_orchestrator_core::asset_container::{
+        append_asset_container, AssetContainerWriteInput,
+    };
+
+    use super::*;
+
+    /// In-memory `ExportFileSystemPort` shared by the exporter tests.
+    #[derive(Default)]
+    pub(crate) struct MemoryFileSystem {
+        pub(crate) files: RefCell<BTreeMap<PathBuf, Vec<u8>>>,
+    }
+
+    impl ExportFileSystemPort for MemoryFileSystem {
+        fn exists(&self, path: &Path) -> bool {
+            self.files.borrow().contains_key(path)
+        }
+
+        fn list_files(&self, dir: &Path) -> Result<Vec<PathBuf>, LuthierError> {
+            Ok(self
+                .files
+                .borrow()
+                .keys()
+                .filter(|path| path.parent() == Some(dir))
+                .cloned()
+                .collect())
+        }
+
+        fn read_bytes(&self, path: &Path) -> Result<Vec<u8>, LuthierError> {
+            self.files
+                .borrow()
+                .get(path)
+                .cloned()
+                .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "missing").into())
+        }
+
+        fn write_bytes(&self, path: &Path, bytes: &[u8]) -> Result<(), LuthierError> {
+            self.files
+                .borrow_mut()
+                .insert(path.to_path_buf(), bytes.to_vec());
+            Ok(())
+        }
+
+        fn remove_file(&self, path: &Path) -> Result<(), LuthierError> {
+            self.files.borrow_mut().remove(path);
+            Ok(())
+        }
+    }
+
+    fn launcher_bytes() -> Vec<u8> {
+        append_asset_container(
+            b"ELF",
+            AssetContainerWriteInput {
+                config_json: br#"{"game_name":"Sample Game"}"#,
+                hero_image: Some(b"RIFF\0\0\0\0WEBPVP8 "),
+                icon_png: Some(b"\x89PNG icon"),
+                named_assets: &[],
+                signing_key: None,
+            },
+        )
+        .expect("container is written")
+    }
+
+    #[test]
+    fn exports_shortcut_and_artwork_idempotently() {
+        let fs = MemoryFileSystem::default();
+        fs.write_bytes(Path::new("/games/sample/sample"), &launcher_bytes())
+            .expect("launcher is stored");
+        let request = SteamShortcutExportRequest {
+            launcher_path: PathBuf::from("/games/sample/sample"),
+            steam_user_dir: PathBuf::from("/steam/userdata/42"),
+            app_name: None,
+        };
+
+        let first = export_steam_shortcut(&request, &fs).expect("first export");
+        assert!(first.created);
+        assert_eq!(first.app_name, "Sample Game");
+        assert_eq!(
+            first.artwork_paths,
+            vec![
+                format!("/steam/userdata/42/config/grid/{}_icon.png", first.app_id),
+                format!("/steam/userdata/42/config/grid/{}_hero.webp", first.app_id),
+            ]
+        );
+
+        let shortcuts = PathBuf::from("/steam/userdata/42/config/shortcuts.vdf");
+        let written = fs.read_bytes(&shortcuts).expect("shortcuts written");
+
+        let second = export_steam_shortcut(&request, &fs).expect("second export");
+        assert!(!second.created);
+        assert_eq!(second.app_id, first.app_id);
+        assert_eq!(fs.read_bytes(&shortcuts).expect("shortcuts"), written);
+        assert!(!fs.exists(Path::new("/steam/userdata/42/config/shortcuts.vdf.bak")));
+    }
+
+    #[test]
+    fn rejects_relative_launcher_paths() {
+        let request = SteamShortcutExportRequest {
+            launcher_path: PathBuf::from("sample"),
+            steam_user_dir: PathBuf::from("/steam/userdata/42"),
+            app_name: None,
+        };
+
+        let err = export_steam_shortcut(&request, &MemoryFileSystem::default())
+            .expect_err("relative launcher is rejected");
+        assert!(matches!(err, LuthierError::SteamShortcutExport(_)));
+    }
+}
@@ -0,0 +1,491 @@
+use std::path::Path;
+
+use luthier_orchestrator_core::config::{
+    FeatureState, LaunchEntry, RuntimePreference, WinecfgFeaturePolicy,
+};
+use luthier_orchestrator_core::GameConfig;
+use serde_json::{Map, Value};
+
+use crate::application::import_game_config::{
+    apply_executable, base_game_config, dll_override_rule, executable_stem, finish_import,
+    optional_state, push_dependency, resolve_executable, set_custom_var, set_optional_state,
+    split_command_line, value_as_bool, value_as_string, ImportWarnings,
+};
+use crate::application::ports::ImportFileSystemPort;
+use crate::models::ImportedGameConfig;
+use crate::LuthierError;
+
+const WARNING_PREFIX: &str = "bottles";
+
+/// Bottles dependencies whose winetricks verb has a different name.
+const RENAMED_DEPENDENCIES: [(&str, &str); 9] = [
+    ("vcredist2005", "vcrun2005"),
+    ("vcredist2008", "vcrun2008"),
+    ("vcredist2010", "vcrun2010"),
+    ("vcredist2012", "vcrun2012"),
+    ("vcredist2013", "vcrun2013"),
+    ("vcredist2015", "vcrun2015"),
+    ("vcredist2019", "vcrun2019"),
+    ("vcredist2022", "vcrun2022"),
+    ("d3dx11", "d3dx11_43"),
+];
+
+/// Bottles dependency name prefixes that are also winetricks verbs.
+const SAME_NAME_DEPENDENCY_PREFIXES: [&str; 15] = [
+    "dotnet",
+    "d3dx9",
+    "d3dx10",
+    "d3dcompiler_",
+    "xact",
+    "xinput",
+    "msxml",
+    "mfc",
+    "quartz",
+    "wmp",
+    "faudio",
+    "allfonts",
+    "corefonts",
+    "cjkfonts",
+    "physx",
+];
+
+/// Bundled with Wine itself, so there is nothing to install.
+const BUILTIN_DEPENDENCIES: [&str; 2] = ["mono", "gecko"];
+
+/// Bottles metadata that has no bearing on how the game runs.
+const METADATA_KEYS: [&str; 12] = [
+    "Name",
+    "Path",
+    "Custom_Path",
+    "Environment",
+    "Creation_Date",
+    "Update_Date",
+    "Versioning",
+    "State",
+    "Installed_Dependencies",
+    "Uninstallers",
+    "session_arguments",
+    "run_in_terminal",
+];
+
+/// Builds a config from a Bottles `bottle.yml`. The program named like the
+/// bottle (otherwise the first by name) becomes the main executable and the
+/// other `External_Programs` become launch entries.
+pub(crate) fn import_bottles_config(
+    source: &str,
+    game_root: &Path,
+    file_system: &dyn ImportFileSystemPort,
+) -> Result<ImportedGameConfig, LuthierError> {
+    let document = serde_yaml::from_str::<Value>(source)
+        .map_err(|err| LuthierError::InvalidImportSource(format!("bottles yaml: {err}")))?;
+    let Value::Object(document) = document else {
+        return Err(LuthierError::InvalidImportSource(
+            "bottles yaml must contain a mapping".to_string(),
+        ));
+    };
+
+    let bottle_name = document.get("Name").and_then(Value::as_str);
+    let mut programs = programs(&document);
+    if programs.is_empty() {
+        return Err(LuthierError::InvalidImportSource(
+            "bottle has no External_Programs to launch".to_string(),
+        ));
+    }
+    let main_index = bottle_name
+        .and_then(|bottle| {
+            programs
+                .iter()
+                .position(|program| program.name.eq_ignore_ascii_case(bottle.trim()))
+        })
+        .unwrap_or(0);
+    let main = programs.remove(main_index);
+
+    let name = [Some(main.name.as_str()), bottle_name]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .find(|name| !name.is_empty())
+        .map(ToString::to_string)
+        .unwrap_or_else(|| executable_stem(&main.path));
+
+    let mut warnings = ImportWarnings::default();
+    let mut config = base_game_config(&name)?;
+    apply_executable(
+        &mut config,
+        game_root,
+        &main.path,
+        &main.field,
+        file_system,
+        &mut warnings,
+    )?;
+    config.launch_args = split_command_line(&main.arguments);
+
+    for program in programs {
+        let resolved = resolve_executable(
+            game_root,
+            &program.path,
+            &program.field,
+            file_system,
+            &mut warnings,
+        )?;
+        config.launch_entries.push(LaunchEntry {
+            name: program.name,
+            relative_exe_path: resolved.relative_exe_path,
+            launch_args: split_command_line(&program.arguments),
+            working_dir: None,
+            env: Default::default(),
+        });
+    }
+
+    for (key, value) in &document {
+        apply_bottle_field(&mut config, key, value, &mut warnings);
+    }
+
+    Ok(finish_import(config, warnings))
+}
+
+struct BottlesProgram {
+    field: String,
+    name: String,
+    path: String,
+    arguments: String,
+}
+
+fn programs(document: &Map<String, Value>) -> Vec<BottlesProgram> {
+    let Some(programs) = document.get("External_Programs").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+
+    let mut out = programs
+        .iter()
+        .filter_map(|(id, program)| {
+            let program = program.as_object()?;
+            if program.get("removed").and_then(value_as_bool) == Some(true) {
+                return None;
+            }
+            let path = program.get("path").and_then(Value::as_str)?.to_string();
+            Some(BottlesProgram {
+                field: format!("External_Programs.{id}.path"),
+                name: program
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .map(|name| name.trim().to_string())
+                    .filter(|name| !name.is_empty())
+                    .unwrap_or_else(|| executable_stem(&path)),
+                arguments: program
+                    .get("arguments")
+                    .and_then(value_as_string)
+                    .unwrap_or_default(),
+                path,
+            })
+        })
+        .collect::<Vec<_>>();
+    out.sort_by_key(|program| program.name.to_ascii_lowercase());
+    out
+}
+
+fn apply_bottle_field(
+    config: &mut GameConfig,
+    key: &str,
+    value: &Value,
+    warnings: &mut ImportWarnings,
+) {
+    match key {
+        "External_Programs" => {}
+        "Runner" => {
+            if let Some(runner) = value.as_str() {
+                apply_runner(config, runner, key, warnings);
+            }
+        }
+        "Arch" => {
+            if value.as_str().map(str::trim) == Some("win32") {
+                warnings.push(
+                    "bottles_arch_win32",
+                    key,
+                    "32-bit prefixes are not supported; the game runs in a 64-bit prefix",
+                );
+            }
+        }
+        "Windows" => {
+            config.winecfg.windows_version = value_as_string(value)
+                .map(|raw| raw.trim().to_string())
+                .filter(|raw| !raw.is_empty());
+        }
+        "DLL_Overrides" => {
+            for (dll, mode) in value.as_object().into_iter().flatten() {
+                let raw_mode = value_as_string(mode).unwrap_or_default();
+                match dll_override_rule(dll, &raw_mode) {
+                    Some(rule) => config.winecfg.dll_overrides.push(rule),
+                    None => warnings.push(
+                        "bottles_dll_override_invalid",
+                        &format!("{key}.{dll}"),
+                        format!("unknown override mode '{raw_mode}'"),
+                    ),
+                }
+            }
+        }
+        "Environment_Variables" => {
+            for (name, raw) in value.as_object().into_iter().flatten() {
+                match value_as_string(raw) {
+                    Some(raw) => set_custom_var(config, name, &raw),
+                    None => warnings.push(
+                        "bottles_env_invalid",
+                        &format!("{key}.{name}"),
+                        "environment value must be a scalar",
+                    ),
+                }
+            }
+        }
+        "Dependencies" => {
+            for (index, dependency) in value.as_array().into_iter().flatten().enumerate() {
+                let Some(dependency) = dependency.as_str() else {
+                    continue;
+                };
+                match winetricks_verb(dependency) {
+                    Some(verb) => push_dependency(config, &verb),
+                    None if BUILTIN_DEPENDENCIES.contains(&dependency.trim()) => {}
+                    None => warnings.push(
+                        "bottles_dependency_unmapped",
+                        &format!("{key}[{index}]"),
+                        format!("no winetricks verb is known for '{dependency}'"),
+                    ),
+                }
+            }
+        }
+        "Parameters" => {
+            for (name, value) in value.as_object().into_iter().flatten() {
+                apply_parameter(config, name, value, &format!("{key}.{name}"), warnings);
+            }
+        }
+        "DXVK" | "VKD3D" | "NVAPI" | "LatencyFleX" => {
+            if !value.as_str().unwrap_or_default().trim().is_empty() {
+                warnings.push(
+                    "bottles_component_version_ignored",
+                    key,
+                    "component versions are chosen by the runtime; only on/off is imported",
+                );
+            }
+        }
+        key if METADATA_KEYS.contains(&key) => {}
+        _ => warnings.ignored_field(WARNING_PREFIX, key, value),
+    }
+}
+
+fn apply_parameter(
+    config: &mut GameConfig,
+    name: &str,
+    value: &Value,
+    field: &str,
+    warnings: &mut ImportWarnings,
+) {
+    let enabled = value_as_bool(value);
+    match name {
+        "dxvk" => {
+            if enabled == Some(false) {
+                set_custom_var(config, "PROTON_USE_WINED3D", "1");
+            }
+        }
+        "vkd3d" => {
+            if enabled == Some(false) {
+                set_custom_var(config, "PROTON_NO_D3D12", "1");
+            }
+        }
+        "dxvk_nvapi" => set_optional_state(&mut config.compatibility.auto_dxvk_nvapi, enabled),
+        "mangohud" => set_optional_state(&mut config.environment.mangohud, enabled),
+        "gamemode" => set_optional_state(&mut config.environment.gamemode, enabled),
+        "gamescope" => set_optional_state(&mut config.environment.gamescope.state, enabled),
+        "discrete_gpu" => set_optional_state(&mut config.environment.prime_offload, enabled),
+        "sync" => {
+            let sync = value
+                .as_str()
+                .unwrap_or_default()
+                .trim()
+                .to_ascii_lowercase();
+            let (esync, fsync) = match sync.as_str() {
+                "esync" => (true, false),
+                "fsync" | "futex2" => (true, true),
+                _ => (false, false),
+            };
+            config.runner.esync = esync;
+            config.runner.fsync = fsync;
+        }
+        "virtual_desktop" => {
+            if enabled == Some(true) {
+                let desktop = &mut config.winecfg.virtual_desktop;
+                desktop.state.state = FeatureState::OptionalOn;
+                desktop.state.use_wine_default = false;
+            }
+        }
+        "virtual_desktop_res" => {
+            config.winecfg.virtual_desktop.resolution = value_as_string(value)
+                .map(|raw| raw.trim().to_string())
+                .filter(|raw| !raw.is_empty());
+        }
+        "decorated" => set_winecfg_policy(&mut config.winecfg.window_decorations, enabled),
+        "fullscreen_capture" => set_winecfg_policy(&mut config.winecfg.auto_capture_mouse, enabled),
+        _ => warnings.ignored_field(WARNING_PREFIX, field, value),
+    }
+}
+
+/// Bottles runners are named like `soda-7.0-9` or `GE-Proton8-26`. Proton
+/// builds are kept as the preferred Proton version; Wine builds cannot be
+/// pinned since the orchestrator uses the host's Wine.
+fn apply_runner(config: &mut GameConfig, raw: &str, field: &str, warnings: &mut ImportWarnings) {
+    let runner = raw.trim();
+    if runner.is_empty() {
+        return;
+    }
+
+    if runner.to_ascii_lowercase().contains("proton") {
+        config.runner.runtime_preference = RuntimePreference::Proton;
+        config.runner.proton_version = runner.to_string();
+    } else {
+        config.runner.runtime_preference = RuntimePreference::Wine;
+        warnings.push(
+            "bottles_wine_version_ignored",
+            field,
+            format!("Wine build '{runner}' cannot be pinned; the host Wine is used"),
+        );
+    }
+}
+
+fn winetricks_verb(dependency: &str) -> Option<String> {
+    let dependency = dependency.trim().to_ascii_lowercase();
+    if let Some((_, verb)) = RENAMED_DEPENDENCIES
+        .iter()
+        .find(|(name, _)| *name == dependency)
+    {
+        return Some((*verb).to_string());
+    }
+
+    SAME_NAME_DEPENDENCY_PREFIXES
+        .iter()
+        .any(|prefix| dependency.starts_with(prefix))
+        .then_some(dependency)
+}
+
+fn set_winecfg_policy(target: &mut WinecfgFeaturePolicy, enabled: Option<bool>) {
+    if let Some(enabled) = enabled {
+        target.state = optional_state(enabled);
+        target.use_wine_default = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::import_lutris::tests::FakeFileSystem;
+
+    #[test]
+    fn imports_bottle_programs_dependencies_and_environment() {
+        let source = r#"
+Name: Sample
+Arch: win64
+Runner: soda-7.0-9
+DXVK: dxvk-2.3
+Windows: win10
+Environment: Gaming
+Environment_Variables:
+  DXVK_HUD: fps
+DLL_Overrides:
+  d3d9: n,b
+Dependencies:
+  - vcredist2019
+  - dotnet48
+  - d3dx11
+  - mono
+  - some-launcher
+Parameters:
+  dxvk: true
+  vkd3d: false
+  gamemode: true
+  sync: fsync
+  virtual_desktop: true
+  virtual_desktop_res: 1024x768
+  decorated: false
+  latencyflex: false
+  fsr: true
+External_Programs:
+  3f1c:
+    name: Sample
+    path: /home/user/.var/app/com.usebottles.bottles/data/bottles/bottles/Sample/drive_c/Sample/sample.exe
+    arguments: -windowed
+  9a7e:
+    name: Config Tool
+    path: Sample/config.exe
+  b210:
+    name: Old
+    path: old.exe
+    removed: true
+"#;
+        let hash = "c".repeat(64);
+        let fs = FakeFileSystem::with_files(&[
+            ("/games/sample/Sample/sample.exe", &hash),
+            ("/games/sample/Sample/config.exe", &hash),
+        ]);
+
+        let imported =
+            import_bottles_config(source, Path::new("/games/sample"), &fs).expect("bottle imports");
+        let config = &imported.config;
+
+        assert_eq!(config.game_name, "Sample");
+        assert_eq!(config.relative_exe_path, "./Sample/sample.exe");
+        assert_eq!(config.launch_args, vec!["-windowed"]);
+        assert_eq!(config.launch_entries.len(), 1);
+        assert_eq!(config.launch_entries[0].name, "Config Tool");
+        assert_eq!(
+            config.launch_entries[0].relative_exe_path,
+            "./Sample/config.exe"
+        );
+        assert_eq!(
+            config.dependencies,
+            vec!["vcrun2019", "dotnet48", "d3dx11_43"]
+        );
+        assert_eq!(
+            config.environment.custom_vars.get("DXVK_HUD"),
+            Some(&"fps".to_string())
+        );
+        assert_eq!(
+            config.environment.custom_vars.get("PROTON_NO_D3D12"),
+            Some(&"1".to_string())
+        );
+        assert_eq!(config.runner.runtime_preference, RuntimePreference::Wine);
+        assert!(config.runner.esync && config.runner.fsync);
+        assert_eq!(config.winecfg.windows_version.as_deref(), Some("win10"));
+        assert_eq!(config.winecfg.dll_overrides[0].mode, "native,builtin");
+        assert_eq!(
+            config.winecfg.virtual_desktop.resolution.as_deref(),
+            Some("1024x768")
+        );
+        assert_eq!(
+            config.winecfg.window_decorations.state,
+            FeatureState::OptionalOff
+        );
+
+        let codes = imported
+            .warnings
+            .iter()
+            .map(|issue| (issue.code.as_str(), issue.field.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            vec![
+                ("import_exe_relocated", "External_Programs.3f1c.path"),
+                ("bottles_component_version_ignored", "DXVK"),
+                ("bottles_dependency_unmapped", "Dependencies[4]"),
+                ("bottles_field_ignored", "Parameters.fsr"),
+                ("bottles_wine_version_ignored", "Runner"),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_bottle_without_programs() {
+        let fs = FakeFileSystem::with_files(&[]);
+
+        let err = import_bottles_config("Name: Empty\nRunner: soda-7.0-9\n", Path::new("/g"), &fs)
+            .expect_err("a bottle without programs cannot be imported");
+
+        assert!(matches!(err, LuthierError::InvalidImportSource(_)));
+    }
+}
@@ -0,0 +1,423 @@
+use std::path::{Component, Path, PathBuf};
+
+use luthier_orchestrator_core::config::{DllOverrideRule, FeatureState};
+use luthier_orchestrator_core::config_migration::CURRENT_CONFIG_VERSION;
+use luthier_orchestrator_core::GameConfig;
+use serde_json::{json, Value};
+
+use crate::application::ports::ImportFileSystemPort;
+use crate::application::validate_game_config::collect_game_config_validation_issues;
+use crate::domain::path_rules::to_relative_inside_game_root;
+use crate::models::ImportedGameConfig;
+use crate::{ConfigValidationIssue, LuthierError};
+
+pub(crate) const IMPORTED_CREATED_BY: &str = "luthier-import";
+
+/// Same defaults the editor starts a new game with.
+pub(crate) fn base_game_config(game_name: &str) -> Result<GameConfig, LuthierError> {
+    let document = json!({
+        "config_version": CURRENT_CONFIG_VERSION,
+        "created_by": IMPORTED_CREATED_BY,
+        "game_name": game_name,
+        "exe_hash": "",
+        "relative_exe_path": "./game.exe",
+        "launch_args": [],
+        "runner": {
+            "proton_version": "GE-Proton-latest",
+            "auto_update": false,
+            "esync": true,
+            "fsync": true,
+            "runtime_preference": "Proton"
+        },
+        "environment": {
+            "gamemode": "OptionalOff",
+            "gamescope": {
+                "state": "OptionalOff",
+                "resolution": null,
+                "fsr": false,
+                "game_width": "",
+                "game_height": "",
+                "output_width": "",
+                "output_height": "",
+                "upscale_method": "fsr",
+                "window_type": "fullscreen",
+                "enable_limiter": false,
+                "fps_limiter": "",
+                "fps_limiter_no_focus": "",
+                "force_grab_cursor": false,
+                "additional_options": ""
+            },
+            "mangohud": "OptionalOff",
+            "prime_offload": "OptionalOff",
+            "custom_vars": {},
+            "env_edits": []
+        },
+        "compatibility": {
+            "wine_wayland": "OptionalOff",
+            "hdr": "OptionalOff",
+            "auto_dxvk_nvapi": "OptionalOff",
+            "easy_anti_cheat_runtime": "OptionalOff",
+            "battleye_runtime": "OptionalOff",
+            "staging": "OptionalOff",
+            "wrapper_commands": []
+        },
+        "winecfg": {
+            "windows_version": null,
+            "dll_overrides": [],
+            "auto_capture_mouse": { "state": "OptionalOn", "use_wine_default": true },
+            "window_decorations": { "state": "OptionalOn", "use_wine_default": true },
+            "window_manager_control": { "state": "OptionalOn", "use_wine_default": true },
+            "virtual_desktop": {
+                "state": { "state": "OptionalOff", "use_wine_default": true },
+                "resolution": null
+            },
+            "screen_dpi": null,
+            "desktop_integration": { "state": "OptionalOn", "use_wine_default": true },
+            "mime_associations": { "state": "OptionalOff", "use_wine_default": true },
+            "desktop_folders": [],
+            "drives": [
+                {
+                    "letter": "Z",
+                    "source_relative_path": ".",
+                    "state": "OptionalOn",
+                    "host_path": null,
+                    "drive_type": "auto",
+                    "label": null,
+                    "serial": null
+                }
+            ],
+            "audio_driver": null
+        },
+        "dependencies": [],
+        "extra_system_dependencies": [],
+        "requirements": {
+            "runtime": {
+                "strict": false,
+                "primary": "ProtonUmu",
+                "fallback_order": ["ProtonNative", "Wine"]
+            },
+            "umu": "MandatoryOn",
+            "winetricks": "OptionalOff",
+            "gamescope": "OptionalOff",
+            "gamemode": "OptionalOff",
+            "mangohud": "OptionalOff",
+            "steam_runtime": "OptionalOff"
+        },
+        "registry_keys": [],
+        "integrity_files": [],
+        "folder_mounts": [],
+        "splash": { "hero_image_url": "" },
+        "scripts": { "pre_launch": [], "post_launch": [] },
+        "profiles": [],
+        "rules": [],
+        "launch_entries": [],
+        "default_entry": null
+    });
+
+    serde_json::from_value(document).map_err(LuthierError::from)
+}
+
+/// Collects what an importer could not carry over, keyed by the source field.
+#[derive(Debug, Default)]
+pub(crate) struct ImportWarnings {
+    issues: Vec<ConfigValidationIssue>,
+}
+
+impl ImportWarnings {
+    pub(crate) fn push(&mut self, code: &str, field: &str, message: impl Into<String>) {
+        self.issues.push(ConfigValidationIssue {
+            code: code.to_string(),
+            field: field.to_string(),
+            message: message.into(),
+        });
+    }
+
+    /// Settings that are off or empty carry no meaning to lose, so only the
+    /// ones that are set are reported.
+    pub(crate) fn ignored_field(&mut self, prefix: &str, field: &str, value: &Value) {
+        if is_unset(value) {
+            return;
+        }
+        self.push(
+            &format!("{prefix}_field_ignored"),
+            field,
+            "setting has no Luthier equivalent and was not imported",
+        );
+    }
+}
+
+/// Appends the validation issues of the generated config so callers see
+/// everything that needs attention in one list.
+pub(crate) fn finish_import(config: GameConfig, warnings: ImportWarnings) -> ImportedGameConfig {
+    let mut issues = warnings.issues;
+    issues.extend(collect_game_config_validation_issues(&config));
+    ImportedGameConfig {
+        config,
+        warnings: issues,
+    }
+}
+
+/// Points the config at `raw` and fingerprints it. A missing executable is
+/// only a warning and leaves `exe_hash` empty.
+pub(crate) fn apply_executable(
+    config: &mut GameConfig,
+    game_root: &Path,
+    raw: &str,
+    field: &str,
+    file_system: &dyn ImportFileSystemPort,
+    warnings: &mut ImportWarnings,
+) -> Result<(), LuthierError> {
+    let resolved = resolve_executable(game_root, raw, field, file_system, warnings)?;
+    config.exe_hash = match &resolved.located {
+        Some(path) => match file_system.sha256_file(path) {
+            Ok(hash) => hash,
+            Err(err) => {
+                warnings.push(
+                    "import_exe_hash_unavailable",
+                    field,
+                    format!("could not hash {}: {err}", path.display()),
+                );
+                String::new()
+            }
+        },
+        None => String::new(),
+    };
+    config.relative_exe_path = resolved.relative_exe_path;
+    Ok(())
+}
+
+pub(crate) struct ResolvedExecutable {
+    /// `./`-prefixed and relative to the game root.
+    pub(crate) relative_exe_path: String,
+    /// The file on disk, when one was found.
+    pub(crate) located: Option<PathBuf>,
+}
+
+/// Maps an executable path from another launcher into the game root.
+///
+/// Relative paths are taken from the game root. When the path does not exist
+/// (the source launcher installed somewhere else), the longest trailing part
+/// of it that exists under the game root is used instead. A path that cannot
+/// be found is kept with a warning as long as it stays inside the game root.
+pub(crate) fn resolve_executable(
+    game_root: &Path,
+    raw: &str,
+    field: &str,
+    file_system: &dyn ImportFileSystemPort,
+    warnings: &mut ImportWarnings,
+) -> Result<ResolvedExecutable, LuthierError> {
+    let cleaned = raw.trim().replace('\\', "/");
+    if cleaned.is_empty() {
+        return Err(LuthierError::InvalidImportSource(format!(
+            "{field} is empty"
+        )));
+    }
+
+    let candidate = if Path::new(&cleaned).is_absolute() {
+        PathBuf::from(&cleaned)
+    } else {
+        game_root.join(&cleaned)
+    };
+
+    let located = if file_system.is_file(&candidate) {
+        Some(candidate.clone())
+    } else {
+        relocate_under_game_root(game_root, &candidate, file_system)
+    };
+
+    match &located {
+        Some(path) if path != &candidate => warnings.push(
+            "import_exe_relocated",
+            field,
+            format!("{cleaned} was not found; using {}", path.display()),
+        ),
+        Some(_) => {}
+        None => warnings.push(
+            "import_exe_missing",
+            field,
+            format!("{cleaned} was not found under the game root; exe_hash is left empty"),
+        ),
+    }
+
+    let path = located.as_deref().unwrap_or(&candidate);
+    Ok(ResolvedExecutable {
+        relative_exe_path: format!("./{}", to_relative_inside_game_root(game_root, path)?),
+        located,
+    })
+}
+
+fn relocate_under_game_root(
+    game_root: &Path,
+    candidate: &Path,
+    file_system: &dyn ImportFileSystemPort,
+) -> Option<PathBuf> {
+    let parts = candidate
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    (0..parts.len()).find_map(|start| {
+        let path = parts[start..]
+            .iter()
+            .fold(game_root.to_path_buf(), |acc, part| acc.join(part));
+        file_system.is_file(&path).then_some(path)
+    })
+}
+
+/// Splits a command line on whitespace, keeping single- or double-quoted
+/// runs together.
+pub(crate) fn split_command_line(raw: &str) -> Vec<String> {
+    let mut out = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut quote = None;
+
+    for ch in raw.chars() {
+        match quote {
+            Some(open) if ch == open => quote = None,
+            Some(_) => current.push(ch),
+            None if ch == '"' || ch == '\'' => {
+                quote = Some(ch);
+                in_token = true;
+            }
+            None if ch.is_whitespace() => {
+                if in_token {
+                    out.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            None => {
+                current.push(ch);
+                in_token = true;
+            }
+        }
+    }
+
+    if in_token {
+        out.push(current);
+    }
+    out
+}
+
+/// Accepts Wine's short (`n,b`) and long (`native,builtin`) override spellings.
+pub(crate) fn dll_override_rule(dll: &str, raw_mode: &str) -> Option<DllOverrideRule> {
+    let normalized = raw_mode.trim().to_ascii_lowercase().replace(' ', "");
+    let mode = match normalized.as_str() {
+        "n" | "native" => "native",
+        "b" | "builtin" => "builtin",
+        "n,b" | "native,builtin" => "native,builtin",
+        "b,n" | "builtin,native" => "builtin,native",
+        "" | "d" | "disabled" | "disable" => "disabled",
+        _ => return None,
+    };
+
+    Some(DllOverrideRule {
+        dll: dll.trim().to_string(),
+        mode: mode.to_string(),
+    })
+}
+
+pub(crate) fn push_dependency(config: &mut GameConfig, verb: &str) {
+    let verb = verb.trim();
+    if !verb.is_empty()
+        && !config
+            .dependencies
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(verb))
+    {
+        config.dependencies.push(verb.to_string());
+    }
+}
+
+pub(crate) fn set_custom_var(config: &mut GameConfig, name: &str, value: &str) {
+    config
+        .environment
+        .custom_vars
+        .insert(name.trim().to_string(), value.to_string());
+}
+
+/// Leaves `target` alone when the source value is not a boolean.
+pub(crate) fn set_optional_state(target: &mut FeatureState, enabled: Option<bool>) {
+    if let Some(enabled) = enabled {
+        *target = optional_state(enabled);
+    }
+}
+
+/// Fallback game name when the source has none.
+pub(crate) fn executable_stem(exe: &str) -> String {
+    Path::new(&exe.replace('\\', "/"))
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+pub(crate) fn optional_state(enabled: bool) -> FeatureState {
+    if enabled {
+        FeatureState::OptionalOn
+    } else {
+        FeatureState::OptionalOff
+    }
+}
+
+/// Reads YAML-style booleans, which launchers also write as strings or 0/1.
+pub(crate) fn value_as_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Bool(flag) => Some(*flag),
+        Value::Number(number) => number.as_i64().map(|raw| raw != 0),
+        Value::String(raw) => match raw.trim().to_ascii_lowercase().as_str() {
+            "true" | "yes" | "on" | "1" => Some(true),
+            "false" | "no" | "off" | "0" | "" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+pub(crate) fn is_unset(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::Bool(flag) => !flag,
+        Value::String(raw) => raw.trim().is_empty(),
+        Value::Array(items) => items.is_empty(),
+        Value::Object(object) => object.is_empty(),
+        Value::Number(_) => false,
+    }
+}
+
+pub(crate) fn value_as_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(raw) => Some(raw.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_command_line_keeps_quoted_runs() {
+        assert_eq!(
+            split_command_line(r#"-windowed  --profile "My Save" 'a b'c"#),
+            vec!["-windowed", "--profile", "My Save", "a bc"]
+        );
+        assert!(split_command_line("   ").is_empty());
+        assert_eq!(split_command_line(r#"-name """#), vec!["-name", ""]);
+    }
+
+    #[test]
+    fn dll_override_rule_expands_short_modes() {
+        let rule = dll_override_rule("d3d9", "n,b").expect("known mode");
+        assert_eq!(rule.mode, "native,builtin");
+        assert_eq!(
+            dll_override_rule("xinput1_3", "").map(|rule| rule.mode),
+            Some("disabled".to_string())
+        );
+        assert!(dll_override_rule("d3d9", "sometimes").is_none());
+    }
+}
@@ -0,0 +1,320 @@
+use std::path::Path;
+
+use luthier_orchestrator_core::config::{FeatureState, RuntimePreference, WrapperCommand};
+use luthier_orchestrator_core::GameConfig;
+use serde_json::{Map, Value};
+
+use crate::application::import_game_config::{
+    apply_executable, base_game_config, executable_stem, finish_import, set_custom_var,
+    set_optional_state, split_command_line, value_as_bool, value_as_string, ImportWarnings,
+};
+use crate::application::ports::ImportFileSystemPort;
+use crate::models::ImportedGameConfig;
+use crate::LuthierError;
+
+const WARNING_PREFIX: &str = "heroic";
+
+/// Keys Heroic writes next to the per-game object in `GamesConfig/<app>.json`.
+const FILE_METADATA_KEYS: [&str; 2] = ["version", "explicit"];
+
+/// Builds a config from a Heroic per-game settings file
+/// (`GamesConfig/<app>.json`). Sideloaded games keep their executable in
+/// Heroic's library rather than in this file, so `exe` overrides or replaces
+/// `targetExe`.
+pub(crate) fn import_heroic_config(
+    source: &str,
+    game_root: &Path,
+    exe: Option<&str>,
+    file_system: &dyn ImportFileSystemPort,
+) -> Result<ImportedGameConfig, LuthierError> {
+    let document = serde_json::from_str::<Value>(source)
+        .map_err(|err| LuthierError::InvalidImportSource(format!("heroic json: {err}")))?;
+    let (app_name, settings) = game_settings(&document)?;
+
+    let (exe, exe_field) = match exe {
+        Some(exe) => (exe.to_string(), "exe".to_string()),
+        None => {
+            let target = settings
+                .get("targetExe")
+                .and_then(Value::as_str)
+                .filter(|raw| !raw.trim().is_empty())
+                .ok_or_else(|| {
+                    LuthierError::InvalidImportSource(format!(
+                        "{app_name}.targetExe is not set; pass the game executable explicitly"
+                    ))
+                })?;
+            (target.to_string(), format!("{app_name}.targetExe"))
+        }
+    };
+
+    let mut warnings = ImportWarnings::default();
+    let mut config = base_game_config(&executable_stem(&exe))?;
+    apply_executable(
+        &mut config,
+        game_root,
+        &exe,
+        &exe_field,
+        file_system,
+        &mut warnings,
+    )?;
+
+    for (key, value) in settings {
+        let field = format!("{app_name}.{key}");
+        apply_setting(&mut config, key, value, &field, &mut warnings);
+    }
+
+    Ok(finish_import(config, warnings))
+}
+
+/// Heroic nests the settings under the app name; a bare settings object is
+/// accepted as well.
+fn game_settings(document: &Value) -> Result<(&str, &Map<String, Value>), LuthierError> {
+    let Value::Object(object) = document else {
+        return Err(LuthierError::InvalidImportSource(
+            "heroic json must contain an object".to_string(),
+        ));
+    };
+    if object.contains_key("wineVersion") {
+        return Ok(("settings", object));
+    }
+
+    object
+        .iter()
+        .filter(|(key, _)| !FILE_METADATA_KEYS.contains(&key.as_str()))
+        .find_map(|(key, value)| value.as_object().map(|settings| (key.as_str(), settings)))
+        .ok_or_else(|| {
+            LuthierError::InvalidImportSource("heroic json has no game settings".to_string())
+        })
+}
+
+fn apply_setting(
+    config: &mut GameConfig,
+    key: &str,
+    value: &Value,
+    field: &str,
+    warnings: &mut ImportWarnings,
+) {
+    let enabled = value_as_bool(value);
+    match key {
+        "targetExe" => {}
+        "launcherArgs" => {
+            config.launch_args = value_as_string(value)
+                .map(|raw| split_command_line(&raw))
+                .unwrap_or_default();
+        }
+        "wineVersion" => apply_wine_version(config, value, field, warnings),
+        "winePrefix" => {
+            if !value.as_str().unwrap_or_default().trim().is_empty() {
+                warnings.push(
+                    "heroic_prefix_ignored",
+                    field,
+                    "Luthier creates and manages its own prefix; the Heroic prefix is not reused",
+                );
+            }
+        }
+        "enableEsync" => {
+            if let Some(enabled) = enabled {
+                config.runner.esync = enabled;
+            }
+        }
+        "enableFsync" => {
+            if let Some(enabled) = enabled {
+                config.runner.fsync = enabled;
+            }
+        }
+        "autoInstallDxvk" => {
+            if enabled == Some(false) {
+                set_custom_var(config, "PROTON_USE_WINED3D", "1");
+            }
+        }
+        "autoInstallVkd3d" => {
+            if enabled == Some(false) {
+                set_custom_var(config, "PROTON_NO_D3D12", "1");
+            }
+        }
+        "autoInstallDxvkNvapi" => {
+            set_optional_state(&mut config.compatibility.auto_dxvk_nvapi, enabled)
+        }
+        "eacRuntime" => {
+            set_optional_state(&mut config.compatibility.easy_anti_cheat_runtime, enabled)
+        }
+        "battlEyeRuntime" => {
+            set_optional_state(&mut config.compatibility.battleye_runtime, enabled)
+        }
+        "enableWineWayland" => set_optional_state(&mut config.compatibility.wine_wayland, enabled),
+        "useGameMode" => set_optional_state(&mut config.environment.gamemode, enabled),
+        "showMangohud" => set_optional_state(&mut config.environment.mangohud, enabled),
+        "nvidiaPrime" => set_optional_state(&mut config.environment.prime_offload, enabled),
+        // Older Heroic releases spell it "enviromentOptions".
+        "enviromentOptions" | "environmentOptions" => {
+            for (index, option) in value.as_array().into_iter().flatten().enumerate() {
+                let pair = option
+                    .get("key")
+                    .and_then(Value::as_str)
+                    .zip(option.get("value").and_then(value_as_string));
+                match pair {
+                    Some((name, value)) if !name.trim().is_empty() => {
+                        set_custom_var(config, name, &value);
+                    }
+                    _ => warnings.push(
+                        "heroic_env_invalid",
+                        &format!("{field}[{index}]"),
+                        "environment option needs 'key' and 'value'",
+                    ),
+                }
+            }
+        }
+        "wrapperOptions" => {
+            for option in value.as_array().into_iter().flatten() {
+                let Some(executable) = option.get("exe").and_then(Value::as_str) else {
+                    continue;
+                };
+                config.compatibility.wrapper_commands.push(WrapperCommand {
+                    state: FeatureState::OptionalOn,
+                    executable: executable.trim().to_string(),
+                    args: option
+                        .get("args")
+                        .and_then(value_as_string)
+                        .unwrap_or_default(),
+                });
+            }
+        }
+        _ => warnings.ignored_field(WARNING_PREFIX, field, value),
+    }
+}
+
+/// `wineVersion` is `{ "name": "Proton - GE-Proton8-26", "type": "proton", ... }`.
+/// Proton builds are kept as the preferred Proton version; other runners
+/// cannot be pinned since the orchestrator uses the host's Wine.
+fn apply_wine_version(
+    config: &mut GameConfig,
+    value: &Value,
+    field: &str,
+    warnings: &mut ImportWarnings,
+) {
+    let name = value
+        .get("name")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .unwrap_or_default();
+    let kind = value
+        .get("type")
+        .and_then(Value::as_str)
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+
+    if kind == "proton" || name.to_ascii_lowercase().contains("proton") {
+        let version = name.strip_prefix("Proton - ").unwrap_or(name).trim();
+        config.runner.runtime_preference = RuntimePreference::Proton;
+        if !version.is_empty() {
+            config.runner.proton_version = version.to_string();
+        }
+    } else if !name.is_empty() {
+        config.runner.runtime_preference = RuntimePreference::Wine;
+        warnings.push(
+            "heroic_wine_version_ignored",
+            field,
+            format!("Wine build '{name}' cannot be pinned; the host Wine is used"),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::import_lutris::tests::FakeFileSystem;
+
+    #[test]
+    fn imports_heroic_games_config() {
+        let source = r#"{
+            "x2nJ7cA1": {
+                "targetExe": "/mnt/games/Sample/Sample.exe",
+                "launcherArgs": "-dx11 \"-profile=a b\"",
+                "wineVersion": { "bin": "/x/proton", "name": "Proton - GE-Proton9-2", "type": "proton" },
+                "winePrefix": "/home/user/Games/Heroic/Prefixes/default/Sample",
+                "enableEsync": true,
+                "enableFsync": false,
+                "autoInstallDxvk": true,
+                "autoInstallVkd3d": false,
+                "autoInstallDxvkNvapi": true,
+                "eacRuntime": true,
+                "showMangohud": true,
+                "nvidiaPrime": false,
+                "enviromentOptions": [{ "key": "DXVK_ASYNC", "value": "1" }],
+                "wrapperOptions": [{ "exe": "obs-gamecapture", "args": "" }],
+                "showFps": false,
+                "enableFSR": true
+            },
+            "version": "v0",
+            "explicit": true
+        }"#;
+        let hash = "b".repeat(64);
+        let fs = FakeFileSystem::with_files(&[("/games/sample/Sample.exe", &hash)]);
+
+        let imported = import_heroic_config(source, Path::new("/games/sample"), None, &fs)
+            .expect("heroic config imports");
+        let config = &imported.config;
+
+        assert_eq!(config.game_name, "Sample");
+        assert_eq!(config.relative_exe_path, "./Sample.exe");
+        assert_eq!(config.exe_hash, hash);
+        assert_eq!(config.launch_args, vec!["-dx11", "-profile=a b"]);
+        assert_eq!(config.runner.proton_version, "GE-Proton9-2");
+        assert!(!config.runner.fsync);
+        assert_eq!(
+            config.environment.custom_vars.get("PROTON_NO_D3D12"),
+            Some(&"1".to_string())
+        );
+        assert!(!config
+            .environment
+            .custom_vars
+            .contains_key("PROTON_USE_WINED3D"));
+        assert_eq!(
+            config.environment.custom_vars.get("DXVK_ASYNC"),
+            Some(&"1".to_string())
+        );
+        assert_eq!(
+            config.compatibility.easy_anti_cheat_runtime,
+            FeatureState::OptionalOn
+        );
+        assert_eq!(config.environment.mangohud, FeatureState::OptionalOn);
+        assert_eq!(config.compatibility.wrapper_commands.len(), 1);
+
+        let codes = imported
+            .warnings
+            .iter()
+            .map(|issue| (issue.code.as_str(), issue.field.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            codes,
+            vec![
+                ("import_exe_relocated", "x2nJ7cA1.targetExe"),
+                ("heroic_field_ignored", "x2nJ7cA1.enableFSR"),
+                ("heroic_prefix_ignored", "x2nJ7cA1.winePrefix"),
+            ]
+        );
+    }
+
+    #[test]
+    fn requires_an_executable_for_sideloaded_games() {
+        let fs = FakeFileSystem::with_files(&[]);
+        let source = r#"{ "abc": { "wineVersion": { "name": "Wine - 9.0", "type": "wine" } } }"#;
+
+        let err = import_heroic_config(source, Path::new("/games/sample"), None, &fs)
+            .expect_err("missing targetExe is rejected");
+        assert!(matches!(err, LuthierError::InvalidImportSource(_)));
+
+        let imported = import_heroic_config(
+            source,
+            Path::new("/games/sample"),
+            Some("bin/game.exe"),
+            &fs,
+        )
+        .expect("explicit exe is used");
+        assert_eq!(imported.config.relative_exe_path, "./bin/game.exe");
+        assert_eq!(
+            imported.config.runner.runtime_preference,
+            RuntimePreference::Wine
+        );
+    }
+}
@@ -0,0 +1,610 @@
+use std::path::Path;
+
+use luthier_orchestrator_core::config::{FeatureState, RegistryKey, RuntimePreference};
+use luthier_orchestrator_core::GameConfig;
+use serde_json::{Map, Value};
+
+use crate::application::import_game_config::{
+    apply_executable, base_game_config, dll_override_rule, executable_stem, finish_import,
+    optional_state, push_dependency, set_custom_var, split_command_line, value_as_bool,
+    value_as_string, ImportWarnings,
+};
+use crate::application::ports::ImportFileSystemPort;
+use crate::models::ImportedGameConfig;
+use crate::LuthierError;
+
+const WARNING_PREFIX: &str = "lutris";
+const GAMEDIR_VARIABLE: &str = "$GAMEDIR";
+
+/// The sections both Lutris file shapes share. Game configs keep them at the
+/// top level; installer scripts nest them under `script`.
+struct LutrisSections<'a> {
+    field_prefix: &'static str,
+    name: Option<&'a str>,
+    runner: Option<&'a str>,
+    game: Option<&'a Map<String, Value>>,
+    wine: Option<&'a Map<String, Value>>,
+    system: Option<&'a Map<String, Value>>,
+    installer: Option<&'a Vec<Value>>,
+}
+
+impl<'a> LutrisSections<'a> {
+    fn from_document(document: &'a Map<String, Value>) -> Self {
+        let script = document.get("script").and_then(Value::as_object);
+        let sections = script.unwrap_or(document);
+
+        Self {
+            field_prefix: if script.is_some() { "script." } else { "" },
+            name: document.get("name").and_then(Value::as_str),
+            runner: document.get("runner").and_then(Value::as_str),
+            game: sections.get("game").and_then(Value::as_object),
+            wine: sections.get("wine").and_then(Value::as_object),
+            system: sections.get("system").and_then(Value::as_object),
+            installer: script
+                .and_then(|script| script.get("installer"))
+                .and_then(Value::as_array),
+        }
+    }
+
+    fn field(&self, section: &str, key: &str) -> String {
+        format!("{}{section}.{key}", self.field_prefix)
+    }
+}
+
+/// Builds a config from a Lutris game config (`~/.config/lutris/games/*.yml`)
+/// or a Wine installer script. `$GAMEDIR` and relative paths are resolved
+/// against `game_root`.
+pub(crate) fn import_lutris_config(
+    source: &str,
+    game_root: &Path,
+    file_system: &dyn ImportFileSystemPort,
+) -> Result<ImportedGameConfig, LuthierError> {
+    let document = serde_yaml::from_str::<Value>(source)
+        .map_err(|err| LuthierError::InvalidImportSource(format!("lutris yaml: {err}")))?;
+    let Value::Object(document) = document else {
+        return Err(LuthierError::InvalidImportSource(
+            "lutris yaml must contain a mapping".to_string(),
+        ));
+    };
+
+    let sections = LutrisSections::from_document(&document);
+    if let Some(runner) = sections.runner {
+        if !runner.trim().eq_ignore_ascii_case("wine") {
+            return Err(LuthierError::InvalidImportSource(format!(
+                "lutris runner '{runner}' is not supported; only wine games can be imported"
+            )));
+        }
+    }
+
+    let game = sections.game.ok_or_else(|| {
+        LuthierError::InvalidImportSource(format!(
+            "lutris yaml has no '{}game' section",
+            sections.field_prefix
+        ))
+    })?;
+    let exe_field = sections.field("game", "exe");
+    let exe = game
+        .get("exe")
+        .and_then(Value::as_str)
+        .ok_or_else(|| LuthierError::InvalidImportSource(format!("{exe_field} is required")))?;
+    let exe = substitute_game_dir(exe, game_root);
+
+    let mut warnings = ImportWarnings::default();
+    let mut config = base_game_config(&game_name(sections.name, &exe))?;
+    apply_executable(
+        &mut config,
+        game_root,
+        &exe,
+        &exe_field,
+        file_system,
+        &mut warnings,
+    )?;
+
+    apply_game_section(&mut config, &sections, game, &mut warnings);
+    if let Some(wine) = sections.wine {
+        apply_wine_section(&mut config, &sections, wine, &mut warnings);
+    }
+    if let Some(system) = sections.system {
+        apply_system_section(&mut config, &sections, system, &mut warnings);
+    }
+    if let Some(installer) = sections.installer {
+        apply_installer_tasks(&mut config, installer, &mut warnings);
+    }
+
+    Ok(finish_import(config, warnings))
+}
+
+fn apply_game_section(
+    config: &mut GameConfig,
+    sections: &LutrisSections<'_>,
+    game: &Map<String, Value>,
+    warnings: &mut ImportWarnings,
+) {
+    for (key, value) in game {
+        let field = sections.field("game", key);
+        match key.as_str() {
+            "exe" => {}
+            "args" => match value {
+                Value::Array(items) => {
+                    config.launch_args = items.iter().filter_map(value_as_string).collect();
+                }
+                other => {
+                    config.launch_args = value_as_string(other)
+                        .map(|raw| split_command_line(&raw))
+                        .unwrap_or_default();
+                }
+            },
+            "prefix" => warnings.push(
+                "lutris_prefix_ignored",
+                &field,
+                "Luthier creates and manages its own prefix; the Lutris prefix is not reused",
+            ),
+            "arch" => {
+                if value.as_str().map(str::trim) == Some("win32") {
+                    warnings.push(
+                        "lutris_arch_win32",
+                        &field,
+                        "32-bit prefixes are not supported; the game runs in a 64-bit prefix",
+                    );
+                }
+            }
+            _ => warnings.ignored_field(WARNING_PREFIX, &field, value),
+        }
+    }
+}
+
+fn apply_wine_section(
+    config: &mut GameConfig,
+    sections: &LutrisSections<'_>,
+    wine: &Map<String, Value>,
+    warnings: &mut ImportWarnings,
+) {
+    let mut virtual_desktop = false;
+    let mut virtual_desktop_resolution = None;
+
+    for (key, value) in wine {
+        let field = sections.field("wine", key);
+        match key.as_str() {
+            "version" => {
+                if let Some(version) = value.as_str() {
+                    apply_wine_version(config, version, &field, warnings);
+                }
+            }
+            "esync" => {
+                if let Some(enabled) = value_as_bool(value) {
+                    config.runner.esync = enabled;
+                }
+            }
+            "fsync" => {
+                if let Some(enabled) = value_as_bool(value) {
+                    config.runner.fsync = enabled;
+                }
+            }
+            "dxvk" => {
+                if value_as_bool(value) == Some(false) {
+                    set_custom_var(config, "PROTON_USE_WINED3D", "1");
+                }
+            }
+            "vkd3d" => {
+                if value_as_bool(value) == Some(false) {
+                    set_custom_var(config, "PROTON_NO_D3D12", "1");
+                }
+            }
+            "dxvk_nvapi" => {
+                if let Some(enabled) = value_as_bool(value) {
+                    config.compatibility.auto_dxvk_nvapi = optional_state(enabled);
+                }
+            }
+            "battleye" => {
+                if let Some(enabled) = value_as_bool(value) {
+                    config.compatibility.battleye_runtime = optional_state(enabled);
+                }
+            }
+            "eac" => {
+                if let Some(enabled) = value_as_bool(value) {
+                    config.compatibility.easy_anti_cheat_runtime = optional_state(enabled);
+                }
+            }
+            "overrides" => apply_dll_overrides(config, &field, value, warnings),
+            "Desktop" => virtual_desktop = value_as_bool(value).unwrap_or(false),
+            "WineDesktop" => virtual_desktop_resolution = value_as_string(value),
+            _ => warnings.ignored_field(WARNING_PREFIX, &field, value),
+        }
+    }
+
+    if virtual_desktop {
+        let desktop = &mut config.winecfg.virtual_desktop;
+        desktop.state.state = FeatureState::OptionalOn;
+        desktop.state.use_wine_default = false;
+        desktop.resolution = virtual_desktop_resolution
+            .map(|raw| raw.trim().to_string())
+            .filter(|raw| !raw.is_empty());
+    }
+}
+
+/// Lutris names builds like `lutris-GE-Proton8-26-x86_64`. Proton builds are
+/// kept as the preferred Proton version; Wine builds cannot be pinned since
+/// the orchestrator uses the host's Wine.
+fn apply_wine_version(
+    config: &mut GameConfig,
+    raw: &str,
+    field: &str,
+    warnings: &mut ImportWarnings,
+) {
+    let trimmed = raw.trim();
+    let name = trimmed.strip_prefix("lutris-").unwrap_or(trimmed);
+    let name = name.strip_suffix("-x86_64").unwrap_or(name);
+    if name.is_empty() {
+        return;
+    }
+
+    if name.to_ascii_lowercase().contains("proton") {
+        config.runner.runtime_preference = RuntimePreference::Proton;
+        config.runner.proton_version = name.to_string();
+    } else {
+        config.runner.runtime_preference = RuntimePreference::Wine;
+        warnings.push(
+            "lutris_wine_version_ignored",
+            field,
+            format!("Wine build '{trimmed}' cannot be pinned; the host Wine is used"),
+        );
+    }
+}
+
+fn apply_dll_overrides(
+    config: &mut GameConfig,
+    field: &str,
+    value: &Value,
+    warnings: &mut ImportWarnings,
+) {
+    let Some(overrides) = value.as_object() else {
+        return;
+    };
+
+    for (dll, mode) in overrides {
+        let raw_mode = value_as_string(mode).unwrap_or_default();
+        match dll_override_rule(dll, &raw_mode) {
+            Some(rule) => {
+                config
+                    .winecfg
+                    .dll_overrides
+                    .retain(|existing| !existing.dll.eq_ignore_ascii_case(&rule.dll));
+                config.winecfg.dll_overrides.push(rule);
+            }
+            None => warnings.push(
+                "lutris_dll_override_invalid",
+                &format!("{field}.{dll}"),
+                format!("unknown override mode '{raw_mode}'"),
+            ),
+        }
+    }
+}
+
+fn apply_system_section(
+    config: &mut GameConfig,
+    sections: &LutrisSections<'_>,
+    system: &Map<String, Value>,
+    warnings: &mut ImportWarnings,
+) {
+    for (key, value) in system {
+        let field = sections.field("system", key);
+        match key.as_str() {
+            "env" => {
+                let Some(env) = value.as_object() else {
+                    continue;
+                };
+                for (name, raw) in env {
+                    match value_as_string(raw) {
+                        Some(raw) => set_custom_var(config, name, &raw),
+                        None => warnings.push(
+                            "lutris_env_invalid",
+                            &format!("{field}.{name}"),
+                            "environment value must be a scalar",
+                        ),
+                    }
+                }
+            }
+            "gamemode" => {
+                if let Some(enabled) = value_as_bool(value) {
+                    config.environment.gamemode = optional_state(enabled);
+                }
+            }
+            "mangohud" => {
+                if let Some(enabled) = value_as_bool(value) {
+                    config.environment.mangohud = optional_state(enabled);
+                }
+            }
+            "prime" => {
+                if let Some(enabled) = value_as_bool(value) {
+                    config.environment.prime_offload = optional_state(enabled);
+                }
+            }
+            _ => warnings.ignored_field(WARNING_PREFIX, &field, value),
+        }
+    }
+}
+
+/// Only installer steps that configure the prefix carry over; files are
+/// expected to be in the game root already.
+fn apply_installer_tasks(config: &mut GameConfig, steps: &[Value], warnings: &mut ImportWarnings) {
+    for (index, step) in steps.iter().enumerate() {
+        let field = format!("script.installer[{index}]");
+        let Some(task) = step
+            .as_object()
+            .and_then(|step| step.get("task"))
+            .and_then(Value::as_object)
+        else {
+            let directive = step
+                .as_object()
+                .and_then(|step| step.keys().next())
+                .map(String::as_str)
+                .unwrap_or("unknown");
+            warnings.push(
+                "lutris_task_ignored",
+                &field,
+                format!("installer step '{directive}' was not imported"),
+            );
+            continue;
+        };
+
+        match task.get("name").and_then(Value::as_str).unwrap_or_default() {
+            "winetricks" => {
+                let apps = task
+                    .get("app")
+                    .and_then(value_as_string)
+                    .unwrap_or_default();
+                for verb in apps.split_whitespace() {
+                    push_dependency(config, verb);
+                }
+            }
+            "set_regedit" => match registry_key_from_task(task) {
+                Some(key) => config.registry_keys.push(key),
+                None => warnings.push(
+                    "lutris_regedit_invalid",
+                    &field,
+                    "set_regedit needs 'path' and 'key'",
+                ),
+            },
+            "create_prefix" => {}
+            other => warnings.push(
+                "lutris_task_ignored",
+                &field,
+                format!("installer task '{other}' was not imported"),
+            ),
+        }
+    }
+}
+
+fn registry_key_from_task(task: &Map<String, Value>) -> Option<RegistryKey> {
+    let path = task.get("path").and_then(Value::as_str)?.trim();
+    let name = task.get("key").and_then(value_as_string)?;
+    if path.is_empty() || name.trim().is_empty() {
+        return None;
+    }
+
+    let value_type = task
+        .get("type")
+        .and_then(Value::as_str)
+        .map(|raw| raw.trim().to_ascii_uppercase())
+        .unwrap_or_else(|| "REG_SZ".to_string());
+    let raw_value = task
+        .get("value")
+        .and_then(value_as_string)
+        .unwrap_or_default();
+    // Lutris writes DWORDs as decimal numbers; registry keys store them as hex.
+    let value = match (value_type.as_str(), raw_value.trim().parse::<u32>()) {
+        ("REG_DWORD", Ok(number)) => format!("{number:08x}"),
+        _ => raw_value,
+    };
+
+    Some(RegistryKey {
+        path: path.to_string(),
+        name: name.trim().to_string(),
+        value_type,
+        value,
+    })
+}
+
+fn substitute_game_dir(raw: &str, game_root: &Path) -> String {
+    raw.replace(GAMEDIR_VARIABLE, &game_root.to_string_lossy())
+}
+
+fn game_name(name: Option<&str>, exe: &str) -> String {
+    name.map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(ToString::to_string)
+        .unwrap_or_else(|| executable_stem(exe))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    use super::*;
+
+    /// `ImportFileSystemPort` over a fixed set of files and their hashes,
+    /// shared by the importer tests.
+    pub(crate) struct FakeFileSystem {
+        files: HashMap<PathBuf, String>,
+    }
+
+    impl FakeFileSystem {
+        pub(crate) fn with_files(files: &[(&str, &str)]) -> Self {
+            Self {
+                files: files
+                    .iter()
+                    .map(|(path, hash)| (PathBuf::from(path), hash.to_string()))
+                    .collect(),
+            }
+        }
+    }
+
+    impl ImportFileSystemPort for FakeFileSystem {
+        fn is_file(&self, path: &Path) -> bool {
+            self.files.contains_key(path)
+        }
+
+        fn sha256_file(&self, path: &Path) -> Result<String, LuthierError> {
+            self.files
+                .get(path)
+                .cloned()
+                .ok_or_else(|| LuthierError::Io(std::io::Error::from(std::io::ErrorKind::NotFound)))
+        }
+    }
+
+    fn codes(imported: &ImportedGameConfig) -> Vec<&str> {
+        imported
+            .warnings
+            .iter()
+            .map(|issue| issue.code.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn imports_lutris_game_config() {
+        let source = r#"
+name: Sample Game
+game:
+  exe: /home/user/Games/sample/drive_c/Sample/sample.exe
+  args: -windowed --profile "My Save"
+  prefix: /home/user/Games/sample
+wine:
+  version: lutris-GE-Proton8-26-x86_64
+  esync: true
+  fsync: false
+  dxvk: false
+  dxvk_nvapi: true
+  overrides:
+    d3d9: n,b
+    xinput1_3: disabled
+  Desktop: true
+  WineDesktop: 1280x720
+  fsr: true
+system:
+  env:
+    DXVK_HUD: fps
+    SAMPLE_LEVEL: 3
+  gamemode: true
+  prime: true
+"#;
+        let hash = "a".repeat(64);
+        let fs = FakeFileSystem::with_files(&[("/games/sample/Sample/sample.exe", &hash)]);
+
+        let imported = import_lutris_config(source, Path::new("/games/sample"), &fs)
+            .expect("lutris config imports");
+        let config = &imported.config;
+
+        assert_eq!(config.game_name, "Sample Game");
+        assert_eq!(config.relative_exe_path, "./Sample/sample.exe");
+        assert_eq!(config.exe_hash, hash);
+        assert_eq!(
+            config.launch_args,
+            vec!["-windowed", "--profile", "My Save"]
+        );
+        assert_eq!(config.runner.proton_version, "GE-Proton8-26");
+        assert!(config.runner.esync);
+        assert!(!config.runner.fsync);
+        assert_eq!(
+            config.environment.custom_vars.get("PROTON_USE_WINED3D"),
+            Some(&"1".to_string())
+        );
+        assert_eq!(
+            config.environment.custom_vars.get("SAMPLE_LEVEL"),
+            Some(&"3".to_string())
+        );
+        assert_eq!(
+            config.compatibility.auto_dxvk_nvapi,
+            FeatureState::OptionalOn
+        );
+        assert_eq!(config.environment.gamemode, FeatureState::OptionalOn);
+        assert_eq!(config.environment.prime_offload, FeatureState::OptionalOn);
+        assert_eq!(config.winecfg.dll_overrides.len(), 2);
+        assert_eq!(config.winecfg.dll_overrides[0].mode, "native,builtin");
+        assert_eq!(config.winecfg.dll_overrides[1].mode, "disabled");
+        assert_eq!(
+            config.winecfg.virtual_desktop.resolution.as_deref(),
+            Some("1280x720")
+        );
+        assert_eq!(
+            codes(&imported),
+            vec![
+                "import_exe_relocated",
+                "lutris_prefix_ignored",
+                "lutris_field_ignored",
+            ]
+        );
+    }
+
+    #[test]
+    fn imports_lutris_installer_tasks() {
+        let source = r#"
+name: Installer Game
+runner: wine
+script:
+  game:
+    exe: $GAMEDIR/game.exe
+    prefix: $GAMEDIR
+  installer:
+    - task:
+        name: create_prefix
+        prefix: $GAMEDIR
+    - task:
+        name: winetricks
+        app: vcrun2019 d3dx9
+    - task:
+        name: set_regedit
+        path: HKEY_CURRENT_USER\Software\Sample
+        key: Windowed
+        value: 1
+        type: REG_DWORD
+    - extract:
+        file: setup
+    - task:
+        name: wineexec
+        executable: setup
+"#;
+        let fs = FakeFileSystem::with_files(&[]);
+
+        let imported = import_lutris_config(source, Path::new("/games/installer"), &fs)
+            .expect("installer script imports");
+        let config = &imported.config;
+
+        assert_eq!(config.relative_exe_path, "./game.exe");
+        assert!(config.exe_hash.is_empty());
+        assert_eq!(config.dependencies, vec!["vcrun2019", "d3dx9"]);
+        assert_eq!(config.registry_keys.len(), 1);
+        assert_eq!(config.registry_keys[0].value, "00000001");
+        assert_eq!(
+            codes(&imported),
+            vec![
+                "import_exe_missing",
+                "lutris_prefix_ignored",
+                "lutris_task_ignored",
+                "lutris_task_ignored",
+                "exe_hash_invalid",
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_non_wine_runner_and_exe_outside_root() {
+        let fs = FakeFileSystem::with_files(&[]);
+
+        let err = import_lutris_config(
+            "runner: linux\nscript:\n  game:\n    exe: game.sh\n",
+            Path::new("/games/native"),
+            &fs,
+        )
+        .expect_err("native runner is rejected");
+        assert!(matches!(err, LuthierError::InvalidImportSource(_)));
+
+        let err = import_lutris_config(
+            "game:\n  exe: /elsewhere/game.exe\n",
+            Path::new("/games/sample"),
+            &fs,
+        )
+        .expect_err("exe outside root is rejected");
+        assert!(matches!(err, LuthierError::PathOutsideGameRoot(_)));
+    }
+}
@@ -1,4 +1,11 @@
 pub mod create_orchestrator_binary;
+pub mod desktop_integration;
+pub mod export_steam_shortcut;
 pub mod hash;
+pub mod import_bottles;
+pub mod import_game_config;
+pub mod import_heroic;
+pub mod import_lutris;
 pub mod ports;
+pub mod resolve_config_templates;
 pub mod validate_game_config;
@@ -1,5 +1,7 @@
 use std::path::{Path, PathBuf};
 
+use serde_json::Value;
+
 use crate::LuthierError;
 
 pub trait OrchestratorBinaryReaderPort: Send + Sync {
@@ -27,6 +29,8 @@ pub struct OrchestratorPayloadInjectionRequest<'a> {
     pub config_json_bytes: &'a [u8],
     pub hero_image_bytes: Option<&'a [u8]>,
     pub icon_png_bytes: Option<&'a [u8]>,
+    /// Raw contents of an Ed25519 key file; the payload is signed when present.
+    pub signing_key_bytes: Option<&'a [u8]>,
     pub output_path: &'a Path,
     pub options: OrchestratorPayloadInjectionOptions,
 }
@@ -44,3 +48,35 @@ pub trait OrchestratorPayloadInjectorPort: Send + Sync {
         request: OrchestratorPayloadInjectionRequest<'_>,
     ) -> Result<OrchestratorPayloadInjectionResult, LuthierError>;
 }
+
+/// Reads one config layer (a payload or a template it extends) as a JSON value.
+/// Callers decide which authoring formats they accept.
+pub trait ConfigDocumentLoaderPort {
+    fn load_document(&self, path: &Path) -> Result<Value, LuthierError>;
+}
+
+/// File checks an importer needs to locate and fingerprint the game executable.
+pub trait ImportFileSystemPort {
+    fn is_file(&self, path: &Path) -> bool;
+    fn sha256_file(&self, path: &Path) -> Result<String, LuthierError>;
+}
+
+/// File access for exporters that register a launcher with another
+/// application (Steam shortcuts, desktop entries).
+pub trait ExportFileSystemPort {
+    fn exists(&self, path: &Path) -> bool;
+    /// Files directly inside `dir`; empty when it does not exist.
+    fn list_files(&self, dir: &Path) -> Result<Vec<PathBuf>, LuthierError>;
+    fn read_bytes(&self, path: &Path) -> Result<Vec<u8>, LuthierError>;
+    /// Creates missing parent directories and replaces `path` atomically.
+    fn write_bytes(&self, path: &Path, bytes: &[u8]) -> Result<(), LuthierError>;
+    fn remove_file(&self, path: &Path) -> Result<(), LuthierError>;
+}
+
+/// Raster work needed to install a launcher icon into an icon theme.
+pub trait IconScalerPort {
+    /// Longest side of the PNG, in pixels.
+    fn png_size(&self, png: &[u8]) -> Result<u32, LuthierError>;
+    /// Fits the PNG into a transparent `size`x`size` square.
+    fn scale_png(&self, png: &[u8], size: u32) -> Result<Vec<u8>, LuthierError>;
+}
@@ -0,0 +1,217 @@
+use std::path::{Component, Path, PathBuf};
+
+use luthier_orchestrator_core::config_overlay::merge_config_overlay;
+use luthier_orchestrator_core::OrchestratorError;
+use serde_json::{Map, Value};
+
+use crate::application::ports::ConfigDocumentLoaderPort;
+use crate::models::ResolvedConfigDocument;
+use crate::LuthierError;
+
+const EXTENDS_FIELD: &str = "extends";
+
+/// Loads `path` and every template it `extends`, depth first, and merges them in
+/// order: each base in the order listed, then the document itself on top.
+/// `extends` holds one path or a list of paths relative to the extending file.
+pub(crate) fn resolve_config_templates(
+    path: &Path,
+    loader: &dyn ConfigDocumentLoaderPort,
+) -> Result<ResolvedConfigDocument, LuthierError> {
+    let mut layers = Vec::new();
+    collect_layers(&normalize_path(path), loader, &mut Vec::new(), &mut layers)?;
+
+    let mut document = Value::Object(Map::new());
+    let mut paths = Vec::with_capacity(layers.len());
+    for (layer_path, layer) in layers {
+        merge_config_overlay(&mut document, layer).map_err(|err| match err {
+            OrchestratorError::InvalidConfigOverlay(message) => {
+                LuthierError::InvalidConfigTemplate(format!("{} ({message})", layer_path.display()))
+            }
+            other => LuthierError::from(other),
+        })?;
+        paths.push(layer_path);
+    }
+
+    Ok(ResolvedConfigDocument {
+        document,
+        layers: paths,
+    })
+}
+
+fn collect_layers(
+    path: &Path,
+    loader: &dyn ConfigDocumentLoaderPort,
+    stack: &mut Vec<PathBuf>,
+    layers: &mut Vec<(PathBuf, Value)>,
+) -> Result<(), LuthierError> {
+    if stack.iter().any(|ancestor| ancestor == path) {
+        let chain = stack
+            .iter()
+            .chain(std::iter::once(&path.to_path_buf()))
+            .map(|entry| entry.display().to_string())
+            .collect::<Vec<_>>();
+        return Err(LuthierError::ConfigTemplateCycle(chain.join(" -> ")));
+    }
+
+    let mut document = loader.load_document(path)?;
+    let Value::Object(object) = &mut document else {
+        return Err(LuthierError::InvalidConfigTemplate(format!(
+            "{} must contain an object",
+            path.display()
+        )));
+    };
+    let bases = parse_extends(object.remove(EXTENDS_FIELD), path)?;
+
+    let dir = path.parent().unwrap_or_else(|| Path::new(""));
+    stack.push(path.to_path_buf());
+    for base in bases {
+        collect_layers(&normalize_path(&dir.join(base)), loader, stack, layers)?;
+    }
+    stack.pop();
+
+    layers.push((path.to_path_buf(), document));
+    Ok(())
+}
+
+fn parse_extends(raw: Option<Value>, path: &Path) -> Result<Vec<String>, LuthierError> {
+    let invalid = || {
+        LuthierError::InvalidConfigTemplate(format!(
+            "{}: '{EXTENDS_FIELD}' must be a path or a list of paths",
+            path.display()
+        ))
+    };
+
+    match raw {
+        None => Ok(Vec::new()),
+        Some(Value::String(base)) => Ok(vec![base]),
+        Some(Value::Array(bases)) => bases
+            .into_iter()
+            .map(|base| match base {
+                Value::String(base) => Ok(base),
+                _ => Err(invalid()),
+            })
+            .collect(),
+        Some(_) => Err(invalid()),
+    }
+}
+
+/// Lexically folds `.` and `..` so the same template reached through different
+/// relative paths is recognized when detecting cycles.
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !out.pop() {
+                    out.push("..");
+                }
+            }
+            other => out.push(other.as_os_str()),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use super::*;
+
+    struct InMemoryLoader(HashMap<PathBuf, Value>);
+
+    impl ConfigDocumentLoaderPort for InMemoryLoader {
+        fn load_document(&self, path: &Path) -> Result<Value, LuthierError> {
+            self.0.get(path).cloned().ok_or_else(|| {
+                LuthierError::Io(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    path.display().to_string(),
+                ))
+            })
+        }
+    }
+
+    fn loader(entries: &[(&str, Value)]) -> InMemoryLoader {
+        InMemoryLoader(
+            entries
+                .iter()
+                .map(|(path, value)| (PathBuf::from(path), value.clone()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn merges_bases_in_order_below_the_payload() {
+        let loader = loader(&[
+            (
+                "games/witcher.json",
+                json!({
+                    "extends": ["../templates/proton.json", "../templates/gamescope.json"],
+                    "game_name": "Witcher",
+                    "dependencies": { "$append": ["d3dx9"] }
+                }),
+            ),
+            (
+                "templates/proton.json",
+                json!({
+                    "extends": "./base.json",
+                    "runner": { "proton_version": "GE-Proton9-10" },
+                    "dependencies": ["vcrun2019"]
+                }),
+            ),
+            (
+                "templates/base.json",
+                json!({ "runner": { "proton_version": "proton", "esync": true } }),
+            ),
+            (
+                "templates/gamescope.json",
+                json!({ "environment": { "gamescope": { "fsr": true } } }),
+            ),
+        ]);
+
+        let resolved =
+            resolve_config_templates(Path::new("games/witcher.json"), &loader).expect("resolve");
+
+        assert_eq!(
+            resolved.document,
+            json!({
+                "runner": { "proton_version": "GE-Proton9-10", "esync": true },
+                "dependencies": ["vcrun2019", "d3dx9"],
+                "environment": { "gamescope": { "fsr": true } },
+                "game_name": "Witcher"
+            })
+        );
+        assert_eq!(
+            resolved.layers,
+            vec![
+                PathBuf::from("templates/base.json"),
+                PathBuf::from("templates/proton.json"),
+                PathBuf::from("templates/gamescope.json"),
+                PathBuf::from("games/witcher.json"),
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_template_cycles() {
+        let loader = loader(&[
+            ("a.json", json!({ "extends": "b.json" })),
+            ("b.json", json!({ "extends": "./a.json" })),
+        ]);
+
+        let err = resolve_config_templates(Path::new("a.json"), &loader).expect_err("cycle");
+        assert!(
+            matches!(err, LuthierError::ConfigTemplateCycle(chain) if chain == "a.json -> b.json -> a.json")
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_extends() {
+        let loader = loader(&[("a.json", json!({ "extends": 3 }))]);
+        let err = resolve_config_templates(Path::new("a.json"), &loader).expect_err("invalid");
+        assert!(matches!(err, LuthierError::InvalidConfigTemplate(_)));
+    }
+}
@@ -0,0 +1,188 @@
+/// Key that ties an installed entry back to its launcher, so reinstalling and
+/// uninstalling find it even after the game was renamed.
+pub(crate) const LAUNCHER_KEY: &str = "X-Luthier-Launcher";
+
+pub(crate) const DESKTOP_ID_PREFIX: &str = "luthier-";
+
+/// Sizes installed under `icons/hicolor/<size>x<size>/apps`. 48 is the size
+/// the icon theme spec requires every application icon to provide.
+pub(crate) const HICOLOR_SIZES: [u32; 7] = [16, 24, 32, 48, 64, 128, 256];
+pub(crate) const REQUIRED_HICOLOR_SIZE: u32 = 48;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DesktopEntry {
+    pub(crate) name: String,
+    pub(crate) launcher_path: String,
+    pub(crate) working_dir: String,
+    pub(crate) icon_name: Option<String>,
+}
+
+const ACTIONS: [(&str, &str, &str); 2] = [
+    ("doctor", "Run Doctor", "--doctor"),
+    ("winecfg", "Wine Configuration", "--winecfg"),
+];
+
+pub(crate) fn render_desktop_entry(entry: &DesktopEntry) -> String {
+    let exec = exec_quote(&entry.launcher_path);
+    let mut out = String::from("[Desktop Entry]\n");
+    out.push_str("Type=Application\n");
+    out.push_str("Version=1.5\n");
+    out.push_str(&format!("Name={}\n", escape_value(&entry.name)));
+    out.push_str(&format!("Exec={exec} --play-splash\n"));
+    out.push_str(&format!("Path={}\n", escape_value(&entry.working_dir)));
+    if let Some(icon_name) = &entry.icon_name {
+        out.push_str(&format!("Icon={}\n", escape_value(icon_name)));
+    }
+    out.push_str("Terminal=false\n");
+    out.push_str("Categories=Game;\n");
+    out.push_str("StartupNotify=true\n");
+    out.push_str(&format!(
+        "Actions={};\n",
+        ACTIONS.map(|(id, _, _)| id).join(";")
+    ));
+    out.push_str(&format!(
+        "{LAUNCHER_KEY}={}\n",
+        escape_value(&entry.launcher_path)
+    ));
+
+    for (id, name, flag) in ACTIONS {
+        out.push_str(&format!("\n[Desktop Action {id}]\n"));
+        out.push_str(&format!("Name={name}\n"));
+        out.push_str(&format!("Exec={exec} {flag}\n"));
+    }
+    out
+}
+
+/// Reads a key from the `[Desktop Entry]` group, undoing value escapes.
+pub(crate) fn desktop_entry_value(contents: &str, key: &str) -> Option<String> {
+    let mut in_main_group = false;
+    for line in contents.lines() {
+        let line = line.trim();
+        if line.starts_with('[') {
+            in_main_group = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_main_group {
+            continue;
+        }
+        if let Some((name, value)) = line.split_once('=') {
+            if name.trim() == key {
+                return Some(unescape_value(value.trim()));
+            }
+        }
+    }
+    None
+}
+
+/// `luthier-<slug>` from the game name; the desktop file is `<id>.desktop` and
+/// the icon is named after it.
+pub(crate) fn desktop_id(game_name: &str, attempt: usize) -> String {
+    let mut slug = String::new();
+    for ch in game_name.chars() {
+        if ch.is_ascii_alphanumeric() {
+            slug.push(ch.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    let slug = if slug.is_empty() { "game" } else { slug };
+
+    match attempt {
+        0 => format!("{DESKTOP_ID_PREFIX}{slug}"),
+        n => format!("{DESKTOP_ID_PREFIX}{slug}-{}", n + 1),
+    }
+}
+
+/// Quotes one `Exec` argument as the Desktop Entry spec requires: inside
+/// double quotes, `"`, `` ` ``, `$` and `\` are backslash-escaped, and a
+/// literal `%` is doubled so it is not read as a field code.
+fn exec_quote(arg: &str) -> String {
+    let mut quoted = String::from("\"");
+    for ch in arg.chars() {
+        match ch {
+            '"' | '`' | '$' | '\\' => {
+                quoted.push('\\');
+                quoted.push(ch);
+            }
+            '%' => quoted.push_str("%%"),
+            _ => quoted.push(ch),
+        }
+    }
+    quoted.push('"');
+    // The whole value is itself a string value, so its backslashes are
+    // escaped once more.
+    quoted.replace('\\', "\\\\")
+}
+
+fn escape_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
+fn unescape_value(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            out.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('s') => out.push(' '),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_entry_with_quoted_exec_and_actions() {
+        let entry = DesktopEntry {
+            name: "Sample: 100% \"Edition\"".to_string(),
+            launcher_path: "/games/My $Game/game".to_string(),
+            working_dir: "/games/My $Game".to_string(),
+            icon_name: Some("luthier-sample".to_string()),
+        };
+
+        let rendered = render_desktop_entry(&entry);
+        assert!(rendered.contains("Exec=\"/games/My \\\\$Game/game\" --play-splash\n"));
+        assert!(rendered.contains("Exec=\"/games/My \\\\$Game/game\" --doctor\n"));
+        assert!(rendered.contains("Actions=doctor;winecfg;\n"));
+        assert_eq!(
+            desktop_entry_value(&rendered, LAUNCHER_KEY).as_deref(),
+            Some("/games/My $Game/game")
+        );
+        assert_eq!(
+            desktop_entry_value(&rendered, "Name").as_deref(),
+            Some("Sample: 100% \"Edition\"")
+        );
+    }
+
+    #[test]
+    fn derives_desktop_ids_from_game_names() {
+        assert_eq!(
+            desktop_id("Half-Life 2: Episode One", 0),
+            "luthier-half-life-2-episode-one"
+        );
+        assert_eq!(desktop_id("  ***  ", 0), "luthier-game");
+        assert_eq!(desktop_id("Sample", 1), "luthier-sample-2");
+    }
+}
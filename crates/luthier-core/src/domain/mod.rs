@@ -1,2 +1,4 @@
+pub mod desktop_entry;
 pub mod path_rules;
+pub mod steam_shortcuts;
 pub mod validation_rules;
@@ -0,0 +1,373 @@
+use crate::LuthierError;
+
+const TAG_MAP: u8 = 0x00;
+const TAG_STRING: u8 = 0x01;
+const TAG_INT32: u8 = 0x02;
+const TAG_FLOAT32: u8 = 0x03;
+const TAG_UINT64: u8 = 0x07;
+const TAG_MAP_END: u8 = 0x08;
+
+const SHORTCUTS_KEY: &str = "shortcuts";
+
+/// One node of Steam's binary KeyValues format, as used by `shortcuts.vdf`.
+/// Entries keep their file order so untouched shortcuts round-trip unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum VdfValue {
+    Map(Vec<(String, VdfValue)>),
+    String(String),
+    Int32(u32),
+    /// Raw bits, so values are written back exactly as read.
+    Float32(u32),
+    UInt64(u64),
+}
+
+pub(crate) fn parse_binary_vdf(bytes: &[u8]) -> Result<Vec<(String, VdfValue)>, LuthierError> {
+    let mut reader = VdfReader { bytes, offset: 0 };
+    if bytes.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let root = reader.read_map()?;
+    if reader.offset != bytes.len() {
+        return Err(invalid("unexpected data after the root object"));
+    }
+    Ok(root)
+}
+
+pub(crate) fn render_binary_vdf(root: &[(String, VdfValue)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_map(&mut out, root);
+    out
+}
+
+struct VdfReader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl VdfReader<'_> {
+    fn read_map(&mut self) -> Result<Vec<(String, VdfValue)>, LuthierError> {
+        let mut entries = Vec::new();
+        loop {
+            let tag = self.read_u8()?;
+            if tag == TAG_MAP_END {
+                return Ok(entries);
+            }
+
+            let key = self.read_cstring()?;
+            let value = match tag {
+                TAG_MAP => VdfValue::Map(self.read_map()?),
+                TAG_STRING => VdfValue::String(self.read_cstring()?),
+                TAG_INT32 => VdfValue::Int32(u32::from_le_bytes(self.read_array()?)),
+                TAG_FLOAT32 => VdfValue::Float32(u32::from_le_bytes(self.read_array()?)),
+                TAG_UINT64 => VdfValue::UInt64(u64::from_le_bytes(self.read_array()?)),
+                other => {
+                    return Err(invalid(&format!(
+                        "unsupported value type 0x{other:02x} for '{key}'"
+                    )))
+                }
+            };
+            entries.push((key, value));
+        }
+    }
+
+    fn read_u8(&mut self) -> Result<u8, LuthierError> {
+        let byte = *self
+            .bytes
+            .get(self.offset)
+            .ok_or_else(|| invalid("file is truncated"))?;
+        self.offset += 1;
+        Ok(byte)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], LuthierError> {
+        let end = self.offset + N;
+        let slice = self
+            .bytes
+            .get(self.offset..end)
+            .ok_or_else(|| invalid("file is truncated"))?;
+        self.offset = end;
+        let mut out = [0u8; N];
+        out.copy_from_slice(slice);
+        Ok(out)
+    }
+
+    fn read_cstring(&mut self) -> Result<String, LuthierError> {
+        let rest = &self.bytes[self.offset..];
+        let len = rest
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or_else(|| invalid("unterminated string"))?;
+        let value = String::from_utf8_lossy(&rest[..len]).into_owned();
+        self.offset += len + 1;
+        Ok(value)
+    }
+}
+
+fn write_map(out: &mut Vec<u8>, entries: &[(String, VdfValue)]) {
+    for (key, value) in entries {
+        let tag = match value {
+            VdfValue::Map(_) => TAG_MAP,
+            VdfValue::String(_) => TAG_STRING,
+            VdfValue::Int32(_) => TAG_INT32,
+            VdfValue::Float32(_) => TAG_FLOAT32,
+            VdfValue::UInt64(_) => TAG_UINT64,
+        };
+        out.push(tag);
+        write_cstring(out, key);
+        match value {
+            VdfValue::Map(children) => write_map(out, children),
+            VdfValue::String(text) => write_cstring(out, text),
+            VdfValue::Int32(number) | VdfValue::Float32(number) => {
+                out.extend_from_slice(&number.to_le_bytes())
+            }
+            VdfValue::UInt64(number) => out.extend_from_slice(&number.to_le_bytes()),
+        }
+    }
+    out.push(TAG_MAP_END);
+}
+
+fn write_cstring(out: &mut Vec<u8>, value: &str) {
+    out.extend(value.bytes().filter(|byte| *byte != 0));
+    out.push(0);
+}
+
+fn invalid(message: &str) -> LuthierError {
+    LuthierError::SteamShortcutExport(format!("invalid shortcuts.vdf: {message}"))
+}
+
+/// What a Luthier launcher shortcut points at. Paths are unquoted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SteamShortcut {
+    pub(crate) app_name: String,
+    pub(crate) exe: String,
+    pub(crate) start_dir: String,
+    pub(crate) icon: String,
+    pub(crate) launch_options: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ShortcutUpsert {
+    pub(crate) app_id: u32,
+    pub(crate) created: bool,
+}
+
+/// Id Steam derives for a non-Steam shortcut; it also names the artwork in
+/// `config/grid`.
+pub(crate) fn shortcut_app_id(quoted_exe: &str, app_name: &str) -> u32 {
+    let mut key = Vec::with_capacity(quoted_exe.len() + app_name.len());
+    key.extend_from_slice(quoted_exe.as_bytes());
+    key.extend_from_slice(app_name.as_bytes());
+    crc32(&key) | 0x8000_0000
+}
+
+/// Adds the shortcut, or updates the entry that already runs the same
+/// executable. An existing entry keeps its app id (and so its artwork and
+/// play time), and fields Luthier does not manage are left alone.
+pub(crate) fn upsert_shortcut(
+    root: &mut Vec<(String, VdfValue)>,
+    shortcut: &SteamShortcut,
+) -> Result<ShortcutUpsert, LuthierError> {
+    let shortcuts = match field_mut(root, SHORTCUTS_KEY) {
+        Some(VdfValue::Map(entries)) => entries,
+        Some(_) => return Err(invalid("'shortcuts' is not an object")),
+        None => {
+            root.push((SHORTCUTS_KEY.to_string(), VdfValue::Map(Vec::new())));
+            match root.last_mut() {
+                Some((_, VdfValue::Map(entries))) => entries,
+                _ => return Err(invalid("'shortcuts' could not be created")),
+            }
+        }
+    };
+
+    let quoted_exe = quote(&shortcut.exe);
+    let existing = shortcuts.iter_mut().find_map(|(_, entry)| match entry {
+        VdfValue::Map(fields) if entry_exe(fields).as_deref() == Some(shortcut.exe.as_str()) => {
+            Some(fields)
+        }
+        _ => None,
+    });
+
+    let (fields, created) = match existing {
+        Some(fields) => (fields, false),
+        None => {
+            let index = next_index(shortcuts);
+            shortcuts.push((index, VdfValue::Map(default_fields())));
+            match shortcuts.last_mut() {
+                Some((_, VdfValue::Map(fields))) => (fields, true),
+                _ => return Err(invalid("shortcut entry could not be created")),
+            }
+        }
+    };
+
+    let app_id = match field_mut(fields, "appid") {
+        Some(VdfValue::Int32(app_id)) if !created && *app_id != 0 => *app_id,
+        _ => shortcut_app_id(&quoted_exe, &shortcut.app_name),
+    };
+
+    set_field(fields, "appid", VdfValue::Int32(app_id));
+    set_field(
+        fields,
+        "AppName",
+        VdfValue::String(shortcut.app_name.clone()),
+    );
+    set_field(fields, "Exe", VdfValue::String(quoted_exe));
+    set_field(
+        fields,
+        "StartDir",
+        VdfValue::String(quote(&shortcut.start_dir)),
+    );
+    set_field(fields, "icon", VdfValue::String(shortcut.icon.clone()));
+    set_field(
+        fields,
+        "LaunchOptions",
+        VdfValue::String(shortcut.launch_options.clone()),
+    );
+
+    Ok(ShortcutUpsert { app_id, created })
+}
+
+fn default_fields() -> Vec<(String, VdfValue)> {
+    let string = |key: &str| (key.to_string(), VdfValue::String(String::new()));
+    let int = |key: &str, value: u32| (key.to_string(), VdfValue::Int32(value));
+    vec![
+        int("appid", 0),
+        string("AppName"),
+        string("Exe"),
+        string("StartDir"),
+        string("icon"),
+        string("ShortcutPath"),
+        string("LaunchOptions"),
+        int("IsHidden", 0),
+        int("AllowDesktopConfig", 1),
+        int("AllowOverlay", 1),
+        int("OpenVR", 0),
+        int("Devkit", 0),
+        string("DevkitGameID"),
+        int("DevkitOverrideAppID", 0),
+        int("LastPlayTime", 0),
+        string("FlatpakAppID"),
+        ("tags".to_string(), VdfValue::Map(Vec::new())),
+    ]
+}
+
+fn entry_exe(fields: &[(String, VdfValue)]) -> Option<String> {
+    fields.iter().find_map(|(key, value)| match value {
+        VdfValue::String(exe) if key.eq_ignore_ascii_case("exe") => {
+            Some(exe.trim().trim_matches('"').to_string())
+        }
+        _ => None,
+    })
+}
+
+fn next_index(entries: &[(String, VdfValue)]) -> String {
+    entries
+        .iter()
+        .filter_map(|(key, _)| key.parse::<u32>().ok())
+        .max()
+        .map_or(0, |max| max + 1)
+        .to_string()
+}
+
+/// Steam has written some keys in different cases over time (`AppName`,
+/// `appname`), so lookups ignore case.
+fn field_mut<'a>(fields: &'a mut [(String, VdfValue)], key: &str) -> Option<&'a mut VdfValue> {
+    fields
+        .iter_mut()
+        .find(|(existing, _)| existing.eq_ignore_ascii_case(key))
+        .map(|(_, value)| value)
+}
+
+fn set_field(fields: &mut Vec<(String, VdfValue)>, key: &str, value: VdfValue) {
+    match field_mut(fields, key) {
+        Some(existing) => *existing = value,
+        None => fields.push((key.to_string(), value)),
+    }
+}
+
+fn quote(path: &str) -> String {
+    format!("\"{path}\"")
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xffff_ffffu32;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shortcut() -> SteamShortcut {
+        SteamShortcut {
+            app_name: "Sample".to_string(),
+            exe: "/games/sample/sample".to_string(),
+            start_dir: "/games/sample".to_string(),
+            icon: "/steam/config/grid/icon.png".to_string(),
+            launch_options: "--play-splash".to_string(),
+        }
+    }
+
+    #[test]
+    fn crc32_matches_the_reference_check_value() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(shortcut_app_id("\"a\"", "b") & 0x8000_0000, 0x8000_0000);
+    }
+
+    #[test]
+    fn round_trips_other_shortcuts_and_updates_in_place() {
+        let mut root = Vec::new();
+        let first = upsert_shortcut(&mut root, &shortcut()).expect("shortcut is added");
+        assert!(first.created);
+
+        let mut other = shortcut();
+        other.exe = "/games/other/other".to_string();
+        other.app_name = "Other".to_string();
+        upsert_shortcut(&mut root, &other).expect("second shortcut is added");
+
+        let bytes = render_binary_vdf(&root);
+        let mut parsed = parse_binary_vdf(&bytes).expect("rendered vdf parses");
+        assert_eq!(parsed, root);
+
+        let mut renamed = shortcut();
+        renamed.app_name = "Sample (Luthier)".to_string();
+        let again = upsert_shortcut(&mut parsed, &renamed).expect("shortcut is updated");
+        assert!(!again.created);
+        assert_eq!(again.app_id, first.app_id);
+
+        let Some((_, VdfValue::Map(entries))) = parsed.first() else {
+            panic!("shortcuts map is missing");
+        };
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].0, "1");
+        let Some((_, VdfValue::Map(fields))) = entries.first() else {
+            panic!("first shortcut is missing");
+        };
+        assert!(fields.contains(&(
+            "AppName".to_string(),
+            VdfValue::String("Sample (Luthier)".to_string())
+        )));
+        assert!(fields.contains(&(
+            "Exe".to_string(),
+            VdfValue::String("\"/games/sample/sample\"".to_string())
+        )));
+    }
+
+    #[test]
+    fn rejects_truncated_files() {
+        let bytes = render_binary_vdf(&[(
+            SHORTCUTS_KEY.to_string(),
+            VdfValue::Map(vec![("0".to_string(), VdfValue::Int32(1))]),
+        )]);
+
+        let err = parse_binary_vdf(&bytes[..bytes.len() - 3]).expect_err("truncated");
+        assert!(matches!(err, LuthierError::SteamShortcutExport(_)));
+    }
+}
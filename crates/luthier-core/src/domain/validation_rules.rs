@@ -1,6 +1,13 @@
 use std::collections::HashSet;
 
-use luthier_orchestrator_core::GameConfig;
+use luthier_orchestrator_core::config_overlay::apply_launch_profile;
+use luthier_orchestrator_core::host_rules::apply_host_rule_patch;
+use luthier_orchestrator_core::launch_entries::MAIN_LAUNCH_ENTRY;
+use luthier_orchestrator_core::variables::check_variables;
+use luthier_orchestrator_core::{
+    GameConfig, HostPredicate, OrchestratorError, ScriptInterpreter, ScriptStep,
+};
+use serde_json::Value;
 
 use crate::{ConfigValidationIssue, LuthierError};
 
@@ -15,6 +22,19 @@ pub(crate) fn validate_game_config_relative_paths(config: &GameConfig) -> Result
         normalize_relative_payload_path(path)?;
     }
 
+    for entry in &config.launch_entries {
+        normalize_relative_payload_path(&entry.relative_exe_path)?;
+        if let Some(working_dir) = &entry.working_dir {
+            normalize_relative_payload_path(working_dir)?;
+        }
+    }
+
+    for step in script_steps(config).map(|(_, step)| step) {
+        if let Some(cwd) = &step.cwd {
+            normalize_relative_payload_path(cwd)?;
+        }
+    }
+
     let mut seen_mount_targets = HashSet::new();
     for mount in &config.folder_mounts {
         normalize_relative_payload_path(&mount.source_relative_path)?;
@@ -33,6 +53,170 @@ pub(crate) fn validate_game_config_relative_paths(config: &GameConfig) -> Result
 pub(crate) fn collect_game_config_validation_issues(
     config: &GameConfig,
 ) -> Vec<ConfigValidationIssue> {
+    let mut issues = collect_config_issues(config);
+    let base_issues = issues
+        .iter()
+        .map(|issue| (issue.code.clone(), issue.field.clone()))
+        .collect::<HashSet<_>>();
+
+    let profiles = config
+        .profiles
+        .iter()
+        .map(|profile| (profile.name.as_str(), &profile.overlay))
+        .collect::<Vec<_>>();
+    collect_patch_issues(
+        &PROFILE_PATCHES,
+        &profiles,
+        |index| apply_launch_profile(config, &config.profiles[index].name),
+        &base_issues,
+        &mut issues,
+    );
+
+    let rules = config
+        .rules
+        .iter()
+        .map(|rule| (rule.name.as_str(), &rule.patch))
+        .collect::<Vec<_>>();
+    collect_patch_issues(
+        &RULE_PATCHES,
+        &rules,
+        |index| apply_host_rule_patch(config, &config.rules[index]),
+        &base_issues,
+        &mut issues,
+    );
+    for (index, rule) in config.rules.iter().enumerate() {
+        collect_host_predicate_issues(&rule.when, &format!("rules[{index}].when"), &mut issues);
+    }
+
+    issues
+}
+
+/// Named partial configs merged over the base config: launch profiles and host rules.
+struct PatchList {
+    label: &'static str,
+    list_field: &'static str,
+    patch_field: &'static str,
+}
+
+const PROFILE_PATCHES: PatchList = PatchList {
+    label: "profile",
+    list_field: "profiles",
+    patch_field: "overlay",
+};
+
+const RULE_PATCHES: PatchList = PatchList {
+    label: "rule",
+    list_field: "rules",
+    patch_field: "patch",
+};
+
+/// Each patch is checked by merging it over the base config and validating the
+/// result; only issues the base config does not already have are reported,
+/// under the patch.
+fn collect_patch_issues(
+    kind: &PatchList,
+    entries: &[(&str, &Value)],
+    apply: impl Fn(usize) -> Result<GameConfig, OrchestratorError>,
+    base_issues: &HashSet<(String, String)>,
+    issues: &mut Vec<ConfigValidationIssue>,
+) {
+    let PatchList {
+        label,
+        list_field,
+        patch_field,
+    } = kind;
+    let mut seen_names = HashSet::new();
+
+    for (index, (name, patch)) in entries.iter().enumerate() {
+        let field = format!("{list_field}[{index}]");
+        let name = name.trim();
+        if name.is_empty() {
+            issues.push(issue(
+                &format!("{label}_name_required"),
+                &format!("{field}.name"),
+                format!("{label} name is required"),
+            ));
+            continue;
+        }
+        if !seen_names.insert(name.to_ascii_lowercase()) {
+            issues.push(issue(
+                &format!("{label}_name_duplicate"),
+                &format!("{field}.name"),
+                format!("{label} '{name}' is defined more than once"),
+            ));
+            continue;
+        }
+        let patch_code = format!("{label}_{patch_field}_invalid");
+        let patch_path = format!("{field}.{patch_field}");
+        if !patch.is_object() {
+            issues.push(issue(
+                &patch_code,
+                &patch_path,
+                format!("{label} {patch_field} must be an object"),
+            ));
+            continue;
+        }
+
+        let resolved = match apply(index) {
+            Ok(resolved) => resolved,
+            Err(err) => {
+                issues.push(issue(&patch_code, &patch_path, err.to_string()));
+                continue;
+            }
+        };
+        for resolved_issue in collect_config_issues(&resolved) {
+            if base_issues.contains(&(resolved_issue.code.clone(), resolved_issue.field.clone())) {
+                continue;
+            }
+            issues.push(issue(
+                &resolved_issue.code,
+                &format!("{patch_path}.{}", resolved_issue.field),
+                format!("{label} '{name}': {}", resolved_issue.message),
+            ));
+        }
+    }
+}
+
+fn collect_host_predicate_issues(
+    predicate: &HostPredicate,
+    field: &str,
+    issues: &mut Vec<ConfigValidationIssue>,
+) {
+    for (index, os_id) in predicate.os_id.iter().enumerate() {
+        if os_id.trim().is_empty() {
+            issues.push(issue(
+                "rule_os_id_invalid",
+                &format!("{field}.os_id[{index}]"),
+                "os-release ID is empty",
+            ));
+        }
+    }
+
+    for (index, name) in predicate.env_present.iter().enumerate() {
+        if let Some(reason) = validate_env_var_name(name) {
+            issues.push(issue(
+                "rule_env_name_invalid",
+                &format!("{field}.env_present[{index}]"),
+                reason,
+            ));
+        }
+    }
+
+    if let Some(screen) = predicate.screen {
+        let inverted = |min: Option<u32>, max: Option<u32>| matches!((min, max), (Some(min), Some(max)) if min > max);
+        if inverted(screen.min_width, screen.max_width)
+            || inverted(screen.min_height, screen.max_height)
+        {
+            issues.push(issue(
+                "rule_screen_bounds_invalid",
+                &format!("{field}.screen"),
+                "minimum screen size is larger than the maximum",
+            ));
+        }
+    }
+}
+
+fn collect_config_issues(config: &GameConfig) -> Vec<ConfigValidationIssue> {
     let mut issues = Vec::new();
 
     if let Err(err) = validate_game_config_relative_paths(config) {
@@ -316,9 +500,201 @@ pub(crate) fn collect_game_config_validation_issues(
         }
     }
 
+    collect_launch_entry_issues(config, &mut issues);
+    collect_script_issues(config, &mut issues);
+    collect_variable_issues(config, &mut issues);
+
     issues
 }
 
+/// Every string that is expanded at launch must only use known `${...}` variables.
+fn collect_variable_issues(config: &GameConfig, issues: &mut Vec<ConfigValidationIssue>) {
+    let mut expanded = Vec::new();
+    for (index, arg) in config.launch_args.iter().enumerate() {
+        expanded.push((format!("launch_args[{index}]"), arg));
+    }
+    for (key, value) in &config.environment.custom_vars {
+        expanded.push((format!("environment.custom_vars.{key}"), value));
+    }
+    for (index, edit) in config.environment.env_edits.iter().enumerate() {
+        let field = format!("environment.env_edits[{index}]");
+        if let Some(reason) = validate_env_var_name(&edit.name) {
+            issues.push(issue(
+                "env_var_name_invalid",
+                &format!("{field}.name"),
+                reason,
+            ));
+        }
+        expanded.push((format!("{field}.value"), &edit.value));
+    }
+    for (index, wrapper) in config.compatibility.wrapper_commands.iter().enumerate() {
+        expanded.push((
+            format!("compatibility.wrapper_commands[{index}].args"),
+            &wrapper.args,
+        ));
+    }
+    for (field, step) in script_steps(config) {
+        // Shell bodies use `${VAR}` themselves and are left alone.
+        if step.interpreter == ScriptInterpreter::Prefix {
+            expanded.push((format!("{field}.command"), &step.command));
+        }
+        for (arg_index, arg) in step.args.iter().enumerate() {
+            expanded.push((format!("{field}.args[{arg_index}]"), arg));
+        }
+    }
+    for (index, entry) in config.launch_entries.iter().enumerate() {
+        for (arg_index, arg) in entry.launch_args.iter().enumerate() {
+            expanded.push((
+                format!("launch_entries[{index}].launch_args[{arg_index}]"),
+                arg,
+            ));
+        }
+        for (key, value) in &entry.env {
+            expanded.push((format!("launch_entries[{index}].env.{key}"), value));
+        }
+    }
+
+    for (field, value) in expanded {
+        match check_variables(value) {
+            Ok(()) => {}
+            Err(OrchestratorError::UnknownVariable(name)) => issues.push(issue(
+                "variable_unknown",
+                &field,
+                format!("unknown variable '${{{name}}}'"),
+            )),
+            Err(err) => issues.push(issue("variable_syntax_invalid", &field, err.to_string())),
+        }
+    }
+}
+
+fn collect_launch_entry_issues(config: &GameConfig, issues: &mut Vec<ConfigValidationIssue>) {
+    let mut seen_names = HashSet::new();
+    for (index, entry) in config.launch_entries.iter().enumerate() {
+        let field = format!("launch_entries[{index}]");
+        let name = entry.name.trim();
+        if name.is_empty() {
+            issues.push(issue(
+                "launch_entry_name_required",
+                &format!("{field}.name"),
+                "launch entry name is required",
+            ));
+        } else if name.eq_ignore_ascii_case(MAIN_LAUNCH_ENTRY) {
+            issues.push(issue(
+                "launch_entry_name_reserved",
+                &format!("{field}.name"),
+                format!("'{MAIN_LAUNCH_ENTRY}' is reserved for relative_exe_path"),
+            ));
+        } else if !seen_names.insert(name.to_ascii_lowercase()) {
+            issues.push(issue(
+                "launch_entry_name_duplicate",
+                &format!("{field}.name"),
+                format!("launch entry '{name}' is defined more than once"),
+            ));
+        }
+
+        if entry.relative_exe_path.trim().is_empty() {
+            issues.push(issue(
+                "launch_entry_exe_required",
+                &format!("{field}.relative_exe_path"),
+                "launch entry executable is required",
+            ));
+        }
+
+        for key in entry.env.keys() {
+            if let Some(reason) = validate_env_var_name(key) {
+                issues.push(issue(
+                    "env_var_name_invalid",
+                    &format!("{field}.env.{key}"),
+                    reason,
+                ));
+            }
+        }
+    }
+
+    if let Some(default_entry) = &config.default_entry {
+        if default_entry != MAIN_LAUNCH_ENTRY
+            && !config
+                .launch_entries
+                .iter()
+                .any(|entry| &entry.name == default_entry)
+        {
+            issues.push(issue(
+                "default_entry_unknown",
+                "default_entry",
+                format!("default entry '{default_entry}' is not a launch entry"),
+            ));
+        }
+    }
+}
+
+/// Pre-launch then post-launch steps with their dotted field.
+fn script_steps(config: &GameConfig) -> impl Iterator<Item = (String, &ScriptStep)> {
+    phase_steps("pre_launch", &config.scripts.pre_launch)
+        .chain(phase_steps("post_launch", &config.scripts.post_launch))
+}
+
+fn phase_steps<'a>(
+    phase: &'static str,
+    steps: &'a [ScriptStep],
+) -> impl Iterator<Item = (String, &'a ScriptStep)> {
+    steps
+        .iter()
+        .enumerate()
+        .map(move |(index, step)| (format!("scripts.{phase}[{index}]"), step))
+}
+
+fn collect_script_issues(config: &GameConfig, issues: &mut Vec<ConfigValidationIssue>) {
+    let mut seen_names = HashSet::new();
+    for (field, step) in script_steps(config) {
+        let name = step.name.trim();
+        if name.is_empty() {
+            issues.push(issue(
+                "script_step_name_required",
+                &format!("{field}.name"),
+                "script step name is required",
+            ));
+        } else if !seen_names.insert(name.to_ascii_lowercase()) {
+            issues.push(issue(
+                "script_step_name_duplicate",
+                &format!("{field}.name"),
+                format!("script step '{name}' is defined more than once"),
+            ));
+        }
+
+        if step.command.trim().is_empty() {
+            issues.push(issue(
+                "script_step_command_required",
+                &format!("{field}.command"),
+                "script step command is required",
+            ));
+        }
+
+        if step.timeout_secs == 0 {
+            issues.push(issue(
+                "script_step_timeout_invalid",
+                &format!("{field}.timeout_secs"),
+                "timeout must be at least 1 second",
+            ));
+        }
+
+        if step.background && field.starts_with("scripts.post_launch") {
+            issues.push(issue(
+                "script_step_background_post_launch",
+                &format!("{field}.background"),
+                "background steps are only supported before launch",
+            ));
+        }
+
+        if step.background && step.run_once {
+            issues.push(issue(
+                "script_step_run_once_background",
+                &format!("{field}.run_once"),
+                "run-once steps cannot run in the background, since their outcome is never known",
+            ));
+        }
+    }
+}
+
 fn issue(code: &str, field: &str, message: impl Into<String>) -> ConfigValidationIssue {
     ConfigValidationIssue {
         code: code.to_string(),
@@ -573,11 +949,13 @@ mod tests {
     use std::collections::HashMap;
 
     use luthier_orchestrator_core::config::{
-        CompatibilityConfig, DllOverrideRule, EnvConfig, FeatureState, FolderMount, GameConfig,
-        GamescopeConfig, RegistryKey, RequirementsConfig, RunnerConfig, RuntimeCandidate,
-        RuntimePolicy, RuntimePreference, ScriptsConfig, SplashConfig, SystemDependency,
-        VirtualDesktopConfig, WineDesktopFolderMapping, WineDriveMapping, WinecfgConfig,
-        WinecfgFeaturePolicy, WrapperCommand,
+        CompatibilityConfig, DllOverrideRule, EnvConfig, EnvEdit, EnvEditMode, FeatureState,
+        FolderMount, GameConfig, GamescopeConfig, HostPredicate, HostRule, LaunchEntry,
+        LaunchProfile, RegistryKey, RequirementsConfig, RunnerConfig, RuntimeCandidate,
+        RuntimePolicy, RuntimePreference, ScreenSizePredicate, ScriptInterpreter, ScriptStep,
+        ScriptsConfig, SplashConfig, SystemDependency, VirtualDesktopConfig,
+        WineDesktopFolderMapping, WineDriveMapping, WinecfgConfig, WinecfgFeaturePolicy,
+        WrapperCommand,
     };
 
     use super::{collect_game_config_validation_issues, validate_game_config_relative_paths};
@@ -854,6 +1232,225 @@ mod tests {
             .all(|issue| issue.field.as_str() != "winecfg.audio_driver"));
     }
 
+    #[test]
+    fn reports_profile_issues_under_the_profile_overlay() {
+        let mut cfg = sample_config();
+        cfg.profiles = vec![
+            LaunchProfile {
+                name: "Deck".to_string(),
+                description: String::new(),
+                overlay: serde_json::json!({ "environment": { "custom_vars": { "1BAD": "x" } } }),
+            },
+            LaunchProfile {
+                name: "deck".to_string(),
+                description: String::new(),
+                overlay: serde_json::json!({}),
+            },
+            LaunchProfile {
+                name: "Broken".to_string(),
+                description: String::new(),
+                overlay: serde_json::json!(["not", "an", "object"]),
+            },
+        ];
+
+        let issues = collect_game_config_validation_issues(&cfg);
+
+        let env_issue = find_issue(
+            &issues,
+            "env_var_name_invalid",
+            "profiles[0].overlay.environment.custom_vars.1BAD",
+        )
+        .expect("resolved profile issues must be reported under the overlay");
+        assert!(env_issue.message.starts_with("profile 'Deck': "));
+        assert!(find_issue(&issues, "profile_name_duplicate", "profiles[1].name").is_some());
+        assert!(find_issue(&issues, "profile_overlay_invalid", "profiles[2].overlay").is_some());
+        assert!(issues
+            .iter()
+            .all(|issue| issue.field != "environment.custom_vars.1BAD"));
+    }
+
+    #[test]
+    fn reports_host_rule_patch_and_predicate_issues() {
+        let mut cfg = sample_config();
+        cfg.rules = vec![
+            HostRule {
+                name: "nvidia".to_string(),
+                when: HostPredicate {
+                    env_present: vec!["BAD-NAME".to_string()],
+                    screen: Some(ScreenSizePredicate {
+                        min_width: Some(1920),
+                        max_width: Some(1280),
+                        ..ScreenSizePredicate::default()
+                    }),
+                    ..HostPredicate::default()
+                },
+                patch: serde_json::json!({ "registry_keys": { "$replace": [
+                    { "path": "", "name": "x", "value_type": "REG_SZ", "value": "" }
+                ] } }),
+            },
+            HostRule {
+                name: "identity".to_string(),
+                when: HostPredicate::default(),
+                patch: serde_json::json!({ "exe_hash": "b" }),
+            },
+        ];
+
+        let issues = collect_game_config_validation_issues(&cfg);
+
+        assert!(find_issue(
+            &issues,
+            "registry_path_required",
+            "rules[0].patch.registry_keys[0].path"
+        )
+        .is_some());
+        assert!(find_issue(
+            &issues,
+            "rule_env_name_invalid",
+            "rules[0].when.env_present[0]"
+        )
+        .is_some());
+        assert!(find_issue(
+            &issues,
+            "rule_screen_bounds_invalid",
+            "rules[0].when.screen"
+        )
+        .is_some());
+        let locked = find_issue(&issues, "rule_patch_invalid", "rules[1].patch")
+            .expect("patches must not change the payload identity");
+        assert!(locked.message.contains("exe_hash"));
+    }
+
+    #[test]
+    fn reports_launch_entry_issues_and_unknown_default_entry() {
+        let mut cfg = sample_config();
+        let entry = |name: &str| LaunchEntry {
+            name: name.to_string(),
+            relative_exe_path: "./tools/editor.exe".to_string(),
+            launch_args: Vec::new(),
+            working_dir: None,
+            env: HashMap::from([("BAD-NAME".to_string(), "1".to_string())]),
+        };
+        cfg.launch_entries = vec![entry("Editor"), entry("editor"), entry("Main")];
+        cfg.default_entry = Some("Server".to_string());
+
+        let issues = collect_game_config_validation_issues(&cfg);
+        assert!(find_issue(
+            &issues,
+            "launch_entry_name_duplicate",
+            "launch_entries[1].name"
+        )
+        .is_some());
+        assert!(find_issue(
+            &issues,
+            "env_var_name_invalid",
+            "launch_entries[0].env.BAD-NAME"
+        )
+        .is_some());
+        assert!(find_issue(
+            &issues,
+            "launch_entry_name_reserved",
+            "launch_entries[2].name"
+        )
+        .is_some());
+        assert!(find_issue(&issues, "default_entry_unknown", "default_entry").is_some());
+
+        cfg.launch_entries[0].working_dir = Some("../outside".to_string());
+        assert!(validate_game_config_relative_paths(&cfg).is_err());
+    }
+
+    #[test]
+    fn reports_script_step_issues() {
+        let mut cfg = sample_config();
+        let step = |name: &str, interpreter: ScriptInterpreter, command: &str| ScriptStep {
+            name: name.to_string(),
+            interpreter,
+            command: command.to_string(),
+            args: Vec::new(),
+            cwd: None,
+            timeout_secs: 600,
+            mandatory: false,
+            run_once: false,
+            background: false,
+        };
+        cfg.scripts.pre_launch = vec![
+            step("saves", ScriptInterpreter::Bash, "echo ${HOME}"),
+            step("redist", ScriptInterpreter::Prefix, "${DRIVE_C}/${REDIST}"),
+        ];
+        let mut post = step("Saves", ScriptInterpreter::Sh, " ");
+        post.background = true;
+        post.run_once = true;
+        post.timeout_secs = 0;
+        cfg.scripts.post_launch = vec![post];
+
+        let issues = collect_game_config_validation_issues(&cfg);
+        assert!(find_issue(
+            &issues,
+            "script_step_name_duplicate",
+            "scripts.post_launch[0].name"
+        )
+        .is_some());
+        for (code, field) in [
+            (
+                "script_step_command_required",
+                "scripts.post_launch[0].command",
+            ),
+            (
+                "script_step_timeout_invalid",
+                "scripts.post_launch[0].timeout_secs",
+            ),
+            (
+                "script_step_background_post_launch",
+                "scripts.post_launch[0].background",
+            ),
+            (
+                "script_step_run_once_background",
+                "scripts.post_launch[0].run_once",
+            ),
+            ("variable_unknown", "scripts.pre_launch[1].command"),
+        ] {
+            assert!(find_issue(&issues, code, field).is_some(), "{code} {field}");
+        }
+        assert!(find_issue(&issues, "variable_unknown", "scripts.pre_launch[0].command").is_none());
+
+        cfg.scripts.pre_launch[0].cwd = Some("/tmp".to_string());
+        assert!(validate_game_config_relative_paths(&cfg).is_err());
+    }
+
+    #[test]
+    fn reports_unknown_and_malformed_variables() {
+        let mut cfg = sample_config();
+        cfg.launch_args = vec![
+            "-save=${WIN_USER_DOCUMENTS}".to_string(),
+            "${STEAM}".to_string(),
+        ];
+        cfg.environment
+            .custom_vars
+            .insert("MODS".to_string(), "${GAME_ROOT/mods".to_string());
+        cfg.environment.env_edits = vec![EnvEdit {
+            name: "LD_LIBRARY_PATH".to_string(),
+            value: "${HOST_ENV:SDK_ROOT}/lib:${PREFIXX}".to_string(),
+            mode: EnvEditMode::Prepend,
+            separator: ":".to_string(),
+        }];
+
+        let issues = collect_game_config_validation_issues(&cfg);
+        assert!(find_issue(&issues, "variable_unknown", "launch_args[0]").is_none());
+        assert!(find_issue(&issues, "variable_unknown", "launch_args[1]").is_some());
+        assert!(find_issue(
+            &issues,
+            "variable_syntax_invalid",
+            "environment.custom_vars.MODS"
+        )
+        .is_some());
+        let edit_issue = find_issue(
+            &issues,
+            "variable_unknown",
+            "environment.env_edits[0].value",
+        )
+        .expect("unknown variable in env edit");
+        assert!(edit_issue.message.contains("${PREFIXX}"));
+    }
+
     fn find_issue<'a>(
         issues: &'a [ConfigValidationIssue],
         code: &str,
@@ -900,6 +1497,7 @@ mod tests {
                 mangohud: FeatureState::OptionalOff,
                 prime_offload: FeatureState::OptionalOff,
                 custom_vars: HashMap::new(),
+                env_edits: Vec::new(),
             },
             compatibility: CompatibilityConfig {
                 wine_wayland: FeatureState::OptionalOff,
@@ -974,9 +1572,13 @@ mod tests {
             folder_mounts: vec![],
             splash: SplashConfig::default(),
             scripts: ScriptsConfig {
-                pre_launch: String::new(),
-                post_launch: String::new(),
+                pre_launch: Vec::new(),
+                post_launch: Vec::new(),
             },
+            profiles: Vec::new(),
+            rules: Vec::new(),
+            launch_entries: Vec::new(),
+            default_entry: None,
         }
     }
 }
@@ -28,6 +28,21 @@ pub enum LuthierError {
     #[error("duplicate folder mount target windows path: {0}")]
     DuplicateFolderMountTarget(String),
 
+    #[error("config template cycle: {0}")]
+    ConfigTemplateCycle(String),
+
+    #[error("invalid config template: {0}")]
+    InvalidConfigTemplate(String),
+
+    #[error("invalid import source: {0}")]
+    InvalidImportSource(String),
+
+    #[error("steam shortcut export failed: {0}")]
+    SteamShortcutExport(String),
+
+    #[error("desktop integration failed: {0}")]
+    DesktopIntegration(String),
+
     #[error("invalid game config ({issues_len} issue(s)); first: {first_issue}")]
     InvalidGameConfig {
         issues: Vec<ConfigValidationIssue>,
@@ -74,6 +89,21 @@ impl LuthierError {
             Self::DuplicateFolderMountTarget(_) => {
                 LayeredErrorRef::Domain(domain::DomainErrorKind::DuplicateFolderMountTarget)
             }
+            Self::ConfigTemplateCycle(_) => {
+                LayeredErrorRef::Domain(domain::DomainErrorKind::ConfigTemplateCycle)
+            }
+            Self::InvalidConfigTemplate(_) => {
+                LayeredErrorRef::Domain(domain::DomainErrorKind::InvalidConfigTemplate)
+            }
+            Self::InvalidImportSource(_) => {
+                LayeredErrorRef::Domain(domain::DomainErrorKind::InvalidImportSource)
+            }
+            Self::SteamShortcutExport(_) => {
+                LayeredErrorRef::Domain(domain::DomainErrorKind::SteamShortcutExport)
+            }
+            Self::DesktopIntegration(_) => {
+                LayeredErrorRef::Domain(domain::DomainErrorKind::DesktopIntegration)
+            }
             Self::InvalidGameConfig {
                 issues,
                 issues_len,
@@ -130,6 +160,11 @@ mod domain {
         PathOutsideGameRoot,
         InvalidFolderMountTarget,
         DuplicateFolderMountTarget,
+        ConfigTemplateCycle,
+        InvalidConfigTemplate,
+        InvalidImportSource,
+        SteamShortcutExport,
+        DesktopIntegration,
     }
 }
 
@@ -1,8 +1,47 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::LuthierError;
 
 pub(crate) fn read_bytes(path: &Path) -> Result<Vec<u8>, LuthierError> {
     fs::read(path).map_err(LuthierError::from)
 }
+
+pub(crate) fn write_bytes_atomic(path: &Path, bytes: &[u8]) -> Result<(), LuthierError> {
+    let parent = path
+        .parent()
+        .ok_or_else(|| LuthierError::InvalidRelativePath(path.display().to_string()))?;
+    fs::create_dir_all(parent)?;
+
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let tmp_path = parent.join(format!(".{file_name}.tmp-{}", std::process::id()));
+
+    fs::write(&tmp_path, bytes)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
+
+pub(crate) fn list_files(dir: &Path) -> Result<Vec<PathBuf>, LuthierError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+pub(crate) fn remove_file(path: &Path) -> Result<(), LuthierError> {
+    fs::remove_file(path).map_err(LuthierError::from)
+}
@@ -0,0 +1,40 @@
+use std::io::Cursor;
+
+use image::{imageops, imageops::FilterType, DynamicImage, ImageFormat, RgbaImage};
+
+use crate::LuthierError;
+
+pub(crate) fn png_size(png: &[u8]) -> Result<u32, LuthierError> {
+    let image = decode_png(png)?;
+    Ok(image.width().max(image.height()))
+}
+
+pub(crate) fn scale_png(png: &[u8], size: u32) -> Result<Vec<u8>, LuthierError> {
+    let image = decode_png(png)?;
+    if image.width() == size && image.height() == size {
+        return Ok(png.to_vec());
+    }
+
+    let scaled = image.resize(size, size, FilterType::Lanczos3).to_rgba8();
+    let mut canvas = RgbaImage::new(size, size);
+    imageops::overlay(
+        &mut canvas,
+        &scaled,
+        i64::from((size - scaled.width()) / 2),
+        i64::from((size - scaled.height()) / 2),
+    );
+
+    let mut out = Cursor::new(Vec::new());
+    DynamicImage::ImageRgba8(canvas)
+        .write_to(&mut out, ImageFormat::Png)
+        .map_err(icon_error)?;
+    Ok(out.into_inner())
+}
+
+fn decode_png(png: &[u8]) -> Result<DynamicImage, LuthierError> {
+    image::load_from_memory_with_format(png, ImageFormat::Png).map_err(icon_error)
+}
+
+fn icon_error(err: image::ImageError) -> LuthierError {
+    LuthierError::DesktopIntegration(format!("embedded icon: {err}"))
+}
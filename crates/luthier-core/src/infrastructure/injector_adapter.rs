@@ -3,6 +3,7 @@ use std::path::{Path, PathBuf};
 use luthier_orchestrator_core::injector::{
     inject_from_parts as orchestrator_inject_from_parts, InjectOptions as OrchestratorInjectOptions,
 };
+use luthier_orchestrator_core::signing::PayloadSigningKey;
 
 use crate::LuthierError;
 
@@ -26,6 +27,7 @@ impl From<OrchestratorInjectionOptions> for OrchestratorInjectOptions {
         Self {
             backup_existing: value.backup_existing,
             make_executable: value.make_executable,
+            signing_key: None,
         }
     }
 }
@@ -36,6 +38,7 @@ pub(crate) struct OrchestratorInjectionRequest<'a> {
     pub config_json_bytes: &'a [u8],
     pub hero_image_bytes: Option<&'a [u8]>,
     pub icon_png_bytes: Option<&'a [u8]>,
+    pub signing_key_bytes: Option<&'a [u8]>,
     pub output_path: &'a Path,
     pub options: OrchestratorInjectionOptions,
 }
@@ -50,13 +53,19 @@ pub(crate) struct OrchestratorInjectionResult {
 pub(crate) fn inject_orchestrator_payload(
     request: OrchestratorInjectionRequest<'_>,
 ) -> Result<OrchestratorInjectionResult, LuthierError> {
+    let mut options: OrchestratorInjectOptions = request.options.into();
+    if let Some(key_bytes) = request.signing_key_bytes {
+        options.signing_key = Some(PayloadSigningKey::from_key_file_bytes(key_bytes)?);
+    }
+
     let result = orchestrator_inject_from_parts(
         request.base_bytes,
         request.config_json_bytes,
         request.hero_image_bytes,
         request.icon_png_bytes,
+        &[],
         request.output_path,
-        request.options.into(),
+        options,
     )?;
 
     Ok(OrchestratorInjectionResult {
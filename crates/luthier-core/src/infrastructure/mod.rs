@@ -1,2 +1,4 @@
 pub mod file_io;
+pub mod icon_scaler;
 pub mod injector_adapter;
+pub mod xdg;
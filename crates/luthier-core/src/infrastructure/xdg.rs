@@ -0,0 +1,10 @@
+use std::env;
+use std::path::PathBuf;
+
+/// `$XDG_DATA_HOME`, or `~/.local/share` when it is unset or relative.
+pub(crate) fn data_home() -> Option<PathBuf> {
+    env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+}
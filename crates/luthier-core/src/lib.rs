@@ -6,21 +6,28 @@ mod error;
 mod infrastructure;
 mod models;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use luthier_orchestrator_core::GameConfig;
 
 use crate::application::ports::{
-    OrchestratorBinaryReaderPort, OrchestratorPayloadInjectionRequest,
-    OrchestratorPayloadInjectionResult, OrchestratorPayloadInjectorPort,
+    ExportFileSystemPort, IconScalerPort, ImportFileSystemPort, OrchestratorBinaryReaderPort,
+    OrchestratorPayloadInjectionRequest, OrchestratorPayloadInjectionResult,
+    OrchestratorPayloadInjectorPort,
 };
 use crate::infrastructure::injector_adapter::{
     OrchestratorInjectionOptions, OrchestratorInjectionRequest,
 };
 
+pub use application::ports::ConfigDocumentLoaderPort;
 pub use error::ConfigValidationIssue;
 pub use error::LuthierError;
-pub use models::{CreateOrchestratorRequest, CreateOrchestratorResult};
+pub use models::{
+    CreateOrchestratorRequest, CreateOrchestratorResult, DesktopEntryInstallRequest,
+    DesktopEntryInstallResult, DesktopEntryUninstallRequest, DesktopEntryUninstallResult,
+    ImportedGameConfig, ResolvedConfigDocument, SteamShortcutExportRequest,
+    SteamShortcutExportResult,
+};
 
 #[derive(Debug, Clone, Copy, Default)]
 struct LocalOrchestratorBinaryReaderAdapter;
@@ -45,6 +52,7 @@ impl OrchestratorPayloadInjectorPort for LocalOrchestratorPayloadInjectorAdapter
                 config_json_bytes: request.config_json_bytes,
                 hero_image_bytes: request.hero_image_bytes,
                 icon_png_bytes: request.icon_png_bytes,
+                signing_key_bytes: request.signing_key_bytes,
                 output_path: request.output_path,
                 options: OrchestratorInjectionOptions {
                     backup_existing: request.options.backup_existing,
@@ -61,6 +69,57 @@ impl OrchestratorPayloadInjectorPort for LocalOrchestratorPayloadInjectorAdapter
     }
 }
 
+#[derive(Debug, Clone, Copy, Default)]
+struct LocalImportFileSystemAdapter;
+
+impl ImportFileSystemPort for LocalImportFileSystemAdapter {
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn sha256_file(&self, path: &Path) -> Result<String, LuthierError> {
+        application::hash::sha256_file(path)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct LocalExportFileSystemAdapter;
+
+impl ExportFileSystemPort for LocalExportFileSystemAdapter {
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn list_files(&self, dir: &Path) -> Result<Vec<PathBuf>, LuthierError> {
+        infrastructure::file_io::list_files(dir)
+    }
+
+    fn read_bytes(&self, path: &Path) -> Result<Vec<u8>, LuthierError> {
+        infrastructure::file_io::read_bytes(path)
+    }
+
+    fn write_bytes(&self, path: &Path, bytes: &[u8]) -> Result<(), LuthierError> {
+        infrastructure::file_io::write_bytes_atomic(path, bytes)
+    }
+
+    fn remove_file(&self, path: &Path) -> Result<(), LuthierError> {
+        infrastructure::file_io::remove_file(path)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct LocalIconScalerAdapter;
+
+impl IconScalerPort for LocalIconScalerAdapter {
+    fn png_size(&self, png: &[u8]) -> Result<u32, LuthierError> {
+        infrastructure::icon_scaler::png_size(png)
+    }
+
+    fn scale_png(&self, png: &[u8], size: u32) -> Result<Vec<u8>, LuthierError> {
+        infrastructure::icon_scaler::scale_png(png, size)
+    }
+}
+
 pub fn create_orchestrator_binary(
     request: &CreateOrchestratorRequest,
 ) -> Result<CreateOrchestratorResult, LuthierError> {
@@ -96,6 +155,93 @@ pub fn collect_game_config_validation_issues(config: &GameConfig) -> Vec<ConfigV
     application::validate_game_config::collect_game_config_validation_issues(config)
 }
 
+/// Flattens a payload and the templates it `extends` into a single document.
+/// Only the flattened config is meant to be embedded.
+pub fn resolve_config_templates(
+    path: &Path,
+    loader: &dyn ConfigDocumentLoaderPort,
+) -> Result<ResolvedConfigDocument, LuthierError> {
+    application::resolve_config_templates::resolve_config_templates(path, loader)
+}
+
+/// Converts a Lutris game config or installer script (YAML text) into a
+/// config for the game installed at `game_root`.
+pub fn import_lutris_config(
+    source: &str,
+    game_root: &Path,
+) -> Result<ImportedGameConfig, LuthierError> {
+    application::import_lutris::import_lutris_config(
+        source,
+        game_root,
+        &LocalImportFileSystemAdapter,
+    )
+}
+
+/// Converts a Heroic per-game settings file (`GamesConfig/<app>.json`). `exe`
+/// is required when the file has no `targetExe`, as for sideloaded games.
+pub fn import_heroic_config(
+    source: &str,
+    game_root: &Path,
+    exe: Option<&str>,
+) -> Result<ImportedGameConfig, LuthierError> {
+    application::import_heroic::import_heroic_config(
+        source,
+        game_root,
+        exe,
+        &LocalImportFileSystemAdapter,
+    )
+}
+
+/// Converts a Bottles `bottle.yml`; extra programs become launch entries.
+pub fn import_bottles_config(
+    source: &str,
+    game_root: &Path,
+) -> Result<ImportedGameConfig, LuthierError> {
+    application::import_bottles::import_bottles_config(
+        source,
+        game_root,
+        &LocalImportFileSystemAdapter,
+    )
+}
+
+/// Adds the launcher to a Steam user's `shortcuts.vdf` (or updates the entry
+/// already pointing at it) and copies its icon and hero image into
+/// `config/grid`. Steam only reads the file on startup.
+pub fn export_steam_shortcut(
+    request: &SteamShortcutExportRequest,
+) -> Result<SteamShortcutExportResult, LuthierError> {
+    application::export_steam_shortcut::export_steam_shortcut(
+        request,
+        &LocalExportFileSystemAdapter,
+    )
+}
+
+/// Installs a freedesktop menu entry for the launcher (with `--doctor` and
+/// `--winecfg` actions) and its icon in the hicolor theme.
+pub fn install_desktop_entry(
+    request: &DesktopEntryInstallRequest,
+) -> Result<DesktopEntryInstallResult, LuthierError> {
+    application::desktop_integration::install_desktop_entry(
+        request,
+        &LocalExportFileSystemAdapter,
+        &LocalIconScalerAdapter,
+    )
+}
+
+pub fn uninstall_desktop_entry(
+    request: &DesktopEntryUninstallRequest,
+) -> Result<DesktopEntryUninstallResult, LuthierError> {
+    application::desktop_integration::uninstall_desktop_entry(
+        request,
+        &LocalExportFileSystemAdapter,
+    )
+}
+
+/// `$XDG_DATA_HOME`, falling back to `~/.local/share`.
+pub fn xdg_data_home() -> Option<PathBuf> {
+    infrastructure::xdg::data_home()
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -229,6 +375,7 @@ mod tests {
                 mangohud: FeatureState::OptionalOff,
                 prime_offload: FeatureState::OptionalOff,
                 custom_vars: HashMap::new(),
+                env_edits: Vec::new(),
             },
             compatibility: CompatibilityConfig {
                 wine_wayland: FeatureState::OptionalOff,
@@ -294,9 +441,13 @@ mod tests {
             folder_mounts: vec![],
             splash: SplashConfig::default(),
             scripts: ScriptsConfig {
-                pre_launch: String::new(),
-                post_launch: String::new(),
+                pre_launch: Vec::new(),
+                post_launch: Vec::new(),
             },
+            profiles: Vec::new(),
+            rules: Vec::new(),
+            launch_entries: Vec::new(),
+            default_entry: None,
         }
     }
 
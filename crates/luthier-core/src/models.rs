@@ -3,6 +3,8 @@ use std::path::PathBuf;
 use luthier_orchestrator_core::GameConfig;
 use serde::{Deserialize, Serialize};
 
+use crate::ConfigValidationIssue;
+
 #[derive(Debug, Clone)]
 pub struct CreateOrchestratorRequest {
     pub base_binary_path: PathBuf,
@@ -12,6 +14,8 @@ pub struct CreateOrchestratorRequest {
     pub icon_png_bytes: Option<Vec<u8>>,
     pub backup_existing: bool,
     pub make_executable: bool,
+    /// Ed25519 key file used to sign the embedded manifest.
+    pub signing_key_path: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,3 +24,72 @@ pub struct CreateOrchestratorResult {
     pub config_size_bytes: usize,
     pub config_sha256_hex: String,
 }
+
+/// Config with every template it extends merged in.
+#[derive(Debug, Clone)]
+pub struct ResolvedConfigDocument {
+    pub document: serde_json::Value,
+    /// Files that contributed to `document`, in merge order; the payload itself is last.
+    pub layers: Vec<PathBuf>,
+}
+
+/// Config produced from another launcher's game definition.
+#[derive(Debug, Clone)]
+pub struct ImportedGameConfig {
+    pub config: GameConfig,
+    /// Source settings that were dropped or approximated, followed by any
+    /// validation issues in the generated config.
+    pub warnings: Vec<ConfigValidationIssue>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SteamShortcutExportRequest {
+    /// Absolute path of the generated launcher; its directory is the game root.
+    pub launcher_path: PathBuf,
+    /// `userdata/<account id>` inside the Steam installation.
+    pub steam_user_dir: PathBuf,
+    /// Shortcut title; defaults to the game name embedded in the launcher.
+    pub app_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SteamShortcutExportResult {
+    pub app_id: u32,
+    pub app_name: String,
+    pub shortcuts_path: String,
+    /// False when an existing shortcut for the launcher was updated.
+    pub created: bool,
+    pub artwork_paths: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DesktopEntryInstallRequest {
+    /// Absolute path of the generated launcher; its directory is the game root.
+    pub launcher_path: PathBuf,
+    /// `$XDG_DATA_HOME`; entries go to `applications`, icons to `icons/hicolor`.
+    pub data_home: PathBuf,
+    /// Menu title; defaults to the game name embedded in the launcher.
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesktopEntryInstallResult {
+    pub desktop_id: String,
+    pub desktop_file: String,
+    pub icon_files: Vec<String>,
+    /// False when the launcher's existing entry was rewritten.
+    pub created: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct DesktopEntryUninstallRequest {
+    pub launcher_path: PathBuf,
+    pub data_home: PathBuf,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesktopEntryUninstallResult {
+    /// None when no entry was installed for the launcher.
+    pub desktop_id: Option<String>,
+    pub removed_files: Vec<String>,
+}
@@ -1,13 +1,26 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
 use crate::error::OrchestratorError;
+use crate::signing::{sign_manifest, ManifestSignature, PayloadSigningKey};
 
 pub const CONTAINER_MAGIC: &[u8] = b"GOASv2";
 const MANIFEST_LEN_BYTES: usize = 8;
 const SHA256_BYTES: usize = 32;
-const FOOTER_BYTES: usize = CONTAINER_MAGIC.len() + MANIFEST_LEN_BYTES + SHA256_BYTES;
+pub(crate) const FOOTER_BYTES: usize = CONTAINER_MAGIC.len() + MANIFEST_LEN_BYTES + SHA256_BYTES;
 const CONTAINER_VERSION: u32 = 2;
+const MAX_ASSET_NAME_LEN: usize = 128;
+const ZSTD_LEVEL: i32 = 12;
+/// Entries smaller than this are stored as-is; the zstd frame overhead eats the gain.
+const MIN_COMPRESSIBLE_BYTES: usize = 256;
+/// Upper bound accepted for `decompressed_len`, guarding against decompression bombs.
+const MAX_DECOMPRESSED_ASSET_BYTES: u64 = 256 * 1024 * 1024;
+
+/// MIME type reported for named entries written without one.
+pub const DEFAULT_NAMED_ASSET_MIME_TYPE: &str = "application/octet-stream";
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -15,6 +28,8 @@ pub enum AssetType {
     ConfigJson,
     HeroImage,
     IconPng,
+    /// Free-form slot identified by the entry `name` instead of its type.
+    Named,
 }
 
 impl AssetType {
@@ -23,10 +38,34 @@ impl AssetType {
             Self::ConfigJson => "config_json",
             Self::HeroImage => "hero_image",
             Self::IconPng => "icon_png",
+            Self::Named => "named",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetCompression {
+    #[default]
+    None,
+    Zstd,
+}
+
+impl AssetCompression {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Zstd => "zstd",
         }
     }
+
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
 }
 
+/// `offset`, `len` and `sha256_hex` always describe the bytes stored in the binary.
+/// Compressed entries additionally record the size and hash of the original bytes.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AssetManifestEntry {
     #[serde(rename = "type")]
@@ -34,12 +73,66 @@ pub struct AssetManifestEntry {
     pub offset: u64,
     pub len: u64,
     pub sha256_hex: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mime_type: Option<String>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub flags: u32,
+    #[serde(default, skip_serializing_if = "AssetCompression::is_none")]
+    pub compression: AssetCompression,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decompressed_len: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decompressed_sha256_hex: Option<String>,
+}
+
+impl AssetManifestEntry {
+    /// Human-readable identifier used in errors and listings.
+    pub fn label(&self) -> String {
+        match (self.asset_type, self.name.as_deref()) {
+            (AssetType::Named, Some(name)) => format!("named:{name}"),
+            (asset_type, _) => asset_type.as_str().to_string(),
+        }
+    }
+
+    /// MIME type of a named entry, falling back to the generic binary type.
+    pub fn named_mime_type(&self) -> &str {
+        self.mime_type
+            .as_deref()
+            .unwrap_or(DEFAULT_NAMED_ASSET_MIME_TYPE)
+    }
+}
+
+/// MIME type stored for a named asset: trimmed, with an empty value replaced
+/// by the generic binary type.
+pub(crate) fn normalize_named_mime_type(mime_type: &str) -> &str {
+    let mime_type = mime_type.trim();
+    if mime_type.is_empty() {
+        DEFAULT_NAMED_ASSET_MIME_TYPE
+    } else {
+        mime_type
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AssetManifest {
     pub version: u32,
     pub entries: Vec<AssetManifestEntry>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<ManifestSignature>,
+}
+
+/// Extra asset stored under a caller-chosen name.
+///
+/// `flags` is an opaque bit set; the container stores it verbatim and leaves its
+/// meaning to whoever consumes the asset.
+#[derive(Debug, Clone, Copy)]
+pub struct NamedAssetWriteInput<'a> {
+    pub name: &'a str,
+    pub mime_type: &'a str,
+    pub flags: u32,
+    pub bytes: &'a [u8],
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -47,38 +140,160 @@ pub struct AssetContainerWriteInput<'a> {
     pub config_json: &'a [u8],
     pub hero_image: Option<&'a [u8]>,
     pub icon_png: Option<&'a [u8]>,
+    pub named_assets: &'a [NamedAssetWriteInput<'a>],
+    pub signing_key: Option<&'a PayloadSigningKey>,
+}
+
+/// Asset bytes as stored in the container, decoded only when requested.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedAsset<'a> {
+    stored: &'a [u8],
+    compression: AssetCompression,
+    decompressed_len: u64,
+    decompressed_sha256: Option<[u8; SHA256_BYTES]>,
+}
+
+impl<'a> EncodedAsset<'a> {
+    pub fn stored_bytes(&self) -> &'a [u8] {
+        self.stored
+    }
+
+    pub fn compression(&self) -> AssetCompression {
+        self.compression
+    }
+
+    /// Returns the original bytes, decompressing and re-hashing them if needed.
+    pub fn decode(&self) -> Result<Cow<'a, [u8]>, OrchestratorError> {
+        match self.compression {
+            AssetCompression::None => Ok(Cow::Borrowed(self.stored)),
+            AssetCompression::Zstd => {
+                let capacity = usize::try_from(self.decompressed_len)
+                    .map_err(|_| OrchestratorError::InvalidLength)?;
+                let decoded = zstd::bulk::decompress(self.stored, capacity)
+                    .map_err(|err| OrchestratorError::AssetDecodeFailed(err.to_string()))?;
+                if decoded.len() != capacity {
+                    return Err(OrchestratorError::InvalidLength);
+                }
+                if self.decompressed_sha256 != Some(sha256(&decoded)) {
+                    return Err(OrchestratorError::InvalidChecksum);
+                }
+                Ok(Cow::Owned(decoded))
+            }
+        }
+    }
+
+    pub(crate) fn from_entry(
+        entry: &AssetManifestEntry,
+        stored: &'a [u8],
+    ) -> Result<Self, OrchestratorError> {
+        match entry.compression {
+            AssetCompression::None => Ok(Self {
+                stored,
+                compression: AssetCompression::None,
+                decompressed_len: entry.len,
+                decompressed_sha256: None,
+            }),
+            AssetCompression::Zstd => {
+                let (Some(decompressed_len), Some(decompressed_sha256_hex)) = (
+                    entry.decompressed_len,
+                    entry.decompressed_sha256_hex.as_deref(),
+                ) else {
+                    return Err(OrchestratorError::InvalidManifest(format!(
+                        "compressed entry {} must declare decompressed_len and decompressed_sha256_hex",
+                        entry.label()
+                    )));
+                };
+                if decompressed_len == 0 || decompressed_len > MAX_DECOMPRESSED_ASSET_BYTES {
+                    return Err(OrchestratorError::InvalidLength);
+                }
+                Ok(Self {
+                    stored,
+                    compression: AssetCompression::Zstd,
+                    decompressed_len,
+                    decompressed_sha256: Some(parse_sha256_hex(decompressed_sha256_hex)?),
+                })
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedAsset<'a> {
+    pub name: String,
+    pub mime_type: String,
+    pub flags: u32,
+    pub data: EncodedAsset<'a>,
+}
+
+impl<'a> NamedAsset<'a> {
+    pub fn bytes(&self) -> Result<Cow<'a, [u8]>, OrchestratorError> {
+        self.data.decode()
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ParsedAssetContainer<'a> {
     pub manifest: AssetManifest,
-    config_json: &'a [u8],
-    hero_image: Option<&'a [u8]>,
-    icon_png: Option<&'a [u8]>,
+    config_json: EncodedAsset<'a>,
+    hero_image: Option<EncodedAsset<'a>>,
+    icon_png: Option<EncodedAsset<'a>>,
+    named_assets: Vec<NamedAsset<'a>>,
 }
 
 impl<'a> ParsedAssetContainer<'a> {
-    pub fn config_json(&self) -> &'a [u8] {
-        self.config_json
+    pub fn config_json(&self) -> Result<Cow<'a, [u8]>, OrchestratorError> {
+        self.config_json.decode()
     }
 
-    pub fn hero_image(&self) -> Option<&'a [u8]> {
+    pub fn hero_image(&self) -> Result<Option<Cow<'a, [u8]>>, OrchestratorError> {
         self.hero_image
+            .as_ref()
+            .map(EncodedAsset::decode)
+            .transpose()
     }
 
-    pub fn icon_png(&self) -> Option<&'a [u8]> {
-        self.icon_png
+    pub fn icon_png(&self) -> Result<Option<Cow<'a, [u8]>>, OrchestratorError> {
+        self.icon_png.as_ref().map(EncodedAsset::decode).transpose()
     }
 
-    pub fn get(&self, asset_type: AssetType) -> Option<&'a [u8]> {
-        match asset_type {
-            AssetType::ConfigJson => Some(self.config_json),
-            AssetType::HeroImage => self.hero_image,
-            AssetType::IconPng => self.icon_png,
-        }
+    pub fn named_assets(&self) -> &[NamedAsset<'a>] {
+        &self.named_assets
+    }
+
+    pub fn named_asset(&self, name: &str) -> Option<&NamedAsset<'a>> {
+        self.named_assets.iter().find(|asset| asset.name == name)
+    }
+
+    /// Returns the single asset stored for a built-in type, decompressed on demand.
+    /// Named entries are addressed through [`Self::named_asset`] since several may coexist.
+    pub fn get(&self, asset_type: AssetType) -> Result<Option<Cow<'a, [u8]>>, OrchestratorError> {
+        let encoded = match asset_type {
+            AssetType::ConfigJson => Some(&self.config_json),
+            AssetType::HeroImage => self.hero_image.as_ref(),
+            AssetType::IconPng => self.icon_png.as_ref(),
+            AssetType::Named => None,
+        };
+        encoded.map(EncodedAsset::decode).transpose()
     }
 }
 
+/// Checks that a named asset identifier is safe to store and to use as a file name.
+pub fn validate_asset_name(name: &str) -> Result<(), OrchestratorError> {
+    if name.is_empty() || name.len() > MAX_ASSET_NAME_LEN {
+        return Err(OrchestratorError::InvalidAssetName(name.to_string()));
+    }
+    if name.starts_with('.') {
+        return Err(OrchestratorError::InvalidAssetName(name.to_string()));
+    }
+    let valid = name
+        .chars()
+        .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'));
+    if !valid {
+        return Err(OrchestratorError::InvalidAssetName(name.to_string()));
+    }
+    Ok(())
+}
+
 pub fn append_asset_container(
     base_binary: &[u8],
     input: AssetContainerWriteInput<'_>,
@@ -92,7 +307,7 @@ pub fn append_asset_container(
     let mut out = Vec::new();
     out.extend_from_slice(base_binary);
 
-    let mut entries = Vec::with_capacity(3);
+    let mut entries = Vec::with_capacity(3 + input.named_assets.len());
     append_asset(
         &mut out,
         &mut entries,
@@ -114,10 +329,35 @@ pub fn append_asset_container(
         append_asset(&mut out, &mut entries, AssetType::IconPng, bytes)?;
     }
 
-    let manifest = AssetManifest {
+    let mut seen_names = HashSet::new();
+    for named in input.named_assets {
+        validate_asset_name(named.name)?;
+        if !seen_names.insert(named.name) {
+            return Err(OrchestratorError::DuplicateAssetType(format!(
+                "named:{}",
+                named.name
+            )));
+        }
+        if named.bytes.is_empty() {
+            return Err(OrchestratorError::InvalidLength);
+        }
+
+        append_asset(&mut out, &mut entries, AssetType::Named, named.bytes)?;
+        if let Some(entry) = entries.last_mut() {
+            entry.name = Some(named.name.to_string());
+            entry.mime_type = Some(normalize_named_mime_type(named.mime_type).to_string());
+            entry.flags = named.flags;
+        }
+    }
+
+    let mut manifest = AssetManifest {
         version: CONTAINER_VERSION,
         entries,
+        signature: None,
     };
+    if let Some(key) = input.signing_key {
+        sign_manifest(&mut manifest, key)?;
+    }
     let manifest_bytes = serde_json::to_vec(&manifest)?;
     let manifest_len =
         u64::try_from(manifest_bytes.len()).map_err(|_| OrchestratorError::InvalidLength)?;
@@ -140,29 +380,108 @@ pub fn parse_asset_container(binary: &[u8]) -> Result<ParsedAssetContainer<'_>,
         .len()
         .checked_sub(FOOTER_BYTES)
         .ok_or(OrchestratorError::ContainerTruncated)?;
-    let magic_start = footer_start;
-    let magic_end = magic_start + CONTAINER_MAGIC.len();
-    if &binary[magic_start..magic_end] != CONTAINER_MAGIC {
+    let total_len = u64::try_from(binary.len()).map_err(|_| OrchestratorError::InvalidLength)?;
+    let footer = parse_container_footer(&binary[footer_start..], total_len)?;
+    let manifest_start =
+        usize::try_from(footer.manifest_start).map_err(|_| OrchestratorError::InvalidLength)?;
+    let manifest = parse_container_manifest(&binary[manifest_start..footer_start], &footer)?;
+    validate_manifest_entries(&manifest, footer.manifest_start)?;
+
+    let mut config_json: Option<EncodedAsset<'_>> = None;
+    let mut hero_image: Option<EncodedAsset<'_>> = None;
+    let mut icon_png: Option<EncodedAsset<'_>> = None;
+    let mut named_assets = Vec::new();
+
+    for entry in &manifest.entries {
+        let (asset_offset, asset_len) = entry_range(entry)?;
+        let asset_bytes = &binary[asset_offset..asset_offset + asset_len];
+        verify_stored_checksum(entry, asset_bytes)?;
+        let encoded = EncodedAsset::from_entry(entry, asset_bytes)?;
+
+        match entry.asset_type {
+            AssetType::ConfigJson => {
+                config_json = Some(encoded);
+            }
+            AssetType::HeroImage => {
+                hero_image = Some(encoded);
+            }
+            AssetType::IconPng => {
+                icon_png = Some(encoded);
+            }
+            AssetType::Named => {
+                named_assets.push(NamedAsset {
+                    name: entry.name.clone().unwrap_or_default(),
+                    mime_type: entry.named_mime_type().to_string(),
+                    flags: entry.flags,
+                    data: encoded,
+                });
+            }
+        }
+    }
+
+    let config_json = config_json.ok_or_else(|| {
+        OrchestratorError::MissingRequiredAsset(AssetType::ConfigJson.as_str().to_string())
+    })?;
+
+    Ok(ParsedAssetContainer {
+        manifest,
+        config_json,
+        hero_image,
+        icon_png,
+        named_assets,
+    })
+}
+
+/// Location of the manifest, as declared by the trailing footer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ContainerFooter {
+    pub(crate) manifest_start: u64,
+    pub(crate) manifest_len: u64,
+    manifest_sha256: [u8; SHA256_BYTES],
+}
+
+/// Parses the last [`FOOTER_BYTES`] of a container whose total size is `total_len`.
+pub(crate) fn parse_container_footer(
+    footer: &[u8],
+    total_len: u64,
+) -> Result<ContainerFooter, OrchestratorError> {
+    if footer.len() != FOOTER_BYTES {
+        return Err(OrchestratorError::ContainerTruncated);
+    }
+
+    let magic_end = CONTAINER_MAGIC.len();
+    if &footer[..magic_end] != CONTAINER_MAGIC {
         return Err(OrchestratorError::ContainerNotFound);
     }
 
-    let manifest_len_start = magic_end;
-    let manifest_len_end = manifest_len_start + MANIFEST_LEN_BYTES;
+    let manifest_len_end = magic_end + MANIFEST_LEN_BYTES;
     let mut manifest_len_bytes = [0_u8; MANIFEST_LEN_BYTES];
-    manifest_len_bytes.copy_from_slice(&binary[manifest_len_start..manifest_len_end]);
-    let manifest_len_u64 = u64::from_le_bytes(manifest_len_bytes);
-    let manifest_len =
-        usize::try_from(manifest_len_u64).map_err(|_| OrchestratorError::InvalidLength)?;
-    if manifest_len > footer_start {
-        return Err(OrchestratorError::InvalidLength);
-    }
+    manifest_len_bytes.copy_from_slice(&footer[magic_end..manifest_len_end]);
+    let manifest_len = u64::from_le_bytes(manifest_len_bytes);
 
-    let manifest_start = footer_start - manifest_len;
-    let manifest_bytes = &binary[manifest_start..footer_start];
+    let footer_start = total_len
+        .checked_sub(FOOTER_BYTES as u64)
+        .ok_or(OrchestratorError::ContainerTruncated)?;
+    let manifest_start = footer_start
+        .checked_sub(manifest_len)
+        .ok_or(OrchestratorError::InvalidLength)?;
+
+    let mut manifest_sha256 = [0_u8; SHA256_BYTES];
+    manifest_sha256.copy_from_slice(&footer[manifest_len_end..]);
+
+    Ok(ContainerFooter {
+        manifest_start,
+        manifest_len,
+        manifest_sha256,
+    })
+}
 
-    let expected_manifest_checksum = &binary[manifest_len_end..manifest_len_end + SHA256_BYTES];
-    let actual_manifest_checksum = sha256(manifest_bytes);
-    if actual_manifest_checksum != expected_manifest_checksum {
+/// Checks the manifest bytes against the footer checksum and decodes them.
+pub(crate) fn parse_container_manifest(
+    manifest_bytes: &[u8],
+    footer: &ContainerFooter,
+) -> Result<AssetManifest, OrchestratorError> {
+    if sha256(manifest_bytes) != footer.manifest_sha256 {
         return Err(OrchestratorError::InvalidChecksum);
     }
 
@@ -172,90 +491,71 @@ pub fn parse_asset_container(binary: &[u8]) -> Result<ParsedAssetContainer<'_>,
     if manifest.version != CONTAINER_VERSION {
         return Err(OrchestratorError::InvalidManifestVersion(manifest.version));
     }
+    Ok(manifest)
+}
 
+/// Structural checks that need no asset bytes: uniqueness, names, bounds,
+/// compression metadata and the required `config_json` entry.
+pub(crate) fn validate_manifest_entries(
+    manifest: &AssetManifest,
+    manifest_start: u64,
+) -> Result<(), OrchestratorError> {
     let mut seen_config = false;
     let mut seen_hero = false;
     let mut seen_icon = false;
-    let mut config_json: Option<&[u8]> = None;
-    let mut hero_image: Option<&[u8]> = None;
-    let mut icon_png: Option<&[u8]> = None;
+    let mut seen_names = HashSet::new();
 
     for entry in &manifest.entries {
         let already_seen = match entry.asset_type {
-            AssetType::ConfigJson => {
-                if seen_config {
-                    true
-                } else {
-                    seen_config = true;
-                    false
-                }
-            }
-            AssetType::HeroImage => {
-                if seen_hero {
-                    true
-                } else {
-                    seen_hero = true;
-                    false
-                }
-            }
-            AssetType::IconPng => {
-                if seen_icon {
-                    true
-                } else {
-                    seen_icon = true;
-                    false
-                }
+            AssetType::ConfigJson => std::mem::replace(&mut seen_config, true),
+            AssetType::HeroImage => std::mem::replace(&mut seen_hero, true),
+            AssetType::IconPng => std::mem::replace(&mut seen_icon, true),
+            AssetType::Named => {
+                let name = entry.name.as_deref().ok_or_else(|| {
+                    OrchestratorError::InvalidManifest("named asset entry has no name".to_string())
+                })?;
+                validate_asset_name(name)?;
+                !seen_names.insert(name)
             }
         };
         if already_seen {
-            return Err(OrchestratorError::DuplicateAssetType(
-                entry.asset_type.as_str().to_string(),
-            ));
+            return Err(OrchestratorError::DuplicateAssetType(entry.label()));
         }
 
-        let asset_offset =
-            usize::try_from(entry.offset).map_err(|_| OrchestratorError::InvalidLength)?;
-        let asset_len = usize::try_from(entry.len).map_err(|_| OrchestratorError::InvalidLength)?;
-        let asset_end = asset_offset
-            .checked_add(asset_len)
+        let asset_end = entry
+            .offset
+            .checked_add(entry.len)
             .ok_or(OrchestratorError::InvalidLength)?;
-
         if asset_end > manifest_start {
-            return Err(OrchestratorError::AssetOutOfBounds(
-                entry.asset_type.as_str().to_string(),
-            ));
+            return Err(OrchestratorError::AssetOutOfBounds(entry.label()));
         }
 
-        let asset_bytes = &binary[asset_offset..asset_end];
-        let expected_asset_checksum = parse_sha256_hex(&entry.sha256_hex)?;
-        let actual_asset_checksum = sha256(asset_bytes);
-        if expected_asset_checksum != actual_asset_checksum {
-            return Err(OrchestratorError::InvalidChecksum);
-        }
+        parse_sha256_hex(&entry.sha256_hex)?;
+        EncodedAsset::from_entry(entry, &[])?;
+    }
 
-        match entry.asset_type {
-            AssetType::ConfigJson => {
-                config_json = Some(asset_bytes);
-            }
-            AssetType::HeroImage => {
-                hero_image = Some(asset_bytes);
-            }
-            AssetType::IconPng => {
-                icon_png = Some(asset_bytes);
-            }
-        }
+    if !seen_config {
+        return Err(OrchestratorError::MissingRequiredAsset(
+            AssetType::ConfigJson.as_str().to_string(),
+        ));
     }
+    Ok(())
+}
 
-    let config_json = config_json.ok_or_else(|| {
-        OrchestratorError::MissingRequiredAsset(AssetType::ConfigJson.as_str().to_string())
-    })?;
+pub(crate) fn entry_range(entry: &AssetManifestEntry) -> Result<(usize, usize), OrchestratorError> {
+    let offset = usize::try_from(entry.offset).map_err(|_| OrchestratorError::InvalidLength)?;
+    let len = usize::try_from(entry.len).map_err(|_| OrchestratorError::InvalidLength)?;
+    Ok((offset, len))
+}
 
-    Ok(ParsedAssetContainer {
-        manifest,
-        config_json,
-        hero_image,
-        icon_png,
-    })
+pub(crate) fn verify_stored_checksum(
+    entry: &AssetManifestEntry,
+    stored: &[u8],
+) -> Result<(), OrchestratorError> {
+    if parse_sha256_hex(&entry.sha256_hex)? != sha256(stored) {
+        return Err(OrchestratorError::InvalidChecksum);
+    }
+    Ok(())
 }
 
 fn append_asset(
@@ -265,17 +565,56 @@ fn append_asset(
     bytes: &[u8],
 ) -> Result<(), OrchestratorError> {
     let offset = u64::try_from(out.len()).map_err(|_| OrchestratorError::InvalidLength)?;
-    let len = u64::try_from(bytes.len()).map_err(|_| OrchestratorError::InvalidLength)?;
-    out.extend_from_slice(bytes);
+    let compressed = compress_if_worthwhile(bytes)?;
+    let stored = compressed.as_deref().unwrap_or(bytes);
+    let len = u64::try_from(stored.len()).map_err(|_| OrchestratorError::InvalidLength)?;
+    out.extend_from_slice(stored);
+
+    let (compression, decompressed_len, decompressed_sha256_hex) = if compressed.is_some() {
+        (
+            AssetCompression::Zstd,
+            Some(u64::try_from(bytes.len()).map_err(|_| OrchestratorError::InvalidLength)?),
+            Some(to_lower_hex(&sha256(bytes))),
+        )
+    } else {
+        (AssetCompression::None, None, None)
+    };
+
     entries.push(AssetManifestEntry {
         asset_type,
         offset,
         len,
-        sha256_hex: to_lower_hex(&sha256(bytes)),
+        sha256_hex: to_lower_hex(&sha256(stored)),
+        name: None,
+        mime_type: None,
+        flags: 0,
+        compression,
+        decompressed_len,
+        decompressed_sha256_hex,
     });
     Ok(())
 }
 
+/// Compresses with zstd and keeps the result only when it saves at least 5%.
+/// Already-compressed formats (PNG, JPEG, WebP) usually fall below that bar.
+fn compress_if_worthwhile(bytes: &[u8]) -> Result<Option<Vec<u8>>, OrchestratorError> {
+    let len = u64::try_from(bytes.len()).map_err(|_| OrchestratorError::InvalidLength)?;
+    if bytes.len() < MIN_COMPRESSIBLE_BYTES || len > MAX_DECOMPRESSED_ASSET_BYTES {
+        return Ok(None);
+    }
+
+    let compressed = zstd::bulk::compress(bytes, ZSTD_LEVEL)?;
+    if compressed.len() < bytes.len() - bytes.len() / 20 {
+        Ok(Some(compressed))
+    } else {
+        Ok(None)
+    }
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
 fn sha256(bytes: &[u8]) -> [u8; SHA256_BYTES] {
     let mut hasher = Sha256::new();
     hasher.update(bytes);
@@ -334,6 +673,8 @@ mod tests {
                 config_json: br#"{"config_version":1}"#,
                 hero_image: Some(b"hero-image-bytes"),
                 icon_png: Some(b"\x89PNG\r\n\x1a\nicon"),
+                named_assets: &[],
+                signing_key: None,
             },
         )
         .expect("container build should succeed")
@@ -379,9 +720,18 @@ mod tests {
 
         assert_eq!(parsed.manifest.version, 2);
         assert_eq!(parsed.manifest.entries.len(), 3);
-        assert_eq!(parsed.config_json(), br#"{"config_version":1}"#);
-        assert_eq!(parsed.hero_image(), Some(&b"hero-image-bytes"[..]));
-        assert_eq!(parsed.icon_png(), Some(&b"\x89PNG\r\n\x1a\nicon"[..]));
+        assert_eq!(
+            parsed.config_json().expect("config").as_ref(),
+            br#"{"config_version":1}"#
+        );
+        assert_eq!(
+            parsed.hero_image().expect("hero").as_deref(),
+            Some(&b"hero-image-bytes"[..])
+        );
+        assert_eq!(
+            parsed.icon_png().expect("icon").as_deref(),
+            Some(&b"\x89PNG\r\n\x1a\nicon"[..])
+        );
     }
 
     #[test]
@@ -392,14 +742,19 @@ mod tests {
                 config_json: br#"{"config_version":1}"#,
                 hero_image: None,
                 icon_png: None,
+                named_assets: &[],
+                signing_key: None,
             },
         )
         .expect("container build");
 
         let parsed = parse_asset_container(&binary).expect("parse");
-        assert_eq!(parsed.config_json(), br#"{"config_version":1}"#);
-        assert!(parsed.hero_image().is_none());
-        assert!(parsed.icon_png().is_none());
+        assert_eq!(
+            parsed.config_json().expect("config").as_ref(),
+            br#"{"config_version":1}"#
+        );
+        assert!(parsed.hero_image().expect("hero").is_none());
+        assert!(parsed.icon_png().expect("icon").is_none());
     }
 
     #[test]
@@ -451,4 +806,260 @@ mod tests {
         let err = parse_asset_container(&malformed).expect_err("must require config");
         assert!(matches!(err, OrchestratorError::MissingRequiredAsset(_)));
     }
+
+    #[test]
+    fn roundtrips_named_assets_with_metadata() {
+        let binary = append_asset_container(
+            b"ELF",
+            AssetContainerWriteInput {
+                config_json: br#"{"config_version":1}"#,
+                hero_image: None,
+                icon_png: None,
+                named_assets: &[
+                    NamedAssetWriteInput {
+                        name: "logo.svg",
+                        mime_type: "image/svg+xml",
+                        flags: 0b101,
+                        bytes: b"<svg/>",
+                    },
+                    NamedAssetWriteInput {
+                        name: "readme",
+                        mime_type: "",
+                        flags: 0,
+                        bytes: b"notes",
+                    },
+                ],
+                signing_key: None,
+            },
+        )
+        .expect("container build");
+
+        let parsed = parse_asset_container(&binary).expect("parse");
+        assert_eq!(parsed.named_assets().len(), 2);
+
+        let logo = parsed.named_asset("logo.svg").expect("logo asset");
+        assert_eq!(logo.mime_type, "image/svg+xml");
+        assert_eq!(logo.flags, 0b101);
+        assert_eq!(logo.bytes().expect("logo bytes").as_ref(), b"<svg/>");
+
+        let readme = parsed.named_asset("readme").expect("readme asset");
+        assert_eq!(readme.mime_type, DEFAULT_NAMED_ASSET_MIME_TYPE);
+        assert!(parsed.get(AssetType::Named).expect("get").is_none());
+    }
+
+    #[test]
+    fn parses_legacy_manifest_without_named_fields() {
+        let binary = sample_binary_with_all_assets();
+        let manifest = parse_asset_container(&binary).expect("parse").manifest;
+        let legacy_entries = manifest
+            .entries
+            .iter()
+            .map(|entry| {
+                format!(
+                    r#"{{"type":"{}","offset":{},"len":{},"sha256_hex":"{}"}}"#,
+                    entry.asset_type.as_str(),
+                    entry.offset,
+                    entry.len,
+                    entry.sha256_hex
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+        let legacy_manifest = format!(r#"{{"version":2,"entries":[{legacy_entries}]}}"#);
+        let legacy = replace_manifest_bytes(&binary, legacy_manifest.as_bytes());
+
+        let parsed = parse_asset_container(&legacy).expect("legacy manifest must parse");
+        assert_eq!(
+            parsed.hero_image().expect("hero").as_deref(),
+            Some(&b"hero-image-bytes"[..])
+        );
+        assert!(parsed.named_assets().is_empty());
+    }
+
+    #[test]
+    fn rejects_duplicate_named_asset_on_write() {
+        let named = NamedAssetWriteInput {
+            name: "extra",
+            mime_type: "text/plain",
+            flags: 0,
+            bytes: b"a",
+        };
+        let err = append_asset_container(
+            b"ELF",
+            AssetContainerWriteInput {
+                config_json: br#"{"config_version":1}"#,
+                hero_image: None,
+                icon_png: None,
+                named_assets: &[named, named],
+                signing_key: None,
+            },
+        )
+        .expect_err("duplicate names must fail");
+        assert!(matches!(err, OrchestratorError::DuplicateAssetType(_)));
+    }
+
+    #[test]
+    fn rejects_unsafe_named_asset_identifiers() {
+        for name in ["", "../escape", ".hidden", "with space"] {
+            let err = validate_asset_name(name).expect_err("name must be rejected");
+            assert!(matches!(err, OrchestratorError::InvalidAssetName(_)));
+        }
+        validate_asset_name("soundtrack-01.ogg").expect("valid name");
+    }
+
+    #[test]
+    fn rejects_named_entry_without_name() {
+        let binary = append_asset_container(
+            b"ELF",
+            AssetContainerWriteInput {
+                config_json: br#"{"config_version":1}"#,
+                hero_image: None,
+                icon_png: None,
+                named_assets: &[NamedAssetWriteInput {
+                    name: "extra",
+                    mime_type: "text/plain",
+                    flags: 0,
+                    bytes: b"a",
+                }],
+                signing_key: None,
+            },
+        )
+        .expect("container build");
+        let mut manifest = parse_asset_container(&binary).expect("parse").manifest;
+        manifest.entries[1].name = None;
+        let malformed = replace_manifest(&binary, &manifest);
+
+        let err = parse_asset_container(&malformed).expect_err("named entry needs a name");
+        assert!(matches!(err, OrchestratorError::InvalidManifest(_)));
+    }
+
+    fn compressible_config() -> Vec<u8> {
+        let mut json = String::from(r#"{"config_version":1,"notes":["#);
+        for index in 0..64 {
+            json.push_str(&format!(r#""repeated note {index}","#));
+        }
+        json.push_str(r#""end"]}"#);
+        json.into_bytes()
+    }
+
+    #[test]
+    fn compresses_large_entries_and_decodes_on_demand() {
+        let config = compressible_config();
+        let binary = append_asset_container(
+            b"ELF",
+            AssetContainerWriteInput {
+                config_json: &config,
+                hero_image: Some(b"tiny"),
+                icon_png: None,
+                named_assets: &[],
+                signing_key: None,
+            },
+        )
+        .expect("container build");
+
+        let parsed = parse_asset_container(&binary).expect("parse");
+        let config_entry = &parsed.manifest.entries[0];
+        assert_eq!(config_entry.compression, AssetCompression::Zstd);
+        assert!(config_entry.len < config.len() as u64);
+        assert_eq!(config_entry.decompressed_len, Some(config.len() as u64));
+        assert_eq!(
+            config_entry.decompressed_sha256_hex.as_deref(),
+            Some(to_lower_hex(&sha256(&config)).as_str())
+        );
+        assert_ne!(
+            config_entry.decompressed_sha256_hex.as_deref(),
+            Some(config_entry.sha256_hex.as_str())
+        );
+        assert_eq!(
+            parsed
+                .get(AssetType::ConfigJson)
+                .expect("decode")
+                .expect("config")
+                .as_ref(),
+            config.as_slice()
+        );
+
+        let hero_entry = &parsed.manifest.entries[1];
+        assert_eq!(hero_entry.compression, AssetCompression::None);
+        assert!(hero_entry.decompressed_sha256_hex.is_none());
+    }
+
+    #[test]
+    fn keeps_incompressible_entries_raw() {
+        let mut state = 0x2545_f491_u32;
+        let noise = (0..4096)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state.to_le_bytes()[0]
+            })
+            .collect::<Vec<_>>();
+        let binary = append_asset_container(
+            b"ELF",
+            AssetContainerWriteInput {
+                config_json: br#"{"config_version":1}"#,
+                hero_image: Some(&noise),
+                icon_png: None,
+                named_assets: &[],
+                signing_key: None,
+            },
+        )
+        .expect("container build");
+
+        let parsed = parse_asset_container(&binary).expect("parse");
+        assert_eq!(
+            parsed.manifest.entries[1].compression,
+            AssetCompression::None
+        );
+        assert_eq!(
+            parsed.hero_image().expect("hero").as_deref(),
+            Some(noise.as_slice())
+        );
+    }
+
+    #[test]
+    fn rejects_compressed_entry_with_wrong_decompressed_hash() {
+        let config = compressible_config();
+        let binary = append_asset_container(
+            b"ELF",
+            AssetContainerWriteInput {
+                config_json: &config,
+                hero_image: None,
+                icon_png: None,
+                named_assets: &[],
+                signing_key: None,
+            },
+        )
+        .expect("container build");
+        let mut manifest = parse_asset_container(&binary).expect("parse").manifest;
+        manifest.entries[0].decompressed_sha256_hex = Some("00".repeat(SHA256_BYTES));
+        let tampered = replace_manifest(&binary, &manifest);
+
+        let parsed = parse_asset_container(&tampered).expect("stored hash still matches");
+        let err = parsed.config_json().expect_err("decoded hash must fail");
+        assert!(matches!(err, OrchestratorError::InvalidChecksum));
+    }
+
+    #[test]
+    fn rejects_compressed_entry_without_decompressed_metadata() {
+        let config = compressible_config();
+        let binary = append_asset_container(
+            b"ELF",
+            AssetContainerWriteInput {
+                config_json: &config,
+                hero_image: None,
+                icon_png: None,
+                named_assets: &[],
+                signing_key: None,
+            },
+        )
+        .expect("container build");
+        let mut manifest = parse_asset_container(&binary).expect("parse").manifest;
+        manifest.entries[0].decompressed_len = None;
+        let malformed = replace_manifest(&binary, &manifest);
+
+        let err = parse_asset_container(&malformed).expect_err("metadata is required");
+        assert!(matches!(err, OrchestratorError::InvalidManifest(_)));
+    }
 }
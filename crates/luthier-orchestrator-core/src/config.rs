@@ -1,56 +1,234 @@
 use std::collections::HashMap;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Deserializer, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Launcher payload consumed by the orchestrator.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct GameConfig {
+    /// Payload format version. Older versions are migrated before validation.
     pub config_version: u32,
+    /// Tool or person that produced the payload.
     pub created_by: String,
+    /// Display name of the game.
     pub game_name: String,
+    /// SHA-256 of the game executable, lowercase hex.
     pub exe_hash: String,
+    /// Game executable, relative to the game root.
     pub relative_exe_path: String,
+    /// Arguments passed to the game executable; expand `${...}` variables.
     pub launch_args: Vec<String>,
     pub runner: RunnerConfig,
     pub environment: EnvConfig,
     pub compatibility: CompatibilityConfig,
     pub winecfg: WinecfgConfig,
+    /// Winetricks verbs installed in the prefix.
     pub dependencies: Vec<String>,
+    /// Host tools checked by the doctor.
     pub extra_system_dependencies: Vec<SystemDependency>,
     pub requirements: RequirementsConfig,
+    /// Registry values imported into the prefix.
     pub registry_keys: Vec<RegistryKey>,
+    /// Files, relative to the game root, that must exist before launch.
     pub integrity_files: Vec<String>,
+    /// Game folders exposed inside the prefix.
     pub folder_mounts: Vec<FolderMount>,
     #[serde(default)]
     pub splash: SplashConfig,
     pub scripts: ScriptsConfig,
+    /// Named partial overlays selectable at launch.
+    #[serde(default)]
+    pub profiles: Vec<LaunchProfile>,
+    /// Config patches applied only on matching hosts, in order.
+    #[serde(default)]
+    pub rules: Vec<HostRule>,
+    /// Extra executables run in the same prefix.
+    #[serde(default)]
+    pub launch_entries: Vec<LaunchEntry>,
+    /// Entry started when `--entry` is not given; `None` or `"main"` runs
+    /// `relative_exe_path`.
+    #[serde(default)]
+    pub default_entry: Option<String>,
+}
+
+/// Extra executable that runs in the game's prefix next to the main exe, such as
+/// a launcher, a config tool or a dedicated server. `env` is applied over
+/// `environment.custom_vars`.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct LaunchEntry {
+    /// Name used by `--entry` and the splash picker; `main` is reserved.
+    pub name: String,
+    /// Executable path relative to the game root.
+    pub relative_exe_path: String,
+    /// Arguments passed to the executable; expand `${...}` variables.
+    #[serde(default)]
+    pub launch_args: Vec<String>,
+    /// Relative to the game root; `None` starts in the game root.
+    #[serde(default)]
+    pub working_dir: Option<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+/// Named way to run the game, chosen at launch time. `overlay` is a partial
+/// `GameConfig` merged over the base config with the template merge rules.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct LaunchProfile {
+    /// Name used by `--profile` and the splash picker.
+    pub name: String,
+    /// Shown next to the profile name.
+    #[serde(default)]
+    pub description: String,
+    #[schemars(with = "serde_json::Map<String, serde_json::Value>")]
+    pub overlay: serde_json::Value,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+/// Config patch applied only on hosts matching `when`. Rules are evaluated in
+/// order after runtime overrides, so later rules win.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct HostRule {
+    /// Shown in the doctor report.
+    pub name: String,
+    #[serde(default)]
+    pub when: HostPredicate,
+    #[schemars(with = "serde_json::Map<String, serde_json::Value>")]
+    pub patch: serde_json::Value,
+}
+
+/// Every condition that is set must hold; an empty predicate always matches.
+/// List conditions match when any listed value matches, except `env_present`,
+/// which needs every listed variable to be set.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, JsonSchema)]
+pub struct HostPredicate {
+    /// Any of these session types.
+    #[serde(default)]
+    pub session_type: Vec<SessionType>,
+    /// Any GPU from one of these vendors.
+    #[serde(default)]
+    pub gpu_vendor: Vec<GpuVendor>,
+    /// Whether the host has more than one GPU.
+    #[serde(default)]
+    pub hybrid_gpu: Option<bool>,
+    /// Any of these os-release IDs, case-insensitive.
+    #[serde(default)]
+    pub os_id: Vec<String>,
+    /// Environment variables that must all be set.
+    #[serde(default)]
+    pub env_present: Vec<String>,
+    /// Bounds a connected display must fit.
+    #[serde(default)]
+    pub screen: Option<ScreenSizePredicate>,
+}
+
+/// Bounds on the size of a connected display, in pixels. Matches when any
+/// display fits all bounds that are set.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, JsonSchema)]
+pub struct ScreenSizePredicate {
+    #[serde(default)]
+    pub min_width: Option<u32>,
+    #[serde(default)]
+    pub max_width: Option<u32>,
+    #[serde(default)]
+    pub min_height: Option<u32>,
+    #[serde(default)]
+    pub max_height: Option<u32>,
+}
+
+/// Display session type.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SessionType {
+    Wayland,
+    X11,
+    Tty,
+}
+
+/// GPU vendor, from the PCI vendor id.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum GpuVendor {
+    Nvidia,
+    Amd,
+    Intel,
+}
+
+impl GpuVendor {
+    /// Maps a PCI vendor id as found in `/sys/class/drm/*/device/vendor`.
+    pub fn from_pci_vendor_id(id: &str) -> Option<Self> {
+        match id.trim().to_ascii_lowercase().as_str() {
+            "0x10de" => Some(Self::Nvidia),
+            "0x1002" | "0x1022" => Some(Self::Amd),
+            "0x8086" => Some(Self::Intel),
+            _ => None,
+        }
+    }
+}
+
+/// Splash screen options.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
 pub struct SplashConfig {
+    /// Source URL of the embedded hero image.
     #[serde(default)]
     pub hero_image_url: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Runtime selection.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct RunnerConfig {
+    /// Proton build to launch with.
     pub proton_version: String,
+    /// Update the runtime automatically.
     pub auto_update: bool,
+    /// Enable eventfd-based synchronization.
     pub esync: bool,
+    /// Enable futex-based synchronization.
     pub fsync: bool,
     pub runtime_preference: RuntimePreference,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Launch environment.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct EnvConfig {
     pub gamemode: FeatureState,
     pub gamescope: GamescopeConfig,
     pub mangohud: FeatureState,
     #[serde(deserialize_with = "deserialize_feature_state_from_bool_or_enum")]
     pub prime_offload: FeatureState,
+    /// Extra environment variables for the game process. Values expand `${...}` variables.
     pub custom_vars: HashMap<String, String>,
+    /// Applied after `custom_vars`, in order.
+    #[serde(default)]
+    pub env_edits: Vec<EnvEdit>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Appends or prepends `value` to a path-like variable such as
+/// `LD_LIBRARY_PATH`, keeping what the launch environment or host already set.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema)]
+pub struct EnvEdit {
+    /// Environment variable to edit.
+    pub name: String,
+    /// Value to add; expands `${...}` variables.
+    pub value: String,
+    pub mode: EnvEditMode,
+    /// Joins the value with the existing one.
+    #[serde(default = "default_env_separator")]
+    pub separator: String,
+}
+
+/// Where the value goes.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EnvEditMode {
+    Append,
+    Prepend,
+}
+
+fn default_env_separator() -> String {
+    ":".to_string()
+}
+
+/// Compatibility layers and wrappers.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct CompatibilityConfig {
     pub wine_wayland: FeatureState,
     pub hdr: FeatureState,
@@ -58,26 +236,34 @@ pub struct CompatibilityConfig {
     pub easy_anti_cheat_runtime: FeatureState,
     pub battleye_runtime: FeatureState,
     pub staging: FeatureState,
+    /// Commands the game is wrapped in, outermost first.
     pub wrapper_commands: Vec<WrapperCommand>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Prefix settings applied through winecfg and the registry.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct WinecfgConfig {
+    /// Windows version reported by Wine.
     #[serde(default)]
     pub windows_version: Option<String>,
+    /// Wine DLL overrides.
     pub dll_overrides: Vec<DllOverrideRule>,
     pub auto_capture_mouse: WinecfgFeaturePolicy,
     pub window_decorations: WinecfgFeaturePolicy,
     pub window_manager_control: WinecfgFeaturePolicy,
     pub virtual_desktop: VirtualDesktopConfig,
+    /// Screen DPI written to the prefix.
     #[serde(default)]
     pub screen_dpi: Option<u16>,
     pub desktop_integration: WinecfgFeaturePolicy,
     #[serde(default = "default_winecfg_feature_policy_optional_off")]
     pub mime_associations: WinecfgFeaturePolicy,
+    /// Shell folders redirected to host paths.
     #[serde(default)]
     pub desktop_folders: Vec<WineDesktopFolderMapping>,
+    /// Extra Wine drives.
     pub drives: Vec<WineDriveMapping>,
+    /// Wine audio driver.
     pub audio_driver: Option<String>,
 }
 
@@ -88,7 +274,8 @@ pub struct AppSettings {
     pub telemetry_opt_in: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+/// Mandatory states are enforced; optional states can be toggled by the player.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
 pub enum FeatureState {
     MandatoryOn,
     MandatoryOff,
@@ -108,9 +295,11 @@ impl FeatureState {
     }
 }
 
-#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+/// Wine setting, optionally left at Wine's own default.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
 pub struct WinecfgFeaturePolicy {
     pub state: FeatureState,
+    /// Leave the setting untouched in the prefix.
     pub use_wine_default: bool,
 }
 
@@ -161,21 +350,24 @@ impl<'de> Deserialize<'de> for WinecfgFeaturePolicy {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+/// Preferred runtime family.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
 pub enum RuntimePreference {
     Auto,
     Proton,
     Wine,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+/// Runtime the orchestrator may launch with.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, JsonSchema)]
 pub enum RuntimeCandidate {
     ProtonUmu,
     ProtonNative,
     Wine,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Host requirements.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct RequirementsConfig {
     pub runtime: RuntimePolicy,
     pub umu: FeatureState,
@@ -186,38 +378,55 @@ pub struct RequirementsConfig {
     pub steam_runtime: FeatureState,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Runtime selection order.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct RuntimePolicy {
+    /// Fail instead of falling back.
     pub strict: bool,
     pub primary: RuntimeCandidate,
+    /// Runtimes tried after the primary.
     pub fallback_order: Vec<RuntimeCandidate>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Gamescope compositor options.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct GamescopeConfig {
     pub state: FeatureState,
+    /// Legacy `WIDTHxHEIGHT` output resolution.
     pub resolution: Option<String>,
+    /// Enable FSR upscaling.
     pub fsr: bool,
+    /// Game render width.
     #[serde(default)]
     pub game_width: String,
+    /// Game render height.
     #[serde(default)]
     pub game_height: String,
+    /// Output width.
     #[serde(default)]
     pub output_width: String,
+    /// Output height.
     #[serde(default)]
     pub output_height: String,
+    /// Gamescope upscaler.
     #[serde(default = "default_gamescope_upscale_method")]
     pub upscale_method: String,
+    /// Gamescope window mode.
     #[serde(default = "default_gamescope_window_type")]
     pub window_type: String,
+    /// Enable the frame limiter.
     #[serde(default)]
     pub enable_limiter: bool,
+    /// Frame limit (1-1000).
     #[serde(default)]
     pub fps_limiter: String,
+    /// Frame limit while unfocused (1-1000).
     #[serde(default)]
     pub fps_limiter_no_focus: String,
+    /// Force gamescope to grab the cursor.
     #[serde(default)]
     pub force_grab_cursor: bool,
+    /// Extra gamescope arguments.
     #[serde(default)]
     pub additional_options: String,
 }
@@ -230,74 +439,160 @@ fn default_gamescope_window_type() -> String {
     "fullscreen".to_string()
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Command prepended to the launch command.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct WrapperCommand {
     pub state: FeatureState,
+    /// Wrapper executable.
     pub executable: String,
+    /// Wrapper arguments, shell-split; each argument expands `${...}` variables.
     pub args: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Wine DLL override.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct DllOverrideRule {
+    /// DLL name without extension.
     pub dll: String,
+    /// Override mode, e.g. `native,builtin`.
     pub mode: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Wine virtual desktop.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct VirtualDesktopConfig {
     pub state: WinecfgFeaturePolicy,
+    /// Desktop size as `WIDTHxHEIGHT`.
     pub resolution: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Wine drive letter mapping.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct WineDriveMapping {
+    /// Drive letter.
     pub letter: String,
+    /// Drive root, relative to the game root.
     pub source_relative_path: String,
     pub state: FeatureState,
+    /// Absolute host path.
     #[serde(default)]
     pub host_path: Option<String>,
+    /// Wine drive type.
     #[serde(default)]
     pub drive_type: Option<String>,
+    /// Volume label.
     #[serde(default)]
     pub label: Option<String>,
+    /// Volume serial.
     #[serde(default)]
     pub serial: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Shell folder redirection.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct WineDesktopFolderMapping {
+    /// Shell folder key.
     pub folder_key: String,
+    /// Name of the folder in the prefix.
     pub shortcut_name: String,
+    /// Host directory.
     pub linux_path: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Registry value imported into the prefix.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct RegistryKey {
+    /// Key path, e.g. `HKCU\Software\Game`.
     pub path: String,
+    /// Value name.
     pub name: String,
+    /// Value type, e.g. `REG_SZ`.
     pub value_type: String,
+    /// Value data.
     pub value: String,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Steps run in order before the game starts and after it exits.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, JsonSchema)]
 pub struct ScriptsConfig {
-    pub pre_launch: String,
-    pub post_launch: String,
+    /// Steps run before the game starts.
+    #[serde(default)]
+    pub pre_launch: Vec<ScriptStep>,
+    /// Steps run after the game exits.
+    #[serde(default)]
+    pub post_launch: Vec<ScriptStep>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct ScriptStep {
+    /// Unique across both phases; names the `GO-SC-*` events and the run-once
+    /// marker.
+    pub name: String,
+    #[serde(default)]
+    pub interpreter: ScriptInterpreter,
+    /// Inline script for `bash`/`sh`. For `prefix`, the Windows program: a
+    /// `./` path is resolved against the game root, anything else (`regedit`,
+    /// `${DRIVE_C}/...`) is handed to the runtime as is.
+    pub command: String,
+    /// Passed as `$1...` to shell scripts and as arguments to prefix programs.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Relative to the game root; defaults to the game root.
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Seconds before the step is killed.
+    #[serde(default = "default_script_timeout_secs")]
+    #[schemars(range(min = 1))]
+    pub timeout_secs: u64,
+    /// A failed mandatory step skips the rest of its phase; before launch it
+    /// also aborts the game.
+    #[serde(default)]
+    pub mandatory: bool,
+    /// Skipped once it has succeeded for the current prefix.
+    #[serde(default)]
+    pub run_once: bool,
+    /// Pre-launch only: started without waiting and killed when the game exits.
+    #[serde(default)]
+    pub background: bool,
+}
+
+/// `bash`/`sh` run inline scripts; `prefix` runs a program with Wine/Proton.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ScriptInterpreter {
+    #[default]
+    Bash,
+    Sh,
+    /// Runs `command` inside the prefix with the selected Wine/Proton runtime.
+    Prefix,
+}
+
+fn default_script_timeout_secs() -> u64 {
+    600
+}
+
+/// Game folder mounted into the prefix.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct FolderMount {
+    /// Source folder, relative to the game root.
     pub source_relative_path: String,
+    /// Windows path inside the prefix.
     pub target_windows_path: String,
+    /// Create the source folder when absent.
     pub create_source_if_missing: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+/// Host dependency checked before launch.
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
 pub struct SystemDependency {
+    /// Dependency name.
     pub name: String,
     pub state: FeatureState,
+    /// Commands looked up on PATH.
     pub check_commands: Vec<String>,
+    /// Environment variables that must be set.
     pub check_env_vars: Vec<String>,
+    /// Paths that must exist.
     pub check_paths: Vec<String>,
 }
 
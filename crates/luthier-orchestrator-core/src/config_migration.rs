@@ -0,0 +1,561 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::config::GameConfig;
+use crate::error::OrchestratorError;
+
+/// Version written by this build. Each older version has exactly one JSON
+/// registered transform to the next one.
+pub const CURRENT_CONFIG_VERSION: u32 = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigMigrationChange {
+    /// Version produced by the migration step that made this change.
+    pub version: u32,
+    pub field: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigMigrationReport {
+    pub from_version: u32,
+    pub to_version: u32,
+    pub changes: Vec<ConfigMigrationChange>,
+}
+
+impl ConfigMigrationReport {
+    pub fn is_noop(&self) -> bool {
+        self.from_version == self.to_version && self.changes.is_empty()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MigratedGameConfig {
+    pub config: GameConfig,
+    pub report: ConfigMigrationReport,
+}
+
+struct Migration {
+    from_version: u32,
+    apply: fn(&mut Map<String, Value>, &mut ChangeLog),
+}
+
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from_version: 1,
+        apply: migrate_v1_to_v2,
+    },
+    Migration {
+        from_version: 2,
+        apply: migrate_v2_to_v3,
+    },
+    Migration {
+        from_version: 3,
+        apply: migrate_v3_to_v4,
+    },
+];
+
+struct ChangeLog {
+    version: u32,
+    changes: Vec<ConfigMigrationChange>,
+}
+
+impl ChangeLog {
+    fn push(&mut self, field: &str, message: impl Into<String>) {
+        self.changes.push(ConfigMigrationChange {
+            version: self.version,
+            field: field.to_string(),
+            message: message.into(),
+        });
+    }
+}
+
+/// Upgrades a config document in place to [`CURRENT_CONFIG_VERSION`].
+/// A missing `config_version` is read as version 1; newer versions are refused.
+pub fn migrate_config_value(value: &mut Value) -> Result<ConfigMigrationReport, OrchestratorError> {
+    let root = value.as_object_mut().ok_or_else(|| {
+        OrchestratorError::ConfigMigration("config root must be a JSON object".to_string())
+    })?;
+
+    let from_version = match root.get("config_version") {
+        None => 1,
+        Some(raw) => raw
+            .as_u64()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|version| *version >= 1)
+            .ok_or_else(|| {
+                OrchestratorError::ConfigMigration(format!(
+                    "config_version must be a positive integer, got {raw}"
+                ))
+            })?,
+    };
+    if from_version > CURRENT_CONFIG_VERSION {
+        return Err(OrchestratorError::UnsupportedConfigVersion {
+            found: from_version,
+            supported: CURRENT_CONFIG_VERSION,
+        });
+    }
+
+    let mut changes = Vec::new();
+    let mut version = from_version;
+    while version < CURRENT_CONFIG_VERSION {
+        let migration = MIGRATIONS
+            .iter()
+            .find(|migration| migration.from_version == version)
+            .ok_or_else(|| {
+                OrchestratorError::ConfigMigration(format!(
+                    "no migration registered from config_version {version}"
+                ))
+            })?;
+        let mut log = ChangeLog {
+            version: version + 1,
+            changes: Vec::new(),
+        };
+        (migration.apply)(root, &mut log);
+        version += 1;
+        root.insert("config_version".to_string(), Value::from(version));
+        changes.extend(log.changes);
+    }
+
+    Ok(ConfigMigrationReport {
+        from_version,
+        to_version: version,
+        changes,
+    })
+}
+
+/// Parses, migrates and deserializes a `GameConfig` JSON document.
+pub fn load_game_config_json(bytes: &[u8]) -> Result<MigratedGameConfig, OrchestratorError> {
+    let mut value: Value = serde_json::from_slice(bytes)?;
+    let report = migrate_config_value(&mut value)?;
+    let config = serde_json::from_value(value)?;
+    Ok(MigratedGameConfig { config, report })
+}
+
+const FEATURE_STATE_FIELDS: &[&str] = &[
+    "environment.gamemode",
+    "environment.mangohud",
+    "environment.gamescope.state",
+    "compatibility.wine_wayland",
+    "compatibility.hdr",
+    "compatibility.auto_dxvk_nvapi",
+    "compatibility.easy_anti_cheat_runtime",
+    "compatibility.battleye_runtime",
+    "compatibility.staging",
+    "requirements.umu",
+    "requirements.winetricks",
+    "requirements.gamescope",
+    "requirements.gamemode",
+    "requirements.mangohud",
+    "requirements.steam_runtime",
+];
+
+const FEATURE_STATE_LIST_FIELDS: &[(&str, &str)] = &[
+    ("compatibility.wrapper_commands", "state"),
+    ("extra_system_dependencies", "state"),
+    ("winecfg.drives", "state"),
+];
+
+const WINECFG_POLICY_FIELDS: &[&str] = &[
+    "winecfg.auto_capture_mouse",
+    "winecfg.window_decorations",
+    "winecfg.window_manager_control",
+    "winecfg.desktop_integration",
+    "winecfg.mime_associations",
+    "winecfg.virtual_desktop.state",
+];
+
+/// v1 allowed several legacy shapes that serde silently absorbed:
+/// `Enabled`/`Disabled` feature states, a boolean `prime_offload` and bare
+/// feature states where winecfg now expects `{ state, use_wine_default }`.
+fn migrate_v1_to_v2(root: &mut Map<String, Value>, log: &mut ChangeLog) {
+    for field in FEATURE_STATE_FIELDS {
+        if let Some(value) = lookup_mut(root, field) {
+            normalize_feature_state(value, field, log);
+        }
+    }
+
+    for (list_field, item_field) in FEATURE_STATE_LIST_FIELDS {
+        if let Some(Value::Array(items)) = lookup_mut(root, list_field) {
+            for (index, item) in items.iter_mut().enumerate() {
+                if let Some(value) = item.get_mut(*item_field) {
+                    let field = format!("{list_field}[{index}].{item_field}");
+                    normalize_feature_state(value, &field, log);
+                }
+            }
+        }
+    }
+
+    if let Some(value) = lookup_mut(root, "environment.prime_offload") {
+        if let Some(enabled) = value.as_bool() {
+            let state = if enabled { "OptionalOn" } else { "OptionalOff" };
+            *value = Value::from(state);
+            log.push(
+                "environment.prime_offload",
+                format!("boolean {enabled} converted to {state}"),
+            );
+        } else {
+            normalize_feature_state(value, "environment.prime_offload", log);
+        }
+    }
+
+    for field in WINECFG_POLICY_FIELDS {
+        let Some(value) = lookup_mut(root, field) else {
+            continue;
+        };
+        if value.is_string() {
+            normalize_feature_state(value, field, log);
+            let state = value.take();
+            *value = serde_json::json!({ "state": state, "use_wine_default": false });
+            log.push(
+                field,
+                "bare feature state wrapped as { state, use_wine_default: false }",
+            );
+        } else if let Some(state) = value.get_mut("state") {
+            normalize_feature_state(state, &format!("{field}.state"), log);
+        }
+    }
+}
+
+/// v2 scripts were one bash string per phase. Each non-blank string becomes a
+/// single bash step keeping the old behaviour: a failing pre-launch script
+/// aborts the launch, a failing post-launch script does not. Profile overlays
+/// and host rule patches are converted the same way.
+fn migrate_v2_to_v3(root: &mut Map<String, Value>, log: &mut ChangeLog) {
+    if let Some(scripts) = root.get_mut("scripts") {
+        migrate_legacy_scripts(scripts, "scripts", log);
+    }
+
+    for (list_field, patch_field) in [("profiles", "overlay"), ("rules", "patch")] {
+        let Some(Value::Array(items)) = root.get_mut(list_field) else {
+            continue;
+        };
+        for (index, item) in items.iter_mut().enumerate() {
+            if let Some(scripts) = item
+                .get_mut(patch_field)
+                .and_then(|patch| patch.get_mut("scripts"))
+            {
+                let field = format!("{list_field}[{index}].{patch_field}.scripts");
+                migrate_legacy_scripts(scripts, &field, log);
+            }
+        }
+    }
+}
+
+fn migrate_legacy_scripts(scripts: &mut Value, field: &str, log: &mut ChangeLog) {
+    for (phase, step_name, mandatory) in [
+        ("pre_launch", "pre-launch", true),
+        ("post_launch", "post-launch", false),
+    ] {
+        let Some(value) = scripts.get_mut(phase) else {
+            continue;
+        };
+        let Some(script) = value.as_str() else {
+            continue;
+        };
+
+        let field = format!("{field}.{phase}");
+        if script.trim().is_empty() {
+            *value = Value::Array(Vec::new());
+            log.push(&field, "empty script converted to an empty step list");
+        } else {
+            *value = serde_json::json!([{
+                "name": step_name,
+                "interpreter": "bash",
+                "command": script,
+                "mandatory": mandatory,
+            }]);
+            log.push(
+                &field,
+                format!("script converted to bash step '{step_name}'"),
+            );
+        }
+    }
+}
+
+/// Up to v3, `${` in launch args, env values and wrapper args reached the game
+/// as is. v4 expands `${...}` there, so existing text is escaped as `$${` to
+/// keep launching with the same strings. Profile overlays and host rule
+/// patches are escaped the same way.
+fn migrate_v3_to_v4(root: &mut Map<String, Value>, log: &mut ChangeLog) {
+    escape_variable_fields(root, "", log);
+
+    for (list_field, patch_field) in [("profiles", "overlay"), ("rules", "patch")] {
+        let Some(Value::Array(items)) = root.get_mut(list_field) else {
+            continue;
+        };
+        for (index, item) in items.iter_mut().enumerate() {
+            if let Some(Value::Object(patch)) = item.get_mut(patch_field) {
+                let prefix = format!("{list_field}[{index}].{patch_field}.");
+                escape_variable_fields(patch, &prefix, log);
+            }
+        }
+    }
+}
+
+fn escape_variable_fields(root: &mut Map<String, Value>, prefix: &str, log: &mut ChangeLog) {
+    if let Some(Value::Array(args)) = root.get_mut("launch_args") {
+        for (index, arg) in args.iter_mut().enumerate() {
+            escape_variables(arg, &format!("{prefix}launch_args[{index}]"), log);
+        }
+    }
+
+    if let Some(Value::Object(vars)) = lookup_mut(root, "environment.custom_vars") {
+        for (name, value) in vars.iter_mut() {
+            escape_variables(
+                value,
+                &format!("{prefix}environment.custom_vars.{name}"),
+                log,
+            );
+        }
+    }
+
+    if let Some(Value::Array(wrappers)) = lookup_mut(root, "compatibility.wrapper_commands") {
+        for (index, wrapper) in wrappers.iter_mut().enumerate() {
+            if let Some(args) = wrapper.get_mut("args") {
+                let field = format!("{prefix}compatibility.wrapper_commands[{index}].args");
+                escape_variables(args, &field, log);
+            }
+        }
+    }
+
+    if let Some(Value::Array(entries)) = root.get_mut("launch_entries") {
+        for (index, entry) in entries.iter_mut().enumerate() {
+            let field = format!("{prefix}launch_entries[{index}]");
+            if let Some(Value::Array(args)) = entry.get_mut("launch_args") {
+                for (arg_index, arg) in args.iter_mut().enumerate() {
+                    escape_variables(arg, &format!("{field}.launch_args[{arg_index}]"), log);
+                }
+            }
+            if let Some(Value::Object(env)) = entry.get_mut("env") {
+                for (name, value) in env.iter_mut() {
+                    escape_variables(value, &format!("{field}.env.{name}"), log);
+                }
+            }
+        }
+    }
+}
+
+fn escape_variables(value: &mut Value, field: &str, log: &mut ChangeLog) {
+    let Some(text) = value.as_str() else {
+        return;
+    };
+    if !text.contains("${") {
+        return;
+    }
+    *value = Value::from(text.replace("${", "$${"));
+    log.push(field, "literal '${' escaped as '$${'");
+}
+
+fn normalize_feature_state(value: &mut Value, field: &str, log: &mut ChangeLog) {
+    let replacement = match value.as_str() {
+        Some("Enabled") => "OptionalOn",
+        Some("Disabled") => "OptionalOff",
+        _ => return,
+    };
+    log.push(
+        field,
+        format!("legacy {value} renamed to \"{replacement}\""),
+    );
+    *value = Value::from(replacement);
+}
+
+fn lookup_mut<'a>(root: &'a mut Map<String, Value>, dotted: &str) -> Option<&'a mut Value> {
+    let mut parts = dotted.split('.');
+    let mut current = root.get_mut(parts.next()?)?;
+    for part in parts {
+        current = current.get_mut(part)?;
+    }
+    Some(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn legacy_v1() -> Value {
+        serde_json::json!({
+            "config_version": 1,
+            "environment": {
+                "gamemode": "Enabled",
+                "mangohud": "MandatoryOff",
+                "prime_offload": true,
+                "gamescope": { "state": "Disabled" },
+                "custom_vars": { "MODE": "Enabled" }
+            },
+            "compatibility": {
+                "wrapper_commands": [{ "state": "Enabled", "executable": "x", "args": "" }]
+            },
+            "winecfg": {
+                "auto_capture_mouse": "Disabled",
+                "window_decorations": { "state": "OptionalOn", "use_wine_default": true },
+                "virtual_desktop": { "state": "OptionalOff", "resolution": null }
+            }
+        })
+    }
+
+    #[test]
+    fn migrates_legacy_v1_shapes_and_reports_each_change() {
+        let mut value = legacy_v1();
+        let report = migrate_config_value(&mut value).expect("migrate");
+
+        assert_eq!(report.from_version, 1);
+        assert_eq!(report.to_version, CURRENT_CONFIG_VERSION);
+        assert_eq!(value["config_version"], CURRENT_CONFIG_VERSION);
+        assert_eq!(value["environment"]["gamemode"], "OptionalOn");
+        assert_eq!(value["environment"]["prime_offload"], "OptionalOn");
+        assert_eq!(value["environment"]["gamescope"]["state"], "OptionalOff");
+        assert_eq!(
+            value["compatibility"]["wrapper_commands"][0]["state"],
+            "OptionalOn"
+        );
+        assert_eq!(
+            value["winecfg"]["auto_capture_mouse"],
+            serde_json::json!({ "state": "OptionalOff", "use_wine_default": false })
+        );
+        assert_eq!(
+            value["winecfg"]["virtual_desktop"]["state"],
+            serde_json::json!({ "state": "OptionalOff", "use_wine_default": false })
+        );
+        // Free-form values are never reinterpreted.
+        assert_eq!(value["environment"]["custom_vars"]["MODE"], "Enabled");
+
+        let fields = report
+            .changes
+            .iter()
+            .map(|change| change.field.as_str())
+            .collect::<Vec<_>>();
+        assert!(fields.contains(&"environment.prime_offload"));
+        assert!(fields.contains(&"compatibility.wrapper_commands[0].state"));
+        assert!(!fields.contains(&"environment.mangohud"));
+        assert!(!fields.contains(&"winecfg.window_decorations"));
+    }
+
+    #[test]
+    fn legacy_script_strings_become_bash_steps() {
+        let mut value = serde_json::json!({
+            "config_version": 2,
+            "scripts": { "pre_launch": "echo pre", "post_launch": "  " },
+            "profiles": [{ "name": "Debug", "overlay": { "scripts": { "post_launch": "echo post" } } }]
+        });
+        let report = migrate_config_value(&mut value).expect("migrate");
+
+        assert_eq!(report.from_version, 2);
+        assert_eq!(
+            value["scripts"]["pre_launch"],
+            serde_json::json!([{
+                "name": "pre-launch",
+                "interpreter": "bash",
+                "command": "echo pre",
+                "mandatory": true
+            }])
+        );
+        assert_eq!(value["scripts"]["post_launch"], serde_json::json!([]));
+        let post = &value["profiles"][0]["overlay"]["scripts"]["post_launch"][0];
+        assert_eq!(post["name"], "post-launch");
+        assert_eq!(post["mandatory"], false);
+        assert!(report
+            .changes
+            .iter()
+            .any(|change| change.field == "profiles[0].overlay.scripts.post_launch"));
+
+        let config: crate::config::ScriptsConfig =
+            serde_json::from_value(value["scripts"].clone()).expect("scripts");
+        assert_eq!(config.pre_launch[0].timeout_secs, 600);
+    }
+
+    #[test]
+    fn literal_variable_syntax_is_escaped_for_v4() {
+        let mut value = serde_json::json!({
+            "config_version": 3,
+            "launch_args": ["-token=${SESSION}", "-windowed"],
+            "environment": { "custom_vars": { "PS1": "${PWD}$ ", "MODE": "fast" } },
+            "compatibility": {
+                "wrapper_commands": [{ "state": "OptionalOn", "executable": "env", "args": "A=$${B}" }]
+            },
+            "launch_entries": [{
+                "name": "Server",
+                "relative_exe_path": "./server.exe",
+                "launch_args": ["${PORT}"],
+                "env": { "MOTD": "${NAME}" }
+            }],
+            "profiles": [{ "name": "Debug", "overlay": { "launch_args": ["${X}"] } }]
+        });
+        let report = migrate_config_value(&mut value).expect("migrate");
+
+        assert_eq!(report.from_version, 3);
+        assert_eq!(
+            value["launch_args"],
+            serde_json::json!(["-token=$${SESSION}", "-windowed"])
+        );
+        assert_eq!(value["environment"]["custom_vars"]["PS1"], "$${PWD}$ ");
+        assert_eq!(value["environment"]["custom_vars"]["MODE"], "fast");
+        assert_eq!(
+            value["compatibility"]["wrapper_commands"][0]["args"],
+            "A=$$${B}"
+        );
+        assert_eq!(value["launch_entries"][0]["launch_args"][0], "$${PORT}");
+        assert_eq!(value["launch_entries"][0]["env"]["MOTD"], "$${NAME}");
+        assert_eq!(value["profiles"][0]["overlay"]["launch_args"][0], "$${X}");
+
+        let fields = report
+            .changes
+            .iter()
+            .map(|change| change.field.as_str())
+            .collect::<Vec<_>>();
+        assert!(fields.contains(&"launch_args[0]"));
+        assert!(fields.contains(&"environment.custom_vars.PS1"));
+        assert!(fields.contains(&"profiles[0].overlay.launch_args[0]"));
+        assert!(!fields.contains(&"launch_args[1]"));
+
+        let variables = crate::variables::VariableContext {
+            game_root: "/games/sample".into(),
+            prefix: "/prefix".into(),
+            windows_user: "steamuser".to_string(),
+            host_env: Default::default(),
+        };
+        assert_eq!(variables.expand("A=$$${B}").expect("expand"), "A=$${B}");
+        assert_eq!(
+            variables.expand("-token=$${SESSION}").expect("expand"),
+            "-token=${SESSION}"
+        );
+    }
+
+    #[test]
+    fn current_version_is_left_untouched() {
+        let mut value = serde_json::json!({ "config_version": CURRENT_CONFIG_VERSION });
+        let report = migrate_config_value(&mut value).expect("migrate");
+        assert!(report.is_noop());
+    }
+
+    #[test]
+    fn missing_version_is_treated_as_v1() {
+        let mut value = serde_json::json!({ "environment": { "gamemode": "Enabled" } });
+        let report = migrate_config_value(&mut value).expect("migrate");
+        assert_eq!(report.from_version, 1);
+        assert_eq!(value["config_version"], CURRENT_CONFIG_VERSION);
+    }
+
+    #[test]
+    fn refuses_configs_from_newer_releases() {
+        let mut value = serde_json::json!({ "config_version": CURRENT_CONFIG_VERSION + 1 });
+        let err = migrate_config_value(&mut value).expect_err("newer version");
+        assert!(matches!(
+            err,
+            OrchestratorError::UnsupportedConfigVersion { .. }
+        ));
+
+        let mut value = serde_json::json!({ "config_version": "two" });
+        let err = migrate_config_value(&mut value).expect_err("invalid version");
+        assert!(matches!(err, OrchestratorError::ConfigMigration(_)));
+    }
+
+    #[test]
+    fn every_version_below_current_has_a_migration() {
+        for version in 1..CURRENT_CONFIG_VERSION {
+            assert!(MIGRATIONS
+                .iter()
+                .any(|migration| migration.from_version == version));
+        }
+    }
+}
@@ -0,0 +1,433 @@
+use serde_json::{Map, Value};
+
+use crate::config::GameConfig;
+use crate::error::OrchestratorError;
+
+const APPEND_DIRECTIVE: &str = "$append";
+const REPLACE_DIRECTIVE: &str = "$replace";
+const MERGE_DIRECTIVE: &str = "$merge";
+
+/// Lists whose entries are identified by key. Overlays merge into the matching
+/// entry (compared case-insensitively, like Wine does) and append the rest.
+struct KeyedList {
+    field: &'static str,
+    keys: &'static [&'static str],
+}
+
+const KEYED_LISTS: &[KeyedList] = &[
+    KeyedList {
+        field: "winecfg.dll_overrides",
+        keys: &["dll"],
+    },
+    KeyedList {
+        field: "registry_keys",
+        keys: &["path", "name"],
+    },
+    KeyedList {
+        field: "scripts.pre_launch",
+        keys: &["name"],
+    },
+    KeyedList {
+        field: "scripts.post_launch",
+        keys: &["name"],
+    },
+];
+
+enum ListMerge {
+    Replace,
+    Append,
+    MergeByKey(&'static KeyedList),
+}
+
+/// Deep-merges one partial config document over `base`. Used for config templates
+/// and launch profiles.
+///
+/// Objects merge key by key and scalars are replaced. A plain array replaces the
+/// base list, except for keyed lists (`winecfg.dll_overrides`, `registry_keys`
+/// and script steps, by `name`), which merge by key. Any list can be written as
+/// `{ "$append": [...] }` or `{ "$replace": [...] }`; keyed lists also accept
+/// `{ "$merge": [...] }`.
+pub fn merge_config_overlay(base: &mut Value, overlay: Value) -> Result<(), OrchestratorError> {
+    merge_value(base, overlay, "")
+}
+
+/// Fields a launch profile overlay may not touch: they identify the payload.
+const PROFILE_LOCKED_FIELDS: &[&str] = &["config_version", "exe_hash", "profiles"];
+
+/// Returns `config` with the named launch profile merged over it.
+pub fn apply_launch_profile(
+    config: &GameConfig,
+    name: &str,
+) -> Result<GameConfig, OrchestratorError> {
+    let profile = config
+        .profiles
+        .iter()
+        .find(|profile| profile.name == name)
+        .ok_or_else(|| OrchestratorError::UnknownLaunchProfile(name.to_string()))?;
+
+    patch_game_config(
+        config,
+        &profile.overlay,
+        &format!("profiles.{name}.overlay"),
+        PROFILE_LOCKED_FIELDS,
+    )
+}
+
+/// Merges a partial document over `config`, refusing patches that set any of
+/// the `locked` top-level fields.
+pub(crate) fn patch_game_config(
+    config: &GameConfig,
+    patch: &Value,
+    field: &str,
+    locked: &[&str],
+) -> Result<GameConfig, OrchestratorError> {
+    if let Some(locked_field) = locked
+        .iter()
+        .find(|locked_field| patch.get(**locked_field).is_some())
+    {
+        return Err(invalid(
+            &format!("{field}.{locked_field}"),
+            "cannot be changed by a patch",
+        ));
+    }
+
+    let mut value = serde_json::to_value(config)?;
+    merge_config_overlay(&mut value, patch.clone())?;
+    Ok(serde_json::from_value(value)?)
+}
+
+fn merge_value(base: &mut Value, overlay: Value, field: &str) -> Result<(), OrchestratorError> {
+    if let Some((mode, items)) = list_overlay(&overlay, field)? {
+        return merge_list(base, mode, items, field);
+    }
+
+    match overlay {
+        Value::Object(overlay) => {
+            if !base.is_object() {
+                *base = Value::Object(Map::new());
+            }
+            if let Value::Object(base) = base {
+                for (key, value) in overlay {
+                    let child = child_field(field, &key);
+                    let slot = base.entry(key).or_insert(Value::Null);
+                    merge_value(slot, value, &child)?;
+                }
+            }
+            Ok(())
+        }
+        other => {
+            *base = other;
+            Ok(())
+        }
+    }
+}
+
+fn list_overlay<'a>(
+    overlay: &'a Value,
+    field: &str,
+) -> Result<Option<(ListMerge, &'a [Value])>, OrchestratorError> {
+    let keyed = KEYED_LISTS.iter().find(|list| list.field == field);
+
+    if let Value::Array(items) = overlay {
+        let mode = keyed.map_or(ListMerge::Replace, ListMerge::MergeByKey);
+        return Ok(Some((mode, items)));
+    }
+
+    let Value::Object(object) = overlay else {
+        return Ok(None);
+    };
+    let Some(directive) = object.keys().find(|key| key.starts_with('$')) else {
+        return Ok(None);
+    };
+    if object.len() != 1 {
+        return Err(invalid(
+            field,
+            format!("'{directive}' must be the only key of its object"),
+        ));
+    }
+
+    let mode = match (directive.as_str(), keyed) {
+        (APPEND_DIRECTIVE, _) => ListMerge::Append,
+        (REPLACE_DIRECTIVE, _) => ListMerge::Replace,
+        (MERGE_DIRECTIVE, Some(keyed)) => ListMerge::MergeByKey(keyed),
+        (MERGE_DIRECTIVE, None) => return Err(invalid(
+            field,
+            "'$merge' is only supported for winecfg.dll_overrides, registry_keys and script steps",
+        )),
+        (other, _) => return Err(invalid(field, format!("unknown list directive '{other}'"))),
+    };
+
+    match &object[directive] {
+        Value::Array(items) => Ok(Some((mode, items))),
+        _ => Err(invalid(field, format!("'{directive}' expects a list"))),
+    }
+}
+
+fn merge_list(
+    base: &mut Value,
+    mode: ListMerge,
+    items: &[Value],
+    field: &str,
+) -> Result<(), OrchestratorError> {
+    let mut merged = match (&mode, base.take()) {
+        (ListMerge::Replace, _) | (_, Value::Null) => Vec::new(),
+        (_, Value::Array(existing)) => existing,
+        (_, _) => return Err(invalid(field, "cannot merge a list into a non-list value")),
+    };
+
+    match mode {
+        ListMerge::Replace | ListMerge::Append => merged.extend(items.iter().cloned()),
+        ListMerge::MergeByKey(keyed) => {
+            for (index, item) in items.iter().enumerate() {
+                let item_field = format!("{field}[{index}]");
+                let key = entry_key(item, keyed, &item_field)?;
+                let existing = merged
+                    .iter_mut()
+                    .find(|candidate| entry_key(candidate, keyed, field).ok() == Some(key.clone()));
+                match existing {
+                    Some(existing) => merge_value(existing, item.clone(), &item_field)?,
+                    None => merged.push(item.clone()),
+                }
+            }
+        }
+    }
+
+    *base = Value::Array(merged);
+    Ok(())
+}
+
+fn entry_key(
+    item: &Value,
+    keyed: &KeyedList,
+    field: &str,
+) -> Result<Vec<String>, OrchestratorError> {
+    keyed
+        .keys
+        .iter()
+        .map(|key| {
+            item.get(key)
+                .and_then(Value::as_str)
+                .map(|value| value.trim().to_ascii_lowercase())
+                .ok_or_else(|| invalid(&child_field(field, key), "merge key is missing"))
+        })
+        .collect()
+}
+
+fn child_field(parent: &str, key: &str) -> String {
+    if parent.is_empty() {
+        key.to_string()
+    } else {
+        format!("{parent}.{key}")
+    }
+}
+
+fn invalid(field: &str, message: impl Into<String>) -> OrchestratorError {
+    OrchestratorError::InvalidConfigOverlay(format!("{field}: {}", message.into()))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn merged(base: Value, overlay: Value) -> Value {
+        let mut base = base;
+        merge_config_overlay(&mut base, overlay).expect("merge");
+        base
+    }
+
+    #[test]
+    fn deep_merges_objects_and_replaces_scalars_and_plain_lists() {
+        let result = merged(
+            json!({
+                "runner": { "proton_version": "GE-Proton9-10", "esync": true },
+                "environment": { "custom_vars": { "A": "1", "B": "2" } },
+                "dependencies": ["vcrun2019", "d3dx9"]
+            }),
+            json!({
+                "runner": { "esync": false },
+                "environment": { "custom_vars": { "B": "3" } },
+                "dependencies": ["dotnet48"]
+            }),
+        );
+
+        assert_eq!(result["runner"]["proton_version"], "GE-Proton9-10");
+        assert_eq!(result["runner"]["esync"], false);
+        assert_eq!(
+            result["environment"]["custom_vars"],
+            json!({ "A": "1", "B": "3" })
+        );
+        assert_eq!(result["dependencies"], json!(["dotnet48"]));
+    }
+
+    #[test]
+    fn append_directive_extends_the_base_list() {
+        let result = merged(
+            json!({ "dependencies": ["vcrun2019"] }),
+            json!({ "dependencies": { "$append": ["d3dx9"] }, "launch_args": { "$append": ["-dx11"] } }),
+        );
+        assert_eq!(result["dependencies"], json!(["vcrun2019", "d3dx9"]));
+        assert_eq!(result["launch_args"], json!(["-dx11"]));
+    }
+
+    #[test]
+    fn keyed_lists_merge_by_key_unless_replaced() {
+        let base = json!({
+            "winecfg": { "dll_overrides": [
+                { "dll": "d3d9", "mode": "builtin" },
+                { "dll": "dinput8", "mode": "native,builtin" }
+            ] },
+            "registry_keys": [
+                { "path": "HKCU\\Software\\Game", "name": "Lang", "value_type": "REG_SZ", "value": "en" }
+            ]
+        });
+
+        let result = merged(
+            base.clone(),
+            json!({
+                "winecfg": { "dll_overrides": [
+                    { "dll": "D3D9", "mode": "native" },
+                    { "dll": "xinput1_3", "mode": "native" }
+                ] },
+                "registry_keys": [
+                    { "path": "hkcu\\software\\game", "name": "Lang", "value": "pt" }
+                ]
+            }),
+        );
+        assert_eq!(
+            result["winecfg"]["dll_overrides"],
+            json!([
+                { "dll": "D3D9", "mode": "native" },
+                { "dll": "dinput8", "mode": "native,builtin" },
+                { "dll": "xinput1_3", "mode": "native" }
+            ])
+        );
+        assert_eq!(result["registry_keys"][0]["value"], "pt");
+        assert_eq!(result["registry_keys"][0]["value_type"], "REG_SZ");
+
+        let result = merged(
+            base,
+            json!({ "winecfg": { "dll_overrides": { "$replace": [{ "dll": "d3d11", "mode": "native" }] } } }),
+        );
+        assert_eq!(
+            result["winecfg"]["dll_overrides"],
+            json!([{ "dll": "d3d11", "mode": "native" }])
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_directives() {
+        let mut base = json!({});
+        let err = merge_config_overlay(&mut base, json!({ "dependencies": { "$merge": [] } }))
+            .expect_err("merge on unkeyed list");
+        assert!(err.to_string().contains("dependencies"));
+
+        let err = merge_config_overlay(
+            &mut base,
+            json!({ "registry_keys": [{ "name": "missing path" }] }),
+        )
+        .expect_err("missing key");
+        assert!(err.to_string().contains("registry_keys[0].path"));
+
+        let err = merge_config_overlay(&mut base, json!({ "launch_args": { "$prepend": [] } }))
+            .expect_err("unknown directive");
+        assert!(err.to_string().contains("$prepend"));
+    }
+
+    /// Smallest complete `GameConfig` document, shared by overlay and rule tests.
+    pub(crate) fn sample_config_value() -> Value {
+        json!({
+            "config_version": 4,
+            "created_by": "tests",
+            "game_name": "Sample",
+            "exe_hash": "a".repeat(64),
+            "relative_exe_path": "./game.exe",
+            "launch_args": ["-windowed"],
+            "runner": {
+                "proton_version": "GE-Proton9-10",
+                "auto_update": false,
+                "esync": true,
+                "fsync": true,
+                "runtime_preference": "Proton"
+            },
+            "environment": {
+                "gamemode": "OptionalOn",
+                "gamescope": { "state": "OptionalOff", "resolution": null, "fsr": false },
+                "mangohud": "OptionalOff",
+                "prime_offload": "OptionalOff",
+                "custom_vars": {}
+            },
+            "compatibility": {
+                "wine_wayland": "OptionalOff",
+                "hdr": "OptionalOff",
+                "auto_dxvk_nvapi": "OptionalOff",
+                "easy_anti_cheat_runtime": "OptionalOff",
+                "battleye_runtime": "OptionalOff",
+                "staging": "OptionalOff",
+                "wrapper_commands": []
+            },
+            "winecfg": {
+                "dll_overrides": [],
+                "auto_capture_mouse": { "state": "OptionalOn", "use_wine_default": true },
+                "window_decorations": { "state": "OptionalOn", "use_wine_default": true },
+                "window_manager_control": { "state": "OptionalOn", "use_wine_default": true },
+                "virtual_desktop": {
+                    "state": { "state": "OptionalOff", "use_wine_default": true },
+                    "resolution": null
+                },
+                "desktop_integration": { "state": "OptionalOn", "use_wine_default": true },
+                "drives": [],
+                "audio_driver": null
+            },
+            "dependencies": [],
+            "extra_system_dependencies": [],
+            "requirements": {
+                "runtime": { "strict": false, "primary": "ProtonNative", "fallback_order": [] },
+                "umu": "OptionalOff",
+                "winetricks": "OptionalOff",
+                "gamescope": "OptionalOff",
+                "gamemode": "OptionalOff",
+                "mangohud": "OptionalOff",
+                "steam_runtime": "OptionalOff"
+            },
+            "registry_keys": [],
+            "integrity_files": [],
+            "folder_mounts": [],
+            "scripts": { "pre_launch": [], "post_launch": [] }
+        })
+    }
+
+    #[test]
+    fn launch_profiles_overlay_the_base_config() {
+        let mut value = sample_config_value();
+        value["profiles"] = json!([
+            {
+                "name": "Steam Deck",
+                "overlay": {
+                    "launch_args": { "$append": ["-deck"] },
+                    "environment": { "gamescope": { "state": "MandatoryOn" } }
+                }
+            },
+            { "name": "Rehash", "overlay": { "exe_hash": "b" } }
+        ]);
+        let mut config = serde_json::from_value::<GameConfig>(value).expect("config");
+
+        let deck = apply_launch_profile(&config, "Steam Deck").expect("profile");
+        assert_eq!(deck.launch_args, vec!["-windowed", "-deck"]);
+        assert_eq!(
+            deck.environment.gamescope.state,
+            crate::config::FeatureState::MandatoryOn
+        );
+        assert_eq!(deck.profiles.len(), 2);
+
+        let err = apply_launch_profile(&config, "Rehash").expect_err("locked field");
+        assert!(err.to_string().contains("exe_hash"));
+
+        config.profiles.clear();
+        let err = apply_launch_profile(&config, "Steam Deck").expect_err("unknown profile");
+        assert!(
+            matches!(err, OrchestratorError::UnknownLaunchProfile(name) if name == "Steam Deck")
+        );
+    }
+}
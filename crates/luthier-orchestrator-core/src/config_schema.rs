@@ -0,0 +1,353 @@
+use jsonschema::error::ValidationErrorKind;
+use jsonschema::paths::{Location, LocationSegment};
+use jsonschema::ValidationError;
+use schemars::generate::SchemaSettings;
+use schemars::transform::{transform_subschemas, Transform};
+use schemars::Schema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::GameConfig;
+use crate::config_migration::CURRENT_CONFIG_VERSION;
+
+/// One place where a document does not match the schema. `field` uses the same
+/// dotted form as `ConfigValidationIssue.field` (`registry_keys[0].path`) and
+/// `pointer` is the RFC 6901 location of the same value (`/registry_keys/0/path`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaIssue {
+    pub code: String,
+    pub field: String,
+    pub pointer: String,
+    pub message: String,
+}
+
+/// JSON Schema (draft 2020-12) of the current `GameConfig` version, derived
+/// from the types in `config.rs`; their doc comments become the descriptions.
+///
+/// It describes the document after migration: legacy shapes accepted by older
+/// `config_version`s are upgraded by `config_migration` before validation, so
+/// `config_version` is pinned to the current version and unknown properties
+/// are rejected.
+pub fn game_config_schema() -> Value {
+    let schema = SchemaSettings::draft2020_12()
+        .with_transform(RejectUnknownProperties)
+        .into_generator()
+        .into_root_schema_for::<GameConfig>();
+    let mut schema = schema.to_value();
+    if let Some(config_version) = schema
+        .pointer_mut("/properties/config_version")
+        .and_then(Value::as_object_mut)
+    {
+        config_version.insert("const".to_string(), Value::from(CURRENT_CONFIG_VERSION));
+    }
+    schema
+}
+
+/// Closes every object schema that lists its properties, so typos in
+/// hand-edited payloads are reported instead of silently ignored by serde.
+#[derive(Clone)]
+struct RejectUnknownProperties;
+
+impl Transform for RejectUnknownProperties {
+    fn transform(&mut self, schema: &mut Schema) {
+        transform_subschemas(self, schema);
+        if let Some(object) = schema.as_object_mut() {
+            if object.contains_key("properties") {
+                object
+                    .entry("additionalProperties")
+                    .or_insert(Value::Bool(false));
+            }
+        }
+    }
+}
+
+/// Validates a (migrated) config document against [`game_config_schema`].
+pub fn validate_game_config_value(value: &Value) -> Vec<SchemaIssue> {
+    validate_against_schema(&game_config_schema(), value)
+}
+
+/// Validates `instance` against a JSON Schema and reports every mismatch as a
+/// [`SchemaIssue`]. An unusable schema is reported as a single `schema_invalid`
+/// issue at the document root.
+pub fn validate_against_schema(schema: &Value, instance: &Value) -> Vec<SchemaIssue> {
+    let validator = match jsonschema::validator_for(schema) {
+        Ok(validator) => validator,
+        Err(error) => return vec![issue("schema_invalid", &Location::new(), error.to_string())],
+    };
+    validator
+        .iter_errors(instance)
+        .flat_map(|error| issues_from_error(&error))
+        .collect()
+}
+
+/// `required` and `additionalProperties` fail on the object; the issue points at
+/// the offending property instead, one issue per property.
+fn issues_from_error(error: &ValidationError<'_>) -> Vec<SchemaIssue> {
+    let location = error.instance_path();
+    match error.kind() {
+        ValidationErrorKind::Required { property } => {
+            let name = property.as_str().unwrap_or_default();
+            vec![issue(
+                "schema_required",
+                &location.join(name),
+                "required property is missing",
+            )]
+        }
+        ValidationErrorKind::AdditionalProperties { unexpected } => unexpected
+            .iter()
+            .map(|name| {
+                issue(
+                    "schema_additional_property",
+                    &location.join(name.as_str()),
+                    "unknown property",
+                )
+            })
+            .collect(),
+        kind => vec![issue(
+            &format!("schema_{}", snake_case(kind.keyword())),
+            location,
+            error.to_string(),
+        )],
+    }
+}
+
+fn issue(code: &str, location: &Location, message: impl Into<String>) -> SchemaIssue {
+    SchemaIssue {
+        code: code.to_string(),
+        field: dotted_field(location),
+        pointer: location.as_str().to_string(),
+        message: message.into(),
+    }
+}
+
+fn dotted_field(location: &Location) -> String {
+    let mut field = String::new();
+    for segment in location {
+        match segment {
+            LocationSegment::Property(key) => {
+                if !field.is_empty() {
+                    field.push('.');
+                }
+                field.push_str(&key);
+            }
+            LocationSegment::Index(index) => field.push_str(&format!("[{index}]")),
+        }
+    }
+    field
+}
+
+/// `anyOf` -> `any_of`, matching the snake_case issue codes used elsewhere.
+fn snake_case(keyword: &str) -> String {
+    let mut code = String::new();
+    for character in keyword.trim_start_matches('$').chars() {
+        if character.is_ascii_uppercase() {
+            code.push('_');
+        }
+        code.push(character.to_ascii_lowercase());
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn minimal_config() -> Value {
+        json!({
+            "config_version": CURRENT_CONFIG_VERSION,
+            "created_by": "tests",
+            "game_name": "Sample",
+            "exe_hash": "a".repeat(64),
+            "relative_exe_path": "./game.exe",
+            "launch_args": [],
+            "runner": {
+                "proton_version": "GE-Proton9-10",
+                "auto_update": false,
+                "esync": true,
+                "fsync": true,
+                "runtime_preference": "Proton"
+            },
+            "environment": {
+                "gamemode": "OptionalOn",
+                "gamescope": { "state": "OptionalOff", "resolution": null, "fsr": false },
+                "mangohud": "OptionalOff",
+                "prime_offload": "OptionalOff",
+                "custom_vars": { "DXVK_HUD": "fps" },
+                "env_edits": [{
+                    "name": "LD_LIBRARY_PATH",
+                    "value": "${GAME_ROOT}/lib",
+                    "mode": "prepend",
+                    "separator": ":"
+                }]
+            },
+            "compatibility": {
+                "wine_wayland": "OptionalOff",
+                "hdr": "OptionalOff",
+                "auto_dxvk_nvapi": "OptionalOff",
+                "easy_anti_cheat_runtime": "OptionalOff",
+                "battleye_runtime": "OptionalOff",
+                "staging": "OptionalOff",
+                "wrapper_commands": []
+            },
+            "winecfg": {
+                "dll_overrides": [{ "dll": "d3d9", "mode": "native,builtin" }],
+                "auto_capture_mouse": { "state": "OptionalOn", "use_wine_default": false },
+                "window_decorations": { "state": "OptionalOn", "use_wine_default": true },
+                "window_manager_control": { "state": "OptionalOn", "use_wine_default": true },
+                "virtual_desktop": {
+                    "state": { "state": "OptionalOff", "use_wine_default": true },
+                    "resolution": null
+                },
+                "desktop_integration": { "state": "OptionalOn", "use_wine_default": true },
+                "drives": [],
+                "audio_driver": null
+            },
+            "dependencies": ["vcrun2019"],
+            "extra_system_dependencies": [],
+            "requirements": {
+                "runtime": { "strict": false, "primary": "ProtonNative", "fallback_order": ["Wine"] },
+                "umu": "OptionalOff",
+                "winetricks": "OptionalOn",
+                "gamescope": "OptionalOff",
+                "gamemode": "OptionalOff",
+                "mangohud": "OptionalOff",
+                "steam_runtime": "OptionalOff"
+            },
+            "registry_keys": [{
+                "path": "HKCU\\Software\\Sample",
+                "name": "Language",
+                "value_type": "REG_SZ",
+                "value": "en"
+            }],
+            "integrity_files": ["./data.pak"],
+            "folder_mounts": [],
+            "scripts": {
+                "pre_launch": [{
+                    "name": "fix-saves",
+                    "interpreter": "bash",
+                    "command": "mkdir -p \"$1\"",
+                    "args": ["${WIN_USER_DOCUMENTS}/Witcher"],
+                    "cwd": null,
+                    "timeout_secs": 30,
+                    "mandatory": true,
+                    "run_once": true,
+                    "background": false
+                }],
+                "post_launch": []
+            },
+            "profiles": [{
+                "name": "Steam Deck",
+                "overlay": { "environment": { "gamescope": { "state": "MandatoryOn" } } }
+            }],
+            "rules": [
+                {
+                    "name": "handheld",
+                    "when": { "os_id": ["steamos"], "screen": { "max_width": 1280 } },
+                    "patch": { "environment": { "gamescope": { "output_width": "1280" } } }
+                },
+                { "name": "nvidia", "when": { "gpu_vendor": ["nvidia"] }, "patch": {} }
+            ],
+            "launch_entries": [{
+                "name": "Server",
+                "relative_exe_path": "./server.exe",
+                "launch_args": ["-dedicated"],
+                "working_dir": null,
+                "env": { "SERVER_PORT": "27015" }
+            }],
+            "default_entry": null
+        })
+    }
+
+    #[test]
+    fn minimal_document_is_valid() {
+        let issues = validate_game_config_value(&minimal_config());
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn schema_covers_every_serialized_field() {
+        // Every field serde writes must be declared, or `additionalProperties: false`
+        // flags it; every required field must be written, or `required` flags it.
+        let config: GameConfig = serde_json::from_value(minimal_config()).expect("deserialize");
+        let serialized = serde_json::to_value(&config).expect("serialize");
+        let issues = validate_game_config_value(&serialized);
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn schema_publishes_enums_and_defaults_from_rust_types() {
+        let schema = game_config_schema();
+        assert_eq!(
+            schema["$defs"]["FeatureState"]["enum"],
+            json!(["MandatoryOn", "MandatoryOff", "OptionalOn", "OptionalOff"])
+        );
+        assert_eq!(
+            schema["$defs"]["GamescopeConfig"]["properties"]["upscale_method"]["default"],
+            "fsr"
+        );
+        assert_eq!(
+            schema["$defs"]["WinecfgConfig"]["properties"]["mime_associations"]["default"],
+            json!({ "state": "OptionalOff", "use_wine_default": false })
+        );
+        assert!(schema["properties"]["game_name"]["description"].is_string());
+    }
+
+    #[test]
+    fn reports_pointer_and_dotted_field_for_each_issue() {
+        let mut value = minimal_config();
+        value["registry_keys"][0]
+            .as_object_mut()
+            .expect("object")
+            .remove("path");
+        value["environment"]["gamemode"] = json!("Sometimes");
+        value["environment"]["custom_vars"]["a/b"] = json!(1);
+        value["runner"]["esync"] = json!("yes");
+        value["winecfg"]["screen_dpi"] = json!(70000);
+        value["splash"] = json!({ "hero_image_url": "", "typo": true });
+
+        let issues = validate_game_config_value(&value);
+        let found = issues
+            .iter()
+            .map(|issue| {
+                (
+                    issue.code.as_str(),
+                    issue.field.as_str(),
+                    issue.pointer.as_str(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        for expected in [
+            (
+                "schema_required",
+                "registry_keys[0].path",
+                "/registry_keys/0/path",
+            ),
+            (
+                "schema_enum",
+                "environment.gamemode",
+                "/environment/gamemode",
+            ),
+            (
+                "schema_type",
+                "environment.custom_vars.a/b",
+                "/environment/custom_vars/a~1b",
+            ),
+            ("schema_type", "runner.esync", "/runner/esync"),
+            (
+                "schema_maximum",
+                "winecfg.screen_dpi",
+                "/winecfg/screen_dpi",
+            ),
+            ("schema_additional_property", "splash.typo", "/splash/typo"),
+        ] {
+            assert!(
+                found.contains(&expected),
+                "missing {expected:?} in {found:?}"
+            );
+        }
+        assert_eq!(issues.len(), 6);
+    }
+}
@@ -0,0 +1,250 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::asset_container::{
+    parse_container_footer, parse_container_manifest, validate_manifest_entries,
+    verify_stored_checksum, AssetManifest, AssetManifestEntry, AssetType, EncodedAsset,
+    FOOTER_BYTES,
+};
+use crate::error::OrchestratorError;
+
+/// Reads a GOASv2 container from any seekable source without loading it whole.
+///
+/// Opening only touches the footer and the manifest; asset bytes are read, checked
+/// and decompressed when requested. `File` streams through seeks, while an
+/// in-memory or memory-mapped buffer can be wrapped in `std::io::Cursor`.
+/// Use [`crate::asset_container::parse_asset_container`] when the bytes are
+/// already in memory and every asset is needed.
+#[derive(Debug)]
+pub struct AssetContainerReader<R> {
+    source: R,
+    manifest: AssetManifest,
+}
+
+impl AssetContainerReader<File> {
+    pub fn open(path: &Path) -> Result<Self, OrchestratorError> {
+        Self::new(File::open(path)?)
+    }
+}
+
+impl<R: Read + Seek> AssetContainerReader<R> {
+    pub fn new(mut source: R) -> Result<Self, OrchestratorError> {
+        let total_len = source.seek(SeekFrom::End(0))?;
+        if total_len < FOOTER_BYTES as u64 {
+            return Err(OrchestratorError::ContainerTruncated);
+        }
+
+        let mut footer_bytes = [0_u8; FOOTER_BYTES];
+        read_range(
+            &mut source,
+            total_len - FOOTER_BYTES as u64,
+            &mut footer_bytes,
+        )?;
+        let footer = parse_container_footer(&footer_bytes, total_len)?;
+
+        let manifest_len =
+            usize::try_from(footer.manifest_len).map_err(|_| OrchestratorError::InvalidLength)?;
+        let mut manifest_bytes = vec![0_u8; manifest_len];
+        read_range(&mut source, footer.manifest_start, &mut manifest_bytes)?;
+        let manifest = parse_container_manifest(&manifest_bytes, &footer)?;
+        validate_manifest_entries(&manifest, footer.manifest_start)?;
+
+        Ok(Self { source, manifest })
+    }
+
+    pub fn manifest(&self) -> &AssetManifest {
+        &self.manifest
+    }
+
+    pub fn into_manifest(self) -> AssetManifest {
+        self.manifest
+    }
+
+    pub fn config_json(&mut self) -> Result<Vec<u8>, OrchestratorError> {
+        self.get(AssetType::ConfigJson)?.ok_or_else(|| {
+            OrchestratorError::MissingRequiredAsset(AssetType::ConfigJson.as_str().to_string())
+        })
+    }
+
+    pub fn hero_image(&mut self) -> Result<Option<Vec<u8>>, OrchestratorError> {
+        self.get(AssetType::HeroImage)
+    }
+
+    pub fn icon_png(&mut self) -> Result<Option<Vec<u8>>, OrchestratorError> {
+        self.get(AssetType::IconPng)
+    }
+
+    /// Reads the single asset stored for a built-in type. Named entries are
+    /// addressed through [`Self::named_asset`].
+    pub fn get(&mut self, asset_type: AssetType) -> Result<Option<Vec<u8>>, OrchestratorError> {
+        if asset_type == AssetType::Named {
+            return Ok(None);
+        }
+        let entry = self
+            .manifest
+            .entries
+            .iter()
+            .find(|entry| entry.asset_type == asset_type)
+            .cloned();
+        entry.map(|entry| self.read_entry(&entry)).transpose()
+    }
+
+    pub fn named_asset(&mut self, name: &str) -> Result<Option<Vec<u8>>, OrchestratorError> {
+        let entry = self
+            .manifest
+            .entries
+            .iter()
+            .find(|entry| {
+                entry.asset_type == AssetType::Named && entry.name.as_deref() == Some(name)
+            })
+            .cloned();
+        entry.map(|entry| self.read_entry(&entry)).transpose()
+    }
+
+    /// Reads one entry of this container's manifest, verifying the stored hash and,
+    /// for compressed entries, the decompressed hash.
+    pub fn read_entry(&mut self, entry: &AssetManifestEntry) -> Result<Vec<u8>, OrchestratorError> {
+        let len = usize::try_from(entry.len).map_err(|_| OrchestratorError::InvalidLength)?;
+        let mut stored = vec![0_u8; len];
+        read_range(&mut self.source, entry.offset, &mut stored)?;
+        verify_stored_checksum(entry, &stored)?;
+        let decoded = EncodedAsset::from_entry(entry, &stored)?.decode()?;
+        Ok(decoded.into_owned())
+    }
+}
+
+fn read_range<R: Read + Seek>(
+    source: &mut R,
+    offset: u64,
+    buf: &mut [u8],
+) -> Result<(), OrchestratorError> {
+    source.seek(SeekFrom::Start(offset))?;
+    source.read_exact(buf).map_err(|err| match err.kind() {
+        std::io::ErrorKind::UnexpectedEof => OrchestratorError::ContainerTruncated,
+        _ => OrchestratorError::Io(err),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::asset_container::{
+        append_asset_container, parse_asset_container, AssetContainerWriteInput,
+        NamedAssetWriteInput,
+    };
+
+    /// Counts the bytes pulled through `read`, to prove unrequested assets stay on disk.
+    struct CountingCursor {
+        inner: Cursor<Vec<u8>>,
+        bytes_read: usize,
+    }
+
+    impl Read for CountingCursor {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let read = self.inner.read(buf)?;
+            self.bytes_read += read;
+            Ok(read)
+        }
+    }
+
+    impl Seek for CountingCursor {
+        fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    fn sample_container(hero: &[u8]) -> Vec<u8> {
+        append_asset_container(
+            b"ELF-MOCK",
+            AssetContainerWriteInput {
+                config_json: br#"{"config_version":1}"#,
+                hero_image: Some(hero),
+                icon_png: None,
+                named_assets: &[NamedAssetWriteInput {
+                    name: "manual.txt",
+                    mime_type: "text/plain",
+                    flags: 0,
+                    bytes: &[b'm'; 2048],
+                }],
+                signing_key: None,
+            },
+        )
+        .expect("container build")
+    }
+
+    #[test]
+    fn reads_manifest_without_touching_assets() {
+        // Pseudo-random bytes so the writer cannot shrink the hero below the manifest.
+        let mut state = 0x9e37_79b9_u32;
+        let hero = (0..64 * 1024)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state.to_le_bytes()[0]
+            })
+            .collect::<Vec<_>>();
+        let binary = sample_container(&hero);
+        let expected = parse_asset_container(&binary).expect("parse").manifest;
+        let total_len = binary.len();
+
+        let mut source = CountingCursor {
+            inner: Cursor::new(binary),
+            bytes_read: 0,
+        };
+        let manifest = AssetContainerReader::new(&mut source)
+            .expect("open")
+            .into_manifest();
+
+        assert_eq!(manifest, expected);
+        assert!(source.bytes_read < total_len / 4);
+    }
+
+    #[test]
+    fn reads_requested_assets_on_demand() {
+        let hero = b"hero-image-bytes".to_vec();
+        let binary = sample_container(&hero);
+        let mut reader = AssetContainerReader::new(Cursor::new(binary)).expect("open");
+
+        assert_eq!(
+            reader.config_json().expect("config"),
+            br#"{"config_version":1}"#
+        );
+        assert_eq!(reader.hero_image().expect("hero"), Some(hero));
+        assert_eq!(reader.icon_png().expect("icon"), None);
+        assert_eq!(
+            reader.named_asset("manual.txt").expect("manual"),
+            Some(vec![b'm'; 2048])
+        );
+        assert_eq!(reader.named_asset("missing").expect("missing"), None);
+    }
+
+    #[test]
+    fn detects_corrupted_asset_only_when_read() {
+        let hero = b"hero-image-bytes".to_vec();
+        let mut binary = sample_container(&hero);
+        let hero_offset = parse_asset_container(&binary)
+            .expect("parse")
+            .manifest
+            .entries[1]
+            .offset;
+        binary[usize::try_from(hero_offset).expect("offset")] ^= 0x01;
+
+        let mut reader = AssetContainerReader::new(Cursor::new(binary)).expect("open");
+        reader.config_json().expect("untouched config still reads");
+        let err = reader.hero_image().expect_err("hero checksum must fail");
+        assert!(matches!(err, OrchestratorError::InvalidChecksum));
+    }
+
+    #[test]
+    fn reports_missing_container() {
+        let err = AssetContainerReader::new(Cursor::new(vec![0_u8; 128])).expect_err("no footer");
+        assert!(matches!(err, OrchestratorError::ContainerNotFound));
+
+        let err = AssetContainerReader::new(Cursor::new(vec![0_u8; 4])).expect_err("too short");
+        assert!(matches!(err, OrchestratorError::ContainerTruncated));
+    }
+}
@@ -1,31 +1,34 @@
 use std::env;
 use std::path::PathBuf;
-use std::process::Command;
 
 use crate::config::{FeatureState, GameConfig, RuntimeCandidate};
+use crate::host_env::HostEnvironment;
 
-use super::{host_probe, CheckStatus, DependencyStatus, RuntimeDiscovery};
+use super::graphics::{vendor_label, DriverKind, GraphicsReport, VulkanIcd};
+use super::{host_probe, sync_primitives, CheckStatus, DependencyStatus, RuntimeDiscovery};
 
 #[derive(Debug, Clone)]
-struct CapabilityProbe {
-    supported: bool,
-    resolved_path: Option<PathBuf>,
-    note: String,
+pub(super) struct CapabilityProbe {
+    pub(super) supported: bool,
+    pub(super) resolved_path: Option<PathBuf>,
+    pub(super) note: String,
 }
 
 pub(super) fn evaluate_dependencies(
+    host: &dyn HostEnvironment,
     config: Option<&GameConfig>,
     runtime: &RuntimeDiscovery,
+    graphics: &GraphicsReport,
 ) -> Vec<DependencyStatus> {
     let gamemode_state = config.map(|cfg| cfg.requirements.gamemode);
-    let gamemoderun_bin = host_probe::find_in_path("gamemoderun");
-    let libgamemode = discover_gamemode_library();
+    let gamemoderun_bin = host.find_executable("gamemoderun");
+    let libgamemode = discover_gamemode_library(host);
 
     let mut out = vec![
         evaluate_component(
             "gamescope",
             config.map(|cfg| cfg.requirements.gamescope),
-            host_probe::find_in_path("gamescope"),
+            host.find_executable("gamescope"),
         ),
         evaluate_gamemoderun_component(
             gamemode_state,
@@ -36,49 +39,87 @@ pub(super) fn evaluate_dependencies(
         evaluate_component(
             "mangohud",
             config.map(|cfg| cfg.requirements.mangohud),
-            host_probe::find_in_path("mangohud"),
+            host.find_executable("mangohud"),
         ),
         evaluate_component(
             "winetricks",
             config.map(|cfg| cfg.requirements.winetricks),
-            host_probe::find_in_path("winetricks"),
+            host.find_executable("winetricks"),
         ),
         evaluate_component(
             "umu-run",
             config.map(|cfg| cfg.requirements.umu),
-            host_probe::discover_umu(),
+            host_probe::discover_umu(host),
         ),
+        evaluate_vulkan_component(graphics),
     ];
 
     if let Some(cfg) = config {
         out.push(evaluate_component(
             "steam-runtime",
             Some(cfg.requirements.steam_runtime),
-            discover_steam_runtime(runtime),
+            discover_steam_runtime(host, runtime),
         ));
 
-        let wine_wayland_probe = probe_wine_wayland_support(runtime);
+        let wine_wayland_probe = probe_wine_wayland_support(host, runtime);
         out.push(evaluate_capability_component(
             "wine-wayland",
             cfg.compatibility.wine_wayland,
             wine_wayland_probe.clone(),
         ));
 
-        let hdr_probe = probe_hdr_support(cfg.compatibility.wine_wayland, &wine_wayland_probe);
+        let hdr_probe = probe_hdr_support(
+            cfg.compatibility.wine_wayland,
+            &wine_wayland_probe,
+            graphics,
+        );
         out.push(evaluate_capability_component(
             "hdr",
             cfg.compatibility.hdr,
             hdr_probe,
         ));
 
-        let nvapi_probe = probe_nvapi_support(runtime);
+        let nvapi_probe = probe_nvapi_support(runtime, graphics);
         out.push(evaluate_capability_component(
             "dxvk-nvapi",
             cfg.compatibility.auto_dxvk_nvapi,
             nvapi_probe,
         ));
 
-        let staging_probe = probe_staging_support(runtime);
+        let mut prime_offload = evaluate_capability_component(
+            "prime-offload",
+            cfg.environment.prime_offload,
+            probe_prime_offload_support(graphics),
+        );
+        if prime_offload.status == CheckStatus::BLOCKER {
+            // Without a second GPU the game still runs on the only one, so a
+            // payload written for laptops must not stop desktops from launching.
+            prime_offload.status = CheckStatus::WARN;
+            prime_offload.note = format!("{}; the game runs on the only GPU", prime_offload.note);
+        }
+        out.push(prime_offload);
+
+        let fsync_probe = sync_primitives::probe_fsync(host);
+        let fsync_active = cfg.runner.fsync && fsync_probe.supported;
+        out.push(evaluate_sync_component(
+            "fsync",
+            cfg.runner.fsync,
+            fsync_probe,
+        ));
+
+        let mut esync = evaluate_sync_component(
+            "esync",
+            cfg.runner.esync,
+            sync_primitives::probe_esync(host),
+        );
+        if fsync_active && esync.status == CheckStatus::WARN {
+            // Proton and Wine pick fsync first, so esync would not be used anyway.
+            esync.status = CheckStatus::INFO;
+            esync.note = format!("{}; fsync takes precedence", esync.note);
+        }
+        out.push(esync);
+
+        let staging_probe = probe_staging_support(host, runtime);
         out.push(evaluate_capability_component(
             "staging",
             cfg.compatibility.staging,
@@ -89,6 +130,7 @@ pub(super) fn evaluate_dependencies(
             && !matches!(gamemode_state, Some(FeatureState::MandatoryOff))
         {
             out.push(evaluate_gamemode_umu_runtime_component(
+                host,
                 gamemode_state,
                 gamemoderun_bin.clone(),
                 libgamemode.clone(),
@@ -99,17 +141,18 @@ pub(super) fn evaluate_dependencies(
         out.push(evaluate_component(
             "eac-runtime",
             Some(cfg.compatibility.easy_anti_cheat_runtime),
-            discover_proton_aux_runtime("PROTON_EAC_RUNTIME", "eac_runtime"),
+            discover_proton_aux_runtime(host, "PROTON_EAC_RUNTIME", "eac_runtime"),
         ));
 
         out.push(evaluate_component(
             "battleye-runtime",
             Some(cfg.compatibility.battleye_runtime),
-            discover_proton_aux_runtime("PROTON_BATTLEYE_RUNTIME", "battleye_runtime"),
+            discover_proton_aux_runtime(host, "PROTON_BATTLEYE_RUNTIME", "battleye_runtime"),
         ));
 
         for dep in &cfg.extra_system_dependencies {
             let found = find_dependency_from_rules(
+                host,
                 dep.check_commands.as_slice(),
                 dep.check_env_vars.as_slice(),
                 dep.check_paths.as_slice(),
@@ -122,12 +165,13 @@ pub(super) fn evaluate_dependencies(
 }
 
 fn evaluate_gamemode_umu_runtime_component(
+    host: &dyn HostEnvironment,
     state: Option<FeatureState>,
     gamemoderun_bin: Option<PathBuf>,
     libgamemode: Option<PathBuf>,
     umu_run: Option<PathBuf>,
 ) -> DependencyStatus {
-    let force_enabled = gamemode_umu_force_enabled();
+    let force_enabled = gamemode_umu_force_enabled(host);
     let host_prereqs_ok = gamemoderun_bin.is_some() && libgamemode.is_some() && umu_run.is_some();
     let resolved_path = gamemoderun_bin
         .as_ref()
@@ -203,11 +247,12 @@ fn evaluate_gamemode_umu_runtime_component(
         found,
         resolved_path,
         note,
+        remediation: Vec::new(),
     }
 }
 
-fn gamemode_umu_force_enabled() -> bool {
-    env::var("LUTHIER_FORCE_GAMEMODE_UMU")
+fn gamemode_umu_force_enabled(host: &dyn HostEnvironment) -> bool {
+    host.env_var("LUTHIER_FORCE_GAMEMODE_UMU")
         .map(|value| {
             value == "1"
                 || value.eq_ignore_ascii_case("true")
@@ -248,20 +293,21 @@ fn evaluate_gamemoderun_component(
 }
 
 fn find_dependency_from_rules(
+    host: &dyn HostEnvironment,
     commands: &[String],
     env_vars: &[String],
     paths: &[String],
 ) -> Option<PathBuf> {
     for command in commands {
-        if let Some(path) = host_probe::find_in_path(command) {
+        if let Some(path) = host.find_executable(command) {
             return Some(path);
         }
     }
 
     for key in env_vars {
-        if let Some(value) = env::var_os(key) {
+        if let Some(value) = host.env_var(key) {
             let path = PathBuf::from(value);
-            if path.exists() {
+            if host.exists(&path) {
                 return Some(path);
             }
         }
@@ -269,7 +315,7 @@ fn find_dependency_from_rules(
 
     for raw_path in paths {
         let path = PathBuf::from(raw_path);
-        if path.exists() {
+        if host.exists(&path) {
             return Some(path);
         }
     }
@@ -277,15 +323,19 @@ fn find_dependency_from_rules(
     None
 }
 
-fn discover_proton_aux_runtime(env_var: &str, folder_name: &str) -> Option<PathBuf> {
-    if let Some(from_env) = env::var_os(env_var) {
+fn discover_proton_aux_runtime(
+    host: &dyn HostEnvironment,
+    env_var: &str,
+    folder_name: &str,
+) -> Option<PathBuf> {
+    if let Some(from_env) = host.env_var(env_var) {
         let candidate = PathBuf::from(from_env);
-        if candidate.exists() {
+        if host.exists(&candidate) {
             return Some(candidate);
         }
     }
 
-    let home = env::var_os("HOME")?;
+    let home = host.env_var("HOME")?;
     let home = PathBuf::from(home);
     let candidates = [
         home.join(".config/heroic/tools/runtimes").join(folder_name),
@@ -294,7 +344,7 @@ fn discover_proton_aux_runtime(env_var: &str, folder_name: &str) -> Option<PathB
         home.join(".local/share/Luthier/runtimes").join(folder_name),
     ];
 
-    candidates.into_iter().find(|path| path.exists())
+    candidates.into_iter().find(|path| host.exists(path))
 }
 
 pub(super) fn evaluate_component(
@@ -349,6 +399,7 @@ pub(super) fn evaluate_component(
         found,
         resolved_path: resolved.map(host_probe::path_to_string),
         note: note.to_string(),
+        remediation: Vec::new(),
     }
 }
 
@@ -411,29 +462,73 @@ fn evaluate_capability_component(
         found,
         resolved_path: probe.resolved_path.map(host_probe::path_to_string),
         note,
+        remediation: Vec::new(),
+    }
+}
+
+/// esync and fsync are plain toggles: when the host cannot provide one,
+/// Proton and Wine quietly fall back to server-side synchronization.
+fn evaluate_sync_component(
+    name: &str,
+    requested: bool,
+    probe: CapabilityProbe,
+) -> DependencyStatus {
+    let (status, note) = match (requested, probe.supported) {
+        (true, true) => (
+            CheckStatus::OK,
+            format!("enabled in payload and supported ({})", probe.note),
+        ),
+        (true, false) => (
+            CheckStatus::WARN,
+            format!(
+                "enabled in payload, but the runner will fall back to wineserver synchronization ({})",
+                probe.note
+            ),
+        ),
+        (false, true) => (
+            CheckStatus::INFO,
+            format!("disabled in payload (supported: {})", probe.note),
+        ),
+        (false, false) => (
+            CheckStatus::INFO,
+            format!("disabled in payload (unsupported: {})", probe.note),
+        ),
+    };
+
+    DependencyStatus {
+        name: name.to_string(),
+        state: None,
+        status,
+        found: probe.supported,
+        resolved_path: probe.resolved_path.map(host_probe::path_to_string),
+        note,
+        remediation: Vec::new(),
     }
 }
 
-fn discover_steam_runtime(runtime: &RuntimeDiscovery) -> Option<PathBuf> {
+fn discover_steam_runtime(
+    host: &dyn HostEnvironment,
+    runtime: &RuntimeDiscovery,
+) -> Option<PathBuf> {
     if matches!(runtime.selected_runtime, Some(RuntimeCandidate::ProtonUmu)) {
         if let Some(umu) = runtime.umu_run.as_deref() {
             return Some(PathBuf::from(umu));
         }
     }
 
-    if let Some(path) = discover_path_from_env_var("STEAM_RUNTIME") {
+    if let Some(path) = discover_path_from_env_var(host, "STEAM_RUNTIME") {
         return Some(path);
     }
 
-    if let Some(path) = host_probe::find_in_path("steam-runtime-launch-client") {
+    if let Some(path) = host.find_executable("steam-runtime-launch-client") {
         return Some(path);
     }
 
-    if let Some(path) = host_probe::find_in_path("steam-runtime-launcher-service") {
+    if let Some(path) = host.find_executable("steam-runtime-launcher-service") {
         return Some(path);
     }
 
-    if let Some(home) = env::var_os("HOME") {
+    if let Some(home) = host.env_var("HOME") {
         let home = PathBuf::from(home);
         let candidates = [
             home.join(".local/share/Steam/ubuntu12_32/steam-runtime/run.sh"),
@@ -441,7 +536,7 @@ fn discover_steam_runtime(runtime: &RuntimeDiscovery) -> Option<PathBuf> {
             home.join(".steam/steam/ubuntu12_32/steam-runtime/run.sh"),
         ];
         for candidate in candidates {
-            if candidate.exists() {
+            if host.exists(&candidate) {
                 return Some(candidate);
             }
         }
@@ -450,8 +545,11 @@ fn discover_steam_runtime(runtime: &RuntimeDiscovery) -> Option<PathBuf> {
     None
 }
 
-fn probe_wine_wayland_support(runtime: &RuntimeDiscovery) -> CapabilityProbe {
-    if !wayland_session_detected() {
+fn probe_wine_wayland_support(
+    host: &dyn HostEnvironment,
+    runtime: &RuntimeDiscovery,
+) -> CapabilityProbe {
+    if !wayland_session_detected(host) {
         return CapabilityProbe {
             supported: false,
             resolved_path: None,
@@ -467,7 +565,7 @@ fn probe_wine_wayland_support(runtime: &RuntimeDiscovery) -> CapabilityProbe {
         },
         Some(RuntimeCandidate::Wine) => {
             let wine_path = runtime.wine.as_deref().map(PathBuf::from);
-            if let Some(driver) = discover_wine_wayland_driver_path(runtime.wine.as_deref()) {
+            if let Some(driver) = discover_wine_wayland_driver_path(host, runtime.wine.as_deref()) {
                 CapabilityProbe {
                     supported: true,
                     resolved_path: Some(driver),
@@ -492,6 +590,7 @@ fn probe_wine_wayland_support(runtime: &RuntimeDiscovery) -> CapabilityProbe {
 fn probe_hdr_support(
     wine_wayland_state: FeatureState,
     wine_wayland_probe: &CapabilityProbe,
+    graphics: &GraphicsReport,
 ) -> CapabilityProbe {
     if !wine_wayland_state.is_enabled() {
         return CapabilityProbe {
@@ -512,20 +611,56 @@ fn probe_hdr_support(
         };
     }
 
+    // HDR output reaches the compositor through DXVK/VKD3D swapchains.
+    let Some(icd) = graphics.vulkan_icds.iter().find(|icd| icd.serves_64bit()) else {
+        return CapabilityProbe {
+            supported: false,
+            resolved_path: wine_wayland_probe.resolved_path.clone(),
+            note: "wine-wayland support is available, but no 64-bit Vulkan driver is installed"
+                .to_string(),
+        };
+    };
+
     CapabilityProbe {
         supported: true,
         resolved_path: wine_wayland_probe.resolved_path.clone(),
-        note: "wine-wayland support is available".to_string(),
+        note: format!(
+            "wine-wayland support is available (Vulkan driver: {})",
+            driver_kind_label(icd.driver_kind)
+        ),
     }
 }
 
-fn probe_nvapi_support(runtime: &RuntimeDiscovery) -> CapabilityProbe {
+fn probe_nvapi_support(runtime: &RuntimeDiscovery, graphics: &GraphicsReport) -> CapabilityProbe {
     match runtime.selected_runtime {
-        Some(RuntimeCandidate::ProtonNative | RuntimeCandidate::ProtonUmu) => CapabilityProbe {
-            supported: true,
-            resolved_path: runtime.proton.as_deref().map(PathBuf::from),
-            note: "selected runtime is Proton".to_string(),
-        },
+        Some(RuntimeCandidate::ProtonNative | RuntimeCandidate::ProtonUmu) => {
+            let resolved_path = runtime.proton.as_deref().map(PathBuf::from);
+            let devices = graphics.describe_devices();
+            if graphics.has_nvidia_driver() {
+                CapabilityProbe {
+                    supported: true,
+                    resolved_path,
+                    note: format!(
+                        "selected runtime is Proton and the NVIDIA driver is in use ({devices})"
+                    ),
+                }
+            } else if devices.is_empty() {
+                CapabilityProbe {
+                    supported: true,
+                    resolved_path,
+                    note: "selected runtime is Proton (GPU vendor could not be detected)"
+                        .to_string(),
+                }
+            } else {
+                CapabilityProbe {
+                    supported: false,
+                    resolved_path,
+                    note: format!(
+                        "selected runtime is Proton, but DXVK-NVAPI needs an NVIDIA GPU on the proprietary driver ({devices})"
+                    ),
+                }
+            }
+        }
         Some(RuntimeCandidate::Wine) => CapabilityProbe {
             supported: false,
             resolved_path: runtime.wine.as_deref().map(PathBuf::from),
@@ -540,11 +675,110 @@ fn probe_nvapi_support(runtime: &RuntimeDiscovery) -> CapabilityProbe {
     }
 }
 
-fn probe_staging_support(runtime: &RuntimeDiscovery) -> CapabilityProbe {
+fn probe_prime_offload_support(graphics: &GraphicsReport) -> CapabilityProbe {
+    let devices = graphics.describe_devices();
+    if graphics.hybrid_gpu {
+        CapabilityProbe {
+            supported: true,
+            resolved_path: None,
+            note: format!("hybrid GPU setup: {devices}"),
+        }
+    } else if devices.is_empty() {
+        // Flatpak and container sandboxes often hide /sys/class/drm.
+        CapabilityProbe {
+            supported: true,
+            resolved_path: None,
+            note: "GPU layout could not be detected in /sys/class/drm".to_string(),
+        }
+    } else {
+        CapabilityProbe {
+            supported: false,
+            resolved_path: None,
+            note: format!("single GPU ({devices}), there is no second GPU to offload to"),
+        }
+    }
+}
+
+/// Whether DXVK/VKD3D can run at all: a Vulkan ICD for each bitness and for
+/// each kernel driver in use.
+fn evaluate_vulkan_component(graphics: &GraphicsReport) -> DependencyStatus {
+    let icds = &graphics.vulkan_icds;
+    let has_64bit = icds.iter().any(VulkanIcd::serves_64bit);
+    let has_32bit = icds.iter().any(VulkanIcd::serves_32bit);
+    let mut missing_drivers = graphics
+        .devices
+        .iter()
+        .filter(|device| device.driver_kind != DriverKind::Unknown)
+        .filter(|device| {
+            !icds.iter().any(|icd| {
+                icd.driver_kind == device.driver_kind || icd.driver_kind == DriverKind::Unknown
+            })
+        })
+        .map(|device| {
+            let vendor = device.vendor.map(vendor_label).unwrap_or("unknown");
+            format!("{vendor} ({})", driver_kind_label(device.driver_kind))
+        })
+        .collect::<Vec<_>>();
+    missing_drivers.dedup();
+
+    let (status, note) = if !has_64bit {
+        (
+            CheckStatus::WARN,
+            "no 64-bit Vulkan ICD installed; DXVK/VKD3D cannot run".to_string(),
+        )
+    } else if !missing_drivers.is_empty() {
+        (
+            CheckStatus::WARN,
+            format!("no Vulkan ICD for {}", missing_drivers.join(", ")),
+        )
+    } else if !has_32bit {
+        (
+            CheckStatus::WARN,
+            "no 32-bit Vulkan ICD installed; 32-bit games cannot use DXVK".to_string(),
+        )
+    } else {
+        let mut kinds = icds
+            .iter()
+            .map(|icd| driver_kind_label(icd.driver_kind))
+            .collect::<Vec<_>>();
+        kinds.sort_unstable();
+        kinds.dedup();
+        (
+            CheckStatus::OK,
+            format!("64-bit and 32-bit ICDs available ({})", kinds.join(", ")),
+        )
+    };
+
+    DependencyStatus {
+        name: "vulkan".to_string(),
+        state: None,
+        status,
+        found: has_64bit,
+        resolved_path: icds
+            .iter()
+            .find(|icd| icd.serves_64bit())
+            .map(|icd| icd.manifest_path.clone()),
+        note,
+        remediation: Vec::new(),
+    }
+}
+
+fn driver_kind_label(kind: DriverKind) -> &'static str {
+    match kind {
+        DriverKind::Mesa => "mesa",
+        DriverKind::Nvidia => "nvidia",
+        DriverKind::Unknown => "unknown",
+    }
+}
+
+fn probe_staging_support(
+    host: &dyn HostEnvironment,
+    runtime: &RuntimeDiscovery,
+) -> CapabilityProbe {
     match runtime.selected_runtime {
         Some(RuntimeCandidate::Wine) => {
             let wine_path = runtime.wine.as_deref().map(PathBuf::from);
-            if let Some(version) = query_wine_version(runtime.wine.as_deref()) {
+            if let Some(version) = query_wine_version(host, runtime.wine.as_deref()) {
                 let version_lower = version.to_ascii_lowercase();
                 if version_lower.contains("staging") {
                     CapabilityProbe {
@@ -583,15 +817,13 @@ fn probe_staging_support(runtime: &RuntimeDiscovery) -> CapabilityProbe {
     }
 }
 
-fn query_wine_version(wine_path: Option<&str>) -> Option<String> {
+fn query_wine_version(host: &dyn HostEnvironment, wine_path: Option<&str>) -> Option<String> {
     let wine_path = wine_path?;
-    let output = Command::new(wine_path).arg("--version").output().ok()?;
-    if !output.status.success() {
+    let output = host.command_output(wine_path, &["--version"])?;
+    if !output.success {
         return None;
     }
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    let merged = format!("{}{}", stdout, stderr);
+    let merged = format!("{}{}", output.stdout, output.stderr);
     let trimmed = merged.trim();
     if trimmed.is_empty() {
         None
@@ -600,16 +832,19 @@ fn query_wine_version(wine_path: Option<&str>) -> Option<String> {
     }
 }
 
-fn wayland_session_detected() -> bool {
-    if env::var_os("WAYLAND_DISPLAY").is_some() {
+fn wayland_session_detected(host: &dyn HostEnvironment) -> bool {
+    if host.env_var("WAYLAND_DISPLAY").is_some() {
         return true;
     }
-    env::var("XDG_SESSION_TYPE")
+    host.env_var("XDG_SESSION_TYPE")
         .map(|value| value.eq_ignore_ascii_case("wayland"))
         .unwrap_or(false)
 }
 
-fn discover_wine_wayland_driver_path(wine_path: Option<&str>) -> Option<PathBuf> {
+fn discover_wine_wayland_driver_path(
+    host: &dyn HostEnvironment,
+    wine_path: Option<&str>,
+) -> Option<PathBuf> {
     let wine_path = PathBuf::from(wine_path?);
     let bin_dir = wine_path.parent()?;
     let candidates = [
@@ -620,11 +855,11 @@ fn discover_wine_wayland_driver_path(wine_path: Option<&str>) -> Option<PathBuf>
         bin_dir.join("../lib/wine/winewayland.drv"),
         bin_dir.join("../lib64/wine/winewayland.drv"),
     ];
-    candidates.into_iter().find(|path| path.exists())
+    candidates.into_iter().find(|path| host.exists(path))
 }
 
-fn discover_path_from_env_var(name: &str) -> Option<PathBuf> {
-    let raw = env::var(name).ok()?;
+fn discover_path_from_env_var(host: &dyn HostEnvironment, name: &str) -> Option<PathBuf> {
+    let raw = host.env_var(name)?;
     let trimmed = raw.trim();
     if trimmed.is_empty() {
         return None;
@@ -635,7 +870,7 @@ fn discover_path_from_env_var(name: &str) -> Option<PathBuf> {
     }
 
     let path = PathBuf::from(trimmed);
-    if path.exists() {
+    if host.exists(&path) {
         Some(path)
     } else {
         None
@@ -649,18 +884,18 @@ fn truthy_flag(value: &str) -> bool {
         || value.eq_ignore_ascii_case("on")
 }
 
-fn discover_gamemode_library() -> Option<PathBuf> {
+fn discover_gamemode_library(host: &dyn HostEnvironment) -> Option<PathBuf> {
     if let Some(path) =
-        discover_shared_library_with_ldconfig(&["libgamemode.so.0", "libgamemode.so"])
+        discover_shared_library_with_ldconfig(host, &["libgamemode.so.0", "libgamemode.so"])
     {
         return Some(path);
     }
 
     let mut dirs = Vec::new();
-    if let Some(ld_library_path) = env::var_os("LD_LIBRARY_PATH") {
+    if let Some(ld_library_path) = host.env_var("LD_LIBRARY_PATH") {
         dirs.extend(env::split_paths(&ld_library_path));
     }
-    if let Some(library_path) = env::var_os("LIBRARY_PATH") {
+    if let Some(library_path) = host.env_var("LIBRARY_PATH") {
         dirs.extend(env::split_paths(&library_path));
     }
 
@@ -682,7 +917,7 @@ fn discover_gamemode_library() -> Option<PathBuf> {
     for dir in dirs {
         for name in ["libgamemode.so.0", "libgamemode.so"] {
             let candidate = dir.join(name);
-            if candidate.is_file() {
+            if host.is_file(&candidate) {
                 return Some(candidate);
             }
         }
@@ -691,15 +926,17 @@ fn discover_gamemode_library() -> Option<PathBuf> {
     None
 }
 
-fn discover_shared_library_with_ldconfig(names: &[&str]) -> Option<PathBuf> {
-    let output = Command::new("ldconfig").arg("-p").output().ok()?;
-    if !output.status.success() {
+fn discover_shared_library_with_ldconfig(
+    host: &dyn HostEnvironment,
+    names: &[&str],
+) -> Option<PathBuf> {
+    let output = host.command_output("ldconfig", &["-p"])?;
+    if !output.success {
         return None;
     }
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
     for name in names {
-        for line in stdout.lines() {
+        for line in output.stdout.lines() {
             let trimmed = line.trim();
             if !trimmed.starts_with(name) {
                 continue;
@@ -709,7 +946,7 @@ fn discover_shared_library_with_ldconfig(names: &[&str]) -> Option<PathBuf> {
                 continue;
             };
             let candidate = PathBuf::from(path.trim());
-            if candidate.is_file() {
+            if host.is_file(&candidate) {
                 return Some(candidate);
             }
         }
@@ -722,13 +959,18 @@ fn discover_shared_library_with_ldconfig(names: &[&str]) -> Option<PathBuf> {
 mod tests {
     use std::path::PathBuf;
 
-    use crate::config::{FeatureState, RuntimeCandidate};
+    use crate::config::{FeatureState, GameConfig, RuntimeCandidate};
+    use crate::config_overlay::tests::sample_config_value;
+    use crate::doctor::graphics::{GpuDevice, GraphicsReport, VulkanIcd};
+    use crate::host_env::FixtureHost;
 
     use super::{
         discover_steam_runtime, evaluate_capability_component, evaluate_component,
-        probe_hdr_support, probe_nvapi_support, probe_staging_support, CapabilityProbe,
-        CheckStatus, RuntimeDiscovery,
+        evaluate_dependencies, evaluate_vulkan_component, probe_hdr_support, probe_nvapi_support,
+        probe_prime_offload_support, probe_staging_support, CapabilityProbe, CheckStatus,
+        DriverKind, RuntimeDiscovery,
     };
+    use crate::config::GpuVendor;
 
     #[test]
     fn steam_runtime_policy_matrix_covers_feature_states() {
@@ -782,7 +1024,7 @@ mod tests {
             Some("/usr/bin/umu-run"),
         );
 
-        let discovered = discover_steam_runtime(&runtime)
+        let discovered = discover_steam_runtime(&FixtureHost::default(), &runtime)
             .expect("ProtonUmu selection must reuse umu-run as steam-runtime capability path");
         assert_eq!(discovered, PathBuf::from("/usr/bin/umu-run"));
     }
@@ -816,19 +1058,31 @@ mod tests {
             note: "Wayland session not detected".to_string(),
         };
 
-        let available_probe = probe_hdr_support(FeatureState::OptionalOn, &available_wine_wayland);
+        let available_probe = probe_hdr_support(
+            FeatureState::OptionalOn,
+            &available_wine_wayland,
+            &amd_desktop(),
+        );
         assert!(available_probe.supported);
         assert!(available_probe
             .note
             .contains("wine-wayland support is available"));
 
-        let missing_probe = probe_hdr_support(FeatureState::MandatoryOn, &unavailable_wine_wayland);
+        let missing_probe = probe_hdr_support(
+            FeatureState::MandatoryOn,
+            &unavailable_wine_wayland,
+            &amd_desktop(),
+        );
         assert!(!missing_probe.supported);
         assert!(missing_probe
             .note
             .contains("wine-wayland support is unavailable"));
 
-        let disabled_probe = probe_hdr_support(FeatureState::OptionalOff, &available_wine_wayland);
+        let disabled_probe = probe_hdr_support(
+            FeatureState::OptionalOff,
+            &available_wine_wayland,
+            &amd_desktop(),
+        );
         assert!(!disabled_probe.supported);
         assert_eq!(disabled_probe.note, "HDR requires wine-wayland enabled");
 
@@ -843,12 +1097,12 @@ mod tests {
             None,
             None,
         );
-        let available_probe = probe_nvapi_support(&proton_runtime);
+        let available_probe = probe_nvapi_support(&proton_runtime, &GraphicsReport::default());
         assert!(available_probe.supported);
         assert!(available_probe.note.contains("selected runtime is Proton"));
 
         let no_runtime = runtime_discovery(None, None, None, None);
-        let missing_probe = probe_nvapi_support(&no_runtime);
+        let missing_probe = probe_nvapi_support(&no_runtime, &GraphicsReport::default());
         assert!(!missing_probe.supported);
         assert_eq!(missing_probe.note, "no runtime selected");
 
@@ -863,7 +1117,7 @@ mod tests {
             None,
             None,
         );
-        let missing_probe = probe_staging_support(&proton_runtime);
+        let missing_probe = probe_staging_support(&FixtureHost::default(), &proton_runtime);
         assert!(!missing_probe.supported);
         assert!(missing_probe
             .note
@@ -871,7 +1125,7 @@ mod tests {
 
         let wine_runtime_without_path =
             runtime_discovery(Some(RuntimeCandidate::Wine), None, None, None);
-        let wine_probe = probe_staging_support(&wine_runtime_without_path);
+        let wine_probe = probe_staging_support(&FixtureHost::default(), &wine_runtime_without_path);
         assert!(!wine_probe.supported);
         assert!(wine_probe
             .note
@@ -886,6 +1140,206 @@ mod tests {
         assert_capability_state_matrix("staging", available_probe, missing_probe);
     }
 
+    #[test]
+    fn graphics_facts_drive_gpu_capability_probes() {
+        let amd = amd_desktop();
+        let proton_runtime = runtime_discovery(
+            Some(RuntimeCandidate::ProtonNative),
+            Some("/opt/proton/proton"),
+            None,
+            None,
+        );
+
+        let nvapi_on_amd = probe_nvapi_support(&proton_runtime, &amd);
+        assert!(!nvapi_on_amd.supported);
+        assert!(nvapi_on_amd.note.contains("amd (amdgpu)"));
+
+        let single_gpu = probe_prime_offload_support(&amd);
+        assert!(!single_gpu.supported);
+        assert!(single_gpu.note.starts_with("single GPU (amd (amdgpu))"));
+
+        let mut hybrid = amd.clone();
+        hybrid.devices.push(gpu(
+            "card1",
+            GpuVendor::Nvidia,
+            "nvidia",
+            DriverKind::Nvidia,
+        ));
+        hybrid.hybrid_gpu = true;
+        let offload = probe_prime_offload_support(&hybrid);
+        assert!(offload.supported);
+        assert_eq!(
+            offload.note,
+            "hybrid GPU setup: amd (amdgpu) + nvidia (nvidia)"
+        );
+        assert!(probe_nvapi_support(&proton_runtime, &hybrid).supported);
+
+        let wayland = CapabilityProbe {
+            supported: true,
+            resolved_path: None,
+            note: "selected runtime is Proton in a Wayland session".to_string(),
+        };
+        let without_vulkan = GraphicsReport {
+            vulkan_icds: Vec::new(),
+            ..amd
+        };
+        let hdr = probe_hdr_support(FeatureState::OptionalOn, &wayland, &without_vulkan);
+        assert!(!hdr.supported);
+        assert!(hdr.note.contains("no 64-bit Vulkan driver"));
+    }
+
+    #[test]
+    fn vulkan_check_requires_icds_for_each_bitness_and_driver() {
+        let amd = amd_desktop();
+        let ok = evaluate_vulkan_component(&amd);
+        assert_eq!(ok.status, CheckStatus::OK);
+        assert_eq!(ok.note, "64-bit and 32-bit ICDs available (mesa)");
+
+        let mut only_64bit = amd.clone();
+        only_64bit.vulkan_icds.retain(|icd| icd.serves_64bit());
+        let warn = evaluate_vulkan_component(&only_64bit);
+        assert_eq!(warn.status, CheckStatus::WARN);
+        assert!(warn.note.contains("32-bit games cannot use DXVK"));
+
+        let mut nvidia_without_icd = amd.clone();
+        nvidia_without_icd.devices.push(gpu(
+            "card1",
+            GpuVendor::Nvidia,
+            "nvidia",
+            DriverKind::Nvidia,
+        ));
+        let missing = evaluate_vulkan_component(&nvidia_without_icd);
+        assert_eq!(missing.status, CheckStatus::WARN);
+        assert_eq!(missing.note, "no Vulkan ICD for nvidia (nvidia)");
+
+        let none = evaluate_vulkan_component(&GraphicsReport::default());
+        assert_eq!(none.status, CheckStatus::WARN);
+        assert!(!none.found);
+    }
+
+    #[test]
+    fn mandatory_prime_offload_never_blocks_a_launch() {
+        let mut config: GameConfig = serde_json::from_value(sample_config_value()).expect("config");
+        config.environment.prime_offload = FeatureState::MandatoryOn;
+        let runtime = runtime_discovery(Some(RuntimeCandidate::ProtonNative), None, None, None);
+        let prime_offload = |graphics: &GraphicsReport| {
+            evaluate_dependencies(&FixtureHost::default(), Some(&config), &runtime, graphics)
+                .into_iter()
+                .find(|dep| dep.name == "prime-offload")
+                .expect("prime-offload check present")
+        };
+
+        // No devices visible, as inside a Flatpak sandbox.
+        let sandboxed = prime_offload(&GraphicsReport::default());
+        assert_eq!(sandboxed.status, CheckStatus::OK);
+        assert!(sandboxed.found);
+        assert!(sandboxed.note.contains("could not be detected"));
+
+        let single_gpu = prime_offload(&amd_desktop());
+        assert_eq!(single_gpu.status, CheckStatus::WARN);
+        assert!(!single_gpu.found);
+        assert!(single_gpu.note.ends_with("; the game runs on the only GPU"));
+    }
+
+    #[test]
+    fn sync_checks_warn_only_when_a_requested_primitive_falls_back() {
+        let mut config: GameConfig = serde_json::from_value(sample_config_value()).expect("config");
+        config.runner.esync = true;
+        config.runner.fsync = true;
+        let host = FixtureHost::from_json(
+            br#"{
+                "futex_waitv": false,
+                "files": {
+                    "/proc/sys/kernel/osrelease": { "contents": "5.15.0-91-generic\n" },
+                    "/proc/self/limits": { "contents": "Max open files            1024                 4096                 files     \n" }
+                }
+            }"#,
+        )
+        .expect("fixture parses");
+        let runtime = runtime_discovery(Some(RuntimeCandidate::ProtonNative), None, None, None);
+
+        let dependencies =
+            evaluate_dependencies(&host, Some(&config), &runtime, &GraphicsReport::default());
+        let find = |name: &str| {
+            dependencies
+                .iter()
+                .find(|dep| dep.name == name)
+                .cloned()
+                .expect("sync check present")
+        };
+
+        let fsync = find("fsync");
+        assert_eq!(fsync.status, CheckStatus::WARN);
+        assert!(!fsync.found);
+        assert!(fsync
+            .note
+            .contains("fall back to wineserver synchronization"));
+        let esync = find("esync");
+        assert_eq!(esync.status, CheckStatus::WARN);
+        assert!(esync
+            .note
+            .contains("esync needs a hard limit of at least 524288"));
+
+        let mut with_fsync = host.clone();
+        with_fsync.futex_waitv = Some(true);
+        let dependencies = evaluate_dependencies(
+            &with_fsync,
+            Some(&config),
+            &runtime,
+            &GraphicsReport::default(),
+        );
+        let statuses = dependencies
+            .iter()
+            .filter(|dep| dep.name.ends_with("sync"))
+            .map(|dep| (dep.name.as_str(), dep.status, dep.found))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            vec![
+                ("fsync", CheckStatus::OK, true),
+                ("esync", CheckStatus::INFO, false),
+            ]
+        );
+
+        config.runner.fsync = false;
+        let dependencies =
+            evaluate_dependencies(&host, Some(&config), &runtime, &GraphicsReport::default());
+        let fsync = dependencies
+            .iter()
+            .find(|dep| dep.name == "fsync")
+            .expect("fsync check present");
+        assert_eq!(fsync.status, CheckStatus::INFO);
+        assert!(fsync.note.starts_with("disabled in payload (unsupported: "));
+    }
+
+    fn gpu(card: &str, vendor: GpuVendor, driver: &str, driver_kind: DriverKind) -> GpuDevice {
+        GpuDevice {
+            card: card.to_string(),
+            vendor: Some(vendor),
+            pci_id: None,
+            driver: Some(driver.to_string()),
+            driver_kind,
+        }
+    }
+
+    fn amd_desktop() -> GraphicsReport {
+        let icd = |arch: &str, library: &str| VulkanIcd {
+            manifest_path: format!("/usr/share/vulkan/icd.d/radeon_icd.{arch}.json"),
+            library_path: Some(library.to_string()),
+            api_version: None,
+            arch: Some(arch.to_string()),
+            driver_kind: DriverKind::Mesa,
+        };
+        GraphicsReport {
+            devices: vec![gpu("card0", GpuVendor::Amd, "amdgpu", DriverKind::Mesa)],
+            vulkan_icds: vec![
+                icd("x86_64", "/usr/lib/libvulkan_radeon.so"),
+                icd("i686", "/usr/lib32/libvulkan_radeon.so"),
+            ],
+            hybrid_gpu: false,
+        }
+    }
+
     fn assert_capability_state_matrix(
         name: &str,
         available_probe: CapabilityProbe,
@@ -935,6 +1389,7 @@ mod tests {
             selected_runtime,
             runtime_status: CheckStatus::INFO,
             runtime_note: "test runtime".to_string(),
+            remediation: Vec::new(),
         }
     }
 }
@@ -0,0 +1,373 @@
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::GpuVendor;
+use crate::host_env::HostEnvironment;
+use crate::host_rules::is_card_name;
+
+const DRM_CLASS_DIR: &str = "/sys/class/drm";
+
+/// Searched in this order, like the Vulkan loader does when neither
+/// `VK_DRIVER_FILES` nor `VK_ICD_FILENAMES` is set.
+const VULKAN_ICD_DIRS: &[&str] = &[
+    "/etc/vulkan/icd.d",
+    "/usr/local/share/vulkan/icd.d",
+    "/usr/share/vulkan/icd.d",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DriverKind {
+    Mesa,
+    Nvidia,
+    Unknown,
+}
+
+/// One DRM card from `/sys/class/drm`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct GpuDevice {
+    /// `card0`, `card1`, ...
+    pub card: String,
+    pub vendor: Option<GpuVendor>,
+    /// PCI `vendor:device`, e.g. `1002:73bf`.
+    pub pci_id: Option<String>,
+    /// Kernel driver bound to the device, e.g. `amdgpu` or `nvidia`.
+    pub driver: Option<String>,
+    pub driver_kind: DriverKind,
+}
+
+/// One Vulkan ICD manifest.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct VulkanIcd {
+    pub manifest_path: String,
+    pub library_path: Option<String>,
+    pub api_version: Option<String>,
+    /// `x86_64`, `i686`, ... from the manifest name or library path; `None`
+    /// when the manifest serves every architecture (as NVIDIA's does).
+    pub arch: Option<String>,
+    pub driver_kind: DriverKind,
+}
+
+impl VulkanIcd {
+    pub fn serves_64bit(&self) -> bool {
+        self.arch.as_deref().is_none_or(|arch| !is_32bit_arch(arch))
+    }
+
+    pub fn serves_32bit(&self) -> bool {
+        self.arch.as_deref().is_none_or(is_32bit_arch)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct GraphicsReport {
+    #[serde(default)]
+    pub devices: Vec<GpuDevice>,
+    #[serde(default)]
+    pub vulkan_icds: Vec<VulkanIcd>,
+    /// More than one GPU with a known vendor, e.g. an iGPU plus a dGPU.
+    #[serde(default)]
+    pub hybrid_gpu: bool,
+}
+
+impl GraphicsReport {
+    pub fn has_nvidia_driver(&self) -> bool {
+        self.devices
+            .iter()
+            .any(|device| device.driver_kind == DriverKind::Nvidia)
+    }
+
+    /// `amd (amdgpu) + nvidia (nvidia)`, for doctor notes.
+    pub fn describe_devices(&self) -> String {
+        self.devices
+            .iter()
+            .filter(|device| device.vendor.is_some())
+            .map(|device| {
+                let vendor = device.vendor.map(vendor_label).unwrap_or("unknown");
+                match &device.driver {
+                    Some(driver) => format!("{vendor} ({driver})"),
+                    None => vendor.to_string(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" + ")
+    }
+}
+
+pub(super) fn probe_graphics(host: &dyn HostEnvironment) -> GraphicsReport {
+    let devices = detect_gpu_devices(host, Path::new(DRM_CLASS_DIR));
+    let hybrid_gpu = devices
+        .iter()
+        .filter(|device| device.vendor.is_some())
+        .count()
+        > 1;
+
+    GraphicsReport {
+        devices,
+        vulkan_icds: discover_vulkan_icds(host),
+        hybrid_gpu,
+    }
+}
+
+fn detect_gpu_devices(host: &dyn HostEnvironment, drm_dir: &Path) -> Vec<GpuDevice> {
+    let mut cards = host
+        .read_dir(drm_dir)
+        .into_iter()
+        .filter_map(|path| path.file_name()?.to_str().map(str::to_string))
+        .filter(|name| is_card_name(name))
+        .collect::<Vec<_>>();
+    cards.sort();
+
+    cards
+        .into_iter()
+        .map(|card| {
+            let device_dir = drm_dir.join(&card).join("device");
+            let vendor_id = host.read_to_string(&device_dir.join("vendor"));
+            let device_id = host.read_to_string(&device_dir.join("device"));
+            let driver = host
+                .read_link(&device_dir.join("driver"))
+                .and_then(|target| target.file_name()?.to_str().map(str::to_string));
+            GpuDevice {
+                vendor: vendor_id.as_deref().and_then(GpuVendor::from_pci_vendor_id),
+                pci_id: vendor_id.zip(device_id).map(|(vendor, device)| {
+                    format!(
+                        "{}:{}",
+                        strip_hex_prefix(&vendor),
+                        strip_hex_prefix(&device)
+                    )
+                }),
+                driver_kind: driver
+                    .as_deref()
+                    .map(kernel_driver_kind)
+                    .unwrap_or(DriverKind::Unknown),
+                driver,
+                card,
+            }
+        })
+        .collect()
+}
+
+fn discover_vulkan_icds(host: &dyn HostEnvironment) -> Vec<VulkanIcd> {
+    let manifests = match ["VK_DRIVER_FILES", "VK_ICD_FILENAMES"]
+        .iter()
+        .find_map(|key| host.env_var(key).filter(|value| !value.trim().is_empty()))
+    {
+        Some(value) => std::env::split_paths(&value)
+            .flat_map(|path| icd_manifests_in(host, path))
+            .collect::<Vec<_>>(),
+        None => VULKAN_ICD_DIRS
+            .iter()
+            .flat_map(|dir| icd_manifests_in(host, PathBuf::from(dir)))
+            .collect(),
+    };
+
+    manifests
+        .into_iter()
+        .filter_map(|path| read_vulkan_icd(host, &path))
+        .collect()
+}
+
+/// The manifest itself, or the `.json` files of a directory sorted by name.
+fn icd_manifests_in(host: &dyn HostEnvironment, path: PathBuf) -> Vec<PathBuf> {
+    if host.is_file(&path) {
+        return vec![path];
+    }
+    let mut files = host
+        .read_dir(&path)
+        .into_iter()
+        .filter(|entry| entry.extension().is_some_and(|ext| ext == "json"))
+        .filter(|entry| host.is_file(entry))
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+fn read_vulkan_icd(host: &dyn HostEnvironment, manifest: &Path) -> Option<VulkanIcd> {
+    let raw = host.read_to_string(manifest)?;
+    let json = serde_json::from_str::<Value>(&raw).ok()?;
+    let icd = json.get("ICD")?;
+    let library_path = icd
+        .get("library_path")
+        .and_then(Value::as_str)
+        .map(str::to_string);
+    let file_name = manifest
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+
+    Some(VulkanIcd {
+        manifest_path: manifest.to_string_lossy().into_owned(),
+        api_version: icd
+            .get("api_version")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        arch: icd_arch(file_name, library_path.as_deref()),
+        driver_kind: icd_driver_kind(file_name, library_path.as_deref()),
+        library_path,
+    })
+}
+
+/// Mesa names its manifests `<driver>_icd.<arch>.json`; other drivers only
+/// give the architecture away through the library directory.
+fn icd_arch(file_name: &str, library_path: Option<&str>) -> Option<String> {
+    let stem = file_name.strip_suffix(".json").unwrap_or(file_name);
+    if let Some((_, arch)) = stem.rsplit_once('.') {
+        if ["x86_64", "i686", "i386", "aarch64", "armv7l", "armv7h"].contains(&arch) {
+            return Some(arch.to_string());
+        }
+    }
+
+    let library = library_path?.to_ascii_lowercase();
+    if ["/lib32/", "/i386-linux-gnu/", "/i686-linux-gnu/"]
+        .iter()
+        .any(|dir| library.contains(dir))
+    {
+        Some("i686".to_string())
+    } else if ["/lib64/", "/x86_64-linux-gnu/"]
+        .iter()
+        .any(|dir| library.contains(dir))
+    {
+        Some("x86_64".to_string())
+    } else {
+        None
+    }
+}
+
+fn icd_driver_kind(file_name: &str, library_path: Option<&str>) -> DriverKind {
+    let library = library_path
+        .and_then(|path| path.rsplit('/').next())
+        .unwrap_or_default();
+    if library.contains("nvidia") || file_name.starts_with("nvidia") {
+        DriverKind::Nvidia
+    } else if library.starts_with("libvulkan_") {
+        DriverKind::Mesa
+    } else {
+        DriverKind::Unknown
+    }
+}
+
+fn kernel_driver_kind(driver: &str) -> DriverKind {
+    match driver {
+        "nvidia" => DriverKind::Nvidia,
+        "amdgpu" | "radeon" | "i915" | "xe" | "nouveau" | "virtio_gpu" | "vmwgfx" | "msm"
+        | "asahi" | "panfrost" | "v3d" => DriverKind::Mesa,
+        _ => DriverKind::Unknown,
+    }
+}
+
+fn is_32bit_arch(arch: &str) -> bool {
+    matches!(arch, "i686" | "i386" | "armv7l" | "armv7h")
+}
+
+fn strip_hex_prefix(value: &str) -> String {
+    let value = value.trim().to_ascii_lowercase();
+    value
+        .strip_prefix("0x")
+        .map(str::to_string)
+        .unwrap_or(value)
+}
+
+pub(super) fn vendor_label(vendor: GpuVendor) -> &'static str {
+    match vendor {
+        GpuVendor::Nvidia => "nvidia",
+        GpuVendor::Amd => "amd",
+        GpuVendor::Intel => "intel",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host_env::FixtureHost;
+
+    fn hybrid_laptop() -> FixtureHost {
+        FixtureHost::from_json(
+            br#"{
+                "files": {
+                    "/sys/class/drm/card0/device/vendor": { "contents": "0x8086\n" },
+                    "/sys/class/drm/card0/device/device": { "contents": "0x9a49\n" },
+                    "/sys/class/drm/card1/device/vendor": { "contents": "0x10de\n" },
+                    "/sys/class/drm/card1/device/device": { "contents": "0x25a2\n" },
+                    "/sys/class/drm/card1-eDP-1/status": { "contents": "connected\n" },
+                    "/usr/share/vulkan/icd.d/intel_icd.x86_64.json": {
+                        "contents": "{\"ICD\":{\"library_path\":\"/usr/lib/libvulkan_intel.so\",\"api_version\":\"1.3.278\"}}"
+                    },
+                    "/usr/share/vulkan/icd.d/intel_icd.i686.json": {
+                        "contents": "{\"ICD\":{\"library_path\":\"/usr/lib32/libvulkan_intel.so\"}}"
+                    },
+                    "/usr/share/vulkan/icd.d/nvidia_icd.json": {
+                        "contents": "{\"ICD\":{\"library_path\":\"libGLX_nvidia.so.0\"}}"
+                    },
+                    "/usr/share/vulkan/icd.d/README": { "contents": "not a manifest" }
+                },
+                "links": {
+                    "/sys/class/drm/card0/device/driver": "../../../bus/pci/drivers/i915",
+                    "/sys/class/drm/card1/device/driver": "../../../bus/pci/drivers/nvidia"
+                }
+            }"#,
+        )
+        .expect("fixture parses")
+    }
+
+    #[test]
+    fn probes_drm_devices_and_vulkan_icds() {
+        let report = probe_graphics(&hybrid_laptop());
+
+        assert!(report.hybrid_gpu);
+        assert!(report.has_nvidia_driver());
+        assert_eq!(report.describe_devices(), "intel (i915) + nvidia (nvidia)");
+        assert_eq!(report.devices[1].pci_id.as_deref(), Some("10de:25a2"));
+        assert_eq!(report.devices[0].driver_kind, DriverKind::Mesa);
+
+        let icds = report
+            .vulkan_icds
+            .iter()
+            .map(|icd| (icd.arch.as_deref(), icd.driver_kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            icds,
+            vec![
+                (Some("i686"), DriverKind::Mesa),
+                (Some("x86_64"), DriverKind::Mesa),
+                (None, DriverKind::Nvidia),
+            ]
+        );
+        assert!(report.vulkan_icds[0].serves_32bit());
+        assert!(!report.vulkan_icds[0].serves_64bit());
+        assert!(report.vulkan_icds[2].serves_32bit() && report.vulkan_icds[2].serves_64bit());
+    }
+
+    #[test]
+    fn driver_override_env_replaces_icd_search_dirs() {
+        let mut host = hybrid_laptop();
+        host.env.insert(
+            "VK_DRIVER_FILES".to_string(),
+            "/usr/share/vulkan/icd.d/nvidia_icd.json".to_string(),
+        );
+
+        let report = probe_graphics(&host);
+        assert_eq!(report.vulkan_icds.len(), 1);
+        assert_eq!(report.vulkan_icds[0].driver_kind, DriverKind::Nvidia);
+    }
+
+    #[test]
+    fn derives_icd_arch_from_name_or_library_dir() {
+        assert_eq!(
+            icd_arch("radeon_icd.x86_64.json", None).as_deref(),
+            Some("x86_64")
+        );
+        assert_eq!(
+            icd_arch(
+                "radeon_icd.json",
+                Some("/usr/lib/i386-linux-gnu/libvulkan_radeon.so")
+            )
+            .as_deref(),
+            Some("i686")
+        );
+        assert_eq!(
+            icd_arch("nvidia_icd.json", Some("libGLX_nvidia.so.0")),
+            None
+        );
+    }
+}
@@ -0,0 +1,404 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{RuntimeCandidate, SessionType};
+use crate::host_env::HostEnvironment;
+use crate::host_rules::detect_session_type;
+
+use super::{CheckStatus, DoctorReport};
+
+/// Doctor runs kept per game, oldest dropped first.
+pub const DOCTOR_HISTORY_LIMIT: usize = 20;
+
+/// The parts of a doctor report that explain why a launch stopped working.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DoctorSnapshot {
+    pub generated_at: String,
+    pub summary: CheckStatus,
+    pub selected_runtime: Option<RuntimeCandidate>,
+    pub proton: Option<String>,
+    /// Name of the Proton build directory, after following symlinks such as
+    /// Heroic's `GE-Proton-latest`.
+    pub proton_version: Option<String>,
+    pub wine: Option<String>,
+    pub umu_run: Option<String>,
+    pub session_type: Option<SessionType>,
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, DependencySnapshot>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencySnapshot {
+    pub status: CheckStatus,
+    pub found: bool,
+    #[serde(default)]
+    pub resolved_path: Option<String>,
+}
+
+impl DoctorSnapshot {
+    pub fn capture(report: &DoctorReport, host: &dyn HostEnvironment) -> Self {
+        let proton_version = report.runtime.proton.as_deref().and_then(|proton| {
+            let proton = Path::new(proton);
+            let resolved = host
+                .canonicalize(proton)
+                .unwrap_or_else(|| proton.to_path_buf());
+            resolved
+                .parent()
+                .and_then(Path::file_name)
+                .map(|name| name.to_string_lossy().into_owned())
+        });
+
+        Self {
+            generated_at: report.generated_at.clone(),
+            summary: report.summary,
+            selected_runtime: report.runtime.selected_runtime,
+            proton: report.runtime.proton.clone(),
+            proton_version,
+            wine: report.runtime.wine.clone(),
+            umu_run: report.runtime.umu_run.clone(),
+            session_type: detect_session_type(host),
+            dependencies: report
+                .dependencies
+                .iter()
+                .map(|dep| {
+                    (
+                        dep.name.clone(),
+                        DependencySnapshot {
+                            status: dep.status,
+                            found: dep.found,
+                            resolved_path: dep.resolved_path.clone(),
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Recent doctor runs of one game and the run that preceded its last
+/// successful launch.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DoctorHistory {
+    /// Oldest first.
+    #[serde(default)]
+    pub runs: Vec<DoctorSnapshot>,
+    #[serde(default)]
+    pub last_successful_launch: Option<DoctorSnapshot>,
+}
+
+impl DoctorHistory {
+    pub fn record_run(&mut self, snapshot: DoctorSnapshot) {
+        self.runs.push(snapshot);
+        let excess = self.runs.len().saturating_sub(DOCTOR_HISTORY_LIMIT);
+        self.runs.drain(..excess);
+    }
+
+    pub fn latest_run(&self) -> Option<&DoctorSnapshot> {
+        self.runs.last()
+    }
+
+    /// Changes from the last successful launch to the latest run; `None`
+    /// until both exist.
+    pub fn drift_since_last_successful_launch(&self) -> Option<Vec<DriftEvent>> {
+        let baseline = self.last_successful_launch.as_ref()?;
+        let latest = self.latest_run()?;
+        Some(diff_snapshots(baseline, latest))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftKind {
+    RuntimeChanged,
+    PathChanged,
+    VersionChanged,
+    SessionChanged,
+    DependencyLost,
+    DependencyAppeared,
+    StatusChanged,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DriftEvent {
+    pub kind: DriftKind,
+    /// Runtime or check the change is about, e.g. `proton` or `mangohud`.
+    pub subject: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl DriftEvent {
+    fn new(kind: DriftKind, subject: &str, before: Option<String>, after: Option<String>) -> Self {
+        Self {
+            kind,
+            subject: subject.to_string(),
+            before,
+            after,
+        }
+    }
+
+    /// One line for terminal output.
+    pub fn describe(&self) -> String {
+        let value = |value: &Option<String>| value.as_deref().unwrap_or("none").to_string();
+        let change = format!("{} -> {}", value(&self.before), value(&self.after));
+        match self.kind {
+            DriftKind::RuntimeChanged => format!("selected runtime changed: {change}"),
+            DriftKind::PathChanged => format!("{} path changed: {change}", self.subject),
+            DriftKind::VersionChanged => format!("{} version changed: {change}", self.subject),
+            DriftKind::SessionChanged => format!("display session changed: {change}"),
+            DriftKind::DependencyLost => {
+                format!("{} disappeared (was {})", self.subject, value(&self.before))
+            }
+            DriftKind::DependencyAppeared => {
+                format!("{} appeared ({})", self.subject, value(&self.after))
+            }
+            DriftKind::StatusChanged => format!("{} status changed: {change}", self.subject),
+        }
+    }
+}
+
+/// What changed on the host between two doctor runs, runtime first, then
+/// dependencies by name.
+pub fn diff_snapshots(baseline: &DoctorSnapshot, current: &DoctorSnapshot) -> Vec<DriftEvent> {
+    let mut events = Vec::new();
+
+    if baseline.selected_runtime != current.selected_runtime {
+        events.push(DriftEvent::new(
+            DriftKind::RuntimeChanged,
+            "runtime",
+            baseline.selected_runtime.map(runtime_label),
+            current.selected_runtime.map(runtime_label),
+        ));
+    }
+    if baseline.proton_version != current.proton_version {
+        events.push(DriftEvent::new(
+            DriftKind::VersionChanged,
+            "proton",
+            baseline.proton_version.clone(),
+            current.proton_version.clone(),
+        ));
+    }
+    for (subject, before, after) in [
+        ("proton", &baseline.proton, &current.proton),
+        ("wine", &baseline.wine, &current.wine),
+        ("umu-run", &baseline.umu_run, &current.umu_run),
+    ] {
+        if before != after {
+            events.push(DriftEvent::new(
+                DriftKind::PathChanged,
+                subject,
+                before.clone(),
+                after.clone(),
+            ));
+        }
+    }
+    if baseline.session_type != current.session_type {
+        events.push(DriftEvent::new(
+            DriftKind::SessionChanged,
+            "session",
+            baseline.session_type.map(session_label),
+            current.session_type.map(session_label),
+        ));
+    }
+
+    for (name, before) in &baseline.dependencies {
+        let after = current.dependencies.get(name);
+        let found_after = after.is_some_and(|dep| dep.found);
+        if before.found && !found_after {
+            events.push(DriftEvent::new(
+                DriftKind::DependencyLost,
+                name,
+                before
+                    .resolved_path
+                    .clone()
+                    .or_else(|| Some("found".to_string())),
+                None,
+            ));
+            continue;
+        }
+        let Some(after) = after else {
+            continue;
+        };
+        if !before.found && after.found {
+            events.push(DriftEvent::new(
+                DriftKind::DependencyAppeared,
+                name,
+                None,
+                after
+                    .resolved_path
+                    .clone()
+                    .or_else(|| Some("found".to_string())),
+            ));
+            continue;
+        }
+        if before.found && before.resolved_path != after.resolved_path {
+            events.push(DriftEvent::new(
+                DriftKind::PathChanged,
+                name,
+                before.resolved_path.clone(),
+                after.resolved_path.clone(),
+            ));
+        }
+        if before.status != after.status {
+            events.push(DriftEvent::new(
+                DriftKind::StatusChanged,
+                name,
+                Some(status_label(before.status).to_string()),
+                Some(status_label(after.status).to_string()),
+            ));
+        }
+    }
+
+    events
+}
+
+fn runtime_label(runtime: RuntimeCandidate) -> String {
+    match runtime {
+        RuntimeCandidate::ProtonUmu => "proton-umu",
+        RuntimeCandidate::ProtonNative => "proton-native",
+        RuntimeCandidate::Wine => "wine",
+    }
+    .to_string()
+}
+
+fn session_label(session: SessionType) -> String {
+    match session {
+        SessionType::Wayland => "wayland",
+        SessionType::X11 => "x11",
+        SessionType::Tty => "tty",
+    }
+    .to_string()
+}
+
+fn status_label(status: CheckStatus) -> &'static str {
+    match status {
+        CheckStatus::OK => "OK",
+        CheckStatus::WARN => "WARN",
+        CheckStatus::BLOCKER => "BLOCKER",
+        CheckStatus::INFO => "INFO",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host_env::FixtureHost;
+
+    fn snapshot() -> DoctorSnapshot {
+        DoctorSnapshot {
+            generated_at: "2026-10-16T20:00:00.000Z".to_string(),
+            summary: CheckStatus::OK,
+            selected_runtime: Some(RuntimeCandidate::ProtonUmu),
+            proton: Some("/home/deck/.steam/root/compatibilitytools.d/GE-Proton10-3/proton".into()),
+            proton_version: Some("GE-Proton10-3".to_string()),
+            wine: None,
+            umu_run: Some("/usr/bin/umu-run".to_string()),
+            session_type: Some(SessionType::Wayland),
+            dependencies: BTreeMap::from([
+                (
+                    "mangohud".to_string(),
+                    DependencySnapshot {
+                        status: CheckStatus::OK,
+                        found: true,
+                        resolved_path: Some("/usr/bin/mangohud".to_string()),
+                    },
+                ),
+                (
+                    "gamescope".to_string(),
+                    DependencySnapshot {
+                        status: CheckStatus::INFO,
+                        found: false,
+                        resolved_path: None,
+                    },
+                ),
+            ]),
+        }
+    }
+
+    #[test]
+    fn identical_snapshots_have_no_drift() {
+        assert!(diff_snapshots(&snapshot(), &snapshot()).is_empty());
+    }
+
+    #[test]
+    fn reports_proton_session_and_dependency_drift() {
+        let baseline = snapshot();
+        let mut current = snapshot();
+        current.proton =
+            Some("/home/deck/.steam/root/compatibilitytools.d/GE-Proton10-4/proton".into());
+        current.proton_version = Some("GE-Proton10-4".to_string());
+        current.session_type = Some(SessionType::X11);
+        current.dependencies.insert(
+            "mangohud".to_string(),
+            DependencySnapshot {
+                status: CheckStatus::WARN,
+                found: false,
+                resolved_path: None,
+            },
+        );
+
+        let events = diff_snapshots(&baseline, &current);
+        let descriptions = events.iter().map(DriftEvent::describe).collect::<Vec<_>>();
+
+        assert_eq!(
+            descriptions,
+            vec![
+                "proton version changed: GE-Proton10-3 -> GE-Proton10-4",
+                "proton path changed: /home/deck/.steam/root/compatibilitytools.d/GE-Proton10-3/proton -> /home/deck/.steam/root/compatibilitytools.d/GE-Proton10-4/proton",
+                "display session changed: wayland -> x11",
+                "mangohud disappeared (was /usr/bin/mangohud)",
+            ]
+        );
+        assert_eq!(events[3].kind, DriftKind::DependencyLost);
+    }
+
+    #[test]
+    fn history_keeps_the_newest_runs_and_diffs_against_the_last_launch() {
+        let mut history = DoctorHistory::default();
+        assert!(history.drift_since_last_successful_launch().is_none());
+
+        for index in 0..DOCTOR_HISTORY_LIMIT + 3 {
+            let mut run = snapshot();
+            run.generated_at = format!("run-{index}");
+            history.record_run(run);
+        }
+        assert_eq!(history.runs.len(), DOCTOR_HISTORY_LIMIT);
+        assert_eq!(history.runs[0].generated_at, "run-3");
+
+        history.last_successful_launch = Some(snapshot());
+        let mut latest = snapshot();
+        latest.selected_runtime = Some(RuntimeCandidate::Wine);
+        history.record_run(latest);
+
+        let drift = history
+            .drift_since_last_successful_launch()
+            .expect("baseline and latest run exist");
+        assert_eq!(drift.len(), 1);
+        assert_eq!(
+            drift[0].describe(),
+            "selected runtime changed: proton-umu -> wine"
+        );
+    }
+
+    #[test]
+    fn capture_resolves_the_proton_build_behind_symlinks() {
+        let host = FixtureHost::from_json(
+            br#"{
+                "env": { "WAYLAND_DISPLAY": "wayland-0" },
+                "files": { "/tools/GE-Proton10-3/proton": { "executable": true } },
+                "links": { "/tools/GE-Proton-latest": "/tools/GE-Proton10-3" }
+            }"#,
+        )
+        .expect("fixture parses");
+        let mut report = super::super::run_doctor_with_host(None, &host);
+        report.runtime.proton = Some("/tools/GE-Proton-latest/proton".to_string());
+
+        let snapshot = DoctorSnapshot::capture(&report, &host);
+
+        assert_eq!(snapshot.proton_version.as_deref(), Some("GE-Proton10-3"));
+        assert_eq!(snapshot.session_type, Some(SessionType::Wayland));
+        assert_eq!(snapshot.dependencies.len(), report.dependencies.len());
+    }
+}
@@ -1,66 +1,68 @@
 use std::env;
-use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-pub(super) fn discover_umu() -> Option<PathBuf> {
-    if let Some(from_env) = env::var_os("UMU_RUNTIME") {
+use crate::host_env::HostEnvironment;
+
+pub(super) fn discover_umu(host: &dyn HostEnvironment) -> Option<PathBuf> {
+    if let Some(from_env) = host.env_var("UMU_RUNTIME") {
         let candidate = PathBuf::from(from_env);
-        if is_executable_file(&candidate) {
+        if host.is_executable(&candidate) {
             return Some(candidate);
         }
     }
 
-    find_in_path("umu-run")
+    host.find_executable("umu-run")
 }
 
-pub(super) fn discover_wine() -> Option<PathBuf> {
-    if let Some(from_env) = env::var_os("WINE") {
+pub(super) fn discover_wine(host: &dyn HostEnvironment) -> Option<PathBuf> {
+    if let Some(from_env) = host.env_var("WINE") {
         let candidate = PathBuf::from(from_env);
-        if is_executable_file(&candidate) {
+        if host.is_executable(&candidate) {
             return Some(candidate);
         }
     }
 
-    find_in_path("wine")
-        .or_else(|| existing_executable_path("/usr/bin/wine"))
-        .or_else(|| existing_executable_path("/usr/local/bin/wine"))
-        .or_else(|| home_relative_executable(".local/bin/wine"))
+    host.find_executable("wine")
+        .or_else(|| existing_executable_path(host, "/usr/bin/wine"))
+        .or_else(|| existing_executable_path(host, "/usr/local/bin/wine"))
+        .or_else(|| home_relative_executable(host, ".local/bin/wine"))
 }
 
 pub(super) fn discover_proton_with_preference(
+    host: &dyn HostEnvironment,
     requested_version: Option<&str>,
 ) -> (Option<PathBuf>, bool) {
     let requested_version = requested_version.map(str::trim).filter(|v| !v.is_empty());
 
     if let Some(requested) = requested_version {
-        if let Some(found) = find_proton_by_requested_version(requested) {
+        if let Some(found) = find_proton_by_requested_version(host, requested) {
             return (Some(found), true);
         }
 
-        return (discover_latest_proton(), false);
+        return (discover_latest_proton(host), false);
     }
 
-    (discover_latest_proton(), false)
+    (discover_latest_proton(host), false)
 }
 
-fn discover_latest_proton() -> Option<PathBuf> {
-    if let Some(from_env) = env::var_os("PROTONPATH") {
-        if let Some(path) = proton_from_path(PathBuf::from(from_env)) {
+fn discover_latest_proton(host: &dyn HostEnvironment) -> Option<PathBuf> {
+    if let Some(from_env) = host.env_var("PROTONPATH") {
+        if let Some(path) = proton_from_path(host, PathBuf::from(from_env)) {
             return Some(path);
         }
     }
 
-    if let Some(paths) = env::var_os("STEAM_COMPAT_TOOL_PATHS") {
+    if let Some(paths) = host.env_var("STEAM_COMPAT_TOOL_PATHS") {
         for p in env::split_paths(&paths) {
-            if let Some(path) = proton_from_path(p) {
+            if let Some(path) = proton_from_path(host, p) {
                 return Some(path);
             }
         }
     }
 
-    for root in known_proton_roots() {
-        if let Some(found) = find_latest_proton_from_root(&root) {
+    for root in known_proton_roots(host) {
+        if let Some(found) = find_latest_proton_from_root(host, &root) {
             return Some(found);
         }
     }
@@ -68,23 +70,26 @@ fn discover_latest_proton() -> Option<PathBuf> {
     None
 }
 
-fn find_proton_by_requested_version(requested_version: &str) -> Option<PathBuf> {
-    if let Some(direct) = proton_from_path(PathBuf::from(requested_version)) {
+fn find_proton_by_requested_version(
+    host: &dyn HostEnvironment,
+    requested_version: &str,
+) -> Option<PathBuf> {
+    if let Some(direct) = proton_from_path(host, PathBuf::from(requested_version)) {
         return Some(direct);
     }
 
-    if let Some(from_env) = env::var_os("PROTONPATH") {
-        if let Some(path) = proton_from_path(PathBuf::from(from_env)) {
-            if proton_path_matches_requested_version(&path, requested_version) {
+    if let Some(from_env) = host.env_var("PROTONPATH") {
+        if let Some(path) = proton_from_path(host, PathBuf::from(from_env)) {
+            if proton_path_matches_requested_version(host, &path, requested_version) {
                 return Some(path);
             }
         }
     }
 
-    if let Some(paths) = env::var_os("STEAM_COMPAT_TOOL_PATHS") {
+    if let Some(paths) = host.env_var("STEAM_COMPAT_TOOL_PATHS") {
         for p in env::split_paths(&paths) {
-            if let Some(path) = proton_from_path(p) {
-                if proton_path_matches_requested_version(&path, requested_version) {
+            if let Some(path) = proton_from_path(host, p) {
+                if proton_path_matches_requested_version(host, &path, requested_version) {
                     return Some(path);
                 }
             }
@@ -94,15 +99,9 @@ fn find_proton_by_requested_version(requested_version: &str) -> Option<PathBuf>
     let mut exact: Option<(SystemTime, PathBuf)> = None;
     let mut fuzzy: Option<(SystemTime, PathBuf)> = None;
 
-    for root in known_proton_roots() {
-        let entries = match fs::read_dir(&root) {
-            Ok(entries) => entries,
-            Err(_) => continue,
-        };
-
-        for entry in entries.flatten() {
-            let entry_path = entry.path();
-            let proton_path = match proton_from_path(entry_path) {
+    for root in known_proton_roots(host) {
+        for entry_path in host.read_dir(&root) {
+            let proton_path = match proton_from_path(host, entry_path) {
                 Some(path) => path,
                 None => continue,
             };
@@ -114,7 +113,7 @@ fn find_proton_by_requested_version(requested_version: &str) -> Option<PathBuf>
                 .unwrap_or_default()
                 .to_ascii_lowercase();
             let requested_lower = requested_version.to_ascii_lowercase();
-            let modified = path_modified_or_epoch(&proton_path);
+            let modified = path_modified_or_epoch(host, &proton_path);
 
             if parent_name == requested_lower {
                 match &exact {
@@ -126,7 +125,7 @@ fn find_proton_by_requested_version(requested_version: &str) -> Option<PathBuf>
                 continue;
             }
 
-            if proton_path_matches_requested_version(&proton_path, requested_version) {
+            if proton_path_matches_requested_version(host, &proton_path, requested_version) {
                 match &fuzzy {
                     Some((best_modified, best_path))
                         if modified < *best_modified
@@ -140,10 +139,10 @@ fn find_proton_by_requested_version(requested_version: &str) -> Option<PathBuf>
     exact.or(fuzzy).map(|(_, path)| path)
 }
 
-fn known_proton_roots() -> Vec<PathBuf> {
+fn known_proton_roots(host: &dyn HostEnvironment) -> Vec<PathBuf> {
     let mut out = Vec::new();
 
-    if let Some(home) = env::var_os("HOME") {
+    if let Some(home) = host.env_var("HOME") {
         let home = PathBuf::from(home);
         // Heroic (native package)
         out.push(home.join(".config/heroic/tools/proton"));
@@ -161,20 +160,24 @@ fn known_proton_roots() -> Vec<PathBuf> {
     out
 }
 
-fn proton_from_path(path: PathBuf) -> Option<PathBuf> {
-    if is_executable_file(&path) {
+fn proton_from_path(host: &dyn HostEnvironment, path: PathBuf) -> Option<PathBuf> {
+    if host.is_executable(&path) {
         return Some(path);
     }
 
     let proton = path.join("proton");
-    if is_executable_file(&proton) {
+    if host.is_executable(&proton) {
         return Some(proton);
     }
 
     None
 }
 
-fn proton_path_matches_requested_version(proton_path: &Path, requested_version: &str) -> bool {
+fn proton_path_matches_requested_version(
+    host: &dyn HostEnvironment,
+    proton_path: &Path,
+    requested_version: &str,
+) -> bool {
     let requested = requested_version.trim();
     if requested.is_empty() {
         return false;
@@ -193,7 +196,7 @@ fn proton_path_matches_requested_version(proton_path: &Path, requested_version:
 
     // Heroic commonly exposes "GE-Proton-latest" as a symlink. Match against the canonical
     // target path too so a request like "GE-Proton10-32" resolves correctly.
-    if let Ok(canonical) = proton_path.canonicalize() {
+    if let Some(canonical) = host.canonicalize(proton_path) {
         let canonical_string = canonical.to_string_lossy().to_ascii_lowercase();
         if canonical_string == requested_lower || canonical_string.contains(&requested_lower) {
             return true;
@@ -220,14 +223,12 @@ fn proton_path_matches_requested_version(proton_path: &Path, requested_version:
     parent_name == requested_lower || parent_name.contains(&requested_lower)
 }
 
-fn find_latest_proton_from_root(root: &Path) -> Option<PathBuf> {
-    let entries = fs::read_dir(root).ok()?;
+fn find_latest_proton_from_root(host: &dyn HostEnvironment, root: &Path) -> Option<PathBuf> {
     let mut best: Option<(SystemTime, PathBuf)> = None;
 
-    for entry in entries.flatten() {
-        let path = entry.path();
-        if let Some(proton) = proton_from_path(path) {
-            let modified = path_modified_or_epoch(&proton);
+    for path in host.read_dir(root) {
+        if let Some(proton) = proton_from_path(host, path) {
+            let modified = path_modified_or_epoch(host, &proton);
             match &best {
                 Some((best_modified, best_path))
                     if modified < *best_modified
@@ -240,61 +241,28 @@ fn find_latest_proton_from_root(root: &Path) -> Option<PathBuf> {
     best.map(|(_, path)| path)
 }
 
-pub(super) fn find_in_path(bin_name: &str) -> Option<PathBuf> {
-    let paths = env::var_os("PATH")?;
-
-    for dir in env::split_paths(&paths) {
-        let candidate = dir.join(bin_name);
-        if is_executable_file(&candidate) {
-            return Some(candidate);
-        }
-    }
-
-    None
-}
-
-fn existing_executable_path(path: &str) -> Option<PathBuf> {
+fn existing_executable_path(host: &dyn HostEnvironment, path: &str) -> Option<PathBuf> {
     let path = PathBuf::from(path);
-    if is_executable_file(&path) {
+    if host.is_executable(&path) {
         Some(path)
     } else {
         None
     }
 }
 
-fn home_relative_executable(path: &str) -> Option<PathBuf> {
-    let home = env::var_os("HOME")?;
+fn home_relative_executable(host: &dyn HostEnvironment, path: &str) -> Option<PathBuf> {
+    let home = host.env_var("HOME")?;
     let full = PathBuf::from(home).join(path);
-    if is_executable_file(&full) {
+    if host.is_executable(&full) {
         Some(full)
     } else {
         None
     }
 }
 
-fn is_executable_file(path: &Path) -> bool {
-    if !path.is_file() {
-        return false;
-    }
-
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-        fs::metadata(path)
-            .map(|meta| meta.permissions().mode() & 0o111 != 0)
-            .unwrap_or(false)
-    }
-
-    #[cfg(not(unix))]
-    {
-        true
-    }
-}
-
-fn path_modified_or_epoch(path: &Path) -> SystemTime {
+fn path_modified_or_epoch(host: &dyn HostEnvironment, path: &Path) -> SystemTime {
     path.parent()
-        .and_then(|parent| fs::metadata(parent).ok())
-        .and_then(|meta| meta.modified().ok())
+        .and_then(|parent| host.modified(parent))
         .unwrap_or(UNIX_EPOCH)
 }
 
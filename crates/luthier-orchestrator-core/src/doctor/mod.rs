@@ -1,16 +1,36 @@
 use chrono::{SecondsFormat, Utc};
 
 use crate::config::GameConfig;
+use crate::host_env::{HostEnvironment, SystemHost};
+use crate::host_rules::{evaluate_host_rules, HostFacts};
 
 mod dependency_checks;
+mod graphics;
+mod history;
 mod host_probe;
 mod models;
+mod remediation;
 mod runtime_selection;
 mod status_policy;
+mod sync_primitives;
 
+pub use graphics::{DriverKind, GpuDevice, GraphicsReport, VulkanIcd};
+pub use history::{
+    diff_snapshots, DependencySnapshot, DoctorHistory, DoctorSnapshot, DriftEvent, DriftKind,
+    DOCTOR_HISTORY_LIMIT,
+};
 pub use models::{CheckStatus, DependencyStatus, DoctorReport, RuntimeDiscovery};
+pub use remediation::Remediation;
 
 pub fn run_doctor(config: Option<&GameConfig>) -> DoctorReport {
+    run_doctor_with_host(config, &SystemHost)
+}
+
+/// Runs the doctor against `host` instead of the current machine.
+pub fn run_doctor_with_host(
+    config: Option<&GameConfig>,
+    host: &dyn HostEnvironment,
+) -> DoctorReport {
     let requested_proton_version = config.and_then(|cfg| {
         let value = cfg.runner.proton_version.trim();
         if value.is_empty() {
@@ -20,12 +40,12 @@ pub fn run_doctor(config: Option<&GameConfig>) -> DoctorReport {
         }
     });
     let (proton_path, proton_version_matched) =
-        host_probe::discover_proton_with_preference(requested_proton_version.as_deref());
+        host_probe::discover_proton_with_preference(host, requested_proton_version.as_deref());
     let proton = proton_path.map(host_probe::path_to_string);
-    let wine = host_probe::discover_wine().map(host_probe::path_to_string);
-    let umu_run = host_probe::discover_umu().map(host_probe::path_to_string);
+    let wine = host_probe::discover_wine(host).map(host_probe::path_to_string);
+    let umu_run = host_probe::discover_umu(host).map(host_probe::path_to_string);
 
-    let runtime = runtime_selection::evaluate_runtime(
+    let mut runtime = runtime_selection::evaluate_runtime(
         config,
         proton.clone(),
         wine.clone(),
@@ -34,9 +54,17 @@ pub fn run_doctor(config: Option<&GameConfig>) -> DoctorReport {
         proton_version_matched,
     );
 
-    let dependencies = status_policy::apply_dependency_status_policy(
-        dependency_checks::evaluate_dependencies(config, &runtime),
+    let graphics = graphics::probe_graphics(host);
+    let mut dependencies = status_policy::apply_dependency_status_policy(
+        dependency_checks::evaluate_dependencies(host, config, &runtime, &graphics),
     );
+    let runtime_packages = runtime_selection::missing_runtime_packages(config, &runtime);
+    remediation::attach_remediation(&mut runtime, &runtime_packages, &mut dependencies, host);
+
+    let host_rules = config
+        .filter(|cfg| !cfg.rules.is_empty())
+        .map(|cfg| evaluate_host_rules(&cfg.rules, &HostFacts::detect_with(host)))
+        .unwrap_or_default();
 
     let mut summary = runtime.runtime_status;
     for dep in &dependencies {
@@ -48,6 +76,8 @@ pub fn run_doctor(config: Option<&GameConfig>) -> DoctorReport {
         has_embedded_config: config.is_some(),
         runtime,
         dependencies,
+        graphics,
+        host_rules,
         summary,
     }
 }
@@ -69,6 +99,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn run_doctor_with_host_reads_only_the_described_host() {
+        let host = crate::host_env::FixtureHost::from_json(
+            br#"{
+                "env": { "HOME": "/home/deck", "PATH": "/usr/bin" },
+                "files": {
+                    "/usr/bin/wine": { "executable": true },
+                    "/usr/bin/mangohud": { "executable": true },
+                    "/home/deck/.steam/root/compatibilitytools.d/GE-Proton9-1/proton": { "executable": true, "modified": 100 },
+                    "/home/deck/.steam/root/compatibilitytools.d/GE-Proton10-3/proton": { "executable": true, "modified": 200 }
+                }
+            }"#,
+        )
+        .expect("fixture parses");
+
+        let report = run_doctor_with_host(None, &host);
+
+        assert_eq!(report.runtime.wine.as_deref(), Some("/usr/bin/wine"));
+        assert_eq!(
+            report.runtime.proton.as_deref(),
+            Some("/home/deck/.steam/root/compatibilitytools.d/GE-Proton10-3/proton")
+        );
+        let found = report
+            .dependencies
+            .iter()
+            .filter(|dep| dep.found)
+            .map(|dep| dep.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec!["mangohud"]);
+    }
+
     #[test]
     fn evaluates_component_policies() {
         let missing_mandatory = super::dependency_checks::evaluate_component(
@@ -1,6 +1,10 @@
 use serde::{Deserialize, Serialize};
 
 use crate::config::{FeatureState, RuntimeCandidate};
+use crate::host_rules::HostRuleEvaluation;
+
+use super::graphics::GraphicsReport;
+use super::remediation::Remediation;
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
 pub enum CheckStatus {
@@ -18,6 +22,10 @@ pub struct DependencyStatus {
     pub found: bool,
     pub resolved_path: Option<String>,
     pub note: String,
+    /// How to install the dependency on this host, native package manager
+    /// first. Only filled for WARN and BLOCKER entries the catalog knows.
+    #[serde(default)]
+    pub remediation: Vec<Remediation>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -28,6 +36,10 @@ pub struct RuntimeDiscovery {
     pub selected_runtime: Option<RuntimeCandidate>,
     pub runtime_status: CheckStatus,
     pub runtime_note: String,
+    /// How to install a runtime the config can use, in candidate order. Only
+    /// filled when no runtime was found and the catalog knows a package.
+    #[serde(default)]
+    pub remediation: Vec<Remediation>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -36,5 +48,11 @@ pub struct DoctorReport {
     pub has_embedded_config: bool,
     pub runtime: RuntimeDiscovery,
     pub dependencies: Vec<DependencyStatus>,
+    /// GPUs and Vulkan drivers found on the host.
+    #[serde(default)]
+    pub graphics: GraphicsReport,
+    /// Host rules of the config and whether this host matched them.
+    #[serde(default)]
+    pub host_rules: Vec<HostRuleEvaluation>,
     pub summary: CheckStatus,
 }
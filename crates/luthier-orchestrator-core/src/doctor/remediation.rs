@@ -0,0 +1,296 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::host_env::HostEnvironment;
+use crate::host_rules::detect_os_lineage;
+
+use super::{CheckStatus, DependencyStatus, RuntimeDiscovery};
+
+const BUILTIN_CATALOG: &str = include_str!("remediation_catalog.json");
+/// User catalog merged over the built-in one, relative to `$HOME`.
+const USER_CATALOG: &str = ".local/share/Luthier/remediation.json";
+const PACKAGES_PLACEHOLDER: &str = "{packages}";
+
+/// Packages that provide a missing dependency and the command installing
+/// them with one package manager.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Remediation {
+    /// Catalog family, e.g. `debian` or `flatpak`.
+    pub family: String,
+    pub manager: String,
+    pub packages: Vec<String>,
+    pub command: String,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RemediationCatalog {
+    #[serde(default)]
+    families: BTreeMap<String, DistroFamily>,
+    /// Package names by dependency name, then by family name.
+    #[serde(default)]
+    dependencies: BTreeMap<String, BTreeMap<String, Vec<String>>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DistroFamily {
+    /// os-release `ID`/`ID_LIKE` values of the family. A family without ids,
+    /// such as Flatpak, is offered on every host after the native one.
+    #[serde(default)]
+    ids: Vec<String>,
+    manager: String,
+    /// Install command, with `{packages}` standing for the package names.
+    install: String,
+}
+
+impl RemediationCatalog {
+    fn builtin() -> Self {
+        serde_json::from_str(BUILTIN_CATALOG).unwrap_or_default()
+    }
+
+    /// Built-in catalog plus the user's entries, which win on conflicts. An
+    /// unreadable user catalog is ignored so the doctor still runs.
+    fn load(host: &dyn HostEnvironment) -> Self {
+        let mut catalog = Self::builtin();
+        let user = host
+            .env_var("HOME")
+            .map(|home| PathBuf::from(home).join(USER_CATALOG))
+            .and_then(|path| host.read_to_string(&path))
+            .and_then(|raw| serde_json::from_str::<Self>(&raw).ok());
+        if let Some(user) = user {
+            catalog.merge(user);
+        }
+        catalog
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.families.extend(other.families);
+        for (dependency, packages) in other.dependencies {
+            self.dependencies
+                .entry(dependency)
+                .or_default()
+                .extend(packages);
+        }
+    }
+
+    /// The family of the closest os-release id, then the distro-independent
+    /// families.
+    fn families_for<'a>(&'a self, lineage: &[String]) -> Vec<(&'a str, &'a DistroFamily)> {
+        let native = lineage.iter().find_map(|id| {
+            self.families
+                .iter()
+                .find(|(_, family)| family.ids.contains(id))
+        });
+        native
+            .into_iter()
+            .chain(
+                self.families
+                    .iter()
+                    .filter(|(_, family)| family.ids.is_empty()),
+            )
+            .map(|(name, family)| (name.as_str(), family))
+            .collect()
+    }
+
+    fn remediation_for(
+        &self,
+        dependency: &str,
+        families: &[(&str, &DistroFamily)],
+    ) -> Vec<Remediation> {
+        let Some(packages_by_family) = self
+            .dependencies
+            .get(dependency)
+            .or_else(|| self.dependencies.get(&dependency.to_ascii_lowercase()))
+        else {
+            return Vec::new();
+        };
+
+        families
+            .iter()
+            .filter_map(|(name, family)| {
+                let packages = packages_by_family.get(*name)?;
+                if packages.is_empty() {
+                    return None;
+                }
+                Some(Remediation {
+                    family: name.to_string(),
+                    manager: family.manager.clone(),
+                    packages: packages.clone(),
+                    command: family
+                        .install
+                        .replace(PACKAGES_PLACEHOLDER, &packages.join(" ")),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Fills the remediation of failing dependencies and, when no runtime was
+/// found, of the runtime from the catalog entries named in `runtime_packages`.
+pub(super) fn attach_remediation(
+    runtime: &mut RuntimeDiscovery,
+    runtime_packages: &[&str],
+    dependencies: &mut [DependencyStatus],
+    host: &dyn HostEnvironment,
+) {
+    if runtime_packages.is_empty() && !dependencies.iter().any(needs_remediation) {
+        return;
+    }
+
+    let catalog = RemediationCatalog::load(host);
+    let families = catalog.families_for(&detect_os_lineage(host));
+    runtime.remediation = runtime_packages
+        .iter()
+        .flat_map(|package| catalog.remediation_for(package, &families))
+        .collect();
+    for dependency in dependencies.iter_mut().filter(|dep| needs_remediation(dep)) {
+        dependency.remediation = catalog.remediation_for(&dependency.name, &families);
+    }
+}
+
+fn needs_remediation(dependency: &DependencyStatus) -> bool {
+    matches!(dependency.status, CheckStatus::WARN | CheckStatus::BLOCKER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::RuntimeCandidate;
+    use crate::host_env::FixtureHost;
+
+    fn missing(name: &str) -> DependencyStatus {
+        DependencyStatus {
+            name: name.to_string(),
+            state: None,
+            status: CheckStatus::WARN,
+            found: false,
+            resolved_path: None,
+            note: "not found".to_string(),
+            remediation: Vec::new(),
+        }
+    }
+
+    fn found_runtime() -> RuntimeDiscovery {
+        RuntimeDiscovery {
+            proton: None,
+            wine: Some("/usr/bin/wine".to_string()),
+            umu_run: None,
+            selected_runtime: Some(RuntimeCandidate::Wine),
+            runtime_status: CheckStatus::OK,
+            runtime_note: String::new(),
+            remediation: Vec::new(),
+        }
+    }
+
+    fn host(json: &str) -> FixtureHost {
+        FixtureHost::from_json(json.as_bytes()).expect("fixture parses")
+    }
+
+    #[test]
+    fn builtin_catalog_parses_and_every_entry_names_a_family() {
+        let catalog: RemediationCatalog =
+            serde_json::from_str(BUILTIN_CATALOG).expect("built-in catalog parses");
+        for (family, expected) in [
+            ("debian", "apt"),
+            ("fedora", "dnf"),
+            ("arch", "pacman"),
+            ("suse", "zypper"),
+            ("flatpak", "flatpak"),
+        ] {
+            assert_eq!(catalog.families[family].manager, expected);
+        }
+        for (dependency, packages) in &catalog.dependencies {
+            for family in packages.keys() {
+                assert!(
+                    catalog.families.contains_key(family),
+                    "{dependency} refers to unknown family {family}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn picks_the_native_family_through_id_like_then_flatpak() {
+        let host = host(
+            r#"{ "files": { "/etc/os-release": { "contents": "ID=pop\nID_LIKE=\"ubuntu debian\"\n" } } }"#,
+        );
+        let mut dependencies = vec![missing("mangohud"), missing("esync")];
+        dependencies.push(DependencyStatus {
+            status: CheckStatus::OK,
+            ..missing("gamescope")
+        });
+
+        attach_remediation(&mut found_runtime(), &[], &mut dependencies, &host);
+
+        let commands = dependencies[0]
+            .remediation
+            .iter()
+            .map(|remediation| remediation.command.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            commands,
+            vec![
+                "sudo apt install mangohud",
+                "flatpak install flathub org.freedesktop.Platform.VulkanLayer.MangoHud",
+            ]
+        );
+        assert!(dependencies[1].remediation.is_empty());
+        assert!(dependencies[2].remediation.is_empty());
+    }
+
+    #[test]
+    fn user_catalog_extends_the_builtin_entries() {
+        let host = host(
+            r#"{
+                "env": { "HOME": "/home/deck" },
+                "files": {
+                    "/etc/os-release": { "contents": "ID=steamos\nID_LIKE=arch\n" },
+                    "/home/deck/.local/share/Luthier/remediation.json": { "contents": "{ \"families\": { \"arch\": { \"ids\": [\"arch\"], \"manager\": \"pacman\", \"install\": \"sudo steamos-readonly disable && sudo pacman -S {packages}\" } }, \"dependencies\": { \"7zip\": { \"arch\": [\"7zip\"] } } }" }
+                }
+            }"#,
+        );
+        let mut dependencies = vec![missing("7zip"), missing("gamescope")];
+
+        attach_remediation(&mut found_runtime(), &[], &mut dependencies, &host);
+
+        assert_eq!(
+            dependencies[0].remediation,
+            vec![Remediation {
+                family: "arch".to_string(),
+                manager: "pacman".to_string(),
+                packages: vec!["7zip".to_string()],
+                command: "sudo steamos-readonly disable && sudo pacman -S 7zip".to_string(),
+            }]
+        );
+        assert_eq!(
+            dependencies[1].remediation[0].command,
+            "sudo steamos-readonly disable && sudo pacman -S gamescope"
+        );
+    }
+
+    #[test]
+    fn missing_runtime_gets_the_packages_of_its_candidates() {
+        let host = host(r#"{ "files": { "/etc/os-release": { "contents": "ID=fedora\n" } } }"#);
+        let mut runtime = RuntimeDiscovery {
+            wine: None,
+            selected_runtime: None,
+            runtime_status: CheckStatus::BLOCKER,
+            ..found_runtime()
+        };
+
+        attach_remediation(&mut runtime, &["umu-run", "wine"], &mut [], &host);
+
+        let commands = runtime
+            .remediation
+            .iter()
+            .map(|remediation| remediation.command.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            commands,
+            vec!["sudo dnf install umu-launcher", "sudo dnf install wine"]
+        );
+    }
+}
@@ -72,6 +72,7 @@ pub(super) fn evaluate_runtime(
             selected_runtime,
             runtime_status,
             runtime_note,
+            remediation: Vec::new(),
         }
     } else {
         let selected_runtime = if has_umu && has_proton {
@@ -124,10 +125,43 @@ pub(super) fn evaluate_runtime(
             selected_runtime,
             runtime_status,
             runtime_note,
+            remediation: Vec::new(),
         }
     }
 }
 
+/// Catalog names of the packages that would provide a runtime when none was
+/// found, in the order the config would try them. Proton itself comes from
+/// Steam or a manual download and has no package.
+pub(super) fn missing_runtime_packages(
+    config: Option<&GameConfig>,
+    runtime: &RuntimeDiscovery,
+) -> Vec<&'static str> {
+    if runtime.selected_runtime.is_some() {
+        return Vec::new();
+    }
+    let candidates = config.map(effective_runtime_candidates).unwrap_or_else(|| {
+        vec![
+            RuntimeCandidate::ProtonUmu,
+            RuntimeCandidate::ProtonNative,
+            RuntimeCandidate::Wine,
+        ]
+    });
+
+    let mut packages = Vec::new();
+    for candidate in candidates {
+        let package = match candidate {
+            RuntimeCandidate::ProtonUmu if runtime.umu_run.is_none() => "umu-run",
+            RuntimeCandidate::Wine if runtime.wine.is_none() => "wine",
+            _ => continue,
+        };
+        if !packages.contains(&package) {
+            packages.push(package);
+        }
+    }
+    packages
+}
+
 fn candidate_available(
     candidate: RuntimeCandidate,
     has_proton: bool,
@@ -204,7 +238,7 @@ mod tests {
         ScriptsConfig, SplashConfig, WinecfgConfig, WinecfgFeaturePolicy,
     };
 
-    use super::{evaluate_runtime, CheckStatus};
+    use super::{evaluate_runtime, missing_runtime_packages, CheckStatus};
 
     #[test]
     fn strict_mode_blocks_when_primary_candidate_is_unavailable() {
@@ -232,6 +266,33 @@ mod tests {
             .contains("no runtime candidate available"));
     }
 
+    #[test]
+    fn missing_runtime_packages_follow_candidate_order() {
+        let cfg = sample_config(
+            false,
+            RuntimeCandidate::Wine,
+            vec![RuntimeCandidate::ProtonNative, RuntimeCandidate::ProtonUmu],
+            RuntimePreference::Auto,
+            "GE-Proton9-10",
+        );
+
+        let missing = evaluate_runtime(Some(&cfg), None, None, None, None, false);
+        assert_eq!(
+            missing_runtime_packages(Some(&cfg), &missing),
+            vec!["wine", "umu-run"]
+        );
+
+        let found = evaluate_runtime(
+            Some(&cfg),
+            None,
+            Some("/usr/bin/wine".to_string()),
+            None,
+            None,
+            false,
+        );
+        assert!(missing_runtime_packages(Some(&cfg), &found).is_empty());
+    }
+
     #[test]
     fn non_strict_mode_uses_fallback_when_primary_is_unavailable() {
         let cfg = sample_config(
@@ -468,6 +529,7 @@ mod tests {
                 mangohud: FeatureState::OptionalOff,
                 prime_offload: FeatureState::OptionalOff,
                 custom_vars: HashMap::new(),
+                env_edits: Vec::new(),
             },
             compatibility: CompatibilityConfig {
                 wine_wayland: FeatureState::OptionalOff,
@@ -533,9 +595,13 @@ mod tests {
             folder_mounts: vec![],
             splash: SplashConfig::default(),
             scripts: ScriptsConfig {
-                pre_launch: String::new(),
-                post_launch: String::new(),
+                pre_launch: Vec::new(),
+                post_launch: Vec::new(),
             },
+            profiles: Vec::new(),
+            rules: Vec::new(),
+            launch_entries: Vec::new(),
+            default_entry: None,
         }
     }
 }
@@ -39,7 +39,16 @@ pub(super) fn worse_status(a: CheckStatus, b: CheckStatus) -> CheckStatus {
 fn should_preserve_dependency_status(name: &str) -> bool {
     matches!(
         name,
-        "gamemoderun" | "gamemode-umu-runtime" | "wine-wayland" | "hdr" | "dxvk-nvapi" | "staging"
+        "gamemoderun"
+            | "gamemode-umu-runtime"
+            | "wine-wayland"
+            | "hdr"
+            | "dxvk-nvapi"
+            | "staging"
+            | "prime-offload"
+            | "vulkan"
+            | "esync"
+            | "fsync"
     )
 }
 
@@ -200,6 +209,10 @@ mod tests {
             "hdr",
             "dxvk-nvapi",
             "staging",
+            "prime-offload",
+            "vulkan",
+            "esync",
+            "fsync",
         ] {
             assert!(should_preserve_dependency_status(name));
         }
@@ -253,6 +266,7 @@ mod tests {
             found,
             resolved_path: None,
             note: note.to_string(),
+            remediation: Vec::new(),
         }
     }
 }
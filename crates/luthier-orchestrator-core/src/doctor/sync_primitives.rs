@@ -0,0 +1,228 @@
+use std::path::{Path, PathBuf};
+
+use crate::host_env::HostEnvironment;
+
+use super::dependency_checks::CapabilityProbe;
+
+/// First mainline kernel with `futex_waitv`.
+const FUTEX_WAITV_KERNEL: (u32, u32) = (5, 16);
+/// Exposed by kernels carrying the out-of-tree futex2 patches (linux-zen,
+/// linux-tkg and older Proton-oriented kernels).
+const FUTEX2_SYSFS: &str = "/sys/kernel/futex2";
+/// Open-file limit esync needs: Wine keeps one eventfd per sync object and
+/// only raises the soft limit up to the hard one.
+const ESYNC_MIN_NOFILE: u64 = 524_288;
+
+const KERNEL_RELEASE: &str = "/proc/sys/kernel/osrelease";
+const PROCESS_LIMITS: &str = "/proc/self/limits";
+
+pub(super) fn probe_fsync(host: &dyn HostEnvironment) -> CapabilityProbe {
+    let release = kernel_release(host);
+    let kernel = release.as_deref().unwrap_or("unknown");
+    let syscall_probe = host.futex_waitv_supported();
+    let kernel_version = release.as_deref().and_then(parse_kernel_version);
+
+    let has_futex_waitv = syscall_probe
+        .or_else(|| kernel_version.map(|version| version >= FUTEX_WAITV_KERNEL))
+        .unwrap_or(false);
+    if has_futex_waitv {
+        return CapabilityProbe {
+            supported: true,
+            resolved_path: None,
+            note: format!("kernel {kernel} provides futex_waitv"),
+        };
+    }
+
+    if host.exists(Path::new(FUTEX2_SYSFS)) {
+        return CapabilityProbe {
+            supported: true,
+            resolved_path: Some(PathBuf::from(FUTEX2_SYSFS)),
+            note: format!("kernel {kernel} carries the futex2 patches"),
+        };
+    }
+
+    let note = match (syscall_probe, kernel_version) {
+        (Some(_), _) => format!("futex_waitv is not implemented by kernel {kernel}"),
+        (None, Some(_)) => format!(
+            "kernel {kernel} predates futex_waitv ({}.{}) and has no futex2 patches",
+            FUTEX_WAITV_KERNEL.0, FUTEX_WAITV_KERNEL.1
+        ),
+        // Without a kernel version there is nothing to contradict the payload.
+        (None, None) => {
+            return CapabilityProbe {
+                supported: true,
+                resolved_path: None,
+                note: "kernel version could not be read; fsync support was not verified"
+                    .to_string(),
+            };
+        }
+    };
+
+    CapabilityProbe {
+        supported: false,
+        resolved_path: None,
+        note,
+    }
+}
+
+pub(super) fn probe_esync(host: &dyn HostEnvironment) -> CapabilityProbe {
+    let Some((soft, hard)) = host
+        .read_to_string(Path::new(PROCESS_LIMITS))
+        .as_deref()
+        .and_then(parse_nofile_limits)
+    else {
+        return CapabilityProbe {
+            supported: true,
+            resolved_path: None,
+            note: "open file limit could not be read; esync support was not verified".to_string(),
+        };
+    };
+
+    let limits = format!(
+        "open file limit {} / {}",
+        limit_label(soft),
+        limit_label(hard)
+    );
+    if hard.is_none_or(|hard| hard >= ESYNC_MIN_NOFILE) {
+        CapabilityProbe {
+            supported: true,
+            resolved_path: None,
+            note: limits,
+        }
+    } else {
+        CapabilityProbe {
+            supported: false,
+            resolved_path: None,
+            note: format!("{limits}, esync needs a hard limit of at least {ESYNC_MIN_NOFILE}"),
+        }
+    }
+}
+
+fn kernel_release(host: &dyn HostEnvironment) -> Option<String> {
+    host.read_to_string(Path::new(KERNEL_RELEASE))
+        .map(|raw| raw.trim().to_string())
+        .filter(|release| !release.is_empty())
+}
+
+/// `(major, minor)` of a release such as `6.8.0-45-generic`.
+fn parse_kernel_version(release: &str) -> Option<(u32, u32)> {
+    let mut parts = release.split('.').map(|part| {
+        let digits = part
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect::<String>();
+        digits.parse::<u32>().ok()
+    });
+    let major = parts.next()??;
+    let minor = parts.next()??;
+    Some((major, minor))
+}
+
+/// Soft and hard `Max open files` from `/proc/<pid>/limits`; `None` stands
+/// for `unlimited`.
+fn parse_nofile_limits(limits: &str) -> Option<(Option<u64>, Option<u64>)> {
+    let values = limits
+        .lines()
+        .find_map(|line| line.strip_prefix("Max open files"))?;
+    let mut fields = values.split_whitespace().map(|value| match value {
+        "unlimited" => Some(None),
+        value => value.parse::<u64>().ok().map(Some),
+    });
+    let soft = fields.next()??;
+    let hard = fields.next()??;
+    Some((soft, hard))
+}
+
+fn limit_label(limit: Option<u64>) -> String {
+    limit.map_or_else(|| "unlimited".to_string(), |value| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::host_env::FixtureHost;
+
+    fn host(json: &str) -> FixtureHost {
+        FixtureHost::from_json(json.as_bytes()).expect("fixture parses")
+    }
+
+    #[test]
+    fn fsync_follows_the_syscall_probe_then_the_kernel_version() {
+        let probed = host(
+            r#"{ "futex_waitv": false,
+                 "files": { "/proc/sys/kernel/osrelease": { "contents": "6.1.0\n" } } }"#,
+        );
+        let probe = probe_fsync(&probed);
+        assert!(!probe.supported);
+        assert_eq!(probe.note, "futex_waitv is not implemented by kernel 6.1.0");
+
+        let modern = host(
+            r#"{ "files": { "/proc/sys/kernel/osrelease": { "contents": "5.16.0-zen1\n" } } }"#,
+        );
+        assert!(probe_fsync(&modern).supported);
+
+        let old = host(
+            r#"{ "files": { "/proc/sys/kernel/osrelease": { "contents": "5.15.0-91-generic\n" } } }"#,
+        );
+        let probe = probe_fsync(&old);
+        assert!(!probe.supported);
+        assert!(probe.note.contains("predates futex_waitv (5.16)"));
+
+        let patched = host(
+            r#"{ "files": {
+                   "/proc/sys/kernel/osrelease": { "contents": "5.13.1-tkg\n" },
+                   "/sys/kernel/futex2/size": { "contents": "64\n" }
+               } }"#,
+        );
+        let probe = probe_fsync(&patched);
+        assert!(probe.supported);
+        assert_eq!(probe.resolved_path, Some(PathBuf::from(FUTEX2_SYSFS)));
+    }
+
+    #[test]
+    fn esync_compares_the_hard_open_file_limit() {
+        let limits = |soft: &str, hard: &str| {
+            format!(
+                "Limit                     Soft Limit           Hard Limit           Units     \n\
+                 Max processes             62397                62397                processes \n\
+                 Max open files            {soft:<20} {hard:<20} files     \n"
+            )
+        };
+
+        assert_eq!(
+            parse_nofile_limits(&limits("1024", "unlimited")),
+            Some((Some(1024), None))
+        );
+
+        let low = FixtureHost {
+            files: [(
+                PathBuf::from(PROCESS_LIMITS),
+                crate::host_env::FixtureFile {
+                    contents: limits("1024", "4096"),
+                    ..Default::default()
+                },
+            )]
+            .into(),
+            ..Default::default()
+        };
+        let probe = probe_esync(&low);
+        assert!(!probe.supported);
+        assert_eq!(
+            probe.note,
+            "open file limit 1024 / 4096, esync needs a hard limit of at least 524288"
+        );
+
+        let mut high = low.clone();
+        if let Some(file) = high.files.get_mut(Path::new(PROCESS_LIMITS)) {
+            file.contents = limits("1024", "1048576");
+        }
+        assert!(probe_esync(&high).supported);
+    }
+
+    #[test]
+    fn parses_kernel_versions() {
+        assert_eq!(parse_kernel_version("6.18.44-fc-v130"), Some((6, 18)));
+        assert_eq!(parse_kernel_version("5.4"), Some((5, 4)));
+        assert_eq!(parse_kernel_version("garbage"), None);
+    }
+}
@@ -26,6 +26,15 @@ pub enum OrchestratorError {
     #[error("required embedded asset is missing: {0}")]
     MissingRequiredAsset(String),
 
+    #[error("invalid named asset identifier: {0:?}")]
+    InvalidAssetName(String),
+
+    #[error("failed to decode embedded asset: {0}")]
+    AssetDecodeFailed(String),
+
+    #[error("embedded asset not found: {0}")]
+    AssetNotFound(String),
+
     #[error("asset points outside allowed binary range: {0}")]
     AssetOutOfBounds(String),
 
@@ -35,6 +44,36 @@ pub enum OrchestratorError {
     #[error("home directory is not available in environment")]
     MissingHomeDir,
 
+    #[error("invalid payload signing key: {0}")]
+    InvalidSigningKey(String),
+
+    #[error("invalid payload signature: {0}")]
+    InvalidSignature(String),
+
+    #[error("launcher is signed; pass --signing-key to re-sign it or --drop-signature to write it unsigned")]
+    SignatureWouldBeDropped,
+
+    #[error("config_version {found} is newer than this build supports ({supported})")]
+    UnsupportedConfigVersion { found: u32, supported: u32 },
+
+    #[error("config migration failed: {0}")]
+    ConfigMigration(String),
+
+    #[error("invalid config overlay: {0}")]
+    InvalidConfigOverlay(String),
+
+    #[error("launch profile not found: {0}")]
+    UnknownLaunchProfile(String),
+
+    #[error("launch entry not found: {0}")]
+    UnknownLaunchEntry(String),
+
+    #[error("unknown variable: ${{{0}}}")]
+    UnknownVariable(String),
+
+    #[error("invalid variable reference: {0}")]
+    InvalidVariableSyntax(String),
+
     #[error("post-injection verification failed")]
     VerificationFailed,
 
@@ -0,0 +1,417 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::OrchestratorError;
+
+/// Captured output of a host command.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandOutput {
+    pub success: bool,
+    #[serde(default)]
+    pub stdout: String,
+    #[serde(default)]
+    pub stderr: String,
+}
+
+/// Everything the doctor and host detection read from the machine: the
+/// environment, the filesystem, executable lookup and command output.
+pub trait HostEnvironment {
+    fn env_var(&self, key: &str) -> Option<String>;
+
+    fn env_var_names(&self) -> Vec<String>;
+
+    fn exists(&self, path: &Path) -> bool;
+
+    fn is_file(&self, path: &Path) -> bool;
+
+    /// A regular file with an executable bit set.
+    fn is_executable(&self, path: &Path) -> bool;
+
+    /// Paths of the entries of a directory; empty when it cannot be read.
+    fn read_dir(&self, path: &Path) -> Vec<PathBuf>;
+
+    fn read_to_string(&self, path: &Path) -> Option<String>;
+
+    fn modified(&self, path: &Path) -> Option<SystemTime>;
+
+    fn canonicalize(&self, path: &Path) -> Option<PathBuf>;
+
+    /// Target of a symlink, as stored in the link.
+    fn read_link(&self, path: &Path) -> Option<PathBuf>;
+
+    /// Runs `program` to completion; `None` when it could not be started.
+    fn command_output(&self, program: &str, args: &[&str]) -> Option<CommandOutput>;
+
+    /// Whether the kernel implements the `futex_waitv` syscall fsync relies
+    /// on; `None` when it cannot be probed directly.
+    fn futex_waitv_supported(&self) -> Option<bool> {
+        None
+    }
+
+    /// First executable named `name` in the directories of `PATH`.
+    fn find_executable(&self, name: &str) -> Option<PathBuf> {
+        let paths = self.env_var("PATH")?;
+        std::env::split_paths(&paths)
+            .map(|dir| dir.join(name))
+            .find(|candidate| self.is_executable(candidate))
+    }
+}
+
+/// The machine the orchestrator runs on.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemHost;
+
+impl HostEnvironment for SystemHost {
+    fn env_var(&self, key: &str) -> Option<String> {
+        std::env::var(key).ok()
+    }
+
+    fn env_var_names(&self) -> Vec<String> {
+        std::env::vars_os()
+            .filter_map(|(key, _)| key.into_string().ok())
+            .collect()
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        path.exists()
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn is_executable(&self, path: &Path) -> bool {
+        if !path.is_file() {
+            return false;
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::metadata(path)
+                .map(|meta| meta.permissions().mode() & 0o111 != 0)
+                .unwrap_or(false)
+        }
+
+        #[cfg(not(unix))]
+        {
+            true
+        }
+    }
+
+    fn read_dir(&self, path: &Path) -> Vec<PathBuf> {
+        fs::read_dir(path)
+            .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+            .unwrap_or_default()
+    }
+
+    fn read_to_string(&self, path: &Path) -> Option<String> {
+        fs::read_to_string(path).ok()
+    }
+
+    fn modified(&self, path: &Path) -> Option<SystemTime> {
+        fs::metadata(path).and_then(|meta| meta.modified()).ok()
+    }
+
+    fn canonicalize(&self, path: &Path) -> Option<PathBuf> {
+        path.canonicalize().ok()
+    }
+
+    fn read_link(&self, path: &Path) -> Option<PathBuf> {
+        fs::read_link(path).ok()
+    }
+
+    fn command_output(&self, program: &str, args: &[&str]) -> Option<CommandOutput> {
+        let output = Command::new(program).args(args).output().ok()?;
+        Some(CommandOutput {
+            success: output.status.success(),
+            stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        })
+    }
+
+    #[cfg(target_os = "linux")]
+    fn futex_waitv_supported(&self) -> Option<bool> {
+        use rustix::io::Errno;
+        use rustix::thread::futex::{self, ClockId, WaitvFlags};
+
+        // An empty wait list is rejected with EINVAL before anything blocks,
+        // while kernels without the syscall answer ENOSYS.
+        match futex::waitv(&[], WaitvFlags::empty(), None, ClockId::Monotonic) {
+            Err(Errno::INVAL) => Some(true),
+            Err(Errno::NOSYS) => Some(false),
+            _ => None,
+        }
+    }
+}
+
+/// A host described by a JSON document, so a user's doctor report can be
+/// reproduced on another machine. Directories are implied by the paths of
+/// the files below them.
+///
+/// ```json
+/// {
+///   "env": { "HOME": "/home/deck", "PATH": "/usr/bin" },
+///   "files": {
+///     "/usr/bin/wine": { "executable": true },
+///     "/etc/os-release": { "contents": "ID=steamos\n" }
+///   },
+///   "links": { "/home/deck/.steam/root": "/home/deck/.local/share/Steam" },
+///   "commands": [
+///     { "program": "/usr/bin/wine", "args": ["--version"], "success": true, "stdout": "wine-9.0\n" }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FixtureHost {
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub files: BTreeMap<PathBuf, FixtureFile>,
+    /// Symlinks, from link path to target.
+    #[serde(default)]
+    pub links: BTreeMap<PathBuf, PathBuf>,
+    #[serde(default)]
+    pub commands: Vec<FixtureCommand>,
+    /// Answer of the `futex_waitv` probe; omitted means the doctor falls
+    /// back to the kernel version.
+    #[serde(default)]
+    pub futex_waitv: Option<bool>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FixtureFile {
+    #[serde(default)]
+    pub executable: bool,
+    #[serde(default)]
+    pub contents: String,
+    /// Modification time in seconds since the Unix epoch. A directory takes
+    /// the newest time of the files below it.
+    #[serde(default)]
+    pub modified: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FixtureCommand {
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(flatten)]
+    pub output: CommandOutput,
+}
+
+/// Symlink hops followed before a fixture path is treated as a loop.
+const MAX_FIXTURE_LINK_DEPTH: usize = 32;
+
+impl FixtureHost {
+    pub fn from_json(raw: &[u8]) -> Result<Self, OrchestratorError> {
+        Ok(serde_json::from_slice(raw)?)
+    }
+
+    pub fn load(path: &Path) -> Result<Self, OrchestratorError> {
+        Self::from_json(&fs::read(path)?)
+    }
+
+    /// Lexically normalizes `path` and follows fixture symlinks in it.
+    fn resolve(&self, path: &Path) -> PathBuf {
+        let mut current = normalize(path);
+        for _ in 0..MAX_FIXTURE_LINK_DEPTH {
+            let Some(next) = self.follow_first_link(&current) else {
+                return current;
+            };
+            current = next;
+        }
+        current
+    }
+
+    fn follow_first_link(&self, path: &Path) -> Option<PathBuf> {
+        let mut prefix = PathBuf::new();
+        let mut components = path.components();
+        while let Some(component) = components.next() {
+            prefix.push(component);
+            if let Some(target) = self.links.get(&prefix) {
+                let target = match prefix.parent() {
+                    Some(parent) if target.is_relative() => parent.join(target),
+                    _ => target.clone(),
+                };
+                return Some(normalize(&target.join(components.as_path())));
+            }
+        }
+        None
+    }
+
+    fn file(&self, path: &Path) -> Option<&FixtureFile> {
+        self.files.get(&self.resolve(path))
+    }
+
+    fn is_dir(&self, resolved: &Path) -> bool {
+        self.files
+            .keys()
+            .chain(self.links.keys())
+            .any(|entry| entry != resolved && entry.starts_with(resolved))
+    }
+}
+
+impl HostEnvironment for FixtureHost {
+    fn env_var(&self, key: &str) -> Option<String> {
+        self.env.get(key).cloned()
+    }
+
+    fn env_var_names(&self) -> Vec<String> {
+        self.env.keys().cloned().collect()
+    }
+
+    fn exists(&self, path: &Path) -> bool {
+        let resolved = self.resolve(path);
+        self.files.contains_key(&resolved) || self.is_dir(&resolved)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.file(path).is_some()
+    }
+
+    fn is_executable(&self, path: &Path) -> bool {
+        self.file(path).is_some_and(|file| file.executable)
+    }
+
+    fn read_dir(&self, path: &Path) -> Vec<PathBuf> {
+        let resolved = self.resolve(path);
+        let names = self
+            .files
+            .keys()
+            .chain(self.links.keys())
+            .filter_map(|entry| entry.strip_prefix(&resolved).ok())
+            .filter_map(|relative| relative.components().next())
+            .map(|component| component.as_os_str().to_os_string())
+            .collect::<BTreeSet<_>>();
+        names.into_iter().map(|name| path.join(name)).collect()
+    }
+
+    fn read_to_string(&self, path: &Path) -> Option<String> {
+        self.file(path).map(|file| file.contents.clone())
+    }
+
+    fn modified(&self, path: &Path) -> Option<SystemTime> {
+        let resolved = self.resolve(path);
+        self.files
+            .iter()
+            .filter(|(entry, _)| entry.starts_with(&resolved))
+            .filter_map(|(_, file)| file.modified)
+            .max()
+            .map(|secs| UNIX_EPOCH + Duration::from_secs(secs))
+    }
+
+    fn canonicalize(&self, path: &Path) -> Option<PathBuf> {
+        self.exists(path).then(|| self.resolve(path))
+    }
+
+    fn read_link(&self, path: &Path) -> Option<PathBuf> {
+        self.links.get(&normalize(path)).cloned()
+    }
+
+    fn command_output(&self, program: &str, args: &[&str]) -> Option<CommandOutput> {
+        self.commands
+            .iter()
+            .find(|command| command.program == program && command.args == args)
+            .map(|command| command.output.clone())
+    }
+
+    fn futex_waitv_supported(&self) -> Option<bool> {
+        self.futex_waitv
+    }
+}
+
+/// Drops `.` and folds `..` without touching the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                out.pop();
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> FixtureHost {
+        FixtureHost::from_json(
+            br#"{
+                "env": { "HOME": "/home/deck", "PATH": "/usr/local/bin:/usr/bin" },
+                "files": {
+                    "/usr/bin/wine": { "executable": true, "modified": 10 },
+                    "/usr/lib/wine/x86_64-unix/winewayland.drv": {},
+                    "/opt/GE-Proton10-3/proton": { "executable": true, "modified": 20 }
+                },
+                "links": { "/home/deck/tools/latest": "/opt/GE-Proton10-3" },
+                "commands": [
+                    { "program": "/usr/bin/wine", "args": ["--version"], "success": true, "stdout": "wine-9.0\n" }
+                ]
+            }"#,
+        )
+        .expect("fixture parses")
+    }
+
+    #[test]
+    fn fixture_host_answers_filesystem_queries() {
+        let host = fixture();
+
+        assert_eq!(
+            host.find_executable("wine"),
+            Some(PathBuf::from("/usr/bin/wine"))
+        );
+        assert!(host.exists(Path::new(
+            "/usr/bin/../lib/wine/x86_64-unix/winewayland.drv"
+        )));
+        assert!(host.exists(Path::new("/usr/lib")));
+        assert!(!host.is_executable(Path::new("/usr/lib/wine/x86_64-unix/winewayland.drv")));
+        assert_eq!(
+            host.read_dir(Path::new("/usr")),
+            vec![PathBuf::from("/usr/bin"), PathBuf::from("/usr/lib")]
+        );
+        assert_eq!(
+            host.modified(Path::new("/opt")),
+            Some(UNIX_EPOCH + Duration::from_secs(20))
+        );
+    }
+
+    #[test]
+    fn fixture_host_follows_links_and_replays_commands() {
+        let host = fixture();
+
+        assert!(host.is_executable(Path::new("/home/deck/tools/latest/proton")));
+        assert_eq!(
+            host.canonicalize(Path::new("/home/deck/tools/latest/proton")),
+            Some(PathBuf::from("/opt/GE-Proton10-3/proton"))
+        );
+        assert_eq!(
+            host.read_dir(Path::new("/home/deck/tools/latest")),
+            vec![PathBuf::from("/home/deck/tools/latest/proton")]
+        );
+        assert_eq!(
+            host.command_output("/usr/bin/wine", &["--version"])
+                .map(|output| output.stdout),
+            Some("wine-9.0\n".to_string())
+        );
+        assert_eq!(host.command_output("ldconfig", &["-p"]), None);
+    }
+
+    #[test]
+    fn fixture_host_rejects_unknown_fields() {
+        assert!(FixtureHost::from_json(br#"{ "enviroment": {} }"#).is_err());
+    }
+}
@@ -0,0 +1,448 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{GameConfig, GpuVendor, HostPredicate, HostRule, SessionType};
+use crate::config_overlay::patch_game_config;
+use crate::error::OrchestratorError;
+use crate::host_env::{HostEnvironment, SystemHost};
+
+/// Fields a host rule patch may not touch.
+const RULE_LOCKED_FIELDS: &[&str] = &["config_version", "exe_hash", "profiles", "rules"];
+
+const DRM_CLASS_DIR: &str = "/sys/class/drm";
+const OS_RELEASE_PATHS: &[&str] = &["/etc/os-release", "/usr/lib/os-release"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScreenSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Host facts host rules are matched against.
+#[derive(Debug, Clone, Default)]
+pub struct HostFacts {
+    pub session_type: Option<SessionType>,
+    /// One entry per recognized DRM card, so a hybrid laptop lists two.
+    pub gpu_vendors: Vec<GpuVendor>,
+    /// Lowercase `ID` from os-release.
+    pub os_id: Option<String>,
+    /// Preferred mode of each connected display.
+    pub screens: Vec<ScreenSize>,
+    pub env_vars: BTreeSet<String>,
+}
+
+impl HostFacts {
+    pub fn detect() -> Self {
+        Self::detect_with(&SystemHost)
+    }
+
+    pub fn detect_with(host: &dyn HostEnvironment) -> Self {
+        Self {
+            session_type: detect_session_type(host),
+            gpu_vendors: detect_gpu_vendors(host, Path::new(DRM_CLASS_DIR)),
+            os_id: OS_RELEASE_PATHS
+                .iter()
+                .find_map(|path| host.read_to_string(Path::new(path)))
+                .and_then(|raw| parse_os_release_id(&raw)),
+            screens: detect_screens(host, Path::new(DRM_CLASS_DIR)),
+            env_vars: host.env_var_names().into_iter().collect(),
+        }
+    }
+
+    pub fn hybrid_gpu(&self) -> bool {
+        self.gpu_vendors.len() > 1
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HostRuleEvaluation {
+    pub name: String,
+    pub matched: bool,
+    /// Conditions that did not hold, by predicate field name.
+    #[serde(default)]
+    pub unmet: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AppliedHostRules {
+    pub config: GameConfig,
+    pub evaluations: Vec<HostRuleEvaluation>,
+}
+
+pub fn evaluate_host_rules(rules: &[HostRule], facts: &HostFacts) -> Vec<HostRuleEvaluation> {
+    rules
+        .iter()
+        .map(|rule| {
+            let unmet = unmet_conditions(&rule.when, facts);
+            HostRuleEvaluation {
+                name: rule.name.clone(),
+                matched: unmet.is_empty(),
+                unmet: unmet.into_iter().map(str::to_string).collect(),
+            }
+        })
+        .collect()
+}
+
+/// Applies, in order, the patch of every rule whose predicate holds on `facts`.
+pub fn apply_host_rules(
+    config: &GameConfig,
+    facts: &HostFacts,
+) -> Result<AppliedHostRules, OrchestratorError> {
+    let evaluations = evaluate_host_rules(&config.rules, facts);
+    let mut patched = config.clone();
+    for (rule, evaluation) in config.rules.iter().zip(&evaluations) {
+        if evaluation.matched {
+            patched = apply_host_rule_patch(&patched, rule)?;
+        }
+    }
+
+    Ok(AppliedHostRules {
+        config: patched,
+        evaluations,
+    })
+}
+
+/// Applies one rule's patch regardless of its predicate.
+pub fn apply_host_rule_patch(
+    config: &GameConfig,
+    rule: &HostRule,
+) -> Result<GameConfig, OrchestratorError> {
+    patch_game_config(
+        config,
+        &rule.patch,
+        &format!("rules.{}.patch", rule.name),
+        RULE_LOCKED_FIELDS,
+    )
+}
+
+fn unmet_conditions(predicate: &HostPredicate, facts: &HostFacts) -> Vec<&'static str> {
+    let mut unmet = Vec::new();
+
+    if !predicate.session_type.is_empty()
+        && !facts
+            .session_type
+            .is_some_and(|session| predicate.session_type.contains(&session))
+    {
+        unmet.push("session_type");
+    }
+
+    if !predicate.gpu_vendor.is_empty()
+        && !facts
+            .gpu_vendors
+            .iter()
+            .any(|vendor| predicate.gpu_vendor.contains(vendor))
+    {
+        unmet.push("gpu_vendor");
+    }
+
+    if predicate
+        .hybrid_gpu
+        .is_some_and(|hybrid| hybrid != facts.hybrid_gpu())
+    {
+        unmet.push("hybrid_gpu");
+    }
+
+    if !predicate.os_id.is_empty()
+        && !facts.os_id.as_deref().is_some_and(|os_id| {
+            predicate
+                .os_id
+                .iter()
+                .any(|wanted| wanted.trim().eq_ignore_ascii_case(os_id))
+        })
+    {
+        unmet.push("os_id");
+    }
+
+    if !predicate
+        .env_present
+        .iter()
+        .all(|name| facts.env_vars.contains(name.trim()))
+    {
+        unmet.push("env_present");
+    }
+
+    if let Some(bounds) = predicate.screen {
+        let fits = |screen: &ScreenSize| {
+            bounds.min_width.is_none_or(|min| screen.width >= min)
+                && bounds.max_width.is_none_or(|max| screen.width <= max)
+                && bounds.min_height.is_none_or(|min| screen.height >= min)
+                && bounds.max_height.is_none_or(|max| screen.height <= max)
+        };
+        if !facts.screens.iter().any(fits) {
+            unmet.push("screen");
+        }
+    }
+
+    unmet
+}
+
+pub(crate) fn detect_session_type(host: &dyn HostEnvironment) -> Option<SessionType> {
+    match host
+        .env_var("XDG_SESSION_TYPE")
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "wayland" => return Some(SessionType::Wayland),
+        "x11" => return Some(SessionType::X11),
+        "tty" => return Some(SessionType::Tty),
+        _ => {}
+    }
+
+    if host.env_var("WAYLAND_DISPLAY").is_some() {
+        Some(SessionType::Wayland)
+    } else if host.env_var("DISPLAY").is_some() {
+        Some(SessionType::X11)
+    } else {
+        None
+    }
+}
+
+fn detect_gpu_vendors(host: &dyn HostEnvironment, drm_dir: &Path) -> Vec<GpuVendor> {
+    let mut cards = drm_entries(host, drm_dir)
+        .into_iter()
+        .filter(|name| is_card_name(name))
+        .collect::<Vec<_>>();
+    cards.sort();
+
+    cards
+        .iter()
+        .filter_map(|card| host.read_to_string(&drm_dir.join(card).join("device/vendor")))
+        .filter_map(|vendor| GpuVendor::from_pci_vendor_id(&vendor))
+        .collect()
+}
+
+fn detect_screens(host: &dyn HostEnvironment, drm_dir: &Path) -> Vec<ScreenSize> {
+    let mut connectors = drm_entries(host, drm_dir)
+        .into_iter()
+        .filter(|name| name.starts_with("card") && name.contains('-'))
+        .collect::<Vec<_>>();
+    connectors.sort();
+
+    connectors
+        .iter()
+        .filter(|connector| {
+            host.read_to_string(&drm_dir.join(connector).join("status"))
+                .is_some_and(|status| status.trim() == "connected")
+        })
+        .filter_map(|connector| host.read_to_string(&drm_dir.join(connector).join("modes")))
+        .filter_map(|modes| modes.lines().next().and_then(parse_mode_line))
+        .collect()
+}
+
+fn drm_entries(host: &dyn HostEnvironment, drm_dir: &Path) -> Vec<String> {
+    host.read_dir(drm_dir)
+        .into_iter()
+        .filter_map(|path| path.file_name()?.to_str().map(str::to_string))
+        .collect()
+}
+
+/// `card0`, `card1`, ... but not connectors such as `card0-eDP-1`.
+pub(crate) fn is_card_name(name: &str) -> bool {
+    name.strip_prefix("card")
+        .is_some_and(|index| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Parses a DRM mode such as `1280x800` (interlaced modes end with `i`).
+fn parse_mode_line(line: &str) -> Option<ScreenSize> {
+    let (width, height) = line.trim().split_once('x')?;
+    let height = height.trim_end_matches('i');
+    Some(ScreenSize {
+        width: width.parse().ok()?,
+        height: height.parse().ok()?,
+    })
+}
+
+/// `ID` followed by the `ID_LIKE` entries of os-release, lowercase, so
+/// derivatives can be matched by the distribution they are based on.
+pub(crate) fn detect_os_lineage(host: &dyn HostEnvironment) -> Vec<String> {
+    let Some(raw) = OS_RELEASE_PATHS
+        .iter()
+        .find_map(|path| host.read_to_string(Path::new(path)))
+    else {
+        return Vec::new();
+    };
+
+    let mut lineage = parse_os_release_id(&raw).into_iter().collect::<Vec<_>>();
+    for id in parse_os_release_field(&raw, "ID_LIKE")
+        .unwrap_or_default()
+        .split_whitespace()
+    {
+        if !lineage.iter().any(|known| known == id) {
+            lineage.push(id.to_string());
+        }
+    }
+    lineage
+}
+
+fn parse_os_release_id(raw: &str) -> Option<String> {
+    parse_os_release_field(raw, "ID")
+}
+
+fn parse_os_release_field(raw: &str, key: &str) -> Option<String> {
+    raw.lines().find_map(|line| {
+        let value = line.trim().strip_prefix(key)?.strip_prefix('=')?;
+        let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+        (!value.is_empty()).then(|| value.to_ascii_lowercase())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::config::{FeatureState, ScreenSizePredicate};
+    use crate::config_overlay::tests::sample_config_value;
+
+    fn deck_facts() -> HostFacts {
+        HostFacts {
+            session_type: Some(SessionType::Wayland),
+            gpu_vendors: vec![GpuVendor::Amd],
+            os_id: Some("steamos".to_string()),
+            screens: vec![ScreenSize {
+                width: 1280,
+                height: 800,
+            }],
+            env_vars: ["SteamDeck".to_string()].into_iter().collect(),
+        }
+    }
+
+    fn rule(name: &str, when: HostPredicate, patch: serde_json::Value) -> HostRule {
+        HostRule {
+            name: name.to_string(),
+            when,
+            patch,
+        }
+    }
+
+    #[test]
+    fn reports_each_unmet_condition() {
+        let rules = vec![
+            rule(
+                "deck",
+                HostPredicate {
+                    os_id: vec!["SteamOS".to_string()],
+                    env_present: vec!["SteamDeck".to_string()],
+                    screen: Some(ScreenSizePredicate {
+                        max_width: Some(1280),
+                        max_height: Some(800),
+                        ..ScreenSizePredicate::default()
+                    }),
+                    ..HostPredicate::default()
+                },
+                json!({}),
+            ),
+            rule(
+                "hybrid-x11",
+                HostPredicate {
+                    session_type: vec![SessionType::X11],
+                    gpu_vendor: vec![GpuVendor::Nvidia],
+                    hybrid_gpu: Some(true),
+                    ..HostPredicate::default()
+                },
+                json!({}),
+            ),
+            rule("always", HostPredicate::default(), json!({})),
+        ];
+
+        let evaluations = evaluate_host_rules(&rules, &deck_facts());
+
+        assert!(evaluations[0].matched, "{:?}", evaluations[0]);
+        assert_eq!(
+            evaluations[1].unmet,
+            vec!["session_type", "gpu_vendor", "hybrid_gpu"]
+        );
+        assert!(evaluations[2].matched);
+    }
+
+    #[test]
+    fn applies_matching_rules_in_order() {
+        let mut value = sample_config_value();
+        value["rules"] = json!([
+            {
+                "name": "deck",
+                "when": { "os_id": ["steamos"] },
+                "patch": {
+                    "environment": { "gamescope": { "state": "MandatoryOn", "output_width": "1280" } }
+                }
+            },
+            {
+                "name": "wayland-hdr",
+                "when": { "session_type": ["wayland"] },
+                "patch": {
+                    "compatibility": { "hdr": "OptionalOn" },
+                    "environment": { "gamescope": { "output_width": "1920" } }
+                }
+            },
+            {
+                "name": "nvidia",
+                "when": { "gpu_vendor": ["nvidia"] },
+                "patch": { "compatibility": { "auto_dxvk_nvapi": "MandatoryOn" } }
+            }
+        ]);
+        let config = serde_json::from_value::<GameConfig>(value).expect("config");
+
+        let applied = apply_host_rules(&config, &deck_facts()).expect("apply");
+
+        let gamescope = &applied.config.environment.gamescope;
+        assert_eq!(gamescope.state, FeatureState::MandatoryOn);
+        assert_eq!(gamescope.output_width, "1920");
+        assert_eq!(applied.config.compatibility.hdr, FeatureState::OptionalOn);
+        assert_eq!(
+            applied.config.compatibility.auto_dxvk_nvapi,
+            FeatureState::OptionalOff
+        );
+        let matched = applied
+            .evaluations
+            .iter()
+            .filter(|evaluation| evaluation.matched)
+            .map(|evaluation| evaluation.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(matched, vec!["deck", "wayland-hdr"]);
+    }
+
+    #[test]
+    fn rule_patches_cannot_touch_identity_fields() {
+        let mut config =
+            serde_json::from_value::<GameConfig>(sample_config_value()).expect("config");
+        config.rules = vec![rule(
+            "bad",
+            HostPredicate::default(),
+            json!({ "rules": [] }),
+        )];
+
+        let err = apply_host_rules(&config, &deck_facts()).expect_err("locked field");
+        assert!(err.to_string().contains("rules.bad.patch.rules"));
+    }
+
+    #[test]
+    fn parses_sysfs_and_os_release_values() {
+        assert_eq!(
+            parse_mode_line("1920x1080i\n"),
+            Some(ScreenSize {
+                width: 1920,
+                height: 1080
+            })
+        );
+        assert_eq!(parse_mode_line("garbage"), None);
+        assert_eq!(
+            parse_os_release_id("NAME=\"Fedora Linux\"\nID=fedora\nID_LIKE=\"rhel\"\n").as_deref(),
+            Some("fedora")
+        );
+        assert_eq!(
+            parse_os_release_id("ID_LIKE=arch\nID=\"SteamOS\"\n").as_deref(),
+            Some("steamos")
+        );
+        assert!(is_card_name("card1"));
+        assert!(!is_card_name("card1-eDP-1"));
+        assert!(!is_card_name("renderD128"));
+        assert_eq!(
+            GpuVendor::from_pci_vendor_id("0x10DE\n"),
+            Some(GpuVendor::Nvidia)
+        );
+    }
+}
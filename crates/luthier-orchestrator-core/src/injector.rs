@@ -5,15 +5,19 @@ use std::path::{Path, PathBuf};
 use sha2::{Digest, Sha256};
 
 use crate::asset_container::{
-    append_asset_container, parse_asset_container, AssetContainerWriteInput, AssetManifest,
+    append_asset_container, normalize_named_mime_type, parse_asset_container,
+    AssetContainerWriteInput, AssetManifest, AssetType, NamedAssetWriteInput,
 };
-use crate::config::GameConfig;
+use crate::config_migration::load_game_config_json;
+use crate::container_reader::AssetContainerReader;
 use crate::error::OrchestratorError;
+use crate::signing::PayloadSigningKey;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct InjectOptions {
     pub backup_existing: bool,
     pub make_executable: bool,
+    pub signing_key: Option<PayloadSigningKey>,
 }
 
 impl Default for InjectOptions {
@@ -21,6 +25,7 @@ impl Default for InjectOptions {
         Self {
             backup_existing: true,
             make_executable: true,
+            signing_key: None,
         }
     }
 }
@@ -32,12 +37,35 @@ pub struct InjectionResult {
     pub config_sha256_hex: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedAssetFile {
+    pub name: String,
+    pub mime_type: String,
+    pub flags: u32,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractedNamedAsset {
+    pub name: String,
+    pub mime_type: String,
+    pub flags: u32,
+    pub bytes: Vec<u8>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ExtractedAssets {
     pub manifest: AssetManifest,
     pub config_json: Vec<u8>,
     pub hero_image: Option<Vec<u8>>,
     pub icon_png: Option<Vec<u8>>,
+    pub named_assets: Vec<ExtractedNamedAsset>,
+}
+
+impl ExtractedAssets {
+    pub fn named_asset(&self, name: &str) -> Option<&ExtractedNamedAsset> {
+        self.named_assets.iter().find(|asset| asset.name == name)
+    }
 }
 
 pub fn inject_from_files(
@@ -45,6 +73,7 @@ pub fn inject_from_files(
     config_json_path: &Path,
     hero_image_path: Option<&Path>,
     icon_png_path: Option<&Path>,
+    named_asset_files: &[NamedAssetFile],
     output_path: &Path,
     options: InjectOptions,
 ) -> Result<InjectionResult, OrchestratorError> {
@@ -60,12 +89,27 @@ pub fn inject_from_files(
     } else {
         None
     };
+    let named_asset_bytes = named_asset_files
+        .iter()
+        .map(|file| fs::read(&file.path))
+        .collect::<Result<Vec<_>, _>>()?;
+    let named_assets = named_asset_files
+        .iter()
+        .zip(&named_asset_bytes)
+        .map(|(file, bytes)| NamedAssetWriteInput {
+            name: &file.name,
+            mime_type: &file.mime_type,
+            flags: file.flags,
+            bytes,
+        })
+        .collect::<Vec<_>>();
 
     inject_from_parts(
         &base_bytes,
         &config_bytes,
         hero_image_bytes.as_deref(),
         icon_png_bytes.as_deref(),
+        &named_assets,
         output_path,
         options,
     )
@@ -76,11 +120,20 @@ pub fn inject_from_parts(
     config_bytes: &[u8],
     hero_image_bytes: Option<&[u8]>,
     icon_png_bytes: Option<&[u8]>,
+    named_assets: &[NamedAssetWriteInput<'_>],
     output_path: &Path,
     options: InjectOptions,
 ) -> Result<InjectionResult, OrchestratorError> {
-    // Validate schema before embedding.
-    let _: GameConfig = serde_json::from_slice(config_bytes)?;
+    // Validate schema before embedding. Older config versions are embedded
+    // migrated, so repacking or rebasing an old launcher also upgrades it.
+    let migrated = load_game_config_json(config_bytes)?;
+    let migrated_bytes;
+    let config_bytes = if migrated.report.is_noop() {
+        config_bytes
+    } else {
+        migrated_bytes = serde_json::to_vec_pretty(&migrated.config)?;
+        migrated_bytes.as_slice()
+    };
 
     let injected = append_asset_container(
         base_bytes,
@@ -88,6 +141,8 @@ pub fn inject_from_parts(
             config_json: config_bytes,
             hero_image: hero_image_bytes,
             icon_png: icon_png_bytes,
+            named_assets,
+            signing_key: options.signing_key.as_ref(),
         },
     )?;
 
@@ -107,6 +162,8 @@ pub fn inject_from_parts(
     if extracted.config_json != config_bytes
         || !option_slice_matches(extracted.hero_image.as_deref(), hero_image_bytes)
         || !option_slice_matches(extracted.icon_png.as_deref(), icon_png_bytes)
+        || !named_assets_match(&extracted.named_assets, named_assets)
+        || extracted.manifest.signature.is_some() != options.signing_key.is_some()
     {
         return Err(OrchestratorError::VerificationFailed);
     }
@@ -119,32 +176,291 @@ pub fn inject_from_parts(
 }
 
 pub fn read_manifest_from_file(path: &Path) -> Result<AssetManifest, OrchestratorError> {
-    let bin = fs::read(path)?;
-    let parsed = parse_asset_container(&bin)?;
-    Ok(parsed.manifest.clone())
+    Ok(AssetContainerReader::open(path)?.into_manifest())
 }
 
 pub fn extract_assets_from_file(path: &Path) -> Result<ExtractedAssets, OrchestratorError> {
-    let bin = fs::read(path)?;
-    let parsed = parse_asset_container(&bin)?;
+    let mut reader = AssetContainerReader::open(path)?;
+    let config_json = reader.config_json()?;
+    let hero_image = reader.hero_image()?;
+    let icon_png = reader.icon_png()?;
+
+    let manifest = reader.manifest().clone();
+    let named_assets = manifest
+        .entries
+        .iter()
+        .filter(|entry| entry.asset_type == AssetType::Named)
+        .map(|entry| {
+            Ok(ExtractedNamedAsset {
+                name: entry.name.clone().unwrap_or_default(),
+                mime_type: entry.named_mime_type().to_string(),
+                flags: entry.flags,
+                bytes: reader.read_entry(entry)?,
+            })
+        })
+        .collect::<Result<_, OrchestratorError>>()?;
+
     Ok(ExtractedAssets {
-        manifest: parsed.manifest.clone(),
-        config_json: parsed.config_json().to_vec(),
-        hero_image: parsed.hero_image().map(ToOwned::to_owned),
-        icon_png: parsed.icon_png().map(ToOwned::to_owned),
+        manifest,
+        config_json,
+        hero_image,
+        icon_png,
+        named_assets,
     })
 }
 
 pub fn extract_config_from_file(path: &Path) -> Result<Vec<u8>, OrchestratorError> {
-    Ok(extract_assets_from_file(path)?.config_json)
+    AssetContainerReader::open(path)?.config_json()
 }
 
 pub fn extract_hero_image_from_file(path: &Path) -> Result<Option<Vec<u8>>, OrchestratorError> {
-    Ok(extract_assets_from_file(path)?.hero_image)
+    AssetContainerReader::open(path)?.hero_image()
 }
 
 pub fn extract_icon_png_from_file(path: &Path) -> Result<Option<Vec<u8>>, OrchestratorError> {
-    Ok(extract_assets_from_file(path)?.icon_png)
+    AssetContainerReader::open(path)?.icon_png()
+}
+
+pub fn extract_named_asset_from_file(
+    path: &Path,
+    name: &str,
+) -> Result<Option<ExtractedNamedAsset>, OrchestratorError> {
+    let mut reader = AssetContainerReader::open(path)?;
+    let Some(entry) = reader
+        .manifest()
+        .entries
+        .iter()
+        .find(|entry| entry.asset_type == AssetType::Named && entry.name.as_deref() == Some(name))
+        .cloned()
+    else {
+        return Ok(None);
+    };
+
+    Ok(Some(ExtractedNamedAsset {
+        name: name.to_string(),
+        mime_type: entry.named_mime_type().to_string(),
+        flags: entry.flags,
+        bytes: reader.read_entry(&entry)?,
+    }))
+}
+
+/// What happens to a built-in optional asset when a launcher is repacked.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum AssetChange {
+    #[default]
+    Keep,
+    Replace(Vec<u8>),
+    Remove,
+}
+
+/// Changes applied by [`replace_assets`]. Anything not mentioned is carried over
+/// from the launcher's current container.
+#[derive(Debug, Clone, Default)]
+pub struct AssetReplacement {
+    pub config_json: Option<Vec<u8>>,
+    pub hero_image: AssetChange,
+    pub icon_png: AssetChange,
+    /// Added, or replacing the named asset with the same name in place.
+    pub named_assets: Vec<ExtractedNamedAsset>,
+    pub remove_named_assets: Vec<String>,
+}
+
+/// Returns the base binary that precedes the embedded `GOASv2` container.
+/// The container is fully validated first, so a corrupt launcher is never cut
+/// at a guessed offset.
+pub fn strip_asset_container(binary: &[u8]) -> Result<&[u8], OrchestratorError> {
+    let parsed = parse_asset_container(binary)?;
+    let base_len = container_start(&parsed.manifest)?;
+    Ok(&binary[..base_len])
+}
+
+/// Rewrites the container of an existing launcher on top of its own base
+/// binary. `output_path` may be the launcher itself; the usual backup, atomic
+/// write and verification from [`inject_from_parts`] apply.
+pub fn replace_assets(
+    launcher_path: &Path,
+    replacement: &AssetReplacement,
+    output_path: &Path,
+    options: InjectOptions,
+) -> Result<InjectionResult, OrchestratorError> {
+    let binary = fs::read(launcher_path)?;
+    let parsed = parse_asset_container(&binary)?;
+    let base = &binary[..container_start(&parsed.manifest)?];
+
+    let current_config = parsed.config_json()?;
+    let config_bytes = replacement
+        .config_json
+        .as_deref()
+        .unwrap_or(&current_config);
+    let current_hero = parsed.hero_image()?;
+    let hero_image = apply_asset_change(current_hero.as_deref(), &replacement.hero_image);
+    let current_icon = parsed.icon_png()?;
+    let icon_png = apply_asset_change(current_icon.as_deref(), &replacement.icon_png);
+
+    for name in &replacement.remove_named_assets {
+        if parsed.named_asset(name).is_none() {
+            return Err(OrchestratorError::AssetNotFound(format!("named:{name}")));
+        }
+    }
+
+    let mut named = Vec::new();
+    for asset in parsed.named_assets() {
+        if replacement.remove_named_assets.contains(&asset.name) {
+            continue;
+        }
+        let updated = replacement
+            .named_assets
+            .iter()
+            .find(|candidate| candidate.name == asset.name);
+        named.push(match updated {
+            Some(updated) => updated.clone(),
+            None => ExtractedNamedAsset {
+                name: asset.name.clone(),
+                mime_type: asset.mime_type.clone(),
+                flags: asset.flags,
+                bytes: asset.bytes()?.into_owned(),
+            },
+        });
+    }
+    for asset in &replacement.named_assets {
+        if parsed.named_asset(&asset.name).is_none() {
+            named.push(asset.clone());
+        }
+    }
+    let named_inputs = named
+        .iter()
+        .map(|asset| NamedAssetWriteInput {
+            name: &asset.name,
+            mime_type: &asset.mime_type,
+            flags: asset.flags,
+            bytes: &asset.bytes,
+        })
+        .collect::<Vec<_>>();
+
+    inject_from_parts(
+        base,
+        config_bytes,
+        hero_image,
+        icon_png,
+        &named_inputs,
+        output_path,
+        options,
+    )
+}
+
+/// Signature a launcher ends up with once its payload has been rewritten.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RewrittenSignature {
+    Signed,
+    Dropped,
+    Unsigned,
+}
+
+impl RewrittenSignature {
+    /// Rewriting moves asset offsets, so an old signature only survives with a key.
+    pub fn after_rewrite(was_signed: bool, will_sign: bool) -> Self {
+        match (was_signed, will_sign) {
+            (_, true) => Self::Signed,
+            (true, false) => Self::Dropped,
+            (false, false) => Self::Unsigned,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Signed => "signed",
+            Self::Dropped => "dropped",
+            Self::Unsigned => "unsigned",
+        }
+    }
+}
+
+/// Reports what [`replace_assets`] will do to the launcher's signature. A
+/// signed launcher repacked without a key is refused unless `drop_signature`
+/// is set: hosts with the `refuse` signature policy would no longer start it.
+pub fn check_repack_signature(
+    launcher_path: &Path,
+    options: &InjectOptions,
+    drop_signature: bool,
+) -> Result<RewrittenSignature, OrchestratorError> {
+    let was_signed = read_manifest_from_file(launcher_path)?.signature.is_some();
+    let signature = RewrittenSignature::after_rewrite(was_signed, options.signing_key.is_some());
+    if signature == RewrittenSignature::Dropped && !drop_signature {
+        return Err(OrchestratorError::SignatureWouldBeDropped);
+    }
+    Ok(signature)
+}
+
+/// SHA-256 of the base binary a launcher was built from (the bytes before its container).
+pub fn launcher_base_sha256_hex(launcher_path: &Path) -> Result<String, OrchestratorError> {
+    let binary = fs::read(launcher_path)?;
+    Ok(sha256_hex(strip_asset_container(&binary)?))
+}
+
+/// Moves a launcher's current payload onto `new_base`. The embedded signature
+/// cannot survive this (asset offsets change), so pass a key in `options` to re-sign.
+pub fn rebase_launcher(
+    launcher_path: &Path,
+    new_base: &[u8],
+    output_path: &Path,
+    options: InjectOptions,
+) -> Result<InjectionResult, OrchestratorError> {
+    let assets = extract_assets_from_file(launcher_path)?;
+    let named_assets = assets
+        .named_assets
+        .iter()
+        .map(|asset| NamedAssetWriteInput {
+            name: &asset.name,
+            mime_type: &asset.mime_type,
+            flags: asset.flags,
+            bytes: &asset.bytes,
+        })
+        .collect::<Vec<_>>();
+
+    inject_from_parts(
+        new_base,
+        &assets.config_json,
+        assets.hero_image.as_deref(),
+        assets.icon_png.as_deref(),
+        &named_assets,
+        output_path,
+        options,
+    )
+}
+
+/// Assets are written right after the base binary, so the lowest entry offset
+/// is where the container begins.
+fn container_start(manifest: &AssetManifest) -> Result<usize, OrchestratorError> {
+    let start = manifest
+        .entries
+        .iter()
+        .map(|entry| entry.offset)
+        .min()
+        .ok_or_else(|| {
+            OrchestratorError::MissingRequiredAsset(AssetType::ConfigJson.as_str().to_string())
+        })?;
+    usize::try_from(start).map_err(|_| OrchestratorError::InvalidLength)
+}
+
+fn apply_asset_change<'a>(current: Option<&'a [u8]>, change: &'a AssetChange) -> Option<&'a [u8]> {
+    match change {
+        AssetChange::Keep => current,
+        AssetChange::Replace(bytes) => Some(bytes),
+        AssetChange::Remove => None,
+    }
+}
+
+fn named_assets_match(
+    actual: &[ExtractedNamedAsset],
+    expected: &[NamedAssetWriteInput<'_>],
+) -> bool {
+    actual.len() == expected.len()
+        && actual.iter().zip(expected).all(|(a, b)| {
+            a.name == b.name
+                && a.mime_type == normalize_named_mime_type(b.mime_type)
+                && a.flags == b.flags
+                && a.bytes == b.bytes
+        })
 }
 
 fn option_slice_matches(actual: Option<&[u8]>, expected: Option<&[u8]>) -> bool {
@@ -228,7 +544,9 @@ mod tests {
     use std::time::{SystemTime, UNIX_EPOCH};
 
     use super::*;
+    use crate::asset_container::DEFAULT_NAMED_ASSET_MIME_TYPE;
     use crate::config::*;
+    use crate::config_migration::CURRENT_CONFIG_VERSION;
 
     #[test]
     fn injects_and_extracts_roundtrip_with_all_assets() {
@@ -245,6 +563,7 @@ mod tests {
             &cfg_bytes,
             Some(hero),
             Some(icon),
+            &[],
             &out_path,
             InjectOptions::default(),
         )
@@ -273,6 +592,7 @@ mod tests {
             &cfg_bytes,
             None,
             None,
+            &[],
             &out_path,
             InjectOptions::default(),
         )
@@ -300,6 +620,7 @@ mod tests {
             &cfg_bytes,
             Some(hero),
             Some(icon),
+            &[],
             &out_path,
             InjectOptions::default(),
         )
@@ -342,6 +663,7 @@ mod tests {
             &cfg_path,
             None,
             None,
+            &[],
             &out_path,
             InjectOptions::default(),
         )
@@ -353,6 +675,344 @@ mod tests {
         fs::remove_dir_all(root).expect("cleanup");
     }
 
+    #[test]
+    fn injects_and_extracts_named_assets_from_files() {
+        let root = make_temp_dir("inject-named-assets");
+        let base_path = root.join("base.bin");
+        let cfg_path = root.join("config.json");
+        let manual_path = root.join("manual.pdf");
+        let out_path = root.join("luthier-orchestrator");
+
+        fs::write(&base_path, b"BASE-BINARY").expect("write base");
+        fs::write(
+            &cfg_path,
+            serde_json::to_vec(&sample_config()).expect("serialize config"),
+        )
+        .expect("write config");
+        fs::write(&manual_path, b"%PDF-1.7 manual").expect("write manual");
+
+        inject_from_files(
+            &base_path,
+            &cfg_path,
+            None,
+            None,
+            &[NamedAssetFile {
+                name: "manual.pdf".to_string(),
+                mime_type: "application/pdf".to_string(),
+                flags: 1,
+                path: manual_path,
+            }],
+            &out_path,
+            InjectOptions::default(),
+        )
+        .expect("inject config");
+
+        let manual = extract_named_asset_from_file(&out_path, "manual.pdf")
+            .expect("extract")
+            .expect("named asset should exist");
+        assert_eq!(manual.mime_type, "application/pdf");
+        assert_eq!(manual.flags, 1);
+        assert_eq!(manual.bytes, b"%PDF-1.7 manual");
+        assert!(extract_named_asset_from_file(&out_path, "missing")
+            .expect("extract")
+            .is_none());
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn strips_container_back_to_base_binary() {
+        let cfg_bytes = serde_json::to_vec(&sample_config()).expect("serialize config");
+        let binary = append_asset_container(
+            b"BASE-BINARY",
+            AssetContainerWriteInput {
+                config_json: &cfg_bytes,
+                hero_image: Some(b"hero"),
+                icon_png: None,
+                named_assets: &[],
+                signing_key: None,
+            },
+        )
+        .expect("container build");
+
+        assert_eq!(
+            strip_asset_container(&binary).expect("strip"),
+            b"BASE-BINARY"
+        );
+        assert!(matches!(
+            strip_asset_container(b"BASE-BINARY-WITHOUT-CONTAINER-PADDING-PADDING-PADDING"),
+            Err(OrchestratorError::ContainerNotFound)
+        ));
+    }
+
+    #[test]
+    fn replaces_assets_in_place_and_keeps_the_rest() {
+        let root = make_temp_dir("replace-assets-in-place");
+        let launcher = root.join("game");
+        let cfg_bytes = serde_json::to_vec(&sample_config()).expect("serialize config");
+
+        inject_from_parts(
+            b"BASE-BINARY",
+            &cfg_bytes,
+            Some(b"hero"),
+            Some(b"\x89PNG\r\n\x1a\nicon"),
+            &[
+                NamedAssetWriteInput {
+                    name: "manual.pdf",
+                    mime_type: "application/pdf",
+                    flags: 0,
+                    bytes: b"%PDF old",
+                },
+                NamedAssetWriteInput {
+                    name: "notes.txt",
+                    mime_type: "text/plain",
+                    flags: 0,
+                    bytes: b"notes",
+                },
+            ],
+            &launcher,
+            InjectOptions::default(),
+        )
+        .expect("inject config");
+        let original = fs::read(&launcher).expect("read launcher");
+
+        let mut new_config = sample_config();
+        new_config.game_name = "Renamed Game".to_string();
+        let new_cfg_bytes = serde_json::to_vec(&new_config).expect("serialize config");
+        replace_assets(
+            &launcher,
+            &AssetReplacement {
+                config_json: Some(new_cfg_bytes.clone()),
+                icon_png: AssetChange::Remove,
+                named_assets: vec![ExtractedNamedAsset {
+                    name: "manual.pdf".to_string(),
+                    mime_type: "application/pdf".to_string(),
+                    flags: 1,
+                    bytes: b"%PDF new".to_vec(),
+                }],
+                remove_named_assets: vec!["notes.txt".to_string()],
+                ..AssetReplacement::default()
+            },
+            &launcher,
+            InjectOptions::default(),
+        )
+        .expect("replace assets");
+
+        let extracted = extract_assets_from_file(&launcher).expect("extract");
+        assert_eq!(extracted.config_json, new_cfg_bytes);
+        assert_eq!(extracted.hero_image, Some(b"hero".to_vec()));
+        assert!(extracted.icon_png.is_none());
+        assert_eq!(extracted.named_assets.len(), 1);
+        let manual = extracted.named_asset("manual.pdf").expect("manual");
+        assert_eq!(manual.bytes, b"%PDF new");
+        assert_eq!(manual.flags, 1);
+
+        let repacked = fs::read(&launcher).expect("read launcher");
+        assert_eq!(
+            strip_asset_container(&repacked).expect("strip"),
+            b"BASE-BINARY"
+        );
+        assert_eq!(
+            fs::read(root.join("game.bak")).expect("read backup"),
+            original
+        );
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn rebases_launcher_onto_new_base() {
+        let root = make_temp_dir("rebase-launcher");
+        let launcher = root.join("game");
+        let cfg_bytes = serde_json::to_vec(&sample_config()).expect("serialize config");
+        inject_from_parts(
+            b"OLD-BASE",
+            &cfg_bytes,
+            Some(b"hero"),
+            None,
+            &[NamedAssetWriteInput {
+                name: "manual.pdf",
+                mime_type: "application/pdf",
+                flags: 2,
+                bytes: b"%PDF",
+            }],
+            &launcher,
+            InjectOptions::default(),
+        )
+        .expect("inject config");
+        assert_eq!(
+            launcher_base_sha256_hex(&launcher).expect("old base hash"),
+            sha256_hex(b"OLD-BASE")
+        );
+
+        rebase_launcher(
+            &launcher,
+            b"NEW-BASE-BUILD",
+            &launcher,
+            InjectOptions::default(),
+        )
+        .expect("rebase");
+
+        assert_eq!(
+            launcher_base_sha256_hex(&launcher).expect("new base hash"),
+            sha256_hex(b"NEW-BASE-BUILD")
+        );
+        let extracted = extract_assets_from_file(&launcher).expect("extract");
+        assert_eq!(extracted.config_json, cfg_bytes);
+        assert_eq!(extracted.hero_image, Some(b"hero".to_vec()));
+        assert_eq!(
+            extracted.named_asset("manual.pdf").expect("manual").flags,
+            2
+        );
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn repacks_legacy_payload_with_string_scripts() {
+        let root = make_temp_dir("replace-assets-legacy");
+        let launcher = root.join("game");
+        let mut legacy = serde_json::to_value(sample_config()).expect("serialize config");
+        legacy["config_version"] = serde_json::json!(1);
+        legacy["scripts"] = serde_json::json!({ "pre_launch": "echo ready", "post_launch": "" });
+        let legacy_bytes = serde_json::to_vec(&legacy).expect("serialize legacy config");
+        let binary = append_asset_container(
+            b"BASE-BINARY",
+            AssetContainerWriteInput {
+                config_json: &legacy_bytes,
+                hero_image: None,
+                icon_png: None,
+                named_assets: &[],
+                signing_key: None,
+            },
+        )
+        .expect("write legacy launcher");
+        fs::write(&launcher, binary).expect("write launcher");
+
+        let result = replace_assets(
+            &launcher,
+            &AssetReplacement {
+                hero_image: AssetChange::Replace(b"hero".to_vec()),
+                ..AssetReplacement::default()
+            },
+            &launcher,
+            InjectOptions::default(),
+        )
+        .expect("repack legacy launcher");
+
+        let extracted = extract_assets_from_file(&launcher).expect("extract");
+        assert_eq!(result.config_len, extracted.config_json.len());
+        let config: GameConfig =
+            serde_json::from_slice(&extracted.config_json).expect("migrated config");
+        assert_eq!(config.config_version, CURRENT_CONFIG_VERSION);
+        assert_eq!(config.scripts.pre_launch.len(), 1);
+        assert_eq!(config.scripts.pre_launch[0].command, "echo ready");
+        assert!(config.scripts.post_launch.is_empty());
+        assert_eq!(extracted.hero_image, Some(b"hero".to_vec()));
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn named_asset_verification_compares_mime_types() {
+        let written = vec![ExtractedNamedAsset {
+            name: "manual.pdf".to_string(),
+            mime_type: DEFAULT_NAMED_ASSET_MIME_TYPE.to_string(),
+            flags: 0,
+            bytes: b"%PDF".to_vec(),
+        }];
+        let expected = |mime_type| {
+            [NamedAssetWriteInput {
+                name: "manual.pdf",
+                mime_type,
+                flags: 0,
+                bytes: b"%PDF",
+            }]
+        };
+
+        assert!(named_assets_match(&written, &expected(" ")));
+        assert!(!named_assets_match(&written, &expected("application/pdf")));
+    }
+
+    #[test]
+    fn refuses_to_remove_unknown_named_asset() {
+        let root = make_temp_dir("replace-assets-unknown");
+        let launcher = root.join("game");
+        let cfg_bytes = serde_json::to_vec(&sample_config()).expect("serialize config");
+        inject_from_parts(
+            b"BASE-BINARY",
+            &cfg_bytes,
+            None,
+            None,
+            &[],
+            &launcher,
+            InjectOptions::default(),
+        )
+        .expect("inject config");
+
+        let err = replace_assets(
+            &launcher,
+            &AssetReplacement {
+                remove_named_assets: vec!["missing".to_string()],
+                ..AssetReplacement::default()
+            },
+            &launcher,
+            InjectOptions::default(),
+        )
+        .expect_err("unknown asset must fail");
+        assert!(matches!(err, OrchestratorError::AssetNotFound(_)));
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
+    #[test]
+    fn repacking_a_signed_launcher_needs_a_key_or_an_explicit_drop() {
+        let root = make_temp_dir("replace-assets-signed");
+        let launcher = root.join("game");
+        let cfg_bytes = serde_json::to_vec(&sample_config()).expect("serialize config");
+        let key = PayloadSigningKey::generate("tests").expect("key");
+        inject_from_parts(
+            b"BASE-BINARY",
+            &cfg_bytes,
+            None,
+            None,
+            &[],
+            &launcher,
+            InjectOptions {
+                signing_key: Some(key.clone()),
+                ..InjectOptions::default()
+            },
+        )
+        .expect("inject config");
+
+        let unsigned = InjectOptions::default();
+        let err = check_repack_signature(&launcher, &unsigned, false)
+            .expect_err("signed launcher without key must be refused");
+        assert!(matches!(err, OrchestratorError::SignatureWouldBeDropped));
+        assert_eq!(
+            check_repack_signature(&launcher, &unsigned, true).expect("explicit drop"),
+            RewrittenSignature::Dropped
+        );
+        let signed = InjectOptions {
+            signing_key: Some(key),
+            ..InjectOptions::default()
+        };
+        assert_eq!(
+            check_repack_signature(&launcher, &signed, false).expect("re-sign"),
+            RewrittenSignature::Signed
+        );
+
+        replace_assets(&launcher, &AssetReplacement::default(), &launcher, unsigned)
+            .expect("repack unsigned");
+        assert_eq!(
+            check_repack_signature(&launcher, &InjectOptions::default(), false)
+                .expect("unsigned launcher"),
+            RewrittenSignature::Unsigned
+        );
+
+        fs::remove_dir_all(root).expect("cleanup");
+    }
+
     fn make_temp_dir(prefix: &str) -> PathBuf {
         let millis = SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -366,7 +1026,7 @@ mod tests {
 
     fn sample_config() -> GameConfig {
         GameConfig {
-            config_version: 1,
+            config_version: CURRENT_CONFIG_VERSION,
             created_by: "test".to_string(),
             game_name: "Sample Game".to_string(),
             exe_hash: "a1b2c3".to_string(),
@@ -400,6 +1060,7 @@ mod tests {
                 mangohud: FeatureState::OptionalOff,
                 prime_offload: FeatureState::OptionalOff,
                 custom_vars: HashMap::new(),
+                env_edits: Vec::new(),
             },
             compatibility: CompatibilityConfig {
                 wine_wayland: FeatureState::OptionalOff,
@@ -465,9 +1126,13 @@ mod tests {
             folder_mounts: Vec::new(),
             splash: SplashConfig::default(),
             scripts: ScriptsConfig {
-                pre_launch: String::new(),
-                post_launch: String::new(),
+                pre_launch: Vec::new(),
+                post_launch: Vec::new(),
             },
+            profiles: Vec::new(),
+            rules: Vec::new(),
+            launch_entries: Vec::new(),
+            default_entry: None,
         }
     }
 }
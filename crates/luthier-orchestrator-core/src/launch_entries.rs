@@ -0,0 +1,110 @@
+use serde::Serialize;
+
+use crate::config::GameConfig;
+use crate::error::OrchestratorError;
+
+/// Reserved entry name that selects `relative_exe_path`, so the main executable
+/// can be picked explicitly even when `default_entry` points elsewhere.
+pub const MAIN_LAUNCH_ENTRY: &str = "main";
+
+/// Executable, arguments and environment picked for one launch. The prefix,
+/// mounts and everything else in the config stay shared between entries.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ResolvedLaunchEntry {
+    /// `None` for the main executable in `relative_exe_path`.
+    pub name: Option<String>,
+    pub relative_exe_path: String,
+    pub launch_args: Vec<String>,
+    pub working_dir: Option<String>,
+    /// Sorted by key so launch plans are stable.
+    pub env: Vec<(String, String)>,
+}
+
+/// Resolves `requested`, falling back to `default_entry` and then to the main
+/// executable.
+pub fn resolve_launch_entry(
+    config: &GameConfig,
+    requested: Option<&str>,
+) -> Result<ResolvedLaunchEntry, OrchestratorError> {
+    let name = match requested.or(config.default_entry.as_deref()) {
+        None | Some(MAIN_LAUNCH_ENTRY) => return Ok(main_launch_entry(config)),
+        Some(name) => name,
+    };
+
+    let entry = config
+        .launch_entries
+        .iter()
+        .find(|entry| entry.name == name)
+        .ok_or_else(|| OrchestratorError::UnknownLaunchEntry(name.to_string()))?;
+    let mut env = entry
+        .env
+        .iter()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect::<Vec<_>>();
+    env.sort();
+
+    Ok(ResolvedLaunchEntry {
+        name: Some(entry.name.clone()),
+        relative_exe_path: entry.relative_exe_path.clone(),
+        launch_args: entry.launch_args.clone(),
+        working_dir: entry.working_dir.clone(),
+        env,
+    })
+}
+
+pub fn main_launch_entry(config: &GameConfig) -> ResolvedLaunchEntry {
+    ResolvedLaunchEntry {
+        name: None,
+        relative_exe_path: config.relative_exe_path.clone(),
+        launch_args: config.launch_args.clone(),
+        working_dir: None,
+        env: Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::config::LaunchEntry;
+    use crate::config_overlay::tests::sample_config_value;
+
+    fn config_with_entries() -> GameConfig {
+        let mut config: GameConfig = serde_json::from_value(sample_config_value()).expect("config");
+        config.launch_entries = vec![LaunchEntry {
+            name: "Server".to_string(),
+            relative_exe_path: "./bin/server.exe".to_string(),
+            launch_args: vec!["-dedicated".to_string()],
+            working_dir: Some("./bin".to_string()),
+            env: HashMap::from([
+                ("SERVER_PORT".to_string(), "27015".to_string()),
+                ("DXVK_HUD".to_string(), "0".to_string()),
+            ]),
+        }];
+        config
+    }
+
+    #[test]
+    fn falls_back_to_default_entry_then_main_executable() {
+        let mut config = config_with_entries();
+        let main = resolve_launch_entry(&config, None).expect("main");
+        assert_eq!(main, main_launch_entry(&config));
+
+        config.default_entry = Some("Server".to_string());
+        let server = resolve_launch_entry(&config, None).expect("default");
+        assert_eq!(server.name.as_deref(), Some("Server"));
+        assert_eq!(server.launch_args, vec!["-dedicated"]);
+        assert_eq!(server.env[0].0, "DXVK_HUD");
+
+        let main = resolve_launch_entry(&config, Some(MAIN_LAUNCH_ENTRY)).expect("explicit main");
+        assert_eq!(main.name, None);
+    }
+
+    #[test]
+    fn unknown_entry_is_an_error() {
+        let err =
+            resolve_launch_entry(&config_with_entries(), Some("Editor")).expect_err("unknown");
+        assert!(matches!(err, OrchestratorError::UnknownLaunchEntry(name) if name == "Editor"));
+    }
+}
@@ -2,12 +2,21 @@
 
 pub mod asset_container;
 pub mod config;
+pub mod config_migration;
+pub mod config_overlay;
+pub mod config_schema;
+pub mod container_reader;
 pub mod doctor;
 pub mod error;
+pub mod host_env;
+pub mod host_rules;
 pub mod injector;
+pub mod launch_entries;
 pub mod observability;
 pub mod prefix;
 pub mod process;
+pub mod signing;
+pub mod variables;
 
 pub use config::*;
 pub use error::OrchestratorError;
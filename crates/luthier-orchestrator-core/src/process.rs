@@ -1,4 +1,5 @@
 use std::fs;
+use std::os::unix::process::CommandExt;
 use std::process::{Child, Command, ExitStatus, Stdio};
 use std::thread;
 use std::time::{Duration, Instant};
@@ -188,6 +189,91 @@ pub fn execute_external_command(
     }
 }
 
+/// Process started by [`spawn_background_command`]. It leads its own process
+/// group, so stopping it also stops whatever it started.
+#[derive(Debug)]
+pub struct BackgroundProcess {
+    pub name: String,
+    child: Child,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct BackgroundExit {
+    /// The process was no longer running when it was stopped.
+    pub exited_before_stop: bool,
+    pub exit_code: Option<i32>,
+}
+
+impl BackgroundProcess {
+    /// Sends SIGTERM to the process group and kills the leader if it is still
+    /// running after `grace`.
+    pub fn stop(mut self, grace: Duration) -> BackgroundExit {
+        if let Ok(Some(status)) = self.child.try_wait() {
+            return BackgroundExit {
+                exited_before_stop: true,
+                exit_code: status.code(),
+            };
+        }
+
+        let group = format!("-{}", self.child.id());
+        let _ = Command::new("kill")
+            .args(["-TERM", "--", &group])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        let status = wait_with_timeout(&mut self.child, grace).ok().flatten();
+
+        BackgroundExit {
+            exited_before_stop: false,
+            exit_code: status.and_then(|status| status.code()),
+        }
+    }
+}
+
+/// Starts `command` without waiting for it. `timeout_secs` is ignored: the
+/// caller decides when to [`BackgroundProcess::stop`] it.
+pub fn spawn_background_command(
+    command: &ExternalCommand,
+    env_pairs: &[(String, String)],
+    dry_run: bool,
+) -> (CommandExecutionResult, Option<BackgroundProcess>) {
+    let mut result = CommandExecutionResult {
+        name: command.name.clone(),
+        program: command.program.clone(),
+        args: command.args.clone(),
+        mandatory: command.mandatory,
+        status: StepStatus::Skipped,
+        exit_code: None,
+        duration_ms: 0,
+        error: Some("dry-run mode".to_string()),
+    };
+    if dry_run {
+        return (result, None);
+    }
+
+    let start = Instant::now();
+    let spawned = build_external_process(command, env_pairs)
+        .process_group(0)
+        .spawn();
+    result.duration_ms = start.elapsed().as_millis();
+    match spawned {
+        Ok(child) => {
+            result.status = StepStatus::Success;
+            result.error = None;
+            let process = BackgroundProcess {
+                name: command.name.clone(),
+                child,
+            };
+            (result, Some(process))
+        }
+        Err(err) => {
+            result.status = StepStatus::Failed;
+            result.error = Some(err.to_string());
+            (result, None)
+        }
+    }
+}
+
 fn run_command(command: &PlannedCommand, env_pairs: &[(String, String)]) -> CommandExecutionResult {
     let start = Instant::now();
 
@@ -267,6 +353,10 @@ fn spawn_external_process(
     command: &ExternalCommand,
     env_pairs: &[(String, String)],
 ) -> Result<Child, std::io::Error> {
+    build_external_process(command, env_pairs).spawn()
+}
+
+fn build_external_process(command: &ExternalCommand, env_pairs: &[(String, String)]) -> Command {
     let mut process = Command::new(&command.program);
     process
         .args(&command.args)
@@ -281,7 +371,7 @@ fn spawn_external_process(
         process.env(key, value);
     }
 
-    process.spawn()
+    process
 }
 
 fn wait_with_timeout(
@@ -358,4 +448,23 @@ mod tests {
         assert_eq!(result.status, StepStatus::Skipped);
         assert!(result.error.unwrap_or_default().contains("dry-run"));
     }
+
+    #[test]
+    fn background_command_runs_until_stopped() {
+        let cmd = ExternalCommand {
+            name: "overlay".to_string(),
+            program: "sh".to_string(),
+            args: vec!["-c".to_string(), "sleep 30".to_string()],
+            timeout_secs: None,
+            cwd: None,
+            mandatory: false,
+        };
+
+        let (result, process) = spawn_background_command(&cmd, &[], false);
+        assert_eq!(result.status, StepStatus::Success);
+        let started = Instant::now();
+        let exit = process.expect("spawned").stop(Duration::from_secs(5));
+        assert!(!exit.exited_before_stop);
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
 }
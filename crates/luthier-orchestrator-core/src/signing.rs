@@ -0,0 +1,403 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::asset_container::AssetManifest;
+use crate::error::OrchestratorError;
+
+pub const SIGNATURE_ALGORITHM: &str = "ed25519";
+const SIGNING_DOMAIN: &[u8] = b"luthier-goas-v2-manifest\0";
+const KEY_BYTES: usize = 32;
+const SIGNATURE_BYTES: usize = 64;
+const FINGERPRINT_HEX_LEN: usize = 16;
+
+/// Signature block stored inside the manifest.
+///
+/// The signed message is the manifest serialized without this block, so it covers
+/// every entry offset, length, checksum and metadata field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestSignature {
+    pub algorithm: String,
+    pub signer: String,
+    pub public_key_hex: String,
+    pub signature_hex: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SignerIdentity {
+    pub signer: String,
+    pub public_key_hex: String,
+    pub fingerprint: String,
+}
+
+#[derive(Clone)]
+pub struct PayloadSigningKey {
+    signer: String,
+    key: SigningKey,
+}
+
+impl fmt::Debug for PayloadSigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PayloadSigningKey")
+            .field("signer", &self.signer)
+            .field("public_key_hex", &self.public_key_hex())
+            .finish()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SigningKeyFile {
+    signer: String,
+    secret_key_hex: String,
+}
+
+impl PayloadSigningKey {
+    /// Creates a fresh key pair using the kernel random source.
+    pub fn generate(signer: &str) -> Result<Self, OrchestratorError> {
+        let mut seed = [0_u8; KEY_BYTES];
+        fs::File::open("/dev/urandom")?.read_exact(&mut seed)?;
+        Self::from_parts(signer, &seed)
+    }
+
+    /// Parses a key file of the form `{"signer": "...", "secret_key_hex": "..."}`.
+    pub fn from_key_file_bytes(bytes: &[u8]) -> Result<Self, OrchestratorError> {
+        let file: SigningKeyFile = serde_json::from_slice(bytes)
+            .map_err(|err| OrchestratorError::InvalidSigningKey(err.to_string()))?;
+        let seed = parse_fixed_hex::<KEY_BYTES>(&file.secret_key_hex)
+            .ok_or_else(|| OrchestratorError::InvalidSigningKey("secret_key_hex".to_string()))?;
+        Self::from_parts(&file.signer, &seed)
+    }
+
+    pub fn from_key_file(path: &Path) -> Result<Self, OrchestratorError> {
+        Self::from_key_file_bytes(&fs::read(path)?)
+    }
+
+    pub fn to_key_file_json(&self) -> Result<String, OrchestratorError> {
+        Ok(serde_json::to_string_pretty(&SigningKeyFile {
+            signer: self.signer.clone(),
+            secret_key_hex: to_lower_hex(self.key.as_bytes()),
+        })?)
+    }
+
+    pub fn signer(&self) -> &str {
+        &self.signer
+    }
+
+    pub fn public_key_hex(&self) -> String {
+        to_lower_hex(self.key.verifying_key().as_bytes())
+    }
+
+    pub fn identity(&self) -> SignerIdentity {
+        identity_for(&self.signer, &self.key.verifying_key())
+    }
+
+    fn from_parts(signer: &str, seed: &[u8; KEY_BYTES]) -> Result<Self, OrchestratorError> {
+        let signer = signer.trim();
+        if signer.is_empty() {
+            return Err(OrchestratorError::InvalidSigningKey(
+                "signer name is empty".to_string(),
+            ));
+        }
+        Ok(Self {
+            signer: signer.to_string(),
+            key: SigningKey::from_bytes(seed),
+        })
+    }
+}
+
+/// Replaces any existing signature block with a fresh one made by `key`.
+pub fn sign_manifest(
+    manifest: &mut AssetManifest,
+    key: &PayloadSigningKey,
+) -> Result<(), OrchestratorError> {
+    manifest.signature = None;
+    let message = signing_message(manifest)?;
+    let signature = key.key.sign(&message);
+    manifest.signature = Some(ManifestSignature {
+        algorithm: SIGNATURE_ALGORITHM.to_string(),
+        signer: key.signer.clone(),
+        public_key_hex: key.public_key_hex(),
+        signature_hex: to_lower_hex(&signature.to_bytes()),
+    });
+    Ok(())
+}
+
+/// Checks the embedded signature against the public key it declares.
+///
+/// Returns `Ok(None)` for unsigned manifests. Whether the signer is trusted is a
+/// separate decision, see [`evaluate_manifest_trust`].
+pub fn verify_manifest_signature(
+    manifest: &AssetManifest,
+) -> Result<Option<SignerIdentity>, OrchestratorError> {
+    let Some(block) = manifest.signature.as_ref() else {
+        return Ok(None);
+    };
+
+    if block.algorithm != SIGNATURE_ALGORITHM {
+        return Err(OrchestratorError::InvalidSignature(format!(
+            "unsupported algorithm '{}'",
+            block.algorithm
+        )));
+    }
+
+    let public_key = parse_fixed_hex::<KEY_BYTES>(&block.public_key_hex)
+        .ok_or_else(|| OrchestratorError::InvalidSignature("malformed public key".to_string()))?;
+    let verifying_key = VerifyingKey::from_bytes(&public_key)
+        .map_err(|err| OrchestratorError::InvalidSignature(err.to_string()))?;
+    let signature_bytes = parse_fixed_hex::<SIGNATURE_BYTES>(&block.signature_hex)
+        .ok_or_else(|| OrchestratorError::InvalidSignature("malformed signature".to_string()))?;
+    let signature = Signature::from_bytes(&signature_bytes);
+
+    let mut unsigned = manifest.clone();
+    unsigned.signature = None;
+    let message = signing_message(&unsigned)?;
+    verifying_key
+        .verify(&message, &signature)
+        .map_err(|_| OrchestratorError::InvalidSignature("signature mismatch".to_string()))?;
+
+    Ok(Some(identity_for(&block.signer, &verifying_key)))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignaturePolicy {
+    /// Only launch payloads signed by a trusted key.
+    Refuse,
+    /// Launch anyway, but report unsigned or untrusted payloads.
+    #[default]
+    Warn,
+    /// Skip signature checks entirely.
+    Ignore,
+}
+
+impl SignaturePolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Refuse => "refuse",
+            Self::Warn => "warn",
+            Self::Ignore => "ignore",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustedKey {
+    pub signer: String,
+    pub public_key_hex: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustStore {
+    #[serde(default)]
+    pub policy: SignaturePolicy,
+    #[serde(default)]
+    pub keys: Vec<TrustedKey>,
+}
+
+impl TrustStore {
+    pub fn trusts(&self, public_key_hex: &str) -> bool {
+        self.keys.iter().any(|key| {
+            key.public_key_hex
+                .trim()
+                .eq_ignore_ascii_case(public_key_hex)
+        })
+    }
+}
+
+pub fn trust_store_path() -> Result<PathBuf, OrchestratorError> {
+    let home = env::var_os("HOME").ok_or(OrchestratorError::MissingHomeDir)?;
+    Ok(PathBuf::from(home).join(".local/share/Luthier/trust-store.json"))
+}
+
+/// Loads the trust store, falling back to an empty store with the default policy
+/// when the file does not exist yet.
+pub fn load_trust_store(path: &Path) -> Result<TrustStore, OrchestratorError> {
+    if !path.exists() {
+        return Ok(TrustStore::default());
+    }
+    let raw = fs::read(path)?;
+    Ok(serde_json::from_slice(&raw)?)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SignatureVerdict {
+    Unsigned,
+    Invalid { reason: String },
+    Untrusted { signer: SignerIdentity },
+    Trusted { signer: SignerIdentity },
+}
+
+impl SignatureVerdict {
+    pub fn is_trusted(&self) -> bool {
+        matches!(self, Self::Trusted { .. })
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Unsigned => "unsigned",
+            Self::Invalid { .. } => "invalid",
+            Self::Untrusted { .. } => "untrusted",
+            Self::Trusted { .. } => "trusted",
+        }
+    }
+}
+
+pub fn evaluate_manifest_trust(manifest: &AssetManifest, store: &TrustStore) -> SignatureVerdict {
+    match verify_manifest_signature(manifest) {
+        Ok(None) => SignatureVerdict::Unsigned,
+        Ok(Some(signer)) if store.trusts(&signer.public_key_hex) => {
+            SignatureVerdict::Trusted { signer }
+        }
+        Ok(Some(signer)) => SignatureVerdict::Untrusted { signer },
+        Err(err) => SignatureVerdict::Invalid {
+            reason: err.to_string(),
+        },
+    }
+}
+
+fn signing_message(unsigned: &AssetManifest) -> Result<Vec<u8>, OrchestratorError> {
+    let mut message = SIGNING_DOMAIN.to_vec();
+    message.extend_from_slice(&serde_json::to_vec(unsigned)?);
+    Ok(message)
+}
+
+fn identity_for(signer: &str, key: &VerifyingKey) -> SignerIdentity {
+    let public_key_hex = to_lower_hex(key.as_bytes());
+    let digest: [u8; 32] = Sha256::digest(key.as_bytes()).into();
+    let mut fingerprint = to_lower_hex(&digest);
+    fingerprint.truncate(FINGERPRINT_HEX_LEN);
+    SignerIdentity {
+        signer: signer.to_string(),
+        public_key_hex,
+        fingerprint,
+    }
+}
+
+fn parse_fixed_hex<const N: usize>(raw: &str) -> Option<[u8; N]> {
+    let trimmed = raw.trim();
+    if trimmed.len() != N * 2 || !trimmed.is_ascii() {
+        return None;
+    }
+    let mut out = [0_u8; N];
+    for (idx, slot) in out.iter_mut().enumerate() {
+        *slot = u8::from_str_radix(&trimmed[idx * 2..idx * 2 + 2], 16).ok()?;
+    }
+    Some(out)
+}
+
+fn to_lower_hex(bytes: &[u8]) -> String {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push(HEX[(b >> 4) as usize] as char);
+        out.push(HEX[(b & 0x0f) as usize] as char);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asset_container::{
+        append_asset_container, parse_asset_container, AssetContainerWriteInput,
+    };
+
+    fn test_key(signer: &str, seed_byte: u8) -> PayloadSigningKey {
+        PayloadSigningKey::from_parts(signer, &[seed_byte; KEY_BYTES]).expect("key")
+    }
+
+    fn signed_manifest(key: &PayloadSigningKey) -> AssetManifest {
+        let binary = append_asset_container(
+            b"ELF",
+            AssetContainerWriteInput {
+                config_json: br#"{"config_version":1}"#,
+                hero_image: Some(b"hero"),
+                icon_png: None,
+                named_assets: &[],
+                signing_key: Some(key),
+            },
+        )
+        .expect("container build");
+        parse_asset_container(&binary).expect("parse").manifest
+    }
+
+    #[test]
+    fn signs_and_verifies_manifest() {
+        let key = test_key("Luthier Team", 7);
+        let manifest = signed_manifest(&key);
+
+        let identity = verify_manifest_signature(&manifest)
+            .expect("verify")
+            .expect("signed");
+        assert_eq!(identity.signer, "Luthier Team");
+        assert_eq!(identity.public_key_hex, key.public_key_hex());
+        assert_eq!(identity.fingerprint.len(), FINGERPRINT_HEX_LEN);
+    }
+
+    #[test]
+    fn detects_tampered_asset_hash() {
+        let key = test_key("Luthier Team", 7);
+        let mut manifest = signed_manifest(&key);
+        manifest.entries[0].sha256_hex = "00".repeat(32);
+
+        let err = verify_manifest_signature(&manifest).expect_err("tamper must be detected");
+        assert!(matches!(err, OrchestratorError::InvalidSignature(_)));
+    }
+
+    #[test]
+    fn evaluates_trust_against_store() {
+        let key = test_key("Luthier Team", 7);
+        let manifest = signed_manifest(&key);
+
+        let empty = TrustStore::default();
+        assert_eq!(
+            evaluate_manifest_trust(&manifest, &empty).as_str(),
+            "untrusted"
+        );
+
+        let store = TrustStore {
+            policy: SignaturePolicy::Refuse,
+            keys: vec![TrustedKey {
+                signer: "team".to_string(),
+                public_key_hex: key.public_key_hex().to_uppercase(),
+            }],
+        };
+        assert!(evaluate_manifest_trust(&manifest, &store).is_trusted());
+
+        let mut unsigned = manifest;
+        unsigned.signature = None;
+        assert_eq!(
+            evaluate_manifest_trust(&unsigned, &store),
+            SignatureVerdict::Unsigned
+        );
+    }
+
+    #[test]
+    fn roundtrips_key_file() {
+        let key = test_key("Packager", 3);
+        let json = key.to_key_file_json().expect("serialize key");
+        let parsed = PayloadSigningKey::from_key_file_bytes(json.as_bytes()).expect("parse key");
+        assert_eq!(parsed.public_key_hex(), key.public_key_hex());
+        assert_eq!(parsed.signer(), "Packager");
+
+        let err =
+            PayloadSigningKey::from_key_file_bytes(br#"{"signer":"x","secret_key_hex":"zz"}"#)
+                .expect_err("bad key must fail");
+        assert!(matches!(err, OrchestratorError::InvalidSigningKey(_)));
+    }
+
+    #[test]
+    fn missing_trust_store_defaults_to_warn() {
+        let store = load_trust_store(Path::new("/nonexistent/luthier/trust-store.json"))
+            .expect("default store");
+        assert_eq!(store.policy, SignaturePolicy::Warn);
+        assert!(store.keys.is_empty());
+    }
+}
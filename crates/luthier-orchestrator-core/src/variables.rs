@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::error::OrchestratorError;
+
+/// `${HOST_ENV:NAME}` reads `NAME` from the environment the orchestrator was
+/// started with; an unset variable expands to an empty string.
+pub const HOST_ENV_PREFIX: &str = "HOST_ENV:";
+
+/// Variables accepted in `${...}` besides `HOST_ENV:NAME`.
+pub const KNOWN_VARIABLES: &[&str] = &[
+    "GAME_ROOT",
+    "PREFIX",
+    "DRIVE_C",
+    "WIN_USER_HOME",
+    "WIN_USER_DOCUMENTS",
+    "WIN_APPDATA",
+    "WIN_LOCAL_APPDATA",
+    "HOME",
+    "XDG_CONFIG_HOME",
+    "XDG_DATA_HOME",
+    "XDG_CACHE_HOME",
+];
+
+enum Segment<'a> {
+    Literal(&'a str),
+    Variable(&'a str),
+}
+
+/// Checks `${...}` syntax and that every referenced variable is known.
+pub fn check_variables(input: &str) -> Result<(), OrchestratorError> {
+    for segment in parse(input)? {
+        if let Segment::Variable(name) = segment {
+            check_variable_name(name)?;
+        }
+    }
+    Ok(())
+}
+
+/// Paths and host environment `${...}` references expand to. The prefix is the
+/// directory holding `drive_c` (`pfx` for Proton).
+#[derive(Debug, Clone)]
+pub struct VariableContext {
+    pub game_root: PathBuf,
+    pub prefix: PathBuf,
+    /// Profile folder under `drive_c/users`: `steamuser` for Proton, the Unix
+    /// user name for Wine.
+    pub windows_user: String,
+    pub host_env: HashMap<String, String>,
+}
+
+impl VariableContext {
+    pub fn from_host_env(game_root: &Path, prefix: &Path, windows_user: &str) -> Self {
+        Self {
+            game_root: game_root.to_path_buf(),
+            prefix: prefix.to_path_buf(),
+            windows_user: windows_user.to_string(),
+            host_env: std::env::vars().collect(),
+        }
+    }
+
+    /// Expands every `${...}` in `input`; `$${` writes a literal `${`.
+    pub fn expand(&self, input: &str) -> Result<String, OrchestratorError> {
+        let mut out = String::with_capacity(input.len());
+        for segment in parse(input)? {
+            match segment {
+                Segment::Literal(text) => out.push_str(text),
+                Segment::Variable(name) => out.push_str(&self.value(name)?),
+            }
+        }
+        Ok(out)
+    }
+
+    pub fn host_var(&self, name: &str) -> Option<&str> {
+        self.host_env.get(name).map(String::as_str)
+    }
+
+    fn value(&self, name: &str) -> Result<String, OrchestratorError> {
+        check_variable_name(name)?;
+        if let Some(host_name) = name.strip_prefix(HOST_ENV_PREFIX) {
+            return Ok(self.host_var(host_name).unwrap_or_default().to_string());
+        }
+
+        let user_home = self
+            .prefix
+            .join("drive_c")
+            .join("users")
+            .join(&self.windows_user);
+        let path = match name {
+            "GAME_ROOT" => self.game_root.clone(),
+            "PREFIX" => self.prefix.clone(),
+            "DRIVE_C" => self.prefix.join("drive_c"),
+            "WIN_USER_HOME" => user_home,
+            "WIN_USER_DOCUMENTS" => user_home.join("Documents"),
+            "WIN_APPDATA" => user_home.join("AppData").join("Roaming"),
+            "WIN_LOCAL_APPDATA" => user_home.join("AppData").join("Local"),
+            "HOME" => self.home(),
+            "XDG_CONFIG_HOME" => self.xdg_dir("XDG_CONFIG_HOME", ".config"),
+            "XDG_DATA_HOME" => self.xdg_dir("XDG_DATA_HOME", ".local/share"),
+            "XDG_CACHE_HOME" => self.xdg_dir("XDG_CACHE_HOME", ".cache"),
+            other => return Err(OrchestratorError::UnknownVariable(other.to_string())),
+        };
+        Ok(path.to_string_lossy().into_owned())
+    }
+
+    fn home(&self) -> PathBuf {
+        self.host_var("HOME").map(PathBuf::from).unwrap_or_default()
+    }
+
+    /// The XDG spec ignores relative values and falls back to the default.
+    fn xdg_dir(&self, name: &str, default_under_home: &str) -> PathBuf {
+        self.host_var(name)
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .unwrap_or_else(|| self.home().join(default_under_home))
+    }
+}
+
+fn check_variable_name(name: &str) -> Result<(), OrchestratorError> {
+    let known = match name.strip_prefix(HOST_ENV_PREFIX) {
+        Some(host_name) => is_env_name(host_name),
+        None => KNOWN_VARIABLES.contains(&name),
+    };
+    if known {
+        Ok(())
+    } else {
+        Err(OrchestratorError::UnknownVariable(name.to_string()))
+    }
+}
+
+fn is_env_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(first) if first == '_' || first.is_ascii_alphabetic())
+        && chars.all(|ch| ch == '_' || ch.is_ascii_alphanumeric())
+}
+
+fn parse(input: &str) -> Result<Vec<Segment<'_>>, OrchestratorError> {
+    let mut segments = Vec::new();
+    let mut rest = input;
+    while let Some(start) = rest.find("${") {
+        // `$${` is an escaped, literal `${`.
+        if rest[..start].ends_with('$') {
+            segments.push(Segment::Literal(&rest[..start - 1]));
+            segments.push(Segment::Literal("${"));
+            rest = &rest[start + 2..];
+            continue;
+        }
+
+        segments.push(Segment::Literal(&rest[..start]));
+        let after_open = &rest[start + 2..];
+        let end = after_open.find('}').ok_or_else(|| {
+            OrchestratorError::InvalidVariableSyntax(format!("unterminated '${{' in '{input}'"))
+        })?;
+        segments.push(Segment::Variable(&after_open[..end]));
+        rest = &after_open[end + 1..];
+    }
+    segments.push(Segment::Literal(rest));
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> VariableContext {
+        VariableContext {
+            game_root: PathBuf::from("/games/witcher"),
+            prefix: PathBuf::from("/prefixes/abc/pfx"),
+            windows_user: "steamuser".to_string(),
+            host_env: HashMap::from([
+                ("HOME".to_string(), "/home/ana".to_string()),
+                ("XDG_CONFIG_HOME".to_string(), "relative/config".to_string()),
+                ("DISPLAY".to_string(), ":0".to_string()),
+            ]),
+        }
+    }
+
+    #[test]
+    fn expands_paths_and_host_env() {
+        let ctx = context();
+        assert_eq!(
+            ctx.expand("-savedir=${WIN_USER_DOCUMENTS}/Witcher")
+                .expect("expand"),
+            "-savedir=/prefixes/abc/pfx/drive_c/users/steamuser/Documents/Witcher"
+        );
+        assert_eq!(
+            ctx.expand("${GAME_ROOT}/mods:${HOST_ENV:DISPLAY}${HOST_ENV:UNSET}")
+                .expect("expand"),
+            "/games/witcher/mods::0"
+        );
+        assert_eq!(
+            ctx.expand("${XDG_CONFIG_HOME}").expect("expand"),
+            "/home/ana/.config"
+        );
+    }
+
+    #[test]
+    fn keeps_plain_dollars_and_escaped_references() {
+        let ctx = context();
+        assert_eq!(
+            ctx.expand("$HOME costs $5, literal $${GAME_ROOT}")
+                .expect("expand"),
+            "$HOME costs $5, literal ${GAME_ROOT}"
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_and_malformed_references() {
+        assert!(matches!(
+            check_variables("${STEAM_ROOT}/x"),
+            Err(OrchestratorError::UnknownVariable(name)) if name == "STEAM_ROOT"
+        ));
+        assert!(matches!(
+            check_variables("${HOST_ENV:1BAD}"),
+            Err(OrchestratorError::UnknownVariable(_))
+        ));
+        assert!(matches!(
+            check_variables("${GAME_ROOT"),
+            Err(OrchestratorError::InvalidVariableSyntax(_))
+        ));
+        assert!(check_variables("${PREFIX} $${NOT_CHECKED}").is_ok());
+    }
+}